@@ -0,0 +1,165 @@
+mod types;
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use anyhow::Context;
+use mina_p2p_messages::binprot::{BinProtRead, BinProtWrite};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Decode or encode any of the mina p2p message types.
+///
+/// Converts between binprot, JSON and hex encoded binprot.
+#[derive(Debug, clap::Args)]
+pub struct Inspect {
+    /// Name of the type, e.g. `MinaBlockBlockStableV2`, `GetBestTipV2.response`
+    /// or one of the aliases, e.g. `block`. See `--list` for all of them.
+    #[arg(required_unless_present = "list")]
+    pub type_name: Option<String>,
+
+    /// Input format.
+    #[arg(long, short, value_enum, default_value_t = Format::Binprot)]
+    pub from: Format,
+
+    /// Output format.
+    #[arg(long, short, value_enum, default_value_t = Format::Json)]
+    pub to: Format,
+
+    /// Input file. Reads from stdin if not set.
+    #[arg(long, short)]
+    pub input: Option<PathBuf>,
+
+    /// Output file. Writes to stdout if not set.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Print hash of the value to stderr, if it's supported for the type.
+    #[arg(long)]
+    pub hash: bool,
+
+    /// List supported types and aliases.
+    #[arg(long)]
+    pub list: bool,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    Binprot,
+    Json,
+    /// Hex encoded binprot.
+    Hex,
+}
+
+pub struct Conversion {
+    from: Format,
+    to: Format,
+    hash: bool,
+    input: Vec<u8>,
+}
+
+impl Inspect {
+    pub fn run(self) -> anyhow::Result<()> {
+        if self.list {
+            for (alias, name) in types::ALIASES {
+                println!("{alias} -> {name}");
+            }
+            for name in types::names() {
+                println!("{name}");
+            }
+            return Ok(());
+        }
+        let Some(type_name) = self.type_name else {
+            anyhow::bail!("type name is required");
+        };
+
+        let input = match &self.input {
+            Some(path) => fs::read(path).with_context(|| format!("reading {path:?}"))?,
+            None => {
+                let mut input = Vec::new();
+                std::io::stdin()
+                    .read_to_end(&mut input)
+                    .context("reading stdin")?;
+                input
+            }
+        };
+        let conv = Conversion {
+            from: self.from,
+            to: self.to,
+            hash: self.hash,
+            input,
+        };
+        let output = types::convert(&type_name, &conv)
+            .ok_or_else(|| anyhow::anyhow!("unknown type: {type_name}, see `--list`"))??;
+
+        match &self.output {
+            Some(path) => fs::write(path, output).with_context(|| format!("writing {path:?}")),
+            None => std::io::stdout()
+                .write_all(&output)
+                .context("writing stdout"),
+        }
+    }
+}
+
+impl Conversion {
+    fn run<T>(&self, hash: impl Fn(&T) -> Option<anyhow::Result<String>>) -> anyhow::Result<Vec<u8>>
+    where
+        T: BinProtRead + BinProtWrite + Serialize + DeserializeOwned,
+    {
+        let value = self.decode::<T>()?;
+        if self.hash {
+            match hash(&value) {
+                Some(hash) => eprintln!("hash: {}", hash.context("computing hash")?),
+                None => eprintln!("hash: not supported for this type"),
+            }
+        }
+        self.encode(&value)
+    }
+
+    fn decode<T>(&self) -> anyhow::Result<T>
+    where
+        T: BinProtRead + DeserializeOwned,
+    {
+        let binprot = match self.from {
+            Format::Json => {
+                return serde_json::from_slice(&self.input).context("decoding json");
+            }
+            Format::Binprot => self.input.clone(),
+            Format::Hex => {
+                let input = std::str::from_utf8(&self.input).context("decoding hex")?;
+                let input = input.trim();
+                hex::decode(input.strip_prefix("0x").unwrap_or(input)).context("decoding hex")?
+            }
+        };
+        let mut bytes = binprot.as_slice();
+        let value = T::binprot_read(&mut bytes).context("decoding binprot")?;
+        if !bytes.is_empty() {
+            eprintln!("warning: {} trailing bytes after the value", bytes.len());
+        }
+        Ok(value)
+    }
+
+    fn encode<T>(&self, value: &T) -> anyhow::Result<Vec<u8>>
+    where
+        T: BinProtWrite + Serialize,
+    {
+        let mut output = match self.to {
+            Format::Json => serde_json::to_vec_pretty(value).context("encoding json")?,
+            Format::Binprot | Format::Hex => {
+                let mut bytes = Vec::new();
+                value
+                    .binprot_write(&mut bytes)
+                    .context("encoding binprot")?;
+                bytes
+            }
+        };
+        match self.to {
+            Format::Binprot => {}
+            Format::Json => output.push(b'\n'),
+            Format::Hex => output = format!("{}\n", hex::encode(output)).into_bytes(),
+        }
+        Ok(output)
+    }
+}
@@ -0,0 +1,360 @@
+//! Registry of the types supported by `openmina inspect`.
+
+use mina_p2p_messages::{gossip::GossipNetMessageV2, rpc, rpc_kernel::RpcMethod, v2};
+
+use super::Conversion;
+
+/// Short names for the most commonly inspected types.
+pub const ALIASES: &[(&str, &str)] = &[
+    ("block", "MinaBlockBlockStableV2"),
+    ("header", "MinaBlockHeaderStableV2"),
+    ("protocol-state", "MinaStateProtocolStateValueStableV2"),
+    ("user-command", "MinaBaseUserCommandStableV2"),
+    ("transaction", "MinaTransactionTransactionStableV2"),
+    ("snark-work", "TransactionSnarkWorkTStableV2"),
+    ("scan-state", "TransactionSnarkScanStateStableV2"),
+    (
+        "staged-ledger-aux",
+        "GetStagedLedgerAuxAndPendingCoinbasesAtHashV2.response",
+    ),
+    (
+        "snark-pool-diff",
+        "NetworkPoolSnarkPoolDiffVersionedStableV2",
+    ),
+    (
+        "transaction-pool-diff",
+        "NetworkPoolTransactionPoolDiffVersionedStableV2",
+    ),
+    ("gossip", "GossipNetMessageV2"),
+];
+
+/// Hash of the value, for the types for which it is supported.
+trait InspectHash {
+    fn inspect_hash(&self) -> Option<anyhow::Result<String>>;
+}
+
+macro_rules! v2_types {
+    (@hash $value:expr) => {
+        None
+    };
+    (@hash $value:expr, $v:ident => $hash:expr) => {{
+        let $v = $value;
+        Some($hash)
+    }};
+    ($($name:ident $(=> |$v:ident| $hash:expr)?),* $(,)?) => {
+        $(
+            impl InspectHash for v2::$name {
+                fn inspect_hash(&self) -> Option<anyhow::Result<String>> {
+                    v2_types!(@hash self $(, $v => $hash)?)
+                }
+            }
+        )*
+
+        const V2_TYPES: &[&str] = &[$(stringify!($name)),*];
+
+        fn convert_v2(name: &str, conv: &Conversion) -> Option<anyhow::Result<Vec<u8>>> {
+            match name {
+                $(stringify!($name) => Some(conv.run::<v2::$name>(<v2::$name as InspectHash>::inspect_hash)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+macro_rules! rpc_methods {
+    ($($name:ident),* $(,)?) => {
+        const RPC_METHODS: &[&str] = &[$(stringify!($name)),*];
+
+        fn convert_rpc(name: &str, conv: &Conversion) -> Option<anyhow::Result<Vec<u8>>> {
+            if let Some(method) = name.strip_suffix(".query") {
+                match method {
+                    $(stringify!($name) => {
+                        Some(conv.run::<<rpc::$name as RpcMethod>::Query>(|_| None))
+                    })*
+                    _ => None,
+                }
+            } else if let Some(method) = name.strip_suffix(".response") {
+                match method {
+                    $(stringify!($name) => {
+                        Some(conv.run::<<rpc::$name as RpcMethod>::Response>(|_| None))
+                    })*
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+    };
+}
+
+rpc_methods!(
+    VersionedRpcMenuV1,
+    GetSomeInitialPeersV1ForV2,
+    GetStagedLedgerAuxAndPendingCoinbasesAtHashV2,
+    AnswerSyncLedgerQueryV2,
+    GetTransitionChainV2,
+    GetTransitionChainProofV1ForV2,
+    GetTransitionKnowledgeV1ForV2,
+    GetAncestryV2,
+    BanNotifyV1,
+    GetBestTipV2,
+    GetNodeStatusV2,
+    GetEpochLedgerV2,
+);
+
+v2_types!(
+    MinaBlockBlockStableV2 => |v| Ok(v.hash().to_string()),
+    NetworkPoolTransactionPoolDiffVersionedStableV2,
+    NetworkPoolSnarkPoolDiffVersionedStableV2,
+    MinaBaseSparseLedgerBaseStableV2,
+    MinaBaseAccountBinableArgStableV2,
+    NetworkPeerPeerStableV1,
+    TransactionSnarkScanStateStableV2,
+    MinaBasePendingCoinbaseStableV2,
+    MinaStateProtocolStateValueStableV2 => |v| Ok(v.hash().to_string()),
+    MinaLedgerSyncLedgerQueryStableV1,
+    MinaLedgerSyncLedgerAnswerStableV2,
+    ConsensusProofOfStakeDataConsensusStateValueStableV2,
+    SyncStatusTStableV1,
+    TrustSystemPeerStatusStableV1,
+    BlockchainSnarkBlockchainStableV2,
+    TransactionWitnessStableV2,
+    ProverExtendBlockchainInputStableV2,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse,
+    SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQuery,
+    MinaBaseUserCommandVerifiableStableV2,
+    MinaBaseZkappStatementStableV2,
+    PicklesProofProofsVerified2ReprStableV2StatementFp,
+    PicklesProofProofsVerified2ReprStableV2StatementProofStateDeferredValuesPlonkFeatureFlags,
+    PicklesProofProofsVerified2ReprStableV2PrevEvalsEvalsEvals,
+    PicklesProofProofsVerified2ReprStableV2PrevEvalsEvals,
+    PicklesProofProofsVerified2ReprStableV2PrevEvals,
+    PicklesWrapWireProofStableV1Bulletproof,
+    MinaBaseVerificationKeyWireStableV1WrapIndex,
+    PicklesReducedMessagesForNextProofOverSameFieldWrapChallengesVectorStableV2AChallenge,
+    SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQueryMetrics,
+    TransactionSnarkWorkTStableV2Proofs,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances,
+    PicklesBaseProofsVerifiedStableV1,
+    LimbVectorConstantHex64StableV1,
+    CompositionTypesBranchDataDomainLog2StableV1,
+    CompositionTypesBranchDataStableV1,
+    PicklesReducedMessagesForNextProofOverSameFieldWrapChallengesVectorStableV2A,
+    CompositionTypesDigestConstantStableV1,
+    PicklesProofProofsVerified2ReprStableV2StatementProofStateDeferredValuesPlonk,
+    PicklesProofProofsVerified2ReprStableV2StatementProofStateDeferredValues,
+    PicklesProofProofsVerified2ReprStableV2MessagesForNextWrapProof,
+    PicklesProofProofsVerified2ReprStableV2StatementProofState,
+    PicklesProofProofsVerified2ReprStableV2Statement,
+    PicklesWrapWireProofCommitmentsStableV1,
+    PicklesWrapWireProofEvaluationsStableV1,
+    PicklesWrapWireProofStableV1,
+    PicklesProofProofsVerified2ReprStableV2MessagesForNextStepProof,
+    PicklesReducedMessagesForNextProofOverSameFieldWrapChallengesVectorStableV2,
+    MinaBaseVerificationKeyWireStableV1,
+    PicklesProofProofsVerified2ReprStableV2,
+    PicklesProofProofsVerifiedMaxStableV2,
+    NonZeroCurvePointUncompressedStableV1,
+    SignatureLibPrivateKeyStableV1,
+    UnsignedExtendedUInt64Int64ForVersionTagsStableV1,
+    UnsignedExtendedUInt32StableV1,
+    ProtocolVersionStableV2,
+    MinaNumbersNatMake32StableV1,
+    MinaNumbersGlobalSlotSpanStableV1,
+    MinaNumbersGlobalSlotSinceGenesisMStableV1,
+    MinaNumbersGlobalSlotSinceHardForkMStableV1,
+    SgnStableV1,
+    MinaStateBlockchainStateValueStableV2SignedAmount,
+    CurrencyFeeStableV1,
+    CurrencyAmountStableV1,
+    CurrencyBalanceStableV1,
+    MinaBaseZkappCommandVerifiableStableV1AccountUpdatesDataA,
+    DataHashLibStateHashStableV1,
+    MinaBaseSparseLedgerBaseStableV2Tree,
+    MinaBasePendingCoinbaseMerkleTreeVersionedStableV2Tree,
+    BlockTimeTimeStableV1,
+    MinaBaseAccountIdDigestStableV1,
+    MinaBaseAccountIdStableV2,
+    MinaBaseAccountTimingStableV2,
+    MinaBaseSignatureStableV1,
+    MinaBaseControlStableV2,
+    MinaBaseTokenIdStableV2,
+    MinaBasePaymentPayloadStableV2,
+    MinaBaseLedgerHash0StableV1,
+    MinaBasePermissionsAuthRequiredStableV2,
+    MinaBasePermissionsStableV2,
+    MinaBaseStakeDelegationStableV2,
+    MinaBaseTransactionStatusFailureStableV2,
+    MinaBaseTransactionStatusFailureCollectionStableV1,
+    MinaBaseTransactionStatusStableV2,
+    MinaBaseSignedCommandPayloadCommonStableV2,
+    MinaBaseSignedCommandPayloadBodyStableV2,
+    MinaBaseSignedCommandPayloadStableV2,
+    MinaBaseSignedCommandStableV2 => |v| v.hash().map(|h| h.to_string()).map_err(Into::into),
+    MinaBaseReceiptChainHashStableV1,
+    MinaBaseStateBodyHashStableV1,
+    MinaBaseAccountUpdateUpdateStableV1Timing,
+    MinaBaseAccountUpdateUpdateStableV1Permissions,
+    MinaBaseAccountUpdateUpdateStableV1VerificationKey,
+    MinaBaseAccountUpdateUpdateStableV1Delegate,
+    MinaBaseAccountUpdateUpdateStableV1VotingFor,
+    MinaBaseAccountUpdateUpdateStableV1AppStateA,
+    MinaBaseAccountUpdateUpdateStableV1ZkappUri,
+    MinaBaseZkappPreconditionProtocolStateEpochDataStableV1EpochSeed,
+    MinaBaseZkappPreconditionProtocolStateStableV1SnarkedLedgerHash,
+    MinaBaseZkappPreconditionAccountStableV2ReceiptChainHash,
+    MinaBaseZkappPreconditionAccountStableV2Delegate,
+    MinaBaseZkappPreconditionProtocolStateEpochDataStableV1StartCheckpoint,
+    MinaBaseZkappPreconditionAccountStableV2ProvedState,
+    MinaBaseZkappPreconditionAccountStableV2StateA,
+    MinaBaseZkappStateValueStableV1,
+    MinaBaseZkappAccountStableV2,
+    MinaBaseAccountIndexStableV1,
+    MinaBaseZkappPreconditionProtocolStateEpochDataStableV1EpochLedger,
+    MinaBaseEpochLedgerValueStableV1,
+    MinaBaseEpochSeedStableV1,
+    MinaBaseZkappPreconditionProtocolStateStableV1AmountA,
+    MinaBaseZkappPreconditionAccountStableV2BalanceA,
+    MinaBaseZkappPreconditionProtocolStateStableV1GlobalSlotA,
+    MinaBaseZkappPreconditionProtocolStateStableV1LengthA,
+    MinaBaseZkappPreconditionProtocolStateStableV1Amount,
+    MinaBaseZkappPreconditionAccountStableV2Balance,
+    MinaBaseZkappPreconditionProtocolStateStableV1GlobalSlot,
+    MinaBaseZkappPreconditionProtocolStateStableV1Length,
+    MinaBaseZkappPreconditionAccountStableV2,
+    MinaBaseZkappPreconditionProtocolStateEpochDataStableV1,
+    MinaBaseZkappPreconditionProtocolStateStableV1,
+    MinaBaseAccountUpdateAuthorizationKindStableV1,
+    MinaBaseAccountUpdateMayUseTokenStableV1,
+    MinaBaseAccountUpdateUpdateTimingInfoStableV1,
+    MinaBaseAccountUpdateUpdateStableV1,
+    MinaBaseAccountUpdateAccountPreconditionStableV1,
+    MinaBaseAccountUpdatePreconditionsStableV1,
+    MinaBaseAccountUpdateBodyEventsStableV1,
+    MinaBaseAccountUpdateBodyStableV1,
+    MinaBaseAccountUpdateBodyFeePayerStableV1,
+    MinaBaseAccountUpdateTStableV1,
+    MinaBaseAccountUpdateFeePayerStableV1,
+    MinaBaseZkappCommandTStableV1WireStableV1AccountUpdatesAACallsA,
+    MinaBaseZkappCommandVerifiableStableV1AccountUpdatesAACallsA,
+    MinaBaseZkappCommandTStableV1WireStableV1AccountUpdatesA,
+    MinaBaseZkappCommandVerifiableStableV1AccountUpdatesA,
+    MinaTransactionLogicTransactionAppliedCoinbaseAppliedStableV2Coinbase,
+    MinaTransactionLogicTransactionAppliedFeeTransferAppliedStableV2FeeTransfer,
+    MinaTransactionLogicTransactionAppliedSignedCommandAppliedCommonStableV2UserCommand,
+    StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B,
+    MinaTransactionLogicTransactionAppliedZkappCommandAppliedStableV1Command,
+    MinaBaseZkappCommandVerifiableStableV1AccountUpdatesAA,
+    MinaBaseZkappCommandTStableV1WireStableV1AccountUpdatesAA,
+    MinaBaseZkappCommandCallForestMakeDigestStrAccountUpdateStableV1,
+    MinaBaseZkappCommandCallForestMakeDigestStrForestStableV1,
+    MinaBaseZkappCommandTStableV1WireStableV1 => |v| v.hash().map(|h| h.to_string()).map_err(Into::into),
+    MinaBaseZkappCommandVerifiableStableV1,
+    MinaBaseUserCommandStableV2 => |v| v.hash().map(|h| h.to_string()).map_err(Into::into),
+    MinaBaseFeeTransferSingleStableV2,
+    MinaBaseFeeTransferStableV2,
+    MinaBaseCoinbaseFeeTransferStableV1,
+    MinaBaseCoinbaseStableV1,
+    MinaBasePendingCoinbaseStackIdStableV1,
+    MinaBasePendingCoinbaseCoinbaseStackStableV1,
+    MinaBasePendingCoinbaseStackHashStableV1,
+    MinaBasePendingCoinbaseStateStackStableV1,
+    MinaBasePendingCoinbaseHashBuilderStableV1,
+    MinaBasePendingCoinbaseUpdateActionStableV1,
+    MinaBasePendingCoinbaseUpdateStableV1,
+    MinaBasePendingCoinbaseStackVersionedStableV1,
+    MinaBasePendingCoinbaseHashVersionedStableV1,
+    MinaBasePendingCoinbaseMerkleTreeVersionedStableV2,
+    MinaBaseStagedLedgerHashAuxHashStableV1,
+    MinaBaseStagedLedgerHashPendingCoinbaseAuxStableV1,
+    MinaBaseStagedLedgerHashNonSnarkStableV1,
+    MinaBaseStagedLedgerHashStableV1,
+    MinaBaseStackFrameStableV1,
+    MinaBaseSokMessageStableV1,
+    MinaBaseProtocolConstantsCheckedValueStableV1,
+    MinaBaseProofStableV2,
+    MinaBasePendingCoinbaseWitnessStableV2,
+    MinaBaseCallStackDigestStableV1,
+    MinaBaseFeeWithProverStableV1,
+    NetworkPeerPeerIdStableV1,
+    MinaTransactionTransactionStableV2 => |v| v.hash().map(|h| h.to_string()).map_err(Into::into),
+    MinaTransactionLogicZkappCommandLogicLocalStateValueStableV1,
+    MinaTransactionLogicTransactionAppliedSignedCommandAppliedCommonStableV2,
+    MinaTransactionLogicTransactionAppliedSignedCommandAppliedBodyStableV2,
+    MinaTransactionLogicTransactionAppliedSignedCommandAppliedStableV2,
+    MinaTransactionLogicTransactionAppliedZkappCommandAppliedStableV1,
+    MinaTransactionLogicTransactionAppliedCommandAppliedStableV2,
+    MinaTransactionLogicTransactionAppliedFeeTransferAppliedStableV2,
+    MinaTransactionLogicTransactionAppliedCoinbaseAppliedStableV2,
+    MinaTransactionLogicTransactionAppliedVaryingStableV2,
+    MinaTransactionLogicTransactionAppliedStableV2,
+    MerkleAddressBinableArgStableV1,
+    TrustSystemBannedStatusStableV1,
+    ConsensusVrfOutputTruncatedStableV1,
+    ConsensusStakeProofStableV2,
+    ConsensusBodyReferenceStableV1,
+    ConsensusGlobalSlotStableV1,
+    ConsensusProofOfStakeDataEpochDataStakingValueVersionedValueStableV1,
+    ConsensusProofOfStakeDataEpochDataNextValueVersionedValueStableV1,
+    MinaStateBlockchainStateValueStableV2LedgerProofStatementSource,
+    MinaStateSnarkedLedgerStatePendingCoinbaseStackStateInitStackStableV1,
+    MinaStateBlockchainStateValueStableV2LedgerProofStatement,
+    MinaStateSnarkedLedgerStateStableV2,
+    MinaStateSnarkedLedgerStateWithSokStableV2,
+    MinaStateBlockchainStateValueStableV2,
+    MinaStateSnarkTransitionValueStableV2,
+    MinaStateProtocolStateBodyValueStableV2,
+    TransactionSnarkProofStableV2,
+    TransactionSnarkStableV2,
+    LedgerProofProdStableV2,
+    TransactionSnarkWorkStatementStableV2,
+    TransactionSnarkWorkTStableV2,
+    StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2Coinbase,
+    StagedLedgerDiffDiffPreDiffWithAtMostOneCoinbaseStableV2Coinbase,
+    StagedLedgerDiffDiffFtStableV1,
+    StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2,
+    StagedLedgerDiffDiffPreDiffWithAtMostOneCoinbaseStableV2,
+    StagedLedgerDiffDiffDiffStableV2,
+    StagedLedgerDiffDiffStableV2,
+    StagedLedgerDiffBodyStableV1,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0,
+    ParallelScanSequenceNumberStableV1,
+    ParallelScanJobStatusStableV1,
+    ParallelScanWeightStableV1,
+    TransactionSnarkScanStateStableV2ScanStateTreesABaseT1Full,
+    TransactionSnarkScanStateStableV2ScanStateTreesABaseT1,
+    TransactionSnarkScanStateStableV2ScanStateTreesAMergeT1Full,
+    TransactionSnarkScanStateStableV2ScanStateTreesAMergeT1,
+    TransactionSnarkScanStateStableV2ScanState,
+    TransactionSnarkScanStateTransactionWithWitnessStableV2,
+    TransactionSnarkScanStateLedgerProofWithSokMessageStableV2,
+    MinaBlockHeaderStableV2 => |v| Ok(v.hash().to_string()),
+    NetworkPoolSnarkPoolDiffVersionedStableV2AddSolvedWork1,
+    SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQueryMetricsA1,
+    TransactionSnarkScanStateStableV2PreviousIncompleteZkappUpdates1,
+);
+
+/// Names of all supported types.
+pub fn names() -> impl Iterator<Item = String> {
+    let rpc = RPC_METHODS
+        .iter()
+        .flat_map(|name| [format!("{name}.query"), format!("{name}.response")]);
+    std::iter::once("GossipNetMessageV2".to_owned())
+        .chain(V2_TYPES.iter().map(|name| name.to_string()))
+        .chain(rpc)
+}
+
+/// Run conversion for the type with the given name or alias.
+///
+/// Returns `None` if the type is unknown.
+pub fn convert(name: &str, conv: &Conversion) -> Option<anyhow::Result<Vec<u8>>> {
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, name)| name);
+    match name {
+        "GossipNetMessageV2" => Some(conv.run::<GossipNetMessageV2>(|_| None)),
+        _ => convert_v2(name, conv).or_else(|| convert_rpc(name, conv)),
+    }
+}
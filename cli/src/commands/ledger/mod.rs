@@ -0,0 +1,252 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use ledger::BaseLedger;
+use mina_p2p_messages::v2;
+use node::{
+    account::AccountPublicKey,
+    daemon_json,
+    ledger::{LedgerExport, LedgerExportKind, LedgerExportMerklePathElem, LedgerExportQuery},
+    transition_frontier::TransitionFrontierSnapshot,
+};
+use openmina_node_native::persistence;
+use reqwest::Url;
+use serde::Serialize;
+
+/// Export ledgers in the format used by the Mina daemon.
+#[derive(Debug, clap::Args)]
+pub struct Ledger {
+    #[command(subcommand)]
+    command: LedgerCommand,
+}
+
+impl Ledger {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self.command {
+            LedgerCommand::Export(command) => command.run(),
+            LedgerCommand::Account(command) => command.run(),
+        }
+    }
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum LedgerCommand {
+    /// Export all accounts of the ledger along with its hash, compatible
+    /// with `mina ledger export`.
+    Export(Export),
+    /// Print the account along with its merkle path.
+    Account(Account),
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Kind {
+    /// Staking epoch ledger.
+    Staking,
+    /// Next epoch ledger.
+    Next,
+    /// Snarked ledger of the transition frontier root.
+    Snarked,
+    /// Staged ledger of the best tip.
+    Staged,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Source {
+    /// Http server address of the running node.
+    #[arg(long, default_value = "http://127.0.0.1:3000")]
+    pub node: Url,
+
+    /// Read the ledger from a transition frontier snapshot (see
+    /// `openmina snapshot export`) instead of the running node.
+    ///
+    /// Epoch ledgers are the ones of the snapshot's root block. Staged
+    /// ledger isn't included in snapshots.
+    #[arg(long, conflicts_with = "work_dir")]
+    pub snapshot: Option<PathBuf>,
+
+    /// Read the ledger from the work dir of a stopped node. Node saves
+    /// its transition frontier root ledgers there on shutdown.
+    ///
+    /// Same as `--snapshot` pointed to the saved snapshot.
+    #[arg(long)]
+    pub work_dir: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Export {
+    #[arg(long, short, value_enum)]
+    pub kind: Kind,
+
+    #[command(flatten)]
+    pub source: Source,
+
+    /// Output file. Writes to stdout if not set.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Account {
+    /// Public key of the account.
+    pub public_key: AccountPublicKey,
+
+    #[arg(long, short, value_enum, default_value_t = Kind::Staged)]
+    pub kind: Kind,
+
+    #[command(flatten)]
+    pub source: Source,
+}
+
+#[derive(Serialize)]
+struct AccountWithMerklePath {
+    ledger_hash: v2::LedgerHash,
+    index: u64,
+    account: daemon_json::Account,
+    merkle_path: Vec<LedgerExportMerklePathElem>,
+}
+
+impl Export {
+    pub fn run(self) -> anyhow::Result<()> {
+        let export = self.source.ledger_export(self.kind, None)?;
+        let ledger = daemon_json::Ledger {
+            accounts: Some(
+                export
+                    .accounts
+                    .iter()
+                    .map(|account| daemon_json::Account::from(&account.account))
+                    .collect(),
+            ),
+            num_accounts: None,
+            balances: None,
+            hash: Some(export.ledger_hash.to_string()),
+            s3_data_hash: None,
+            name: None,
+            add_genesis_winner: None,
+        };
+
+        let mut output = serde_json::to_vec_pretty(&ledger).context("encoding json")?;
+        output.push(b'\n');
+        match &self.output {
+            Some(path) => {
+                fs::write(path, output).with_context(|| format!("writing {path:?}"))?;
+                eprintln!(
+                    "exported {} accounts of ledger {} to {path:?}",
+                    export.accounts.len(),
+                    export.ledger_hash,
+                );
+                Ok(())
+            }
+            None => std::io::stdout()
+                .write_all(&output)
+                .context("writing stdout"),
+        }
+    }
+}
+
+impl Account {
+    pub fn run(self) -> anyhow::Result<()> {
+        let export = self
+            .source
+            .ledger_export(self.kind, Some(&self.public_key))?;
+        let Some(account) = export.accounts.into_iter().next() else {
+            anyhow::bail!("account {} not found", self.public_key);
+        };
+        let account = AccountWithMerklePath {
+            ledger_hash: export.ledger_hash,
+            index: account.index,
+            account: daemon_json::Account::from(&account.account),
+            merkle_path: account.merkle_path.unwrap_or_default(),
+        };
+        println!("{}", serde_json::to_string_pretty(&account)?);
+        Ok(())
+    }
+}
+
+impl Source {
+    fn ledger_export(
+        &self,
+        kind: Kind,
+        public_key: Option<&AccountPublicKey>,
+    ) -> anyhow::Result<LedgerExport> {
+        let kind = LedgerExportKind::from(kind);
+        if let Some(work_dir) = &self.work_dir {
+            let work_dir = shellexpand::full(work_dir)?.into_owned();
+            let path = persistence::ledger_snapshot_path(work_dir);
+            if !path.exists() {
+                anyhow::bail!("{path:?} not found, node didn't save its ledgers yet");
+            }
+            return Self::ledger_export_from_snapshot(&path, kind, public_key);
+        }
+        match &self.snapshot {
+            Some(path) => Self::ledger_export_from_snapshot(path, kind, public_key),
+            None => self.ledger_export_from_node(kind, public_key),
+        }
+    }
+
+    fn ledger_export_from_node(
+        &self,
+        kind: LedgerExportKind,
+        public_key: Option<&AccountPublicKey>,
+    ) -> anyhow::Result<LedgerExport> {
+        let url = self.node.join("ledger/export")?;
+        let query = LedgerExportQuery {
+            kind,
+            public_key: public_key.cloned(),
+        };
+        // Exporting the whole ledger may take a while.
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(600))
+            .build()?;
+        let response = client
+            .get(url.clone())
+            .query(&query)
+            .send()
+            .with_context(|| format!("requesting ledger from {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().unwrap_or_default();
+            anyhow::bail!("node responded with {status}: {error}");
+        }
+        response.json().context("decoding ledger")
+    }
+
+    fn ledger_export_from_snapshot(
+        path: &Path,
+        kind: LedgerExportKind,
+        public_key: Option<&AccountPublicKey>,
+    ) -> anyhow::Result<LedgerExport> {
+        if kind == LedgerExportKind::Staged {
+            anyhow::bail!("staged ledger isn't included in snapshots");
+        }
+        let bytes = fs::read(path).with_context(|| format!("reading {path:?}"))?;
+        let snapshot =
+            TransitionFrontierSnapshot::read(bytes.as_slice()).context("decoding snapshot")?;
+        let snapshot = snapshot.verify().context("verifying snapshot")?;
+        let root_block = &snapshot.root_block;
+        let ledger_hash = kind.ledger_hash(root_block, root_block);
+        let mask = snapshot
+            .snarked_ledgers
+            .into_iter()
+            .find_map(|mut mask| {
+                (&v2::LedgerHash::from_fp(mask.merkle_root()) == ledger_hash).then_some(mask)
+            })
+            .with_context(|| format!("ledger {ledger_hash} isn't included in the snapshot"))?;
+        LedgerExport::from_mask(mask, public_key).map_err(anyhow::Error::msg)
+    }
+}
+
+impl From<Kind> for LedgerExportKind {
+    fn from(value: Kind) -> Self {
+        match value {
+            Kind::Staking => Self::Staking,
+            Kind::Next => Self::Next,
+            Kind::Snarked => Self::Snarked,
+            Kind::Staged => Self::Staged,
+        }
+    }
+}
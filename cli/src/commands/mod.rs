@@ -1,9 +1,20 @@
 pub mod build_info;
+pub mod inspect;
+pub mod ledger;
 pub mod misc;
 pub mod node;
 pub mod replay;
+pub mod snapshot;
 pub mod snark;
 
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use openmina_core::constants::ForkConstants;
+use openmina_core::network::CustomNetworkConfig;
+
 #[derive(Debug, clap::Parser)]
 #[command(name = "openmina", about = "Openmina Cli")]
 pub struct OpenminaCli {
@@ -17,6 +28,11 @@ pub struct OpenminaCli {
     /// Select the network (devnet or mainnet)
     pub network: Network,
 
+    /// Network config file defining a private network. Takes precedence
+    /// over `--network`.
+    #[arg(global = true, long, env = "OPENMINA_NETWORK_CONFIG")]
+    pub network_config: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -27,6 +43,27 @@ pub enum Network {
     Mainnet,
 }
 
+/// Reads a network config file, resolving the genesis config path
+/// relative to the file's directory.
+pub fn network_config_load(path: &Path) -> anyhow::Result<CustomNetworkConfig> {
+    let file = File::open(path).with_context(|| format!("network config file {path:?}"))?;
+    let mut config: CustomNetworkConfig =
+        serde_json::from_reader(file).with_context(|| format!("network config file {path:?}"))?;
+    if let (Some(genesis_config), Some(dir)) = (&mut config.genesis_config, path.parent()) {
+        *genesis_config = dir.join(&*genesis_config);
+    }
+    Ok(config)
+}
+
+/// Hard fork constants from the `proof.fork` section of the genesis
+/// config at `path`.
+pub fn fork_constants(path: &Path) -> anyhow::Result<Option<ForkConstants>> {
+    let file = File::open(path).with_context(|| format!("config file {path:?}"))?;
+    let proof = node::daemon_json::DaemonJson::read_proof(BufReader::new(file))
+        .with_context(|| format!("config file {path:?}"))?;
+    Ok(proof.and_then(|proof| proof.fork))
+}
+
 #[derive(Debug, clap::Subcommand)]
 #[allow(clippy::large_enum_variant)]
 pub enum Command {
@@ -37,9 +74,24 @@ pub enum Command {
     Misc(misc::Misc),
     Replay(replay::Replay),
     BuildInfo(build_info::Command),
+    /// Convert mina p2p messages between binprot, JSON and hex.
+    Inspect(inspect::Inspect),
+    /// Transition frontier snapshots for bootstrapping nodes.
+    Snapshot(snapshot::Snapshot),
+    /// Export ledgers and query accounts with their merkle paths.
+    Ledger(ledger::Ledger),
 }
 
 impl Command {
+    /// Genesis config passed to the command, overriding the one of the
+    /// network config.
+    pub fn genesis_config(&self) -> Option<&Path> {
+        match self {
+            Self::Node(v) => v.config.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn run(self) -> anyhow::Result<()> {
         match self {
             Self::Snark(v) => v.run(),
@@ -47,6 +99,9 @@ impl Command {
             Self::Misc(v) => v.run(),
             Self::Replay(v) => v.run(),
             Self::BuildInfo(v) => v.run(),
+            Self::Inspect(v) => v.run(),
+            Self::Snapshot(v) => v.run(),
+            Self::Ledger(v) => v.run(),
         }
     }
 }
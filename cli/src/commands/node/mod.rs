@@ -1,17 +1,33 @@
-use std::{fs::File, path::PathBuf, sync::Arc};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context;
+use node::ledger::PrecomputedBlockExport;
 use node::{account::AccountSecretKey, transition_frontier::genesis::GenesisConfig};
 
 use reqwest::Url;
+use tokio::signal::unix::{signal, SignalKind};
 
 use node::core::log::inner::Level;
+use node::core::log::LogFilters;
 use node::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
 use node::p2p::identity::SecretKey;
 use node::service::Recorder;
-use node::SnarkerStrategy;
+use node::{SnarkPoolConfig, SnarkerStrategy};
 
-use openmina_node_native::{tracing, NodeBuilder};
+use openmina_node_native::tracing::LogFormat;
+use openmina_node_native::verifier_pool::DEFAULT_VERIFIER_THREADS;
+use openmina_node_native::{persistence, tracing, NodeBuilder};
+
+/// How often known peers are saved to the work dir while the node is running.
+const SAVE_PEERS_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// SQLite archive written in the work dir when running with `--archive`.
+const ARCHIVE_FILE: &str = "archive.sqlite";
 
 /// Openmina node
 #[derive(Debug, clap::Args)]
@@ -35,6 +51,18 @@ pub struct Node {
     #[arg(long, short, env, default_value = "info")]
     pub verbosity: Level,
 
+    /// Log output format, `text` or `json`.
+    #[arg(long, env, default_value = "text")]
+    pub log_format: LogFormat,
+
+    /// Log levels of subsystems overriding `--verbosity`, e.g.
+    /// `p2p=warn,sync=debug`.
+    ///
+    /// Subsystems: p2p, sync, ledger, block_producer, snark. Can be
+    /// changed at runtime with `POST /log/filters`.
+    #[arg(long, env)]
+    pub log_filters: Option<LogFilters>,
+
     #[arg(long, short = 'P', alias = "peer")]
     pub peers: Vec<P2pConnectionOutgoingInitOpts>,
 
@@ -65,6 +93,17 @@ pub struct Node {
     /// MINA_PRIVKEY_PASS must be set to decrypt the keyfile
     #[arg(long, env)]
     pub producer_key: Option<PathBuf>,
+
+    /// Enable block producer in shadow mode with this (pretend) key file.
+    ///
+    /// Full block production pipeline runs for every slot, but produced
+    /// blocks are never broadcasted. Instead they are compared against
+    /// canonical blocks and reported in block producer stats.
+    ///
+    /// MINA_PRIVKEY_PASS must be set to decrypt the keyfile
+    #[arg(long, env, conflicts_with = "producer_key")]
+    pub shadow_producer_key: Option<PathBuf>,
+
     /// Snark fee, in Mina
     #[arg(long, env, default_value_t = 1_000_000)]
     pub snarker_fee: u64,
@@ -72,6 +111,20 @@ pub struct Node {
     #[arg(long, env, default_value = "seq")]
     pub snarker_strategy: SnarkerStrategy,
 
+    /// Maximum number of snarks kept for jobs which are no longer in the
+    /// scan state. When exceeded, snarks with the highest fee get evicted.
+    #[arg(long, env, default_value_t = SnarkPoolConfig::default().max_stale_snarks)]
+    pub max_stale_snarks: usize,
+
+    /// Maximum number of snark job commitments a single peer can hold at
+    /// a time.
+    #[arg(long, env, default_value_t = SnarkPoolConfig::default().max_commitments_per_peer)]
+    pub max_commitments_per_peer: usize,
+
+    /// Number of threads verifying block and snark work proofs.
+    #[arg(long, env, default_value_t = DEFAULT_VERIFIER_THREADS)]
+    pub verifier_threads: usize,
+
     #[arg(long, default_value = "none", env)]
     pub record: String,
 
@@ -83,11 +136,39 @@ pub struct Node {
     // TODO: make this argument required.
     #[arg(short = 'c', long, env)]
     pub config: Option<PathBuf>,
+
+    /// Snapshot written by `openmina snapshot export` to bootstrap from,
+    /// instead of syncing ledgers from peers.
+    #[arg(long, env)]
+    pub bootstrap_snapshot: Option<PathBuf>,
+
+    /// Check lightweight invariants while running the node. Violations
+    /// are logged and available at `GET /invariants/violations`.
+    #[arg(long, env)]
+    pub check_invariants: bool,
+
+    /// Write applied blocks and their transactions to the SQLite archive
+    /// `archive.sqlite` in the work dir.
+    #[arg(long, env)]
+    pub archive: bool,
+
+    /// Write each applied block as `<network>-<height>-<hash>.json` in
+    /// the precomputed block format to this directory.
+    #[arg(long, env, conflicts_with = "precomputed_blocks_file")]
+    pub precomputed_blocks_dir: Option<PathBuf>,
+
+    /// Append each applied block in the precomputed block format to
+    /// this file, one json per line.
+    #[arg(long, env)]
+    pub precomputed_blocks_file: Option<PathBuf>,
 }
 
 impl Node {
     pub fn run(self) -> anyhow::Result<()> {
-        tracing::initialize(self.verbosity);
+        tracing::initialize_with_format(self.log_format, self.verbosity);
+        if let Some(filters) = &self.log_filters {
+            node::core::log::set_log_filters(filters);
+        }
 
         rayon::ThreadPoolBuilder::new()
             .num_threads(num_cpus::get().max(2) - 1)
@@ -95,7 +176,12 @@ impl Node {
             .build_global()
             .context("failed to initialize threadpool")?;
 
-        let (daemon_conf, genesis_conf) = match self.config {
+        let config = self.config.or_else(|| {
+            openmina_core::NetworkConfig::global()
+                .genesis_config
+                .clone()
+        });
+        let (daemon_conf, genesis_conf) = match config {
             Some(config) => {
                 let reader = File::open(config).context("config file {config:?}")?;
                 let config: node::daemon_json::DaemonJson =
@@ -141,23 +227,50 @@ impl Node {
         if let Some(producer_key_path) = self.producer_key {
             node_builder.block_producer_from_file(producer_key_path)?;
         }
+        if let Some(producer_key_path) = self.shadow_producer_key {
+            node_builder
+                .block_producer_from_file(producer_key_path)?
+                .block_producer_shadow()?;
+        }
 
         if let Some(sec_key) = self.run_snarker {
             node_builder.snarker(sec_key, self.snarker_fee, self.snarker_strategy);
         }
+        node_builder.snark_pool_config(SnarkPoolConfig {
+            max_stale_snarks: self.max_stale_snarks,
+            max_commitments_per_peer: self.max_commitments_per_peer,
+        });
+
+        if let Some(path) = self.bootstrap_snapshot {
+            node_builder.bootstrap_snapshot(path)?;
+        }
+        if let Some(dir) = self.precomputed_blocks_dir {
+            node_builder.precomputed_block_export(PrecomputedBlockExport::Dir(dir));
+        } else if let Some(path) = self.precomputed_blocks_file {
+            node_builder.precomputed_block_export(PrecomputedBlockExport::Log(path));
+        }
+        self.check_invariants
+            .then(|| node_builder.check_invariants());
+        node_builder.verifier_threads(self.verifier_threads);
 
         let work_dir = shellexpand::full(&self.work_dir).unwrap().into_owned();
 
+        if self.archive {
+            node_builder.archive(Path::new(&work_dir).join(ARCHIVE_FILE))?;
+        }
+
         node_builder
+            .restore_from_work_dir(&work_dir)
             .http_server(self.port)
             .gather_stats()
             .record(match self.record.trim() {
                 "none" => Recorder::None,
-                "state-with-input-actions" => Recorder::only_input_actions(work_dir),
+                "state-with-input-actions" => Recorder::only_input_actions(&work_dir),
                 _ => panic!("unknown --record strategy"),
             });
 
         let mut node = node_builder.build().context("node build failed!")?;
+        tracing::set_node_id(node.state().p2p.my_id());
 
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
@@ -165,7 +278,38 @@ impl Node {
             .build()
             .unwrap();
 
-        runtime.block_on(node.run_forever());
+        runtime.block_on(async {
+            let mut save_peers_interval = tokio::time::interval(SAVE_PEERS_INTERVAL);
+            save_peers_interval.tick().await;
+            let mut terminate =
+                signal(SignalKind::terminate()).context("listening for termination signal")?;
+            loop {
+                tokio::select! {
+                    _ = node.run_forever() => break,
+                    res = tokio::signal::ctrl_c() => {
+                        res.context("listening for shutdown signal")?;
+                        break;
+                    }
+                    _ = terminate.recv() => break,
+                    _ = save_peers_interval.tick() => {}
+                }
+                if let Err(err) = persistence::peers_save(&work_dir, node.state()) {
+                    openmina_core::warn!(
+                        openmina_core::log::system_time();
+                        kind = "PeersSave",
+                        summary = format!("failed to save known peers: {err:#}")
+                    );
+                }
+            }
+            anyhow::Ok(())
+        })?;
+
+        openmina_core::info!(
+            openmina_core::log::system_time();
+            kind = "Shutdown",
+            summary = format!("shutting down, saving node state to {work_dir}")
+        );
+        persistence::save(&work_dir, &node).context("saving node state")?;
 
         Ok(())
     }
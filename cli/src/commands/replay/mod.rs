@@ -1,3 +1,9 @@
+pub mod replay_block_application;
+pub use replay_block_application::ReplayBlockApplication;
+
+pub mod replay_blocks;
+pub use replay_blocks::ReplayBlocks;
+
 pub mod replay_state_with_input_actions;
 pub use replay_state_with_input_actions::ReplayStateWithInputActions;
 
@@ -10,12 +16,16 @@ pub struct Replay {
 #[derive(Debug, clap::Subcommand)]
 pub enum ReplayCommand {
     StateWithInputActions(ReplayStateWithInputActions),
+    BlockApplication(ReplayBlockApplication),
+    Blocks(ReplayBlocks),
 }
 
 impl Replay {
     pub fn run(self) -> anyhow::Result<()> {
         match self.command {
             ReplayCommand::StateWithInputActions(v) => v.run(),
+            ReplayCommand::BlockApplication(v) => v.run(),
+            ReplayCommand::Blocks(v) => v.run(),
         }
     }
 }
@@ -0,0 +1,79 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use node::ledger::{LedgerApplicationDump, LedgerApplicationReplayPass};
+
+#[derive(Debug, clap::Args)]
+/// Re-apply blocks from a failed block application dump
+/// (`/tmp/failed_application_ctx_<height>.binprot`), tracing ledger
+/// effects and fee excess of each transaction.
+pub struct ReplayBlockApplication {
+    /// Path to the dump.
+    pub path: PathBuf,
+
+    /// Print the trace as json, e.g. to diff it against the OCaml node.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl ReplayBlockApplication {
+    pub fn run(self) -> anyhow::Result<()> {
+        let bytes = fs::read(&self.path).with_context(|| format!("reading {:?}", self.path))?;
+        let dump = LedgerApplicationDump::read(bytes.as_slice()).context("decoding dump")?;
+        let replay = dump.replay();
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&replay)?);
+        } else {
+            for block in &replay.blocks {
+                println!(
+                    "block {}, {}, global slot: {}",
+                    block.height, block.hash, block.global_slot
+                );
+                for txn in &block.transactions {
+                    println!(
+                        "  #{} expected status: {:?}, status: {:?}, fee excess: {:?}, supply increase: {:?}",
+                        txn.index,
+                        txn.expected_status,
+                        txn.status,
+                        txn.fee_excess,
+                        txn.supply_increase,
+                    );
+                    print_pass("first pass", txn.first_pass.as_ref());
+                    print_pass("second pass", txn.second_pass.as_ref());
+                    if let Some(error) = &txn.error {
+                        println!("    error: {error}");
+                    }
+                }
+                println!("  fee excess: {:?}", block.fee_excess);
+                println!("  expected hashes: {:?}", block.expected_hashes);
+                println!("  hashes: {:?}", block.hashes);
+            }
+        }
+
+        match replay.divergence() {
+            Some((block, divergence)) => {
+                anyhow::bail!("block {} diverges: {divergence}", block.height)
+            }
+            None => {
+                eprintln!("all {} blocks applied successfully", replay.blocks.len());
+                Ok(())
+            }
+        }
+    }
+}
+
+fn print_pass(name: &str, pass: Option<&LedgerApplicationReplayPass>) {
+    let Some(pass) = pass else {
+        return;
+    };
+    println!(
+        "    {name}: {} -> {}",
+        pass.ledger_hash_before, pass.ledger_hash_after
+    );
+    for account in &pass.accounts {
+        println!("      {:?}", account.account_id);
+        println!("        before: {:?}", account.before);
+        println!("        after:  {:?}", account.after);
+    }
+}
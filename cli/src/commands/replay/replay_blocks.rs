@@ -0,0 +1,266 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use ledger::{AccountIndex, BaseLedger};
+use mina_p2p_messages::v2;
+use node::{
+    core::block::ArcBlockWithHash,
+    ledger::{LedgerCtx, PrecomputedBlock},
+    transition_frontier::TransitionFrontierSnapshot,
+};
+
+#[derive(Debug, clap::Args)]
+/// Apply precomputed blocks, written by the node with
+/// `--precomputed-blocks-dir` or `--precomputed-blocks-file`, on top of
+/// the ledgers of a snapshot. Only the longest chain descending from the
+/// snapshot's root block is applied.
+///
+/// On a staged ledger hash mismatch the block application is dumped to
+/// `/tmp`, see `openmina replay block-application`.
+pub struct ReplayBlocks {
+    /// Directory with precomputed block json files, or a file with one
+    /// precomputed block json per line.
+    pub path: PathBuf,
+
+    /// Snapshot written by `openmina snapshot export`, whose root block
+    /// is the parent of the first block to apply.
+    #[arg(long)]
+    pub snapshot: PathBuf,
+
+    /// Check that accounts accessed by each block match the ones in
+    /// the precomputed block.
+    #[arg(long)]
+    pub check_accounts: bool,
+}
+
+impl ReplayBlocks {
+    pub fn run(self) -> anyhow::Result<()> {
+        let blocks = precomputed_blocks_load(&self.path)?;
+
+        let file = File::open(&self.snapshot)
+            .with_context(|| format!("opening snapshot {:?}", self.snapshot))?;
+        let snapshot = TransitionFrontierSnapshot::read(BufReader::new(file))
+            .with_context(|| format!("reading snapshot {:?}", self.snapshot))?
+            .verify()
+            .context("verifying snapshot")?;
+
+        let root = snapshot.root_block;
+        let mut ctx = LedgerCtx::default();
+        for mask in snapshot.snarked_ledgers {
+            ctx.insert_additional_snarked_ledger(mask);
+        }
+        let snarked_ledger_hash = root.snarked_ledger_hash().clone();
+        ctx.copy_snarked_ledger_contents_for_sync(
+            snarked_ledger_hash.clone(),
+            snarked_ledger_hash.clone(),
+            true,
+        )
+        .map_err(anyhow::Error::msg)?;
+        let (_, result) = ctx.staged_ledger_reconstruct_sync(
+            snarked_ledger_hash,
+            Some(snapshot.staged_ledger_parts),
+        );
+        result
+            .map_err(anyhow::Error::msg)
+            .context("reconstructing root staged ledger")?;
+        eprintln!("root block {}, {}", root.height(), root.hash());
+
+        let chain = longest_chain(&root, blocks);
+        if chain.is_empty() {
+            anyhow::bail!("no blocks descending from the root block {}", root.hash());
+        }
+
+        let mut pred_block = root;
+        let mut applied = 0;
+        for (block, precomputed) in chain {
+            ctx.block_apply(block.clone(), pred_block)
+                .map_err(anyhow::Error::msg)
+                .with_context(|| format!("applying block {}, {}", block.height(), block.hash()))?;
+
+            if self.check_accounts {
+                let (ledger, _) = ctx
+                    .mask(block.staged_ledger_hash())
+                    .context("staged ledger of the applied block is missing")?;
+                for (index, expected) in &precomputed.data.accounts_accessed {
+                    let account = ledger
+                        .get_at_index(AccountIndex(*index))
+                        .map(|account| v2::MinaBaseAccountBinableArgStableV2::from(&*account));
+                    if account.as_ref() != Some(expected) {
+                        anyhow::bail!(
+                            "block {}, {}: account at index {index} diverges\n  expected: {expected:?}\n  found:    {account:?}",
+                            block.height(),
+                            block.hash()
+                        );
+                    }
+                }
+            }
+
+            println!("block {}, {} applied", block.height(), block.hash());
+            applied += 1;
+            pred_block = block;
+        }
+
+        eprintln!("all {applied} blocks applied successfully");
+        Ok(())
+    }
+}
+
+/// Blocks from the child of `root` up to the highest block descending
+/// from it.
+fn longest_chain<T>(
+    root: &ArcBlockWithHash,
+    mut blocks: Vec<(ArcBlockWithHash, T)>,
+) -> Vec<(ArcBlockWithHash, T)> {
+    blocks.sort_by_key(|(block, _)| block.height());
+    let mut descendants = BTreeMap::new();
+    for (block, precomputed) in blocks {
+        if block.pred_hash() == root.hash() || descendants.contains_key(block.pred_hash()) {
+            descendants.insert(block.hash().clone(), (block, precomputed));
+        }
+    }
+
+    let mut tip = descendants
+        .values()
+        .max_by_key(|(block, _)| block.height())
+        .map(|(block, _)| block.hash().clone());
+    let mut chain = Vec::new();
+    while let Some(entry) = tip.and_then(|hash| descendants.remove(&hash)) {
+        tip = Some(entry.0.pred_hash().clone());
+        chain.push(entry);
+    }
+    chain.reverse();
+    chain
+}
+
+/// Loads precomputed blocks from a directory of json files or from
+/// a file with a json per line.
+fn precomputed_blocks_load(
+    path: &Path,
+) -> anyhow::Result<Vec<(ArcBlockWithHash, PrecomputedBlock)>> {
+    let parse = |json: &str, source: &dyn std::fmt::Debug| {
+        let precomputed: PrecomputedBlock = serde_json::from_str(json)
+            .with_context(|| format!("decoding precomputed block {source:?}"))?;
+        anyhow::Ok((precomputed.block(), precomputed))
+    };
+
+    if path.is_dir() {
+        let mut paths = fs::read_dir(path)
+            .with_context(|| format!("reading {path:?}"))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        paths
+            .iter()
+            .map(|path| {
+                let json = fs::read_to_string(path).with_context(|| format!("reading {path:?}"))?;
+                parse(&json, path)
+            })
+            .collect()
+    } else {
+        let file = File::open(path).with_context(|| format!("opening {path:?}"))?;
+        BufReader::new(file)
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|(i, line)| parse(&line?, &format!("{path:?}:{}", i + 1)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use node::{
+        core::block::{best_tip_fixture, child_block},
+        ledger::PrecomputedBlockData,
+    };
+
+    use super::*;
+
+    fn precomputed(block: &ArcBlockWithHash) -> PrecomputedBlock {
+        let header = block.header();
+        PrecomputedBlock {
+            version: PrecomputedBlock::VERSION,
+            data: PrecomputedBlockData {
+                scheduled_time: header.protocol_state.body.blockchain_state.timestamp,
+                protocol_state: header.protocol_state.clone(),
+                protocol_state_proof: header.protocol_state_proof.clone(),
+                staged_ledger_diff: block.body().staged_ledger_diff.clone(),
+                delta_transition_chain_proof: header.delta_block_chain_proof.clone(),
+                protocol_version: header.current_protocol_version.clone(),
+                proposed_protocol_version: header.proposed_protocol_version_opt.clone(),
+                accounts_accessed: vec![],
+                accounts_created: vec![],
+                tokens_used: vec![],
+                scan_state: None,
+            },
+        }
+    }
+
+    fn hashes<T>(blocks: &[(ArcBlockWithHash, T)]) -> Vec<String> {
+        blocks
+            .iter()
+            .map(|(block, _)| block.hash().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn longest_chain_from_root() {
+        let root = best_tip_fixture();
+        let a1 = child_block(&root, 0);
+        let a2 = child_block(&a1, 0);
+        let a3 = child_block(&a2, 0);
+        let b1 = child_block(&root, 1);
+        let b2 = child_block(&b1, 1);
+        let unrelated = child_block(&child_block(&child_block(&root, 2), 2), 2);
+
+        let blocks = [&b2, &a3, &unrelated, &a1, &b1, &a2]
+            .into_iter()
+            .map(|block| (block.clone(), ()))
+            .collect();
+        let chain = longest_chain(&root, blocks);
+        assert_eq!(hashes(&chain), hashes(&[(a1, ()), (a2, ()), (a3, ())]));
+
+        assert!(longest_chain(&root, vec![(unrelated, ())]).is_empty());
+    }
+
+    #[test]
+    fn load_precomputed_blocks() {
+        let root = best_tip_fixture();
+        let b1 = child_block(&root, 0);
+        let b2 = child_block(&b1, 0);
+        let expected = vec![(b1.clone(), ()), (b2.clone(), ())];
+
+        let dir = tempfile::tempdir().unwrap();
+        for block in [&b1, &b2] {
+            let path = dir
+                .path()
+                .join(format!("devnet-{}-{}.json", block.height(), block.hash()));
+            serde_json::to_writer(File::create(path).unwrap(), &precomputed(block)).unwrap();
+        }
+        File::create(dir.path().join("README")).unwrap();
+        let mut blocks = precomputed_blocks_load(dir.path()).unwrap();
+        blocks.sort_by_key(|(block, _)| block.height());
+        assert_eq!(hashes(&blocks), hashes(&expected));
+
+        let path = dir.path().join("blocks.jsonl");
+        let mut file = File::create(&path).unwrap();
+        for block in [&b1, &b2] {
+            serde_json::to_writer(&mut file, &precomputed(block)).unwrap();
+            writeln!(file).unwrap();
+        }
+        writeln!(file).unwrap();
+        let blocks = precomputed_blocks_load(&path).unwrap();
+        assert_eq!(hashes(&blocks), hashes(&expected));
+
+        writeln!(file, "{{}}").unwrap();
+        let err = precomputed_blocks_load(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("blocks.jsonl:4"));
+    }
+}
@@ -0,0 +1,72 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context;
+use node::transition_frontier::TransitionFrontierSnapshot;
+use reqwest::Url;
+
+#[derive(Debug, clap::Args)]
+pub struct Snapshot {
+    #[command(subcommand)]
+    command: SnapshotCommand,
+}
+
+impl Snapshot {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self.command {
+            SnapshotCommand::Export(command) => command.run(),
+        }
+    }
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SnapshotCommand {
+    /// Export transition frontier root of a synced node, so that other
+    /// nodes can bootstrap from it using `--bootstrap-snapshot`.
+    Export(Export),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Export {
+    /// Http server address of the synced node.
+    #[arg(long, default_value = "http://127.0.0.1:3000")]
+    pub node: Url,
+
+    /// Output file.
+    #[arg(long, short)]
+    pub output: PathBuf,
+}
+
+impl Export {
+    pub fn run(self) -> anyhow::Result<()> {
+        let url = self.node.join("transition-frontier/snapshot")?;
+        // Node needs to copy whole ledgers, so it may take a while.
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(600))
+            .build()?;
+        let response = client
+            .get(url.clone())
+            .send()
+            .with_context(|| format!("requesting snapshot from {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().unwrap_or_default();
+            anyhow::bail!("node responded with {status}: {error}");
+        }
+        let bytes = response.bytes().context("reading snapshot")?;
+
+        let snapshot =
+            TransitionFrontierSnapshot::read(bytes.as_ref()).context("decoding snapshot")?;
+        let ledgers = snapshot.snarked_ledgers.len();
+        let snapshot = snapshot.verify().context("verifying snapshot")?;
+
+        std::fs::write(&self.output, &bytes)
+            .with_context(|| format!("writing {:?}", self.output))?;
+        println!(
+            "exported snapshot with root block {} at height {} and {ledgers} snarked ledger(s) to {:?}",
+            snapshot.root_block.hash(),
+            snapshot.root_block.height(),
+            self.output,
+        );
+        Ok(())
+    }
+}
@@ -43,12 +43,31 @@ fn main() -> anyhow::Result<()> {
     unsafe_signal_handlers::setup();
     let app = commands::OpenminaCli::parse();
 
-    let network_init_result = match app.network {
-        commands::Network::Devnet => openmina_core::NetworkConfig::init("devnet"),
-        commands::Network::Mainnet => openmina_core::NetworkConfig::init("mainnet"),
+    let network_config = match (&app.network_config, app.network) {
+        (Some(path), _) => {
+            let config = commands::network_config_load(path)?;
+            openmina_core::NetworkConfig::custom(config)
+        }
+        (None, commands::Network::Devnet) => openmina_core::NetworkConfig::by_name("devnet"),
+        (None, commands::Network::Mainnet) => openmina_core::NetworkConfig::by_name("mainnet"),
     };
+    let mut network_config = network_config.expect("Failed to initialize network configuration");
 
-    network_init_result.expect("Failed to initialize network configuration");
+    // Chain started from a hard fork config, fork constants must be set
+    // globally before any command reads them.
+    let genesis_config = app
+        .command
+        .genesis_config()
+        .or(network_config.genesis_config.as_deref());
+    if let Some(path) = genesis_config {
+        if let Some(fork) = commands::fork_constants(path)? {
+            network_config = network_config.with_fork(fork);
+        }
+    }
+
+    network_config
+        .init_global()
+        .expect("Failed to initialize network configuration");
 
     app.command.run()
 }
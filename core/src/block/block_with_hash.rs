@@ -254,11 +254,11 @@ fn global_slot_diff(header: &BlockHeader) -> u32 {
 }
 
 fn timestamp(header: &BlockHeader) -> Timestamp {
+    // Genesis timestamp is the one of the chain since the last hard fork,
+    // so the slot since hard fork must be used, not the one since genesis.
     let genesis_timestamp = constants(header).genesis_state_timestamp.0.as_u64();
-    let slot = global_slot_since_genesis(header) as u64;
-    // FIXME: this calculation must use values from the protocol constants,
-    // now it assumes 3 minutes blocks.
-    let time_ms = genesis_timestamp + slot * 3 * 60 * 1000;
+    let slot = global_slot(header) as u64;
+    let time_ms = genesis_timestamp + slot * constraint_constants().block_window_duration_ms;
     Timestamp::new(time_ms * 1_000_000)
 }
 
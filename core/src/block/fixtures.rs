@@ -0,0 +1,26 @@
+//! Blocks for tests, built from a best tip recorded from a real network.
+
+use std::sync::Arc;
+
+use super::{ArcBlockWithHash, BlockWithHash};
+
+/// Best tip decoded from a recorded `BestTipWithProof` rpc response.
+pub fn best_tip_fixture() -> ArcBlockWithHash {
+    let json = include_str!("../../../p2p/tests/files/rpc/best_tip_with_proof_response.json");
+    let response: serde_json::Value = serde_json::from_str(json).unwrap();
+    let block = serde_json::from_value(response["BestTipWithProof"]["best_tip"].clone())
+        .expect("block should decode");
+    BlockWithHash::new(Arc::new(block))
+}
+
+/// Child of `parent`, `fork` makes siblings differ.
+pub fn child_block(parent: &ArcBlockWithHash, fork: u64) -> ArcBlockWithHash {
+    let mut block = (*parent.block).clone();
+    let protocol_state = &mut block.header.protocol_state;
+    protocol_state.previous_state_hash = parent.hash().clone();
+    let consensus_state = &mut protocol_state.body.consensus_state;
+    consensus_state.blockchain_length = (parent.height() + 1).into();
+    let timestamp = &mut protocol_state.body.blockchain_state.timestamp;
+    timestamp.0 .0 = (timestamp.0 .0.as_u64() + 180_000 + fork).into();
+    BlockWithHash::new(Arc::new(block))
+}
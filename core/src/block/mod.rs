@@ -3,6 +3,11 @@ pub use block_with_hash::{BlockHeaderWithHash, BlockWithHash};
 
 pub mod genesis;
 
+#[cfg(any(test, feature = "test-fixtures"))]
+mod fixtures;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub use fixtures::{best_tip_fixture, child_block};
+
 use std::sync::Arc;
 
 pub use mina_p2p_messages::v2::MinaBlockBlockStableV2 as Block;
@@ -1,6 +1,7 @@
 use binprot_derive::BinProtWrite;
 use mina_hasher::Fp;
 use mina_p2p_messages::{bigint, number, v2};
+use serde::{Deserialize, Serialize};
 
 pub const GENESIS_PRODUCER_SK: &str = "EKFKgDtU3rcuFTVSEpmpXSkukjmX4cKefYREi6Sdsk7E7wsT7KRw";
 
@@ -14,14 +15,17 @@ pub fn constraint_constants() -> &'static ConstraintConstants {
     NetworkConfig::global().constraint_constants
 }
 
-#[derive(Clone, Debug)]
+/// State of the chain a hard fork starts from, the genesis block follows
+/// the block with `state_hash`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ForkConstants {
+    #[serde(with = "state_hash_fp")]
     pub state_hash: Fp,
     pub blockchain_length: u32,
     pub global_slot_since_genesis: u32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ConstraintConstants {
     pub sub_windows_per_window: u64,
     pub ledger_depth: u64,
@@ -32,8 +36,26 @@ pub struct ConstraintConstants {
     pub coinbase_amount: u64,
     pub supercharged_coinbase_factor: u64,
     pub account_creation_fee: u64,
+    #[serde(default)]
     pub fork: Option<ForkConstants>,
 }
+
+/// (De)serializes state hash field as a base58 encoded [`v2::StateHash`].
+mod state_hash_fp {
+    use mina_hasher::Fp;
+    use mina_p2p_messages::v2::StateHash;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(fp: &Fp, serializer: S) -> Result<S::Ok, S::Error> {
+        StateHash::from_fp(*fp).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fp, D::Error> {
+        StateHash::deserialize(deserializer)?
+            .to_fp()
+            .map_err(|err| D::Error::custom(format!("invalid state hash: {err:?}")))
+    }
+}
 #[derive(Clone, Debug, BinProtWrite)]
 pub struct ForkConstantsUnversioned {
     previous_state_hash: bigint::BigInt,
@@ -1,16 +1,46 @@
 use std::any::Any;
+use std::collections::VecDeque;
+
+use redux::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of most recent violations kept in [`InvariantViolations`].
+pub const MAX_RECENT_INVARIANT_VIOLATIONS: usize = 100;
 
 pub trait InvariantService: redux::Service {
     fn invariants_state(&mut self) -> &mut InvariantsState;
 }
 
 #[derive(Default)]
-pub struct InvariantsState(Vec<Box<dyn 'static + Send + Any>>);
+pub struct InvariantsState {
+    states: Vec<Box<dyn 'static + Send + Any>>,
+    violations: InvariantViolations,
+}
+
+/// Invariant violations found while running the node.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InvariantViolations {
+    /// Whether invariants are being checked.
+    pub enabled: bool,
+    /// Total number of violations since the node started.
+    pub total: u64,
+    /// Most recent violations, oldest first.
+    pub recent: VecDeque<InvariantViolation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvariantViolation {
+    pub time: Timestamp,
+    pub invariant: String,
+    /// Kind of the action which triggered the check.
+    pub action_kind: String,
+    pub violation: String,
+}
 
 impl InvariantsState {
     pub fn get<T: 'static + Send + Default>(&mut self, i: usize) -> &mut T {
-        self.0.resize_with(i + 1, || Box::new(()));
-        let v = self.0.get_mut(i).unwrap();
+        self.states.resize_with(i + 1, || Box::new(()));
+        let v = self.states.get_mut(i).unwrap();
         if v.is::<T>() {
             v.downcast_mut().unwrap()
         } else {
@@ -22,4 +52,22 @@ impl InvariantsState {
     pub fn take(&mut self) -> Self {
         std::mem::take(self)
     }
+
+    pub fn violations(&self) -> &InvariantViolations {
+        &self.violations
+    }
+
+    pub fn violations_mut(&mut self) -> &mut InvariantViolations {
+        &mut self.violations
+    }
+}
+
+impl InvariantViolations {
+    pub fn push(&mut self, violation: InvariantViolation) {
+        self.total = self.total.saturating_add(1);
+        if self.recent.len() >= MAX_RECENT_INVARIANT_VIOLATIONS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(violation);
+    }
 }
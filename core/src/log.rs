@@ -1,3 +1,11 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tracing::Value;
+
+pub use crate::{debug, error, info, trace, warn};
+
 /// Must only be used in logging and even there it's not prefferable.
 ///
 /// This **MUST** only be used in places which doesn't have access to any
@@ -137,6 +145,236 @@ pub trait ActionEvent {
         T: EventContext;
 }
 
-use tracing::Value;
+/// Subsystems whose log level can be configured separately from the
+/// global verbosity.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSubsystem {
+    P2p,
+    Sync,
+    Ledger,
+    BlockProducer,
+    Snark,
+}
 
-pub use crate::{debug, error, info, trace, warn};
+impl LogSubsystem {
+    pub const ALL: [Self; 5] = [
+        Self::P2p,
+        Self::Sync,
+        Self::Ledger,
+        Self::BlockProducer,
+        Self::Snark,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::P2p => "p2p",
+            Self::Sync => "sync",
+            Self::Ledger => "ledger",
+            Self::BlockProducer => "block_producer",
+            Self::Snark => "snark",
+        }
+    }
+
+    /// Subsystem the code in module `path` belongs to, decided by the
+    /// first path segment naming one.
+    ///
+    /// E.g. `node::transition_frontier::sync::ledger` belongs to `sync`.
+    pub fn from_module_path(path: &str) -> Option<Self> {
+        path.split("::").find_map(|segment| match segment {
+            "p2p" => Some(Self::P2p),
+            "transition_frontier" | "consensus" => Some(Self::Sync),
+            "ledger" | "mina_tree" => Some(Self::Ledger),
+            "block_producer" => Some(Self::BlockProducer),
+            "snark" | "snarks" | "snark_pool" | "external_snark_worker" => Some(Self::Snark),
+            _ => None,
+        })
+    }
+}
+
+impl std::fmt::Display for LogSubsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LogSubsystem {
+    type Err = LogFiltersParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|subsystem| subsystem.as_str() == s)
+            .ok_or_else(|| LogFiltersParseError(format!("unknown subsystem: {s}")))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    const ALL: [Self; 6] = [
+        Self::Off,
+        Self::Error,
+        Self::Warn,
+        Self::Info,
+        Self::Debug,
+        Self::Trace,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Off => Self::OFF,
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = LogFiltersParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| LogFiltersParseError(format!("unknown log level: {s}")))
+    }
+}
+
+/// Log levels of subsystems overriding the global verbosity.
+///
+/// Parsed from `subsystem=level` pairs separated by commas, e.g.
+/// `p2p=warn,sync=debug`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct LogFilters(pub BTreeMap<LogSubsystem, LogLevel>);
+
+#[derive(Debug, Clone)]
+pub struct LogFiltersParseError(String);
+
+impl std::fmt::Display for LogFiltersParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LogFiltersParseError {}
+
+impl std::str::FromStr for LogFilters {
+    type Err = LogFiltersParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|filter| !filter.trim().is_empty())
+            .map(|filter| {
+                let (subsystem, level) = filter.split_once('=').ok_or_else(|| {
+                    LogFiltersParseError(format!("expected `subsystem=level`, got: {filter}"))
+                })?;
+                Ok((subsystem.trim().parse()?, level.trim().parse()?))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+const LOG_LEVEL_UNSET: u8 = u8::MAX;
+
+static LOG_FILTERS: [AtomicU8; LogSubsystem::ALL.len()] =
+    [const { AtomicU8::new(LOG_LEVEL_UNSET) }; LogSubsystem::ALL.len()];
+
+/// Log level of the `subsystem`, if it overrides the global verbosity.
+pub fn log_filter(subsystem: LogSubsystem) -> Option<LogLevel> {
+    let level = LOG_FILTERS[subsystem as usize].load(Ordering::Relaxed);
+    LogLevel::ALL.get(level as usize).copied()
+}
+
+/// Currently active per-subsystem log levels.
+pub fn log_filters() -> LogFilters {
+    LogFilters(
+        LogSubsystem::ALL
+            .into_iter()
+            .filter_map(|subsystem| Some((subsystem, log_filter(subsystem)?)))
+            .collect(),
+    )
+}
+
+/// Replaces per-subsystem log levels. Subsystems missing in `filters`
+/// fall back to the global verbosity.
+///
+/// Can be called at any time, already registered log callsites are
+/// re-evaluated against the new levels.
+pub fn set_log_filters(filters: &LogFilters) {
+    for subsystem in LogSubsystem::ALL {
+        let level = filters
+            .0
+            .get(&subsystem)
+            .map_or(LOG_LEVEL_UNSET, |level| *level as u8);
+        LOG_FILTERS[subsystem as usize].store(level, Ordering::Relaxed);
+    }
+    tracing::callsite::rebuild_interest_cache();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filters_from_str() {
+        let filters: LogFilters = "p2p=warn, block_producer=TRACE".parse().unwrap();
+        assert_eq!(filters.0.get(&LogSubsystem::P2p), Some(&LogLevel::Warn));
+        assert_eq!(
+            filters.0.get(&LogSubsystem::BlockProducer),
+            Some(&LogLevel::Trace)
+        );
+        assert_eq!(filters.0.len(), 2);
+
+        assert!("p2p".parse::<LogFilters>().is_err());
+        assert!("vrf=debug".parse::<LogFilters>().is_err());
+        assert!("p2p=loud".parse::<LogFilters>().is_err());
+    }
+
+    #[test]
+    fn test_log_subsystem_from_module_path() {
+        let subsystem = LogSubsystem::from_module_path;
+        assert_eq!(subsystem("p2p::network::yamux"), Some(LogSubsystem::P2p));
+        assert_eq!(
+            subsystem("node::transition_frontier::sync::ledger::snarked"),
+            Some(LogSubsystem::Sync)
+        );
+        assert_eq!(
+            subsystem("mina_tree::staged_ledger"),
+            Some(LogSubsystem::Ledger)
+        );
+        assert_eq!(
+            subsystem("openmina_node_common::service::block_producer"),
+            Some(LogSubsystem::BlockProducer)
+        );
+        assert_eq!(subsystem("node::snark_pool"), Some(LogSubsystem::Snark));
+        assert_eq!(subsystem("node::rpc::rpc_effects"), None);
+    }
+}
@@ -1,9 +1,13 @@
+use std::path::PathBuf;
+
 use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 
-use crate::constants::ConstraintConstants;
+use crate::constants::{ConstraintConstants, ForkConstants};
 
 // From mina-signer, to avoid dependency
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
 pub enum NetworkId {
     /// Id for all testnets
     TESTNET = 0x00,
@@ -22,10 +26,46 @@ pub struct NetworkConfig {
     pub default_peers: Vec<&'static str>,
     pub circuits_config: &'static CircuitsConfig,
     pub constraint_constants: &'static ConstraintConstants,
+    /// Daemon config (`daemon.json`) with the genesis ledger, for
+    /// user-defined networks.
+    pub genesis_config: Option<PathBuf>,
 }
 
-#[derive(Debug)]
+/// User-defined network, loaded from a network config file.
+///
+/// Circuits can't be compiled by openmina, so these networks use circuits
+/// and verifier indexes of one of the known networks, selected with
+/// `circuits`. Constraint constants are compiled into the circuits and
+/// have to match the ones they were built with.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomNetworkConfig {
+    pub name: String,
+    /// Selects signature prefixes, `testnet` or `mainnet`.
+    pub network_id: NetworkId,
+    /// Known network whose circuits are used, `devnet` or `mainnet`.
+    pub circuits: String,
+    /// Directory with circuit blobs, by default the one of `circuits`.
+    #[serde(default)]
+    pub circuits_directory: Option<String>,
+    /// Hex encoded digests of transaction-merge, transaction-base and
+    /// blockchain-step constraint systems, by default the ones of `circuits`.
+    #[serde(default)]
+    pub constraint_system_digests: Option<[String; 3]>,
+    /// By default the ones of `circuits`.
+    #[serde(default)]
+    pub constraint_constants: Option<ConstraintConstants>,
+    #[serde(default)]
+    pub default_peers: Vec<String>,
+    /// Daemon config (`daemon.json`) with the genesis ledger, relative
+    /// paths are resolved by the caller.
+    #[serde(default)]
+    pub genesis_config: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
 pub struct CircuitsConfig {
+    /// Known network these circuits belong to, selects verifier indexes.
+    pub network: &'static str,
     pub directory_name: &'static str,
 
     pub step_transaction_gates: &'static str,
@@ -53,19 +93,43 @@ impl NetworkConfig {
     }
 
     pub fn init(network_name: &str) -> Result<(), String> {
-        let config = match network_name {
-            "devnet" => Self::devnet_config(),
-            "mainnet" => Self::mainnet_config(),
-            other => Err(format!("Unknown network {other}"))?,
-        };
+        Self::by_name(network_name)?.init_global()
+    }
 
+    /// Initializes global config with a user-defined network.
+    pub fn init_custom(config: CustomNetworkConfig) -> Result<(), String> {
+        Self::custom(config)?.init_global()
+    }
+
+    pub fn init_global(self) -> Result<(), String> {
         CONFIG
-            .set(config)
+            .set(self)
             .map_err(|_| "Double network configuration initialization".to_owned())?;
 
         Ok(())
     }
 
+    pub fn by_name(network_name: &str) -> Result<Self, String> {
+        match network_name {
+            "devnet" => Ok(Self::devnet_config()),
+            "mainnet" => Ok(Self::mainnet_config()),
+            other => Err(format!("Unknown network {other}")),
+        }
+    }
+
+    /// Replaces fork constants, to start the chain from the hard fork
+    /// described by them.
+    pub fn with_fork(self, fork: ForkConstants) -> Self {
+        let constraint_constants = ConstraintConstants {
+            fork: Some(fork),
+            ..self.constraint_constants.clone()
+        };
+        Self {
+            constraint_constants: Box::leak(Box::new(constraint_constants)),
+            ..self
+        }
+    }
+
     fn default_config() -> Self {
         Self::devnet_config()
     }
@@ -80,6 +144,7 @@ impl NetworkConfig {
             default_peers: mainnet::default_peers(),
             circuits_config: &mainnet::CIRCUITS_CONFIG,
             constraint_constants: &mainnet::CONSTRAINT_CONSTANTS,
+            genesis_config: None,
         }
     }
 
@@ -93,8 +158,63 @@ impl NetworkConfig {
             default_peers: devnet::default_peers(),
             circuits_config: &devnet::CIRCUITS_CONFIG,
             constraint_constants: &devnet::CONSTRAINT_CONSTANTS,
+            genesis_config: None,
         }
     }
+
+    /// Config is initialized once and lives until the process exits, so
+    /// values read from the file are leaked to fit the `'static` fields.
+    pub fn custom(config: CustomNetworkConfig) -> Result<Self, String> {
+        fn leak<T>(value: T) -> &'static T {
+            Box::leak(Box::new(value))
+        }
+        fn leak_str(value: String) -> &'static str {
+            Box::leak(value.into_boxed_str())
+        }
+
+        let base = Self::by_name(&config.circuits)?;
+        let (signature_prefix, account_update_hash_param) = match config.network_id {
+            NetworkId::TESTNET => (devnet::SIGNATURE_PREFIX, devnet::ACCOUNT_UPDATE_HASH_PARAM),
+            NetworkId::MAINNET => (
+                mainnet::SIGNATURE_PREFIX,
+                mainnet::ACCOUNT_UPDATE_HASH_PARAM,
+            ),
+        };
+
+        let constraint_system_digests = match config.constraint_system_digests {
+            None => base.constraint_system_digests,
+            Some(digests) => {
+                let mut decoded = [[0; 16]; 3];
+                for (digest, hex_digest) in decoded.iter_mut().zip(digests) {
+                    hex::decode_to_slice(&hex_digest, digest).map_err(|err| {
+                        format!("Invalid constraint system digest {hex_digest}: {err}")
+                    })?;
+                }
+                leak(decoded)
+            }
+        };
+        let circuits_config = match config.circuits_directory {
+            None => base.circuits_config,
+            Some(directory_name) => leak(CircuitsConfig {
+                directory_name: leak_str(directory_name),
+                ..base.circuits_config.clone()
+            }),
+        };
+
+        Ok(Self {
+            name: leak_str(config.name),
+            network_id: config.network_id,
+            signature_prefix,
+            account_update_hash_param,
+            constraint_system_digests,
+            default_peers: config.default_peers.into_iter().map(leak_str).collect(),
+            circuits_config,
+            constraint_constants: config
+                .constraint_constants
+                .map_or(base.constraint_constants, leak),
+            genesis_config: config.genesis_config,
+        })
+    }
 }
 
 // Network constants
@@ -152,6 +272,7 @@ pub mod devnet {
     };
 
     pub const CIRCUITS_CONFIG: CircuitsConfig = CircuitsConfig {
+        network: NAME,
         directory_name: "3.0.0devnet",
 
         step_transaction_gates: "step-step-proving-key-transaction-snark-transaction-0-c33ec5211c07928c87e850a63c6a2079",
@@ -235,6 +356,7 @@ pub mod mainnet {
     };
 
     pub const CIRCUITS_CONFIG: CircuitsConfig = CircuitsConfig {
+        network: NAME,
         directory_name: "3.0.0mainnet",
 
         step_transaction_gates: "step-step-proving-key-transaction-snark-transaction-0-b421ac835a0e73935f3d3569ff87f484",
@@ -292,3 +414,42 @@ pub mod mainnet {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_network_config() {
+        let constraint_constants = serde_json::to_value(&devnet::CONSTRAINT_CONSTANTS).unwrap();
+        let config: CustomNetworkConfig = serde_json::from_value(serde_json::json!({
+            "name": "private",
+            "network_id": "testnet",
+            "circuits": "devnet",
+            "constraint_system_digests": ["00".repeat(16), "01".repeat(16), "02".repeat(16)],
+            "constraint_constants": constraint_constants,
+            "default_peers": ["/ip4/127.0.0.1/tcp/8302/p2p/12D3KooWKQ1YVtqZFzxDmSw8RASCPZpDCQBywnFz76RbrvZCXk5T"],
+        }))
+        .unwrap();
+        let config = NetworkConfig::custom(config).unwrap();
+
+        assert_eq!(config.name, "private");
+        assert_eq!(config.signature_prefix, devnet::SIGNATURE_PREFIX);
+        assert_eq!(config.circuits_config.network, devnet::NAME);
+        assert_eq!(
+            config.constraint_system_digests,
+            &[[0; 16], [1; 16], [2; 16]]
+        );
+        assert_eq!(config.default_peers.len(), 1);
+        assert_eq!(
+            config
+                .constraint_constants
+                .fork
+                .as_ref()
+                .unwrap()
+                .state_hash,
+            devnet::CONSTRAINT_CONSTANTS.fork.unwrap().state_hash
+        );
+        assert!(config.genesis_config.is_none());
+    }
+}
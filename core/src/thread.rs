@@ -0,0 +1,7 @@
+//! Threads, backed by web workers when running in the browser.
+
+#[cfg(not(target_family = "wasm"))]
+pub use std::thread::*;
+
+#[cfg(target_family = "wasm")]
+pub use wasm_thread::*;
@@ -6,10 +6,8 @@ use poly_commitment::{commitment::CommitmentCurve, srs::SRS};
 use super::public_input::scalar_challenge::ScalarChallenge;
 use super::urs_utils;
 
-pub fn accumulator_check(
-    urs: &SRS<Vesta>,
-    proof: &PicklesProofProofsVerified2ReprStableV2,
-) -> bool {
+/// Accumulated commitment and bulletproof challenges of the proof.
+fn accumulator(proof: &PicklesProofProofsVerified2ReprStableV2) -> (Vesta, Vec<Fp>) {
     // accumulator check
     // Note:
     // comms: statement.proof_state.messages_for_next_wrap_proof.challenge_polynomial_commitment
@@ -37,6 +35,15 @@ pub fn accumulator_check(
         .challenge_polynomial_commitment;
     let acc_comm: Vesta = of_coord(acc_comm);
 
+    (acc_comm, bulletproof_challenges)
+}
+
+pub fn accumulator_check(
+    urs: &SRS<Vesta>,
+    proof: &PicklesProofProofsVerified2ReprStableV2,
+) -> bool {
+    let (acc_comm, bulletproof_challenges) = accumulator(proof);
+
     let acc_check =
         urs_utils::batch_dlog_accumulator_check(urs, &[acc_comm], &bulletproof_challenges);
 
@@ -46,3 +53,160 @@ pub fn accumulator_check(
 
     acc_check
 }
+
+/// Checks accumulators of all `proofs` with a single multi-scalar
+/// multiplication, instead of one per proof.
+///
+/// If the combined check fails, proofs are checked one by one and
+/// indices of the ones that failed are returned.
+pub fn accumulator_check_batch(
+    urs: &SRS<Vesta>,
+    proofs: &[&PicklesProofProofsVerified2ReprStableV2],
+) -> Result<(), Vec<usize>> {
+    let (comms, chals): (Vec<_>, Vec<_>) = proofs.iter().map(|proof| accumulator(proof)).unzip();
+    let chals = chals.concat();
+
+    if urs_utils::batch_dlog_accumulator_check(urs, &comms, &chals) {
+        return Ok(());
+    }
+
+    let invalid = proofs
+        .iter()
+        .enumerate()
+        .filter(|(_, proof)| !accumulator_check(urs, proof))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    // Shouldn't happen, but the failed batch must not be treated as valid.
+    if invalid.is_empty() {
+        Err((0..proofs.len()).collect())
+    } else {
+        Err(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use mina_p2p_messages::{binprot::BinProtRead, v2};
+
+    use crate::proofs::gates::devnet_circuit_directory;
+
+    use super::*;
+
+    /// Proofs of merge jobs inputs, found in the circuits' test requests.
+    fn merge_inputs_proofs() -> Vec<PicklesProofProofsVerified2ReprStableV2> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(devnet_circuit_directory())
+            .join("tests");
+        let Ok(dir) = std::fs::read_dir(path) else {
+            return Vec::new();
+        };
+        let mut files = dir
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with("merge-"))
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+
+        files
+            .into_iter()
+            .flat_map(|file| {
+                use v2::*;
+
+                let bytes = std::fs::read(file).unwrap();
+                let request =
+                    ExternalSnarkWorkerRequest::binprot_read(&mut bytes.as_slice()).unwrap();
+                let ExternalSnarkWorkerRequest::PerformJob(
+                    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse(Some((work, _))),
+                ) = request
+                else {
+                    panic!("unexpected request")
+                };
+                let SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances::One(
+                    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single::Merge(merge),
+                ) = work.instances
+                else {
+                    panic!("unexpected work")
+                };
+                let (_, p1, p2) = *merge;
+                [p1.0.proof.0, p2.0.proof.0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_accumulator_check_batch_finds_invalid_proofs() {
+        let mut proofs = merge_inputs_proofs();
+        if proofs.len() < 3 {
+            eprintln!("merge requests not found");
+            assert!(std::env::var("CI").is_err(), "missing circuit files !");
+            return;
+        }
+        proofs.truncate(8);
+        let srs = crate::verifier::get_srs::<Fp>();
+        let srs = srs.lock().unwrap();
+
+        let refs = proofs.iter().collect::<Vec<_>>();
+        assert_eq!(accumulator_check_batch(&srs, &refs), Ok(()));
+
+        // Swapping accumulated commitments invalidates both proofs.
+        let comm = |proof: &PicklesProofProofsVerified2ReprStableV2| {
+            proof
+                .statement
+                .proof_state
+                .messages_for_next_wrap_proof
+                .challenge_polynomial_commitment
+                .clone()
+        };
+        let (comm0, comm2) = (comm(&proofs[0]), comm(&proofs[2]));
+        assert_ne!(comm0, comm2);
+        proofs[0]
+            .statement
+            .proof_state
+            .messages_for_next_wrap_proof
+            .challenge_polynomial_commitment = comm2;
+        proofs[2]
+            .statement
+            .proof_state
+            .messages_for_next_wrap_proof
+            .challenge_polynomial_commitment = comm0;
+
+        let refs = proofs.iter().collect::<Vec<_>>();
+        assert_eq!(accumulator_check_batch(&srs, &refs), Err(vec![0, 2]));
+    }
+
+    /// Compares accumulator checks of a realistic work bundle done one by
+    /// one and in a batch.
+    ///
+    /// cargo test --release -p mina-tree bench_accumulator_check_batch -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_accumulator_check_batch() {
+        let proofs = merge_inputs_proofs();
+        assert!(!proofs.is_empty(), "merge requests not found");
+        let srs = crate::verifier::get_srs::<Fp>();
+        let srs = srs.lock().unwrap();
+
+        for n in [2, 8, 32, 128] {
+            let refs = proofs.iter().cycle().take(n).collect::<Vec<_>>();
+
+            let now = std::time::Instant::now();
+            assert!(refs.iter().all(|proof| accumulator_check(&srs, proof)));
+            let sequential = now.elapsed();
+
+            let now = std::time::Instant::now();
+            assert_eq!(accumulator_check_batch(&srs, &refs), Ok(()));
+            let batched = now.elapsed();
+
+            eprintln!(
+                "proofs={n:<4} sequential={sequential:?} batched={batched:?} speedup={:.1}x",
+                sequential.as_secs_f64() / batched.as_secs_f64()
+            );
+        }
+    }
+}
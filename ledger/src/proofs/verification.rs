@@ -9,7 +9,7 @@ use crate::{
         accumulator_check,
         step::{expand_deferred, StatementProofState},
         unfinalized::AllEvals,
-        verifier_index::make_zkapp_verifier_index,
+        verifier_index::cached_zkapp_verifier_index,
         wrap::Domain,
         BACKEND_TICK_ROUNDS_N,
     },
@@ -656,17 +656,23 @@ pub fn verify_transaction<'a>(
     verifier_index: &VerifierIndex<Pallas>,
     srs: &SRS<Vesta>,
 ) -> bool {
+    let proofs = proofs.into_iter().collect::<Vec<_>>();
+    let transaction_proofs = proofs.iter().map(|(_, proof)| &proof.0).collect::<Vec<_>>();
+    // Accumulators of all proofs are checked with one MSM
+    if let Err(invalid) = accumulator_check::accumulator_check_batch(srs, &transaction_proofs) {
+        eprintln!("verify_transaction: invalid accumulators of proofs {invalid:?}");
+        return false;
+    }
+
     let vk = VK {
         commitments: PlonkVerificationKeyEvals::from(verifier_index),
         index: verifier_index,
         data: (),
     };
 
-    proofs.into_iter().all(|(statement, transaction_proof)| {
-        let accum_check = accumulator_check::accumulator_check(srs, transaction_proof);
-        let verified = verify_impl(statement, transaction_proof, &vk);
-        accum_check && verified
-    })
+    proofs
+        .into_iter()
+        .all(|(statement, transaction_proof)| verify_impl(statement, transaction_proof, &vk))
 }
 
 /// https://github.com/MinaProtocol/mina/blob/bfd1009abdbee78979ff0343cc73a3480e862f58/src/lib/crypto/kimchi_bindings/stubs/src/pasta_fq_plonk_proof.rs#L116
@@ -676,7 +682,7 @@ pub fn verify_zkapp(
     sideloaded_proof: &PicklesProofProofsVerified2ReprStableV2,
     srs: &SRS<Vesta>,
 ) -> bool {
-    let verifier_index = make_zkapp_verifier_index(verification_key);
+    let verifier_index = cached_zkapp_verifier_index(verification_key);
     // https://github.com/MinaProtocol/mina/blob/4e0b324912017c3ff576704ee397ade3d9bda412/src/lib/pickles/pickles.ml#LL260C1-L274C18
     let vk = VK {
         commitments: *verification_key.wrap_index.clone(),
@@ -1,12 +1,14 @@
 use std::{
+    collections::HashMap,
     fs::File,
+    hash::Hash,
     io::{Read, Write},
     path::Path,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use anyhow::Context;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 #[cfg(not(target_family = "wasm"))]
 use openmina_core::{info, log::system_time, warn};
 use sha2::{Digest, Sha256};
@@ -27,6 +29,7 @@ use kimchi::{
 use mina_curves::pasta::Fq;
 use mina_hasher::Fp;
 use poly_commitment::srs::SRS;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     proofs::{field::GroupAffine, BACKEND_TOCK_ROUNDS_N},
@@ -132,7 +135,9 @@ pub fn get_verifier_index(kind: VerifierKind) -> VerifierIndex<Pallas> {
     match kind {
         VerifierKind::Blockchain => {
             cache_one!(VerifierIndex<Pallas>, {
-                let network_name = openmina_core::NetworkConfig::global().name;
+                let network_name = openmina_core::NetworkConfig::global()
+                    .circuits_config
+                    .network;
                 let (json_data, cache_filename) = match network_name {
                     "mainnet" => (
                         include_str!("data/mainnet_blockchain_verifier_index.json"),
@@ -149,7 +154,9 @@ pub fn get_verifier_index(kind: VerifierKind) -> VerifierIndex<Pallas> {
         }
         VerifierKind::Transaction => {
             cache_one!(VerifierIndex<Pallas>, {
-                let network_name = openmina_core::NetworkConfig::global().name;
+                let network_name = openmina_core::NetworkConfig::global()
+                    .circuits_config
+                    .network;
                 let (json_data, cache_filename) = match network_name {
                     "mainnet" => (
                         include_str!("data/mainnet_transaction_verifier_index.json"),
@@ -264,13 +271,7 @@ pub fn make_zkapp_verifier_index(vk: &VerificationKey) -> VerifierIndex<Pallas>
     let domain: Radix2EvaluationDomain<Fq> =
         Radix2EvaluationDomain::new(1 << log2_size as u64).unwrap();
 
-    let srs = {
-        use mina_curves::pasta::Vesta;
-        let degree = 1 << BACKEND_TOCK_ROUNDS_N;
-        let mut srs = SRS::<<Vesta as KimchiCurve>::OtherCurve>::create(degree);
-        srs.add_lagrange_basis(domain);
-        srs
-    };
+    let srs = zkapp_srs(log2_size);
 
     let make_poly = |poly: &InnerCurve<Fp>| poly_commitment::PolyComm {
         unshifted: vec![poly.to_affine()],
@@ -307,7 +308,7 @@ pub fn make_zkapp_verifier_index(vk: &VerificationKey) -> VerifierIndex<Pallas>
     VerifierIndex {
         domain,
         max_poly_size: 1 << BACKEND_TOCK_ROUNDS_N,
-        srs: once_cell::sync::OnceCell::with_value(Arc::new(srs)),
+        srs: once_cell::sync::OnceCell::with_value(srs),
         public,
         prev_challenges: 2,
         sigma_comm: vk.wrap_index.sigma.each_ref().map(make_poly),
@@ -333,3 +334,157 @@ pub fn make_zkapp_verifier_index(vk: &VerificationKey) -> VerifierIndex<Pallas>
         powers_of_alpha,
     }
 }
+
+/// SRS of zkApp verifier indexes, with lagrange basis of the wrap domain.
+///
+/// It doesn't depend on the verification key, so it is created once per
+/// wrap domain and shared by all indexes.
+fn zkapp_srs(log2_size: u64) -> Arc<SRS<Pallas>> {
+    static SRS_BY_DOMAIN: [OnceCell<Arc<SRS<Pallas>>>; 3] =
+        [OnceCell::new(), OnceCell::new(), OnceCell::new()];
+
+    let min_log2_size = wrap_domains(0).h.log2_size();
+    SRS_BY_DOMAIN[(log2_size - min_log2_size) as usize]
+        .get_or_init(|| {
+            use mina_curves::pasta::Vesta;
+            let domain: Radix2EvaluationDomain<Fq> =
+                Radix2EvaluationDomain::new(1 << log2_size).unwrap();
+            let degree = 1 << BACKEND_TOCK_ROUNDS_N;
+            let mut srs = SRS::<<Vesta as KimchiCurve>::OtherCurve>::create(degree);
+            srs.add_lagrange_basis(domain);
+            Arc::new(srs)
+        })
+        .clone()
+}
+
+/// Maximum number of zkApp verifier indexes kept in memory.
+///
+/// SRS is shared between indexes, an entry only holds the key's
+/// commitments and the linearization.
+pub const ZKAPP_VERIFIER_INDEX_CACHE_SIZE: usize = 256;
+
+/// Counters of the zkApp verifier index cache.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ZkappVerifierIndexCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Number of indexes currently cached.
+    pub cached: usize,
+    pub capacity: usize,
+}
+
+/// Cache evicting the least recently used entry once `capacity` is
+/// exceeded.
+struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+    stats: ZkappVerifierIndexCacheStats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+            stats: Default::default(),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        match self.entries.get_mut(key) {
+            Some((value, last_used)) => {
+                *last_used = self.tick;
+                self.stats.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        self.entries.insert(key, (value, self.tick));
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+
+    fn stats(&self) -> ZkappVerifierIndexCacheStats {
+        ZkappVerifierIndexCacheStats {
+            cached: self.entries.len(),
+            capacity: self.capacity,
+            ..self.stats
+        }
+    }
+}
+
+static ZKAPP_VERIFIER_INDEXES: Lazy<Mutex<LruCache<Fp, Arc<VerifierIndex<Pallas>>>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(ZKAPP_VERIFIER_INDEX_CACHE_SIZE)));
+
+/// Verifier index of the zkApp, cached by verification key hash.
+///
+/// Shared by transaction pool verification and block application, which
+/// keep seeing proofs of the same popular zkApps.
+pub fn cached_zkapp_verifier_index(vk: &VerificationKey) -> Arc<VerifierIndex<Pallas>> {
+    let hash = vk.hash();
+    if let Some(index) = ZKAPP_VERIFIER_INDEXES.lock().unwrap().get(&hash) {
+        return index;
+    }
+    // Built without holding the lock, concurrent verifications of other
+    // zkApps don't wait for it.
+    let index = Arc::new(make_zkapp_verifier_index(vk));
+    ZKAPP_VERIFIER_INDEXES
+        .lock()
+        .unwrap()
+        .insert(hash, index.clone());
+    index
+}
+
+pub fn zkapp_verifier_index_cache_stats() -> ZkappVerifierIndexCacheStats {
+    ZKAPP_VERIFIER_INDEXES.lock().unwrap().stats()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some("a"));
+
+        cache.insert(3, "c");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("a"));
+        assert_eq!(cache.get(&3), Some("c"));
+
+        assert_eq!(
+            cache.stats(),
+            ZkappVerifierIndexCacheStats {
+                hits: 3,
+                misses: 1,
+                evictions: 1,
+                cached: 2,
+                capacity: 2,
+            }
+        );
+    }
+}
@@ -526,6 +526,40 @@ macro_rules! impl_number {
                 Self(n)
             }
 
+            /// Same as `of_mina_string_exn`, but returns `None` on invalid input,
+            /// overflow, or more decimals than the currency precision.
+            pub fn of_mina_string(input: &str) -> Option<Self> {
+                const PRECISION: usize = 9;
+
+                let (whole, decimal) = input.split_once('.').unwrap_or((input, ""));
+                if decimal.len() > PRECISION || !decimal.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let decimal = format!("{decimal:0<PRECISION$}").parse::<$inner>().ok()?;
+                whole
+                    .parse::<$inner>()
+                    .ok()?
+                    .checked_mul(<$inner>::pow(10, PRECISION as u32))?
+                    .checked_add(decimal)
+                    .map(Self)
+            }
+
+            /// Inverse of `of_mina_string_exn`, e.g. `1000000001` -> `"1.000000001"`.
+            ///
+            /// https://github.com/MinaProtocol/mina/blob/2ff0292b637684ce0372e7b8e23ec85404dc5091/src/lib/currency/currency.ml#L108
+            pub fn to_mina_string(&self) -> String {
+                const PRECISION: usize = 9;
+
+                let unit = <$inner>::pow(10, PRECISION as u32);
+                let (whole, decimal) = (self.0 / unit, self.0 % unit);
+                if decimal == 0 {
+                    whole.to_string()
+                } else {
+                    let decimal = format!("{decimal:0PRECISION$}");
+                    format!("{whole}.{}", decimal.trim_end_matches('0'))
+                }
+            }
+
             pub fn to_bits(&self) -> [bool; <$inner>::BITS as usize] {
                 use crate::proofs::transaction::legacy_input::bits_iter;
 
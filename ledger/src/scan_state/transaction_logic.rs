@@ -4377,6 +4377,19 @@ pub mod verifiable {
         ZkAppCommand(Box<zkapp_command::verifiable::ZkAppCommand>),
     }
 
+    impl UserCommand {
+        /// Converts a command whose signatures and proofs were already
+        /// verified, without checking them again.
+        pub fn to_valid_unsafe(self) -> valid::UserCommand {
+            match self {
+                UserCommand::SignedCommand(cmd) => valid::UserCommand::SignedCommand(cmd),
+                UserCommand::ZkAppCommand(cmd) => valid::UserCommand::ZkAppCommand(Box::new(
+                    zkapp_command::valid::of_verifiable(*cmd),
+                )),
+            }
+        }
+    }
+
     pub fn compressed_to_pubkey(pubkey: &CompressedPubKey) -> mina_signer::PubKey {
         // Taken from https://github.com/o1-labs/proof-systems/blob/e3fc04ce87f8695288de167115dea80050ab33f4/signer/src/pubkey.rs#L95-L106
         let mut pt = mina_signer::CurvePoint::get_point_from_x(pubkey.x, pubkey.is_odd).unwrap();
@@ -7,10 +7,12 @@ use crate::{
     staged_ledger::diff::AtMostTwo,
 };
 
+use serde::{Deserialize, Serialize};
+
 use self::detail::Detail;
 use self::summary::Summary;
 
-type CountAndFee = (u64, Fee);
+pub type CountAndFee = (u64, Fee);
 
 type FeeSummable = Fee;
 
@@ -18,7 +20,7 @@ fn add_fee(fee1: FeeSummable, fee2: FeeSummable) -> FeeSummable {
     fee1.checked_add(&fee2).unwrap()
 }
 
-#[derive(Copy, Debug, Clone)]
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Reason {
     NoWork,
     NoSpace,
@@ -28,13 +30,13 @@ pub enum Reason {
     End,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Partition {
     First,
     Second,
 }
 
-mod summary {
+pub mod summary {
 
     use super::*;
 
@@ -47,25 +49,25 @@ mod summary {
 
     #[derive(Clone, Debug)]
     pub struct CommandConstraints {
-        insufficient_work: u64,
-        insufficient_space: u64,
+        pub insufficient_work: u64,
+        pub insufficient_space: u64,
     }
 
     #[derive(Clone, Debug)]
     pub struct CompletedWorkConstraints {
-        insufficient_fees: u64,
-        extra_work: u64,
+        pub insufficient_fees: u64,
+        pub extra_work: u64,
     }
 
     #[derive(Clone, Debug)]
     pub struct Summary {
-        partition: Partition,
-        start_resources: Resources,
-        available_slots: u64,
-        required_work_count: u64,
-        discarded_commands: CommandConstraints,
-        discarded_completed_work: CompletedWorkConstraints,
-        end_resources: Resources,
+        pub partition: Partition,
+        pub start_resources: Resources,
+        pub available_slots: u64,
+        pub required_work_count: u64,
+        pub discarded_commands: CommandConstraints,
+        pub discarded_completed_work: CompletedWorkConstraints,
+        pub end_resources: Resources,
     }
 
     pub fn coinbase_fees(coinbase: &AtMostTwo<CoinbaseFeeTransfer>) -> AtMostTwo<FeeSummable> {
@@ -183,19 +185,19 @@ mod summary {
     }
 }
 
-mod detail {
+pub mod detail {
     use super::*;
 
     #[derive(Debug, Clone)]
-    struct Line {
-        reason: Reason,
-        commands: CountAndFee,
-        completed_work: CountAndFee,
-        coinbase: AtMostTwo<Fee>,
+    pub struct Line {
+        pub reason: Reason,
+        pub commands: CountAndFee,
+        pub completed_work: CountAndFee,
+        pub coinbase: AtMostTwo<Fee>,
     }
 
     #[derive(Clone, Debug)]
-    pub struct Detail(Vec<Line>);
+    pub struct Detail(pub Vec<Line>);
 
     impl Detail {
         pub fn init(
@@ -309,7 +311,7 @@ impl DiffCreationLog {
 
     pub fn discard_completed_work(&mut self, why: Reason, completed_work: &work::Unchecked) {
         self.detail.discard_completed_work(why, completed_work);
-        self.summary.discard_command(why);
+        self.summary.discard_completed_work(why);
     }
 
     pub fn end_log(
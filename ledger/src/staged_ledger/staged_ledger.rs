@@ -443,6 +443,22 @@ impl StagedLedger {
         })
     }
 
+    /// Creates staged ledger from its parts without checking scan state
+    /// invariants. Used to replay dumps of failed block applications.
+    pub fn from_parts_unchecked(
+        constraint_constants: ConstraintConstants,
+        ledger: Mask,
+        scan_state: ScanState,
+        pending_coinbase_collection: PendingCoinbase,
+    ) -> Self {
+        Self {
+            scan_state,
+            ledger,
+            constraint_constants,
+            pending_coinbase_collection,
+        }
+    }
+
     /// https://github.com/MinaProtocol/mina/blob/05c2f73d0f6e4f1341286843814ce02dcb3919e0/src/lib/staged_ledger/staged_ledger.ml#434
     fn current_ledger_proof(&self) -> Option<&LedgerProofWithSokMessage> {
         self.scan_state.latest_ledger_proof().map(|(f, _)| f)
@@ -1801,6 +1817,7 @@ impl StagedLedger {
         (
             with_valid_signatures_and_proofs::Diff,
             Vec<(valid::UserCommand, String)>,
+            Vec<DiffCreationLog>,
         ),
         PreDiffError,
     >
@@ -1904,7 +1921,7 @@ impl StagedLedger {
 
             let _valid_on_this_ledger_len = valid_on_this_ledger.len();
 
-            let (diff, _log) = Self::generate(
+            let (diff, log) = Self::generate(
                 constraint_constants,
                 logger,
                 completed_works_seq,
@@ -1959,7 +1976,7 @@ impl StagedLedger {
 
             let diff = with_valid_signatures_and_proofs::Diff { diff };
 
-            Ok((diff, invalid_on_this_ledger))
+            Ok((diff, invalid_on_this_ledger, log))
         })
     }
 
@@ -2120,7 +2137,7 @@ mod tests_ocaml {
 
         let supercharge_coinbase = supercharge_coinbase(sl.ledger.clone(), winner, global_slot);
 
-        let (diff, _invalid_txns) = sl
+        let (diff, _invalid_txns, _log) = sl
             .create_diff(
                 &CONSTRAINT_CONSTANTS,
                 global_slot,
@@ -4588,7 +4605,7 @@ mod tests_ocaml {
                     |_cmds_left, _count_opt, cmds_this_iter, _| {
                         let current_state_view = dummy_state_view(Some(global_slot));
 
-                        let (diff, _invalid_txns) = sl
+                        let (diff, _invalid_txns, _log) = sl
                             .create_diff(
                                 &CONSTRAINT_CONSTANTS,
                                 global_slot,
@@ -4615,6 +4632,108 @@ mod tests_ocaml {
         );
     }
 
+    /// Commands left out of a diff because no snark work is available are
+    /// logged as `NoWork` in the diff creation logs
+    #[test]
+    fn diff_creation_logs_commands_dropped_for_no_work() {
+        use crate::staged_ledger::diff_creation_log::Reason;
+
+        let (ledger_init_state, cmds, cmd_iters) = gen_at_capacity_fixed_blocks(1);
+        let global_slot = Slot::gen_small();
+
+        async_with_ledgers(
+            &ledger_init_state,
+            cmds.clone(),
+            cmd_iters.clone(),
+            |_snarked_ledger, mut sl, _test_mask| {
+                let checked = iter_cmds_acc(
+                    &cmds,
+                    &cmd_iters,
+                    false,
+                    |_cmds_left, _count_opt, cmds_this_iter, checked| {
+                        let (current_state, current_state_view) =
+                            dummy_state_and_view(Some(global_slot));
+                        let state_and_body_hash = hashes_abstract(&current_state);
+
+                        let work_required =
+                            !sl.scan_state.work_statements_for_new_diff().is_empty();
+                        let check = !checked && work_required && !cmds_this_iter.is_empty();
+                        if check {
+                            let (diff, _invalid_txns, logs) = sl
+                                .create_diff(
+                                    &CONSTRAINT_CONSTANTS,
+                                    global_slot,
+                                    None,
+                                    COINBASE_RECEIVER.clone(),
+                                    LOGGER,
+                                    &current_state_view,
+                                    cmds_this_iter.to_vec(),
+                                    |_: &work::Statement| None,
+                                    false,
+                                )
+                                .unwrap();
+
+                            let diff = diff.forget();
+                            assert!(diff.completed_works().is_empty());
+
+                            let included_commands: u64 = logs
+                                .iter()
+                                .map(|log| log.summary.end_resources.commands.0)
+                                .sum();
+                            assert_eq!(diff.commands().len() as u64, included_commands);
+                            for log in &logs {
+                                assert_eq!(log.summary.start_resources.completed_work.0, 0);
+                                assert_eq!(log.summary.end_resources.completed_work.0, 0);
+                            }
+
+                            // Only the slots not requiring work can be filled, the
+                            // remaining commands are discarded for lack of work.
+                            let no_work: u64 = logs
+                                .iter()
+                                .map(|log| log.summary.discarded_commands.insufficient_work)
+                                .sum();
+                            assert!(no_work > 0);
+
+                            // Commands discarded from the first partition are retried
+                            // in the second one, the last log has the dropped ones.
+                            let last = logs.last().unwrap();
+                            let dropped = &last.summary.discarded_commands;
+                            assert_eq!(
+                                dropped.insufficient_work + dropped.insufficient_space,
+                                last.summary.start_resources.commands.0
+                                    - last.summary.end_resources.commands.0
+                            );
+
+                            let no_work_lines = last
+                                .detail
+                                .0
+                                .iter()
+                                .filter(|line| line.reason == Reason::NoWork)
+                                .count() as u64;
+                            assert_eq!(no_work_lines, dropped.insufficient_work);
+                            assert_eq!(last.detail.0.last().unwrap().reason, Reason::End);
+                        }
+
+                        let (_proof, diff) = create_and_apply(
+                            None,
+                            None,
+                            global_slot,
+                            &current_state_view,
+                            state_and_body_hash,
+                            &mut sl,
+                            cmds_this_iter,
+                            stmt_to_work_one_prover,
+                        );
+
+                        (diff, checked || check)
+                    },
+                );
+
+                assert!(checked);
+            },
+        );
+    }
+
     /// https://github.com/MinaProtocol/mina/blob/3753a8593cc1577bcf4da16620daf9946d88e8e5/src/lib/staged_ledger/staged_ledger.ml#L2914
     fn stmt_to_work_restricted(
         work_list: &[work::Statement],
@@ -5605,7 +5724,7 @@ mod tests_ocaml {
             |_snarked_ledger, sl, _test_mask| {
                 let current_state_view = dummy_state_view(Some(global_slot));
 
-                let (diff, _invalid_txns) = sl
+                let (diff, _invalid_txns, _log) = sl
                     .create_diff(
                         &CONSTRAINT_CONSTANTS,
                         global_slot,
@@ -5704,7 +5823,7 @@ mod tests_ocaml {
                 let (current_state, current_state_view) = dummy_state_and_view(Some(global_slot));
                 let state_and_body_hash = { hashes_abstract(&current_state) };
 
-                let (diff, _invalid_txns) = sl
+                let (diff, _invalid_txns, _log) = sl
                     .create_diff(
                         &CONSTRAINT_CONSTANTS,
                         global_slot,
@@ -18,7 +18,7 @@ use crate::{
         currency::{Amount, Balance, BlockTime, Fee, Magnitude, Nonce, Slot, SlotSpan},
         fee_rate::FeeRate,
         transaction_logic::{
-            valid,
+            valid, verifiable,
             zkapp_command::{
                 from_unapplied_sequence::{self, FromUnappliedSequence},
                 MaybeWithStatus, WithHash,
@@ -1478,22 +1478,33 @@ pub mod transaction_hash {
     };
     use mina_signer::Signature;
 
+    use mina_p2p_messages::binprot::BinProtWrite;
+
     use crate::scan_state::transaction_logic::{
-        signed_command::SignedCommand, zkapp_command::AccountUpdate,
+        signed_command::SignedCommand, zkapp_command::AccountUpdate, Coinbase, SingleFeeTransfer,
     };
 
     use super::*;
 
-    pub fn hash_command(cmd: valid::UserCommand) -> ValidCommandWithHash {
-        use mina_p2p_messages::binprot::BinProtWrite;
+    fn to_binprot<T: Into<V>, V: BinProtWrite>(v: T) -> Vec<u8> {
+        let value = v.into();
+        let mut buffer = Vec::with_capacity(32 * 1024);
+        value.binprot_write(&mut buffer).unwrap();
+        buffer
+    }
 
-        fn to_binprot<T: Into<V>, V: BinProtWrite>(v: T) -> Vec<u8> {
-            let value = v.into();
-            let mut buffer = Vec::with_capacity(32 * 1024);
-            value.binprot_write(&mut buffer).unwrap();
-            buffer
-        }
+    fn digest(buffer: &[u8]) -> BlakeHash {
+        let mut hasher = Blake2bVar::new(32).expect("Invalid Blake2bVar output size");
+        hasher.update(buffer);
+
+        let mut hash = [0; 32];
+        hasher
+            .finalize_variable(&mut hash)
+            .expect("Invalid buffer size"); // Never occur
+        Arc::from(hash)
+    }
 
+    pub fn hash_command(cmd: valid::UserCommand) -> ValidCommandWithHash {
         let buffer: Vec<u8> = match &cmd {
             valid::UserCommand::SignedCommand(cmd) => {
                 let mut cmd: SignedCommand = (**cmd).clone();
@@ -1514,18 +1525,22 @@ pub mod transaction_hash {
             }
         };
 
-        let mut hasher = Blake2bVar::new(32).expect("Invalid Blake2bVar output size");
-        hasher.update(&buffer);
-
-        let hash: Arc<[u8; 32]> = {
-            let mut buffer = [0; 32];
-            hasher
-                .finalize_variable(&mut buffer)
-                .expect("Invalid buffer size"); // Never occur
-            Arc::from(buffer)
-        };
+        WithHash {
+            data: cmd,
+            hash: digest(&buffer),
+        }
+    }
 
-        WithHash { data: cmd, hash }
+    /// https://github.com/MinaProtocol/mina/blob/3fe924c80a4d01f418b69f27398f5f93eb652514/src/lib/transaction/transaction_hash.ml#L162
+    pub fn hash_coinbase(coinbase: &Coinbase) -> BlakeHash {
+        digest(&to_binprot::<_, v2::MinaBaseCoinbaseStableV1>(coinbase))
+    }
+
+    /// https://github.com/MinaProtocol/mina/blob/3fe924c80a4d01f418b69f27398f5f93eb652514/src/lib/transaction/transaction_hash.ml#L164
+    pub fn hash_fee_transfer(fee_transfer: &SingleFeeTransfer) -> BlakeHash {
+        digest(&to_binprot::<_, v2::MinaBaseFeeTransferSingleStableV2>(
+            fee_transfer,
+        ))
     }
 }
 
@@ -1626,6 +1641,16 @@ impl TransactionPool {
         self.pool.list_includable_transactions(limit)
     }
 
+    /// Pending commands of each sender, ordered by nonce.
+    pub fn commands_by_sender(
+        &self,
+    ) -> impl Iterator<Item = (&AccountId, &VecDeque<ValidCommandWithHash>)> {
+        self.pool
+            .all_by_sender
+            .iter()
+            .map(|(sender, (commands, _))| (sender, commands))
+    }
+
     pub fn get_accounts_to_revalidate_on_new_best_tip(&self) -> BTreeSet<AccountId> {
         self.pool.all_by_sender.keys().cloned().collect()
     }
@@ -2066,6 +2091,29 @@ impl TransactionPool {
         diff: diff::Diff,
         accounts: &BTreeMap<AccountId, Account>,
     ) -> Result<Vec<valid::UserCommand>, String> {
+        let diff = self.prevalidate(diff, accounts)?;
+
+        Verifier
+            .verify_commands(diff, None)
+            .into_iter()
+            .map(|cmd| {
+                // TODO: Handle invalids
+                match cmd {
+                    crate::verifier::VerifyCommandsResult::Valid(cmd) => Ok(cmd),
+                    e => Err(format!("invalid tx: {:?}", e)),
+                }
+            })
+            .collect()
+    }
+
+    /// Checks well-formedness of the commands and resolves verification
+    /// keys of zkApp commands, from the pool and `accounts`, so that their
+    /// signatures and proofs can be verified.
+    pub fn prevalidate(
+        &self,
+        diff: diff::Diff,
+        accounts: &BTreeMap<AccountId, Account>,
+    ) -> Result<Vec<WithStatus<verifiable::UserCommand>>, String> {
         let well_formedness_errors: HashSet<_> = diff
             .list
             .iter()
@@ -2127,25 +2175,13 @@ impl TransactionPool {
         })
         .map_err(|e| format!("Invalid {:?}", e))?;
 
-        let diff = diff
+        Ok(diff
             .into_iter()
             .map(|MaybeWithStatus { cmd, status: _ }| WithStatus {
                 data: cmd,
                 status: Applied,
             })
-            .collect::<Vec<_>>();
-
-        Verifier
-            .verify_commands(diff, None)
-            .into_iter()
-            .map(|cmd| {
-                // TODO: Handle invalids
-                match cmd {
-                    crate::verifier::VerifyCommandsResult::Valid(cmd) => Ok(cmd),
-                    e => Err(format!("invalid tx: {:?}", e)),
-                }
-            })
-            .collect()
+            .collect())
     }
 
     fn get_rebroadcastable<F>(&mut self, has_timed_out: F) -> Vec<Vec<UserCommand>>
@@ -14,7 +14,41 @@ pub type RpcTag = &'static [u8];
 /// RPC method version.
 pub type RpcVersion = Ver;
 pub type QueryID = u64;
-pub type Sexp = (); // TODO
+
+/// S-expression, as carried by RPC errors.
+///
+/// ```ocaml
+/// type t = Atom of string | List of t list
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, BinProtRead, BinProtWrite, PartialEq, Eq)]
+pub enum Sexp {
+    Atom(super::string::CharString),
+    List(crate::list::List<Sexp>),
+}
+
+impl Sexp {
+    pub fn atom(s: &str) -> Self {
+        Sexp::Atom(s.into())
+    }
+}
+
+impl std::fmt::Display for Sexp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sexp::Atom(atom) => write!(f, "{atom}"),
+            Sexp::List(list) => {
+                write!(f, "(")?;
+                for (i, sexp) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{sexp}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
 
 #[derive(
     Clone, Debug, Serialize, Deserialize, PartialEq, Eq, derive_more::From, derive_more::Into,
@@ -129,14 +163,14 @@ where
     Clone, Debug, Serialize, Deserialize, BinProtRead, BinProtWrite, PartialEq, Eq, thiserror::Error,
 )]
 pub enum Error {
-    #[error("binprot expection")]
-    Bin_io_exn, //(Sexp),
+    #[error("binprot expection: {0}")]
+    Bin_io_exn(Sexp),
     #[error("connection closed")]
     Connection_closed,
-    #[error("write error")]
-    Write_error, //(Sexp),
-    #[error("uncaught exception")]
-    Uncaught_exn, //(Sexp),
+    #[error("write error: {0}")]
+    Write_error(Sexp),
+    #[error("uncaught exception: {0}")]
+    Uncaught_exn(Sexp),
     #[error("unimplemented method {}:{}", .0.to_string(), .1)]
     Unimplemented_rpc(BinprotTag, Ver),
     #[error("unknown query id: {0}")]
@@ -636,4 +670,22 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn error_with_sexp() {
+        use binprot::BinProtWrite;
+
+        use super::{Error, Sexp};
+
+        let encoded = hex::decode("030102000161000162").unwrap();
+        let error = Error::Uncaught_exn(Sexp::List(
+            [Sexp::atom("a"), Sexp::atom("b")].into_iter().collect(),
+        ));
+        assert_eq!(Error::binprot_read(&mut encoded.as_slice()).unwrap(), error);
+        assert_eq!(error.to_string(), "uncaught exception: (a b)");
+
+        let mut buf = Vec::new();
+        error.binprot_write(&mut buf).unwrap();
+        assert_eq!(buf, encoded);
+    }
 }
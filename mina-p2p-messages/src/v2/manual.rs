@@ -1060,6 +1060,26 @@ impl StagedLedgerDiffBodyStableV1 {
         }
     }
 
+    /// Whether the diff has no commands, snark work or coinbase, as is the
+    /// case for blocks produced after `slot_tx_end`.
+    pub fn is_empty(&self) -> bool {
+        let (first, second) = (&self.diff().0, &self.diff().1);
+        first.commands.is_empty()
+            && first.completed_works.is_empty()
+            && matches!(
+                first.coinbase,
+                StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2Coinbase::Zero
+            )
+            && second.as_ref().map_or(true, |second| {
+                second.commands.is_empty()
+                    && second.completed_works.is_empty()
+                    && matches!(
+                        second.coinbase,
+                        StagedLedgerDiffDiffPreDiffWithAtMostOneCoinbaseStableV2Coinbase::Zero
+                    )
+            })
+    }
+
     pub fn completed_works_count(&self) -> usize {
         self.diff().0.completed_works.len()
             + self
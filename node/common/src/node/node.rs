@@ -79,7 +79,10 @@ impl<Serv: Service + AsMut<NodeServiceCommon>> Node<Serv> {
                     None => std::future::pending().await,
                 }
             };
+            #[cfg(not(target_family = "wasm"))]
             let timeout = tokio::time::sleep(Duration::from_millis(100));
+            #[cfg(target_family = "wasm")]
+            let timeout = wasm_timer::Delay::new(Duration::from_millis(100));
 
             tokio::select! {
                 _ = wait_for_events => {
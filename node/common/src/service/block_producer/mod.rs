@@ -9,7 +9,7 @@ use mina_p2p_messages::v2::{
 use node::{
     account::AccountSecretKey,
     block_producer::{vrf_evaluator::VrfEvaluatorInput, BlockProducerEvent},
-    core::{channels::mpsc, constants::constraint_constants},
+    core::{channels::mpsc, constants::constraint_constants, thread},
 };
 
 use crate::EventSender;
@@ -35,7 +35,7 @@ impl BlockProducerService {
             mpsc::unbounded_channel::<VrfEvaluatorInput>();
 
         let producer_keypair = keypair.clone();
-        std::thread::Builder::new()
+        thread::Builder::new()
             .name("openmina_vrf_evaluator".to_owned())
             .spawn(move || {
                 vrf_evaluator::vrf_evaluator(
@@ -97,7 +97,7 @@ impl node::service::BlockProducerService for crate::NodeServiceCommon {
         let keypair = self.block_producer.as_ref().unwrap().keypair();
 
         let tx = self.event_sender().clone();
-        std::thread::spawn(move || {
+        thread::spawn(move || {
             let res = prove(input, keypair, false).map_err(|err| format!("{err:?}"));
             let _ = tx.send(BlockProducerEvent::BlockProve(block_hash, res).into());
         });
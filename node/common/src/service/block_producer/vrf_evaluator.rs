@@ -30,6 +30,21 @@ pub fn vrf_evaluator(
                 (*stake).into(),
                 vrf_evaluator_input.total_currency.into(),
             );
+
+            if vrf_evaluator_input.ignore_threshold {
+                // In shadow mode every slot is won, but prefer the delegator
+                // that actually won it, as only such blocks can be proven.
+                let won_slot = vrf::evaluate_vrf_ignoring_threshold(vrf_input).unwrap();
+                let threshold_met = won_slot.threshold_met;
+                if threshold_met || matches!(vrf_result, VrfEvaluationOutput::SlotLost(_)) {
+                    vrf_result = VrfEvaluationOutput::SlotWon(won_slot);
+                }
+                if threshold_met {
+                    break;
+                }
+                continue;
+            }
+
             vrf_result = vrf::evaluate_vrf(vrf_input).unwrap();
 
             // the first delegate that won the slot
@@ -1,7 +1,9 @@
+use ledger::Mask;
+use mina_p2p_messages::v2::LedgerHash;
 use node::{
     account::AccountSecretKey,
     core::channels::mpsc,
-    ledger::{LedgerCtx, LedgerManager},
+    ledger::{LedgerCtx, LedgerManager, PrecomputedBlockExport},
     p2p::{
         identity::SecretKey as P2pSecretKey,
         service_impl::{
@@ -23,6 +25,7 @@ use crate::{
 };
 
 use super::block_producer::BlockProducerService;
+use super::verifier_pool::{VerifierPool, DEFAULT_VERIFIER_THREADS};
 
 pub struct NodeServiceCommonBuilder {
     rng_seed: [u8; 32],
@@ -32,9 +35,11 @@ pub struct NodeServiceCommonBuilder {
     event_sender: EventSender,
     event_receiver: EventReceiver,
     ledger_manager: Option<LedgerManager>,
+    precomputed_block_export: Option<PrecomputedBlockExport>,
     block_producer: Option<BlockProducerService>,
     p2p: Option<P2pServiceCtx>,
     gather_stats: bool,
+    verifier_threads: usize,
     rpc: RpcService,
 }
 
@@ -55,10 +60,12 @@ impl NodeServiceCommonBuilder {
             event_sender,
             event_receiver: event_receiver.into(),
             ledger_manager: None,
+            precomputed_block_export: None,
             block_producer: None,
             p2p: None,
             rpc: RpcService::new(),
             gather_stats: false,
+            verifier_threads: DEFAULT_VERIFIER_THREADS,
         }
     }
 
@@ -71,12 +78,41 @@ impl NodeServiceCommonBuilder {
     }
 
     pub fn ledger_init(&mut self) -> &mut Self {
+        self.ledger_init_with_snarked_ledgers(Vec::new(), None)
+    }
+
+    /// Initializes ledger service with snarked ledgers available upfront,
+    /// e.g. loaded from the bootstrap snapshot.
+    ///
+    /// Ledger sync starts from the `sync_origin` ledger, when none of them
+    /// matches the ledger being synced.
+    pub fn ledger_init_with_snarked_ledgers(
+        &mut self,
+        ledgers: Vec<Mask>,
+        sync_origin: Option<LedgerHash>,
+    ) -> &mut Self {
         let mut ctx = LedgerCtx::default();
+        for mask in ledgers {
+            ctx.insert_additional_snarked_ledger(mask);
+        }
+        if let Some(hash) = sync_origin {
+            ctx.set_sync_origin_snarked_ledger(hash);
+        }
+        if let Some(export) = self.precomputed_block_export.clone() {
+            ctx.set_precomputed_block_export(export);
+        }
         ctx.set_event_sender(self.event_sender.clone());
         self.ledger_manager = Some(LedgerManager::spawn(ctx));
         self
     }
 
+    /// Write a precomputed block of each applied block. Must be set
+    /// before the ledger is initialized.
+    pub fn precomputed_block_export(&mut self, export: PrecomputedBlockExport) -> &mut Self {
+        self.precomputed_block_export = Some(export);
+        self
+    }
+
     pub fn block_producer_init(&mut self, keypair: AccountSecretKey) -> &mut Self {
         self.block_producer = Some(BlockProducerService::start(
             self.event_sender.clone(),
@@ -102,6 +138,12 @@ impl NodeServiceCommonBuilder {
         self
     }
 
+    /// Number of threads verifying block and snark work proofs.
+    pub fn verifier_threads(&mut self, threads: usize) -> &mut Self {
+        self.verifier_threads = threads;
+        self
+    }
+
     pub fn build(self) -> Result<NodeServiceCommon, NodeServiceCommonBuildError> {
         let ledger_manager = self
             .ledger_manager
@@ -128,6 +170,7 @@ impl NodeServiceCommonBuilder {
             rpc: self.rpc,
             replayer: None,
             invariants_state: Default::default(),
+            verifier: VerifierPool::new(self.verifier_threads),
         })
     }
 }
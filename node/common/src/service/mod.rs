@@ -7,6 +7,7 @@ pub mod record;
 pub mod replay;
 pub mod rpc;
 mod snarks;
+pub mod verifier_pool;
 
 mod builder;
 pub use builder::*;
@@ -87,3 +87,32 @@ impl P2pCryptoService for NodeServiceCommon {
             .expect("unable to create signature")
     }
 }
+
+/// Keys and signatures are only used by libp2p's noise handshake and
+/// pubsub, which aren't available without it.
+#[cfg(not(feature = "p2p-libp2p"))]
+impl P2pCryptoService for NodeServiceCommon {
+    fn generate_random_nonce(&mut self) -> [u8; 24] {
+        self.rng.gen()
+    }
+
+    fn ephemeral_sk(&mut self) -> [u8; 32] {
+        let mut r = [0; 32];
+        self.rng_ephemeral.read(&mut r);
+        r
+    }
+
+    fn static_sk(&mut self) -> [u8; 32] {
+        let mut r = [0; 32];
+        self.rng_static.read(&mut r);
+        r
+    }
+
+    fn sign_key(&mut self, _key: &[u8; 32]) -> Vec<u8> {
+        unreachable!("libp2p is disabled")
+    }
+
+    fn sign_publication(&mut self, _publication: &[u8]) -> Vec<u8> {
+        unreachable!("libp2p is disabled")
+    }
+}
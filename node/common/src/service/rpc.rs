@@ -1,10 +1,12 @@
 use node::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
 use node::rpc::{
     RpcBlockProducerStatsGetResponse, RpcDiscoveryBoostrapStatsResponse,
-    RpcDiscoveryRoutingTableResponse, RpcHealthCheckResponse, RpcLedgerAccountsResponse,
+    RpcDiscoveryRoutingTableResponse, RpcHealthCheckResponse, RpcInvariantViolationsGetResponse,
+    RpcLedgerAccountsResponse, RpcLedgerExportGetResponse, RpcLogFiltersResponse,
     RpcMessageProgressResponse, RpcPeersGetResponse, RpcReadinessCheckResponse, RpcRequest,
     RpcStateGetError, RpcStatusGetResponse, RpcTransactionInjectFailure,
-    RpcTransactionPoolResponse, RpcTransitionFrontierUserCommandsResponse,
+    RpcTransactionPoolResponse, RpcTransitionFrontierSnapshotGetResponse,
+    RpcTransitionFrontierUserCommandsResponse, RpcVerifierStatsGetResponse,
 };
 use serde::{Deserialize, Serialize};
 
@@ -262,6 +264,7 @@ impl node::rpc::RpcService for NodeServiceCommon {
         respond_block_producer_stats_get,
         RpcBlockProducerStatsGetResponse
     );
+    rpc_service_impl!(respond_verifier_stats_get, RpcVerifierStatsGetResponse);
     rpc_service_impl!(
         respond_message_progress_stats_get,
         RpcMessageProgressResponse
@@ -332,6 +335,22 @@ impl node::rpc::RpcService for NodeServiceCommon {
         respond_discovery_bootstrap_stats,
         RpcDiscoveryBoostrapStatsResponse
     );
+    rpc_service_impl!(
+        respond_discovery_record_put,
+        node::rpc::RpcDiscoveryRecordPutResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_record_get,
+        node::rpc::RpcDiscoveryRecordGetResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_start_providing,
+        node::rpc::RpcDiscoveryStartProvidingResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_providers_get,
+        node::rpc::RpcDiscoveryProvidersGetResponse
+    );
     rpc_service_impl!(respond_transaction_pool, RpcTransactionPoolResponse);
     rpc_service_impl!(respond_ledger_accounts, RpcLedgerAccountsResponse);
     rpc_service_impl!(respond_transaction_inject, RpcTransactionInjectResponse);
@@ -343,6 +362,16 @@ impl node::rpc::RpcService for NodeServiceCommon {
         respond_transition_frontier_commands,
         RpcTransitionFrontierUserCommandsResponse
     );
+    rpc_service_impl!(
+        respond_transition_frontier_snapshot_get,
+        RpcTransitionFrontierSnapshotGetResponse
+    );
+    rpc_service_impl!(respond_ledger_export_get, RpcLedgerExportGetResponse);
+    rpc_service_impl!(
+        respond_invariant_violations_get,
+        RpcInvariantViolationsGetResponse
+    );
+    rpc_service_impl!(respond_log_filters, RpcLogFiltersResponse);
 }
 
 #[cfg(test)]
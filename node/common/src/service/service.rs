@@ -14,6 +14,7 @@ use super::{
     p2p::webrtc_with_libp2p::P2pServiceCtx,
     replay::ReplayerState,
     rpc::{RpcSender, RpcService},
+    verifier_pool::VerifierPool,
     EventReceiver, EventSender,
 };
 
@@ -35,6 +36,7 @@ pub struct NodeServiceCommon {
     pub rpc: RpcService,
     pub replayer: Option<ReplayerState>,
     pub invariants_state: InvariantsState,
+    pub verifier: VerifierPool,
 }
 
 impl NodeServiceCommon {
@@ -1,11 +1,14 @@
 use std::sync::{Arc, Mutex};
 
 use ledger::scan_state::scan_state::transaction_snark::{SokDigest, Statement};
+use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
+use ledger::verifier::{Verifier, VerifyCommandsResult};
 use mina_p2p_messages::v2;
 use node::{
     core::snark::{Snark, SnarkJobId},
     snark::{
         block_verify::{SnarkBlockVerifyError, SnarkBlockVerifyId, VerifiableBlockWithHash},
+        user_command_verify::{SnarkUserCommandVerifyError, SnarkUserCommandVerifyId},
         work_verify::{SnarkWorkVerifyError, SnarkWorkVerifyId},
         SnarkEvent, VerifierIndex, VerifierSRS,
     },
@@ -14,6 +17,8 @@ use rand::prelude::*;
 
 use crate::NodeServiceCommon;
 
+use super::verifier_pool::{combined_digest, proof_digest, VerifyOutcome, VerifyPriority};
+
 impl node::service::SnarkBlockVerifyService for NodeServiceCommon {
     fn verify_init(
         &mut self,
@@ -26,26 +31,30 @@ impl node::service::SnarkBlockVerifyService for NodeServiceCommon {
             return;
         }
         let tx = self.event_sender().clone();
-        eprintln!("rayon::spawn_fifo");
-        std::thread::spawn(move || {
-            eprintln!("verify({}) - start", block.hash_ref());
-            let header = block.header_ref();
-            let result = {
+        let digest = proof_digest(block.header_ref());
+        self.verifier.verify(
+            VerifyPriority::Block,
+            digest,
+            vec![digest],
+            move || {
                 let verifier_srs = verifier_srs.lock().expect("Failed to lock the SRS");
-                if !ledger::proofs::verification::verify_block(
-                    header,
+                ledger::proofs::verification::verify_block(
+                    block.header_ref(),
                     &verifier_index,
                     &verifier_srs,
-                ) {
-                    Err(SnarkBlockVerifyError::VerificationFailed)
-                } else {
-                    Ok(())
-                }
-            };
-            eprintln!("verify({}) - end", block.hash_ref());
-
-            let _ = tx.send(SnarkEvent::BlockVerify(req_id, result).into());
-        });
+                )
+            },
+            move |outcome| {
+                let result = match outcome {
+                    VerifyOutcome::Verified => Ok(()),
+                    // Blocks are never shed.
+                    VerifyOutcome::Failed | VerifyOutcome::Shed => {
+                        Err(SnarkBlockVerifyError::VerificationFailed)
+                    }
+                };
+                let _ = tx.send(SnarkEvent::BlockVerify(req_id, result).into());
+            },
+        );
     }
 }
 
@@ -61,8 +70,17 @@ impl node::service::SnarkWorkVerifyService for NodeServiceCommon {
             return;
         }
         let tx = self.event_sender().clone();
-        rayon::spawn_fifo(move || {
-            let result = {
+        // Only proofs that weren't verified before need to be checked.
+        let (digests, work): (Vec<_>, Vec<_>) = work
+            .into_iter()
+            .map(|work| (proof_digest(&work), work))
+            .filter(|(digest, _)| !self.verifier.is_verified(digest))
+            .unzip();
+        self.verifier.verify(
+            VerifyPriority::SnarkWork,
+            combined_digest(&digests),
+            digests,
+            move || {
                 let conv = |proof: &v2::LedgerProofProdStableV2| {
                     (
                         Statement::<SokDigest>::from(&proof.0.statement),
@@ -80,19 +98,74 @@ impl node::service::SnarkWorkVerifyService for NodeServiceCommon {
                     .flatten()
                     .collect::<Vec<_>>();
                 let verifier_srs = verifier_srs.lock().expect("Failed to lock SRS");
-                if !ledger::proofs::verification::verify_transaction(
+                ledger::proofs::verification::verify_transaction(
                     works.iter().map(|(v1, v2)| (v1, v2)),
                     &verifier_index,
                     &verifier_srs,
-                ) {
-                    Err(SnarkWorkVerifyError::VerificationFailed)
-                } else {
-                    Ok(())
-                }
-            };
+                )
+            },
+            move |outcome| {
+                let result = match outcome {
+                    VerifyOutcome::Verified => Ok(()),
+                    VerifyOutcome::Failed => Err(SnarkWorkVerifyError::VerificationFailed),
+                    VerifyOutcome::Shed => Err(SnarkWorkVerifyError::Overloaded),
+                };
+                let _ = tx.send(SnarkEvent::WorkVerify(req_id, result).into());
+            },
+        );
+    }
+}
 
-            let _ = tx.send(SnarkEvent::WorkVerify(req_id, result).into());
-        });
+impl node::snark::user_command_verify_effectful::SnarkUserCommandVerifyService
+    for NodeServiceCommon
+{
+    fn verify_init(
+        &mut self,
+        req_id: SnarkUserCommandVerifyId,
+        _verifier_index: Arc<VerifierIndex>,
+        _verifier_srs: Arc<Mutex<VerifierSRS>>,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
+    ) {
+        if self.replayer.is_some() {
+            return;
+        }
+        let tx = self.event_sender().clone();
+        // Signatures and proofs are identified by the whole command,
+        // together with verification keys of zkApp commands.
+        let digests = commands
+            .iter()
+            .map(|cmd| match &cmd.data {
+                verifiable::UserCommand::SignedCommand(cmd) => {
+                    proof_digest(&v2::MinaBaseSignedCommandStableV2::from(&**cmd))
+                }
+                verifiable::UserCommand::ZkAppCommand(cmd) => proof_digest(
+                    &v2::MinaBaseZkappCommandVerifiableStableV1::from((**cmd).clone()),
+                ),
+            })
+            .collect::<Vec<_>>();
+        let to_verify = commands.clone();
+        self.verifier.verify(
+            VerifyPriority::UserCommand,
+            combined_digest(&digests),
+            digests,
+            move || {
+                Verifier
+                    .verify_commands(to_verify, None)
+                    .iter()
+                    .all(|res| matches!(res, VerifyCommandsResult::Valid(_)))
+            },
+            move |outcome| {
+                let result = match outcome {
+                    VerifyOutcome::Verified => Ok(commands
+                        .into_iter()
+                        .map(|cmd| cmd.data.to_valid_unsafe())
+                        .collect()),
+                    VerifyOutcome::Failed => Err(SnarkUserCommandVerifyError::VerificationFailed),
+                    VerifyOutcome::Shed => Err(SnarkUserCommandVerifyError::Overloaded),
+                };
+                let _ = tx.send(SnarkEvent::UserCommandVerify(req_id, result).into());
+            },
+        );
     }
 }
 
@@ -0,0 +1,514 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use ledger::proofs::verifier_index::zkapp_verifier_index_cache_stats;
+use mina_p2p_messages::binprot::BinProtWrite;
+use node::core::thread;
+use node::snark::SnarkVerifierStats;
+use sha3::{Digest, Sha3_256};
+
+/// Number of verified proof digests remembered. Oldest ones are evicted
+/// first.
+const VERIFIED_PROOFS_CACHE_SIZE: usize = 8192;
+
+/// Maximum number of requests waiting for a free verifier. When full,
+/// the newest queued request of the lowest priority is shed to make
+/// room. Blocks are never shed.
+const MAX_QUEUED_JOBS: usize = 256;
+
+/// Number of verifier threads used by default.
+///
+/// Verification itself is serialized on the SRS lock, the second thread
+/// prepares verifier inputs in the meantime.
+pub const DEFAULT_VERIFIER_THREADS: usize = 2;
+
+pub type ProofDigest = [u8; 32];
+
+/// Digest of a binprot encoded value, identifying a proof together with
+/// the statement it proves.
+pub fn proof_digest<T: BinProtWrite>(value: &T) -> ProofDigest {
+    let mut buf = Vec::new();
+    value
+        .binprot_write(&mut buf)
+        .expect("writing to vec can't fail");
+    Sha3_256::digest(&buf).into()
+}
+
+/// Digest identifying a request to verify multiple proofs.
+pub fn combined_digest(digests: &[ProofDigest]) -> ProofDigest {
+    digests
+        .iter()
+        .fold(Sha3_256::new(), |hasher, digest| {
+            hasher.chain_update(digest)
+        })
+        .finalize()
+        .into()
+}
+
+/// Priority classes of verification requests, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyPriority {
+    Block = 0,
+    SnarkWork = 1,
+    UserCommand = 2,
+}
+
+/// Outcome of a verification request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    Verified,
+    Failed,
+    /// Dropped unverified because the queue was full. Says nothing
+    /// about validity of the proofs, the request can be retried.
+    Shed,
+}
+
+type VerifyFn = Box<dyn FnOnce() -> bool + Send>;
+type RespondFn = Box<dyn FnOnce(VerifyOutcome) + Send>;
+
+struct VerifyJob {
+    key: ProofDigest,
+    proofs: Vec<ProofDigest>,
+    verify: VerifyFn,
+}
+
+#[derive(Default)]
+struct VerifiedProofs {
+    digests: HashSet<ProofDigest>,
+    order: VecDeque<ProofDigest>,
+}
+
+impl VerifiedProofs {
+    fn contains(&self, digest: &ProofDigest) -> bool {
+        self.digests.contains(digest)
+    }
+
+    fn insert(&mut self, digest: ProofDigest) {
+        if !self.digests.insert(digest) {
+            return;
+        }
+        self.order.push_back(digest);
+        if self.order.len() > VERIFIED_PROOFS_CACHE_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.digests.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    queues: [VecDeque<VerifyJob>; 3],
+    /// Responders of queued or running jobs, by job key.
+    in_flight: HashMap<ProofDigest, Vec<RespondFn>>,
+    verified_proofs: VerifiedProofs,
+    stats: SnarkVerifierStats,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    inner: Mutex<Inner>,
+    job_available: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().expect("verifier pool lock poisoned")
+    }
+}
+
+/// Bounded pool of threads verifying proofs of blocks, snark work and
+/// user commands.
+///
+/// Requests are served by priority, proofs that were already verified
+/// are answered from cache and identical requests in flight are
+/// verified only once. Under load, user commands are shed first, then
+/// snark work, blocks never. In the browser, threads are web workers.
+pub struct VerifierPool {
+    shared: Arc<Shared>,
+}
+
+impl VerifierPool {
+    pub fn new(threads: usize) -> Self {
+        let threads = threads.max(1);
+        let shared = Arc::new(Shared::default());
+        shared.lock().stats.threads = threads;
+        for i in 0..threads {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name(format!("openmina_verifier_{i}"))
+                .spawn(move || worker(&shared))
+                .expect("failed to spawn verifier thread");
+        }
+        Self { shared }
+    }
+
+    /// Whether the proof with `digest` was recently verified successfully.
+    pub fn is_verified(&self, digest: &ProofDigest) -> bool {
+        self.shared.lock().verified_proofs.contains(digest)
+    }
+
+    /// Verifies `proofs` by calling `verify` on the pool, unless all of
+    /// them are already verified, and passes the result to `respond`.
+    ///
+    /// `key` identifies the whole request, requests with the same key
+    /// that are already queued or running aren't verified again.
+    ///
+    /// If the queue is full, the newest queued request of lower priority
+    /// is shed to make room, otherwise this request is shed, unless it
+    /// is a block.
+    pub fn verify<V, R>(
+        &self,
+        priority: VerifyPriority,
+        key: ProofDigest,
+        proofs: Vec<ProofDigest>,
+        verify: V,
+        respond: R,
+    ) where
+        V: 'static + Send + FnOnce() -> bool,
+        R: 'static + Send + FnOnce(VerifyOutcome),
+    {
+        let mut inner = self.shared.lock();
+        if proofs
+            .iter()
+            .all(|digest| inner.verified_proofs.contains(digest))
+        {
+            inner.stats.cache_hits += 1;
+            drop(inner);
+            return respond(VerifyOutcome::Verified);
+        }
+        if let Some(responders) = inner.in_flight.get_mut(&key) {
+            responders.push(Box::new(respond));
+            inner.stats.deduplicated += 1;
+            return;
+        }
+
+        let mut shed = Vec::new();
+        if inner.queues.iter().map(VecDeque::len).sum::<usize>() >= MAX_QUEUED_JOBS {
+            let lower = inner.queues[priority as usize + 1..]
+                .iter_mut()
+                .rev()
+                .find_map(VecDeque::pop_back);
+            match lower {
+                Some(job) => shed = inner.in_flight.remove(&job.key).unwrap_or_default(),
+                None if priority != VerifyPriority::Block => {
+                    inner.stats.shed += 1;
+                    drop(inner);
+                    return respond(VerifyOutcome::Shed);
+                }
+                None => {}
+            }
+            inner.stats.shed += shed.len() as u64;
+        }
+
+        inner.in_flight.insert(key, vec![Box::new(respond)]);
+        inner.queues[priority as usize].push_back(VerifyJob {
+            key,
+            proofs,
+            verify: Box::new(verify),
+        });
+        self.shared.job_available.notify_one();
+        drop(inner);
+        shed.into_iter()
+            .for_each(|respond| respond(VerifyOutcome::Shed));
+    }
+
+    pub fn stats(&self) -> SnarkVerifierStats {
+        let inner = self.shared.lock();
+        let [blocks, works, user_commands] = &inner.queues;
+        SnarkVerifierStats {
+            queued_blocks: blocks.len(),
+            queued_works: works.len(),
+            queued_user_commands: user_commands.len(),
+            cached_proofs: inner.verified_proofs.order.len(),
+            zkapp_verifier_indexes: zkapp_verifier_index_cache_stats(),
+            ..inner.stats.clone()
+        }
+    }
+}
+
+impl Default for VerifierPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_VERIFIER_THREADS)
+    }
+}
+
+impl Drop for VerifierPool {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.job_available.notify_all();
+    }
+}
+
+fn worker(shared: &Shared) {
+    loop {
+        let job = {
+            let mut inner = shared.lock();
+            loop {
+                if let Some(job) = inner.queues.iter_mut().find_map(VecDeque::pop_front) {
+                    inner.stats.in_progress += 1;
+                    break job;
+                }
+                if inner.shutdown {
+                    return;
+                }
+                inner = shared
+                    .job_available
+                    .wait(inner)
+                    .expect("verifier pool lock poisoned");
+            }
+        };
+
+        // Verifier panicking on malformed input must not take the pool down.
+        let verified = std::panic::catch_unwind(AssertUnwindSafe(job.verify)).unwrap_or(false);
+
+        let responders = {
+            let mut inner = shared.lock();
+            inner.stats.in_progress -= 1;
+            if verified {
+                inner.stats.verified += 1;
+                job.proofs
+                    .into_iter()
+                    .for_each(|digest| inner.verified_proofs.insert(digest));
+            } else {
+                inner.stats.failed += 1;
+            }
+            inner.in_flight.remove(&job.key).unwrap_or_default()
+        };
+        let outcome = if verified {
+            VerifyOutcome::Verified
+        } else {
+            VerifyOutcome::Failed
+        };
+        responders.into_iter().for_each(|respond| respond(outcome));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_verified_proofs_are_cached_and_duplicates_joined() {
+        let pool = VerifierPool::new(1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (result_tx, result_rx) = mpsc::channel();
+        let respond = || {
+            let result_tx = result_tx.clone();
+            move |outcome| result_tx.send(outcome == VerifyOutcome::Verified).unwrap()
+        };
+        let digest = [1; 32];
+
+        pool.verify(
+            VerifyPriority::Block,
+            digest,
+            vec![digest],
+            move || release_rx.recv().is_ok(),
+            respond(),
+        );
+        pool.verify(
+            VerifyPriority::Block,
+            digest,
+            vec![digest],
+            || unreachable!("joined request must not be verified"),
+            respond(),
+        );
+        release_tx.send(()).unwrap();
+
+        let timeout = Duration::from_secs(5);
+        assert!(result_rx.recv_timeout(timeout).unwrap());
+        assert!(result_rx.recv_timeout(timeout).unwrap());
+        assert!(pool.is_verified(&digest));
+
+        pool.verify(
+            VerifyPriority::Block,
+            digest,
+            vec![digest],
+            || unreachable!("cached proof must not be verified again"),
+            respond(),
+        );
+        assert!(result_rx.recv_timeout(timeout).unwrap());
+
+        let stats = pool.stats();
+        assert_eq!(stats.verified, 1);
+        assert_eq!(stats.deduplicated, 1);
+        assert_eq!(stats.cache_hits, 1);
+    }
+
+    #[test]
+    fn test_full_queue_sheds_snark_work_first() {
+        let pool = VerifierPool::new(1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+        let respond = |id: u16| {
+            let result_tx = result_tx.clone();
+            move |outcome| result_tx.send((id, outcome)).unwrap()
+        };
+        let digest = |id: u16| {
+            let mut digest = [0; 32];
+            digest[..2].copy_from_slice(&id.to_be_bytes());
+            digest
+        };
+        let timeout = Duration::from_secs(5);
+
+        // Keep the only verifier busy so that requests stay queued.
+        pool.verify(
+            VerifyPriority::Block,
+            digest(0),
+            vec![digest(0)],
+            move || {
+                started_tx.send(()).unwrap();
+                release_rx.recv().is_ok()
+            },
+            respond(0),
+        );
+        started_rx.recv_timeout(timeout).unwrap();
+
+        let queued = MAX_QUEUED_JOBS as u16;
+        for id in 1..=queued {
+            pool.verify(
+                VerifyPriority::SnarkWork,
+                digest(id),
+                vec![digest(id)],
+                || true,
+                respond(id),
+            );
+        }
+        assert_eq!(pool.stats().queued_works, MAX_QUEUED_JOBS);
+
+        // Snark work can't displace snark work.
+        let rejected = queued + 1;
+        pool.verify(
+            VerifyPriority::SnarkWork,
+            digest(rejected),
+            vec![digest(rejected)],
+            || unreachable!("shed request must not be verified"),
+            respond(rejected),
+        );
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (rejected, VerifyOutcome::Shed)
+        );
+
+        // Block takes the place of the newest queued snark work.
+        let block = queued + 2;
+        pool.verify(
+            VerifyPriority::Block,
+            digest(block),
+            vec![digest(block)],
+            || true,
+            respond(block),
+        );
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (queued, VerifyOutcome::Shed)
+        );
+
+        let stats = pool.stats();
+        assert_eq!(stats.queued_blocks, 1);
+        assert_eq!(stats.queued_works, MAX_QUEUED_JOBS - 1);
+        assert_eq!(stats.shed, 2);
+
+        release_tx.send(()).unwrap();
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (0, VerifyOutcome::Verified)
+        );
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (block, VerifyOutcome::Verified)
+        );
+        assert!(!pool.is_verified(&digest(queued)));
+    }
+
+    #[test]
+    fn test_full_queue_sheds_user_commands_before_snark_work() {
+        let pool = VerifierPool::new(1);
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+        let respond = |id: u16| {
+            let result_tx = result_tx.clone();
+            move |outcome| result_tx.send((id, outcome)).unwrap()
+        };
+        let digest = |id: u16| {
+            let mut digest = [0; 32];
+            digest[..2].copy_from_slice(&id.to_be_bytes());
+            digest
+        };
+        let timeout = Duration::from_secs(5);
+
+        // Keep the only verifier busy so that requests stay queued.
+        pool.verify(
+            VerifyPriority::Block,
+            digest(0),
+            vec![digest(0)],
+            move || {
+                started_tx.send(()).unwrap();
+                release_rx.recv().is_ok()
+            },
+            respond(0),
+        );
+        started_rx.recv_timeout(timeout).unwrap();
+
+        let queued = MAX_QUEUED_JOBS as u16;
+        for id in 1..queued {
+            pool.verify(
+                VerifyPriority::SnarkWork,
+                digest(id),
+                vec![digest(id)],
+                || true,
+                respond(id),
+            );
+        }
+        pool.verify(
+            VerifyPriority::UserCommand,
+            digest(queued),
+            vec![digest(queued)],
+            || unreachable!("shed request must not be verified"),
+            respond(queued),
+        );
+        assert_eq!(pool.stats().queued_user_commands, 1);
+
+        // User command can't displace snark work.
+        let rejected = queued + 1;
+        pool.verify(
+            VerifyPriority::UserCommand,
+            digest(rejected),
+            vec![digest(rejected)],
+            || unreachable!("shed request must not be verified"),
+            respond(rejected),
+        );
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (rejected, VerifyOutcome::Shed)
+        );
+
+        // Snark work takes the place of the queued user command.
+        let work = queued + 2;
+        pool.verify(
+            VerifyPriority::SnarkWork,
+            digest(work),
+            vec![digest(work)],
+            || true,
+            respond(work),
+        );
+        assert_eq!(
+            result_rx.recv_timeout(timeout).unwrap(),
+            (queued, VerifyOutcome::Shed)
+        );
+
+        let stats = pool.stats();
+        assert_eq!(stats.queued_works, MAX_QUEUED_JOBS);
+        assert_eq!(stats.queued_user_commands, 0);
+        assert_eq!(stats.shed, 2);
+
+        release_tx.send(()).unwrap();
+    }
+}
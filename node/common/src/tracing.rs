@@ -1,15 +1,24 @@
 pub use tracing::Level;
 
 use std::fmt::Result;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-use tracing::field::Visit;
+use node::core::log::{log_filter, system_time, to_rfc_3339, LogSubsystem};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
 use tracing_subscriber::{
     field::{RecordFields, VisitOutput},
     fmt::{
         format::{PrettyVisitor, Writer},
         time::FormatTime,
-        FormatFields,
+        FmtContext, FormatEvent, FormatFields,
     },
+    layer::{Context, Filter, SubscriberExt},
+    registry::LookupSpan,
+    Layer,
 };
 
 #[allow(unused)]
@@ -43,11 +52,35 @@ impl<T> Visit for FilterVisit<T>
 where
     T: Visit,
 {
-    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
         if !field.name().starts_with("trace_") {
             self.0.record_debug(field, value);
         }
     }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if !field.name().starts_with("trace_") {
+            self.0.record_str(field, value);
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if !field.name().starts_with("trace_") {
+            self.0.record_i64(field, value);
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if !field.name().starts_with("trace_") {
+            self.0.record_u64(field, value);
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if !field.name().starts_with("trace_") {
+            self.0.record_bool(field, value);
+        }
+    }
 }
 
 #[derive(Default)]
@@ -65,19 +98,171 @@ impl<'writer> FormatFields<'writer> for TracingFieldFormatter {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable output.
+    #[default]
+    Text,
+    /// One JSON object per line, with event fields flattened into it.
+    Json,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid log format: {0}! expected one of: text/json")]
+pub struct LogFormatParseError(String);
+
+impl FromStr for LogFormat {
+    type Err = LogFormatParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(LogFormatParseError(other.to_owned())),
+        }
+    }
+}
+
+static NODE_ID: OnceLock<String> = OnceLock::new();
+
+/// Sets `node_id` field for JSON log entries that don't carry one, i.e.
+/// the ones not emitted for actions.
+pub fn set_node_id(node_id: impl std::fmt::Display) {
+    let _ = NODE_ID.set(node_id.to_string());
+}
+
+/// Enables events up to the level of the subsystem they are emitted
+/// from (see [`node::core::log::set_log_filters`]), or up to the global
+/// verbosity.
+struct SubsystemFilter {
+    default: LevelFilter,
+}
+
+impl SubsystemFilter {
+    fn level(&self, meta: &Metadata<'_>) -> LevelFilter {
+        meta.module_path()
+            .and_then(LogSubsystem::from_module_path)
+            .and_then(log_filter)
+            .map_or(self.default, Into::into)
+    }
+}
+
+impl<S> Filter<S> for SubsystemFilter {
+    fn enabled(&self, meta: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        *meta.level() <= self.level(meta)
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        // Cached interest is rebuilt when subsystem levels change.
+        if *meta.level() <= self.level(meta) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        LogSubsystem::ALL
+            .into_iter()
+            .filter_map(log_filter)
+            .map(LevelFilter::from)
+            .chain([self.default])
+            .max()
+    }
+}
+
+#[derive(Default)]
+struct JsonVisitor(serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{value:?}").into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+}
+
+/// Formats events as single line JSON objects with `timestamp`, `level`,
+/// `target`, `subsystem` and `node_id` entries, followed by event fields
+/// (`kind`, `summary`, `peer_id`, `block_hash`, ...).
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> Result {
+        let meta = event.metadata();
+        let mut fields = FilterVisit(JsonVisitor::default());
+        event.record(&mut fields);
+        let mut fields = fields.into_inner().0;
+
+        let mut entry = serde_json::Map::new();
+        if let Ok(timestamp) = to_rfc_3339(system_time()) {
+            entry.insert("timestamp".to_owned(), timestamp.into());
+        }
+        entry.insert("level".to_owned(), meta.level().as_str().into());
+        entry.insert("target".to_owned(), meta.target().into());
+        if let Some(subsystem) = meta.module_path().and_then(LogSubsystem::from_module_path) {
+            entry.insert("subsystem".to_owned(), subsystem.as_str().into());
+        }
+        if let Some(node_id) = NODE_ID.get() {
+            entry.insert("node_id".to_owned(), node_id.as_str().into());
+        }
+        entry.append(&mut fields);
+
+        let line = serde_json::to_string(&entry).map_err(|_| std::fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
 pub fn initialize(max_log_level: Level) {
-    let builder = tracing_subscriber::FmtSubscriber::builder()
-        .with_max_level(max_log_level)
+    initialize_with_format(LogFormat::Text, max_log_level)
+}
+
+pub fn initialize_with_format(format: LogFormat, max_log_level: Level) {
+    let filter = SubsystemFilter {
+        default: max_log_level.into(),
+    };
+    let layer = tracing_subscriber::fmt::layer()
         .with_ansi(std::io::IsTerminal::is_terminal(&std::io::stdout()))
         .with_test_writer()
         //.with_timer(ReduxTimer)
         ;
-    if max_log_level != Level::TRACE {
-        let subscriber = builder.fmt_fields(TracingFieldFormatter).finish();
-        tracing::subscriber::set_global_default(subscriber)
-    } else {
-        let subscriber = builder.finish();
-        tracing::subscriber::set_global_default(subscriber)
+    let registry = tracing_subscriber::registry();
+    match format {
+        LogFormat::Json => {
+            let layer = layer.with_ansi(false).event_format(JsonFormat);
+            tracing::subscriber::set_global_default(registry.with(layer.with_filter(filter)))
+        }
+        LogFormat::Text if max_log_level != Level::TRACE => {
+            let layer = layer.fmt_fields(TracingFieldFormatter);
+            tracing::subscriber::set_global_default(registry.with(layer.with_filter(filter)))
+        }
+        LogFormat::Text => {
+            tracing::subscriber::set_global_default(registry.with(layer.with_filter(filter)))
+        }
     }
     .expect("global subscriber should be configurable");
 }
@@ -0,0 +1,2 @@
+mod no_double_production;
+pub use no_double_production::*;
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use mina_p2p_messages::v2::StateHash;
+use node::{ActionKind, ActionWithMeta, Service, Store};
+
+use crate::{Invariant, InvariantResult};
+
+/// Number of most recent produced blocks remembered.
+const MAX_PRODUCED_BLOCKS: usize = 1000;
+
+/// Makes sure block producer never produces more than one block for
+/// the same global slot.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct BlockProducerNoDoubleProduction;
+
+impl Invariant for BlockProducerNoDoubleProduction {
+    /// Hashes of the produced blocks by their global slot.
+    type InternalState = BTreeMap<u32, StateHash>;
+    fn triggers(&self) -> &[ActionKind] {
+        &[ActionKind::BlockProducerBlockProduced]
+    }
+
+    fn check<S: Service>(
+        self,
+        produced: &mut Self::InternalState,
+        store: &Store<S>,
+        _action: &ActionWithMeta,
+    ) -> InvariantResult {
+        let Some(block) = store.state().block_producer.produced_block() else {
+            return InvariantResult::Updated;
+        };
+        record_produced_block(produced, block.global_slot(), block.hash())
+    }
+}
+
+fn record_produced_block(
+    produced: &mut BTreeMap<u32, StateHash>,
+    global_slot: u32,
+    hash: &StateHash,
+) -> InvariantResult {
+    if let Some(prev_hash) = produced.get(&global_slot) {
+        return InvariantResult::Violation(format!(
+            "produced second block for global slot {global_slot}!\nprev: {prev_hash}\nnew: {hash}"
+        ));
+    }
+    produced.insert(global_slot, hash.clone());
+    while produced.len() > MAX_PRODUCED_BLOCKS {
+        produced.pop_first();
+    }
+
+    InvariantResult::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_block_for_same_slot_is_violation() {
+        let mut produced = BTreeMap::new();
+        let hash = StateHash::zero();
+
+        assert!(matches!(
+            record_produced_block(&mut produced, 10, &hash),
+            InvariantResult::Ok
+        ));
+        assert!(matches!(
+            record_produced_block(&mut produced, 11, &hash),
+            InvariantResult::Ok
+        ));
+        assert!(matches!(
+            record_produced_block(&mut produced, 10, &hash),
+            InvariantResult::Violation(_)
+        ));
+    }
+
+    #[test]
+    fn oldest_produced_blocks_are_forgotten() {
+        let mut produced = BTreeMap::new();
+        let hash = StateHash::zero();
+        for slot in 0..=MAX_PRODUCED_BLOCKS as u32 {
+            record_produced_block(&mut produced, slot, &hash);
+        }
+
+        assert_eq!(produced.len(), MAX_PRODUCED_BLOCKS);
+        assert!(!produced.contains_key(&0));
+    }
+}
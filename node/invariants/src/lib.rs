@@ -7,11 +7,25 @@ use no_recursion::*;
 pub mod transition_frontier;
 use transition_frontier::*;
 
-pub use node::core::invariants::{InvariantService, InvariantsState};
+pub mod transaction_pool;
+use transaction_pool::*;
+
+pub mod p2p;
+use p2p::*;
+
+pub mod snark_pool;
+use snark_pool::*;
+
+pub mod block_producer;
+use block_producer::*;
+
+pub use node::core::invariants::{
+    InvariantService, InvariantViolation, InvariantViolations, InvariantsState,
+};
 
 use strum_macros::{EnumDiscriminants, EnumIter, EnumString, IntoStaticStr};
 
-use node::{ActionKind, ActionWithMeta, Store};
+use node::{ActionKind, ActionWithMeta, Service, Store};
 
 pub trait Invariant {
     /// Internal state of the invariant.
@@ -26,8 +40,14 @@ pub trait Invariant {
     /// If empty, an invariant will never be checked!
     fn triggers(&self) -> &[ActionKind];
 
+    /// Whether invariant is cheap and safe enough to be checked in
+    /// production nodes, see [`Invariants::check_all_lightweight`].
+    fn is_lightweight(&self) -> bool {
+        true
+    }
+
     /// Checks the state for invariant violation.
-    fn check<S: redux::Service>(
+    fn check<S: Service>(
         self,
         internal_state: &mut Self::InternalState,
         store: &Store<S>,
@@ -54,7 +74,13 @@ macro_rules! define_invariants_enum {
                 }
             }
 
-            pub fn check<S: InvariantService>(self, store: &mut Store<S>, action: &ActionWithMeta) -> InvariantResult {
+            pub fn is_lightweight(&self) -> bool {
+                match self {
+                    $(Self::$invariant(invariant) => invariant.is_lightweight(),)*
+                }
+            }
+
+            pub fn check<S: Service>(self, store: &mut Store<S>, action: &ActionWithMeta) -> InvariantResult {
                 let mut invariants_state = store.service.invariants_state().take();
                 let res = match self {
                     $(Self::$invariant(invariant) => {
@@ -72,6 +98,11 @@ macro_rules! define_invariants_enum {
 define_invariants_enum! {
     NoRecursion,
     TransitionFrontierOnlySyncsToBetterBlocks,
+    TransitionFrontierBestTipLedgerHashesMatch,
+    TransactionPoolNoncesMatchBestTip,
+    P2pPeersWithinLimits,
+    SnarkPoolJobsMatchScanState,
+    BlockProducerNoDoubleProduction,
 }
 
 lazy_static::lazy_static! {
@@ -94,7 +125,21 @@ impl Invariants {
         <Self as strum::IntoEnumIterator>::iter()
     }
 
-    pub fn check_all<'a, S: InvariantService>(
+    pub fn check_all<'a, S: Service>(
+        store: &'a mut Store<S>,
+        action: &'a ActionWithMeta,
+    ) -> impl 'a + Iterator<Item = (Self, InvariantResult)> {
+        let action_kind = action.action().kind();
+        INVARIANTS_BY_ACTION_KIND
+            .get(action_kind as usize)
+            .unwrap()
+            .iter()
+            .map(|invariant| (*invariant, invariant.check(store, action)))
+    }
+
+    /// Same as [`Invariants::check_all`], but skips invariants which
+    /// aren't [lightweight](Invariant::is_lightweight).
+    pub fn check_all_lightweight<'a, S: Service>(
         store: &'a mut Store<S>,
         action: &'a ActionWithMeta,
     ) -> impl 'a + Iterator<Item = (Self, InvariantResult)> {
@@ -103,6 +148,7 @@ impl Invariants {
             .get(action_kind as usize)
             .unwrap()
             .iter()
+            .filter(|invariant| invariant.is_lightweight())
             .map(|invariant| (*invariant, invariant.check(store, action)))
     }
 
@@ -110,3 +156,37 @@ impl Invariants {
         self.into()
     }
 }
+
+/// Node effects which check [lightweight](Invariant::is_lightweight)
+/// invariants before running [`node::effects`].
+///
+/// Violations are logged and recorded in [`InvariantViolations`]
+/// (exposed through RPC) instead of panicking, so it can be used when
+/// running real nodes.
+pub fn effects_with_invariants<S: Service>(store: &mut Store<S>, action: ActionWithMeta) {
+    let violations = Invariants::check_all_lightweight(store, &action)
+        .filter_map(|(invariant, res)| match res {
+            InvariantResult::Violation(violation) => Some((invariant, violation)),
+            InvariantResult::Updated | InvariantResult::Ok => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (invariant, violation) in violations {
+        let action_kind = action.action().kind();
+        node::core::log::error!(action.time();
+            kind = "InvariantViolation",
+            summary = format!("Invariant({}) violated!", invariant.to_str()),
+            action_kind = format!("{action_kind:?}"),
+            violation = violation.clone(),
+        );
+        let violations = store.service.invariants_state().violations_mut();
+        violations.push(InvariantViolation {
+            time: action.time(),
+            invariant: invariant.to_str().to_owned(),
+            action_kind: format!("{action_kind:?}"),
+            violation,
+        });
+    }
+
+    node::effects(store, action)
+}
@@ -1,4 +1,4 @@
-use node::{ActionKind, ActionWithMeta, Store};
+use node::{ActionKind, ActionWithMeta, Service, Store};
 use strum::VariantArray;
 
 use crate::{Invariant, InvariantResult};
@@ -18,7 +18,13 @@ impl Invariant for NoRecursion {
         ActionKind::VARIANTS
     }
 
-    fn check<S: redux::Service>(
+    /// Checked on every action and panics if action depth isn't
+    /// tracked as expected.
+    fn is_lightweight(&self) -> bool {
+        false
+    }
+
+    fn check<S: Service>(
         self,
         action_stack: &mut Self::InternalState,
         _: &Store<S>,
@@ -0,0 +1,2 @@
+mod peers_within_limits;
+pub use peers_within_limits::*;
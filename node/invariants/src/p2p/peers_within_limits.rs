@@ -0,0 +1,64 @@
+use node::p2p::Limit;
+use node::{ActionKind, ActionWithMeta, Service, Store};
+
+use crate::{Invariant, InvariantResult};
+
+/// Makes sure number of connected peers doesn't exceed the hard limit
+/// on connections (`P2pLimits::max_connections`).
+///
+/// Incoming connections are refused once `P2pLimits::max_peers` is
+/// reached, but outgoing ones might still get us over it, hence we
+/// check the hard limit.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct P2pPeersWithinLimits;
+
+impl Invariant for P2pPeersWithinLimits {
+    type InternalState = ();
+    fn triggers(&self) -> &[ActionKind] {
+        &[ActionKind::P2pPeerReady]
+    }
+
+    fn check<S: Service>(
+        self,
+        _: &mut Self::InternalState,
+        store: &Store<S>,
+        _action: &ActionWithMeta,
+    ) -> InvariantResult {
+        let Some(p2p) = store.state().p2p.ready() else {
+            return InvariantResult::Updated;
+        };
+        let ready_peers = p2p.ready_peers_iter().count();
+        check_ready_peers(ready_peers, p2p.config.limits.max_connections())
+    }
+}
+
+fn check_ready_peers(ready_peers: usize, max_connections: Limit<usize>) -> InvariantResult {
+    if ready_peers > max_connections {
+        InvariantResult::Violation(format!(
+            "too many ready peers: {ready_peers}, max connections: {max_connections}"
+        ))
+    } else {
+        InvariantResult::Ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_peers_over_max_connections_is_violation() {
+        assert!(matches!(
+            check_ready_peers(10, Limit::Some(10)),
+            InvariantResult::Ok
+        ));
+        assert!(matches!(
+            check_ready_peers(11, Limit::Some(10)),
+            InvariantResult::Violation(_)
+        ));
+        assert!(matches!(
+            check_ready_peers(1000, Limit::Unlimited),
+            InvariantResult::Ok
+        ));
+    }
+}
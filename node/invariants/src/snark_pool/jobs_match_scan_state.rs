@@ -0,0 +1,116 @@
+use std::collections::BTreeSet;
+
+use node::core::snark::SnarkJobId;
+use node::ledger::LedgerService;
+use node::{ActionKind, ActionWithMeta, Service, Store};
+
+use crate::{Invariant, InvariantResult};
+
+/// Makes sure that once snark pool jobs are updated, pool contains
+/// exactly the jobs which are available in the best tip's scan state.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct SnarkPoolJobsMatchScanState;
+
+impl Invariant for SnarkPoolJobsMatchScanState {
+    type InternalState = ();
+    fn triggers(&self) -> &[ActionKind] {
+        &[ActionKind::SnarkPoolJobsUpdate]
+    }
+
+    /// Scan state is read from the ledger thread, blocking the state machine.
+    fn is_lightweight(&self) -> bool {
+        false
+    }
+
+    fn check<S: Service>(
+        self,
+        _: &mut Self::InternalState,
+        store: &Store<S>,
+        _action: &ActionWithMeta,
+    ) -> InvariantResult {
+        let Some(best_tip) = store.state().transition_frontier.best_tip() else {
+            return InvariantResult::Updated;
+        };
+        let Some(jobs) = store
+            .service
+            .ledger_manager()
+            .scan_state_available_jobs(best_tip.staged_ledger_hash())
+        else {
+            return InvariantResult::Violation(format!(
+                "staged ledger of the best tip {} not found",
+                best_tip.hash()
+            ));
+        };
+        let expected = jobs.iter().map(SnarkJobId::from).collect::<BTreeSet<_>>();
+        let actual = store
+            .state()
+            .snark_pool
+            .jobs_iter()
+            .map(|job| &job.id)
+            .collect::<BTreeSet<_>>();
+        check_jobs(&expected, &actual)
+    }
+}
+
+fn check_jobs(expected: &BTreeSet<SnarkJobId>, actual: &BTreeSet<&SnarkJobId>) -> InvariantResult {
+    if let Some(id) = actual.iter().find(|id| !expected.contains(**id)) {
+        return InvariantResult::Violation(format!(
+            "job {id} in snark pool isn't available in best tip's scan state"
+        ));
+    }
+    if let Some(id) = expected.iter().find(|id| !actual.contains(id)) {
+        return InvariantResult::Violation(format!(
+            "job {id} available in best tip's scan state is missing in snark pool"
+        ));
+    }
+    InvariantResult::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER_HASHES: [&str; 3] = [
+        "jw9nPCs68UNaKaLZwV6QzdswKWomwQxvTgrpmKWmnFJyswnrn4N",
+        "jwhHYWzvJG8esmqtYXbUZy3UGbLSjhKvn1FSxBGL1JDFHqbHMJc",
+        "jwiLuRrEqNgASgXEqibGs4VqKwSwiuFEtuPD53v8hiTtVuLfmTr",
+    ];
+
+    fn job_id(source: usize, target: usize) -> SnarkJobId {
+        let (source, target) = (LEDGER_HASHES[source], LEDGER_HASHES[target]);
+        format!("{source}_{source}-{target}_{target}")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn same_jobs_match() {
+        let expected = BTreeSet::from([job_id(0, 1), job_id(1, 2)]);
+        let actual = expected.iter().collect();
+        assert!(matches!(
+            check_jobs(&expected, &actual),
+            InvariantResult::Ok
+        ));
+    }
+
+    #[test]
+    fn job_missing_in_scan_state_is_violation() {
+        let expected = BTreeSet::from([job_id(0, 1)]);
+        let extra = job_id(1, 2);
+        let actual = expected.iter().chain([&extra]).collect();
+        assert!(matches!(
+            check_jobs(&expected, &actual),
+            InvariantResult::Violation(_)
+        ));
+    }
+
+    #[test]
+    fn job_missing_in_pool_is_violation() {
+        let expected = BTreeSet::from([job_id(0, 1), job_id(1, 2)]);
+        let actual = expected.iter().take(1).collect();
+        assert!(matches!(
+            check_jobs(&expected, &actual),
+            InvariantResult::Violation(_)
+        ));
+    }
+}
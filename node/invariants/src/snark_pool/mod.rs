@@ -0,0 +1,2 @@
+mod jobs_match_scan_state;
+pub use jobs_match_scan_state::*;
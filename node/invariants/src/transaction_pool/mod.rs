@@ -0,0 +1,2 @@
+mod nonces_match_best_tip;
+pub use nonces_match_best_tip::*;
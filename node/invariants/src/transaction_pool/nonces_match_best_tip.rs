@@ -0,0 +1,107 @@
+use ledger::scan_state::currency::{Magnitude, Nonce};
+use node::transaction_pool::TransactionPoolAction;
+use node::{Action, ActionKind, ActionWithMeta, Service, Store};
+
+use crate::{Invariant, InvariantResult};
+
+/// Makes sure that after the transaction pool is revalidated against
+/// the new best tip, pending commands of each sender start at the
+/// sender's nonce in the best tip ledger and have consecutive nonces.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct TransactionPoolNoncesMatchBestTip;
+
+impl Invariant for TransactionPoolNoncesMatchBestTip {
+    type InternalState = ();
+    fn triggers(&self) -> &[ActionKind] {
+        &[ActionKind::TransactionPoolBestTipChangedWithAccounts]
+    }
+
+    fn check<S: Service>(
+        self,
+        _: &mut Self::InternalState,
+        store: &Store<S>,
+        action: &ActionWithMeta,
+    ) -> InvariantResult {
+        let Action::TransactionPool(TransactionPoolAction::BestTipChangedWithAccounts { accounts }) =
+            action.action()
+        else {
+            return InvariantResult::Updated;
+        };
+
+        let pool = store.state().transaction_pool.pool();
+        for (sender, commands) in pool.commands_by_sender() {
+            // Pool treats accounts missing from the best tip ledger as empty.
+            let account_nonce = accounts
+                .get(sender)
+                .map_or(Nonce::zero(), |account| account.nonce);
+            let nonces = commands
+                .iter()
+                .map(|command| command.data.forget_check().applicable_at_nonce());
+            if let Some((index, expected_nonce)) = first_unexpected_nonce(account_nonce, nonces) {
+                let command = &commands[index];
+                return InvariantResult::Violation(format!(
+                    "unexpected nonce of pending command {:?} from {}, expected: {}, found: {}",
+                    command.hash,
+                    sender.public_key.into_address(),
+                    expected_nonce.as_u32(),
+                    command.data.forget_check().applicable_at_nonce().as_u32(),
+                ));
+            }
+        }
+
+        InvariantResult::Ok
+    }
+}
+
+/// Index of the first nonce, which doesn't follow the previous one,
+/// starting from the account's nonce, along with the expected nonce.
+fn first_unexpected_nonce(
+    account_nonce: Nonce,
+    nonces: impl IntoIterator<Item = Nonce>,
+) -> Option<(usize, Nonce)> {
+    let mut expected_nonce = account_nonce;
+    for (index, nonce) in nonces.into_iter().enumerate() {
+        if nonce != expected_nonce {
+            return Some((index, expected_nonce));
+        }
+        expected_nonce = expected_nonce.succ();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nonces(nonces: &[u32]) -> Vec<Nonce> {
+        nonces.iter().copied().map(Nonce::from_u32).collect()
+    }
+
+    #[test]
+    fn consecutive_nonces_from_account_nonce() {
+        assert_eq!(
+            first_unexpected_nonce(Nonce::from_u32(3), nonces(&[])),
+            None
+        );
+        assert_eq!(
+            first_unexpected_nonce(Nonce::from_u32(3), nonces(&[3, 4, 5])),
+            None
+        );
+    }
+
+    #[test]
+    fn stale_nonce_is_violation() {
+        assert_eq!(
+            first_unexpected_nonce(Nonce::from_u32(3), nonces(&[2, 3])),
+            Some((0, Nonce::from_u32(3)))
+        );
+    }
+
+    #[test]
+    fn nonce_gap_is_violation() {
+        assert_eq!(
+            first_unexpected_nonce(Nonce::from_u32(3), nonces(&[3, 5])),
+            Some((1, Nonce::from_u32(4)))
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use mina_p2p_messages::v2::{LedgerHash, MinaBaseStagedLedgerHashStableV1, StateHash};
+use node::ledger::write::{BlockApplyResult, LedgerWriteAction, LedgerWriteResponse};
+use node::ledger::LedgerAction;
+use node::{Action, ActionKind, ActionWithMeta, Service, Store};
+
+use crate::{Invariant, InvariantResult};
+
+/// Makes sure staged and snarked ledger hashes in the header of an
+/// applied block match the ones the ledger service produced when
+/// applying it.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct TransitionFrontierBestTipLedgerHashesMatch;
+
+impl Invariant for TransitionFrontierBestTipLedgerHashesMatch {
+    type InternalState = ();
+    fn triggers(&self) -> &[ActionKind] {
+        &[ActionKind::LedgerWriteSuccess]
+    }
+
+    fn check<S: Service>(
+        self,
+        _: &mut Self::InternalState,
+        store: &Store<S>,
+        action: &ActionWithMeta,
+    ) -> InvariantResult {
+        let Action::Ledger(LedgerAction::Write(LedgerWriteAction::Success {
+            response:
+                LedgerWriteResponse::BlockApply {
+                    block_hash,
+                    result: Ok(applied),
+                },
+        })) = action.action()
+        else {
+            return InvariantResult::Updated;
+        };
+        let sync = &store.state().transition_frontier.sync;
+        let block = sync
+            .block_state(block_hash)
+            .and_then(|state| state.block())
+            .or_else(|| sync.best_tip().filter(|b| b.hash() == block_hash));
+        let Some(block) = block else {
+            return InvariantResult::Updated;
+        };
+        check_hashes(
+            block.height(),
+            block_hash,
+            block.staged_ledger_hashes(),
+            block.snarked_ledger_hash(),
+            applied,
+        )
+    }
+}
+
+fn check_hashes(
+    height: u32,
+    block_hash: &StateHash,
+    expected_staged: &MinaBaseStagedLedgerHashStableV1,
+    expected_snarked: &LedgerHash,
+    applied: &BlockApplyResult,
+) -> InvariantResult {
+    if expected_staged != &applied.staged_ledger_hashes {
+        return InvariantResult::Violation(format!(
+            "staged ledger hashes of block {height}, {block_hash} don't match applied staged ledger!\nexpected: {}\napplied: {}",
+            serde_json::to_string(expected_staged).unwrap(),
+            serde_json::to_string(&applied.staged_ledger_hashes).unwrap(),
+        ));
+    }
+    if expected_snarked != &applied.snarked_ledger_hash {
+        return InvariantResult::Violation(format!(
+            "snarked ledger hash of block {height}, {block_hash} doesn't match applied ledger proof!\nexpected: {expected_snarked}\napplied: {}",
+            applied.snarked_ledger_hash,
+        ));
+    }
+    InvariantResult::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use mina_p2p_messages::bigint::BigInt;
+    use mina_p2p_messages::v2::{
+        MinaBasePendingCoinbaseHashBuilderStableV1, MinaBasePendingCoinbaseHashVersionedStableV1,
+    };
+
+    use super::*;
+
+    const LEDGER_HASHES: [&str; 2] = [
+        "jw9nPCs68UNaKaLZwV6QzdswKWomwQxvTgrpmKWmnFJyswnrn4N",
+        "jwhHYWzvJG8esmqtYXbUZy3UGbLSjhKvn1FSxBGL1JDFHqbHMJc",
+    ];
+
+    fn ledger_hash(i: usize) -> LedgerHash {
+        LEDGER_HASHES[i].parse().unwrap()
+    }
+
+    fn staged_ledger_hashes(i: usize) -> MinaBaseStagedLedgerHashStableV1 {
+        let pending_coinbase_hash = MinaBasePendingCoinbaseHashVersionedStableV1(
+            MinaBasePendingCoinbaseHashBuilderStableV1(BigInt::zero()),
+        );
+        MinaBaseStagedLedgerHashStableV1::zero(ledger_hash(i), pending_coinbase_hash.into())
+    }
+
+    fn applied(staged: usize, snarked: usize) -> BlockApplyResult {
+        BlockApplyResult {
+            staged_ledger_hashes: staged_ledger_hashes(staged),
+            snarked_ledger_hash: ledger_hash(snarked),
+        }
+    }
+
+    fn check(applied: &BlockApplyResult) -> InvariantResult {
+        check_hashes(
+            1,
+            &StateHash::zero(),
+            &staged_ledger_hashes(0),
+            &ledger_hash(1),
+            applied,
+        )
+    }
+
+    #[test]
+    fn same_hashes_match() {
+        assert!(matches!(check(&applied(0, 1)), InvariantResult::Ok));
+    }
+
+    #[test]
+    fn staged_ledger_hashes_mismatch_is_violation() {
+        assert!(matches!(
+            check(&applied(1, 1)),
+            InvariantResult::Violation(_)
+        ));
+    }
+
+    #[test]
+    fn snarked_ledger_hash_mismatch_is_violation() {
+        assert!(matches!(
+            check(&applied(0, 0)),
+            InvariantResult::Violation(_)
+        ));
+    }
+}
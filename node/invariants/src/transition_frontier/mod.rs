@@ -1,2 +1,5 @@
 mod only_syncs_to_better_blocks;
 pub use only_syncs_to_better_blocks::*;
+
+mod best_tip_ledger_hashes_match;
+pub use best_tip_ledger_hashes_match::*;
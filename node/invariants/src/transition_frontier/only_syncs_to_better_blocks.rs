@@ -1,6 +1,6 @@
 use node::core::block::ArcBlockWithHash;
 use node::core::consensus::consensus_take;
-use node::{ActionKind, ActionWithMeta, Store};
+use node::{ActionKind, ActionWithMeta, Service, Store};
 
 use crate::{Invariant, InvariantResult};
 
@@ -19,7 +19,7 @@ impl Invariant for TransitionFrontierOnlySyncsToBetterBlocks {
         ]
     }
 
-    fn check<S: redux::Service>(
+    fn check<S: Service>(
         self,
         (prev_best_tip, prev_target_best_tip): &mut Self::InternalState,
         store: &Store<S>,
@@ -10,11 +10,12 @@ use warp::{
 };
 
 use node::core::snark::SnarkJobId;
+use node::ledger::LedgerExportQuery;
 use node::rpc::{
     ActionStatsQuery, RpcBlockProducerStatsGetResponse, RpcMessageProgressResponse, RpcPeerInfo,
     RpcRequest, RpcScanStateSummaryGetQuery, RpcScanStateSummaryGetResponse,
     RpcSnarkPoolJobGetResponse, RpcSnarkerWorkersResponse, RpcStateGetError, RpcStatusGetResponse,
-    SyncStatsQuery,
+    RpcVerifierStatsGetResponse, SyncStatsQuery,
 };
 
 use openmina_node_common::rpc::{
@@ -252,7 +253,25 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
                 }
             });
 
-        action_stats.or(sync_stats).or(block_producer_stats)
+        let rpc_sender_clone = rpc_sender.clone();
+        let verifier_stats = warp::path!("stats" / "verifier")
+            .and(warp::get())
+            .then(move || {
+                let rpc_sender_clone = rpc_sender_clone.clone();
+                async move {
+                    let result: RpcVerifierStatsGetResponse = rpc_sender_clone
+                        .oneshot_request(RpcRequest::VerifierStatsGet)
+                        .await
+                        .flatten();
+
+                    with_json_reply(&result, StatusCode::OK)
+                }
+            });
+
+        action_stats
+            .or(sync_stats)
+            .or(block_producer_stats)
+            .or(verifier_stats)
     };
 
     let rpc_sender_clone = rpc_sender.clone();
@@ -524,6 +543,120 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
             }
         });
 
+    let rpc_sender_clone = rpc_sender.clone();
+    let transition_frontier_snapshot = warp::path!("transition-frontier" / "snapshot")
+        .and(warp::get())
+        .then(move || {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::TransitionFrontierSnapshotGet)
+                    .await
+                    .map_or_else(
+                        || {
+                            JsonOrBinary::error(
+                                "response channel dropped",
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                            )
+                        },
+                        |reply: node::rpc::RpcTransitionFrontierSnapshotGetResponse| match reply {
+                            Ok(snapshot) => {
+                                let mut bytes = Vec::new();
+                                match snapshot.store(&mut bytes) {
+                                    Ok(()) => JsonOrBinary::Binary(bytes),
+                                    Err(err) => {
+                                        JsonOrBinary::error(err, StatusCode::INTERNAL_SERVER_ERROR)
+                                    }
+                                }
+                            }
+                            Err(err) => JsonOrBinary::error(err, StatusCode::SERVICE_UNAVAILABLE),
+                        },
+                    )
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let ledger_export = warp::path!("ledger" / "export")
+        .and(warp::get())
+        .and(warp::query())
+        .then(move |query: LedgerExportQuery| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::LedgerExportGet(query))
+                    .await
+                    .map_or_else(
+                        || {
+                            JsonOrBinary::error(
+                                "response channel dropped",
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                            )
+                        },
+                        |reply: node::rpc::RpcLedgerExportGetResponse| match reply {
+                            Ok(export) => JsonOrBinary::json(&*export),
+                            Err(err) => JsonOrBinary::error(err, StatusCode::SERVICE_UNAVAILABLE),
+                        },
+                    )
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let invariant_violations = warp::path!("invariants" / "violations")
+        .and(warp::get())
+        .then(move || {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::InvariantViolationsGet)
+                    .await
+                    .map_or_else(
+                        dropped_channel_response,
+                        |reply: node::rpc::RpcInvariantViolationsGetResponse| {
+                            with_json_reply(&reply, StatusCode::OK)
+                        },
+                    )
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let log_filters_get = warp::path!("log" / "filters")
+        .and(warp::get())
+        .then(move || {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::LogFiltersGet)
+                    .await
+                    .map_or_else(
+                        dropped_channel_response,
+                        |reply: node::rpc::RpcLogFiltersResponse| {
+                            with_json_reply(&reply, StatusCode::OK)
+                        },
+                    )
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let log_filters_post = warp::path!("log" / "filters")
+        .and(warp::post())
+        .and(warp::filters::body::json())
+        .then(move |filters| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::LogFiltersSet(filters))
+                    .await
+                    .map_or_else(
+                        dropped_channel_response,
+                        |reply: node::rpc::RpcLogFiltersResponse| {
+                            with_json_reply(&reply, StatusCode::OK)
+                        },
+                    )
+            }
+        });
+
     let cors = warp::cors()
         .allow_any_origin()
         .allow_headers(vec!["content-type"])
@@ -549,10 +682,19 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
         accounts,
         transaction_post,
         transition_frontier_user_commands,
+        transition_frontier_snapshot,
+        ledger_export,
+        invariant_violations,
+        log_filters_get,
+        log_filters_post,
         healthcheck(rpc_sender.clone()),
         readiness(rpc_sender.clone()),
         discovery::routing_table(rpc_sender.clone()),
         discovery::bootstrap_stats(rpc_sender.clone()),
+        discovery::record_get(rpc_sender.clone()),
+        discovery::record_put(rpc_sender.clone()),
+        discovery::providers_get(rpc_sender.clone()),
+        discovery::start_providing(rpc_sender.clone()),
         super::graphql::routes(rpc_sender),
     );
 
@@ -612,8 +754,11 @@ fn readiness(
 }
 
 mod discovery {
+    use node::p2p::{P2pNetworkKadRecord, P2pNetworkKadRecordKey};
     use node::rpc::{
-        RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryRoutingTableResponse, RpcRequest,
+        RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryProvidersGetResponse,
+        RpcDiscoveryRecordGetResponse, RpcDiscoveryRecordPutResponse,
+        RpcDiscoveryRoutingTableResponse, RpcDiscoveryStartProvidingResponse, RpcRequest,
     };
     use openmina_node_common::rpc::RpcSender;
     use warp::Filter;
@@ -638,6 +783,43 @@ mod discovery {
             .and_then(get_bootstrap_stats)
     }
 
+    pub fn record_get(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("discovery" / "records" / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(get_record)
+    }
+
+    pub fn record_put(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("discovery" / "records" / String)
+            .and(warp::post())
+            .and(warp::filters::body::bytes())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(put_record)
+    }
+
+    pub fn providers_get(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("discovery" / "providers" / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(get_providers)
+    }
+
+    pub fn start_providing(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("discovery" / "providers" / String)
+            .and(warp::post())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(post_start_providing)
+    }
+
     async fn get_routing_table(rpc_sender: RpcSender) -> Result<impl warp::Reply, warp::Rejection> {
         rpc_sender
             .oneshot_request(RpcRequest::DiscoveryRoutingTable)
@@ -659,6 +841,66 @@ mod discovery {
                 |reply: RpcDiscoveryBoostrapStatsResponse| Ok(warp::reply::json(&reply)),
             )
     }
+
+    async fn get_record(
+        key: String,
+        rpc_sender: RpcSender,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let key = P2pNetworkKadRecordKey::from(key.as_str());
+        rpc_sender
+            .oneshot_request(RpcRequest::DiscoveryRecordGet(key))
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcDiscoveryRecordGetResponse| Ok(warp::reply::json(&reply)),
+            )
+    }
+
+    async fn put_record(
+        key: String,
+        value: bytes::Bytes,
+        rpc_sender: RpcSender,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let record = P2pNetworkKadRecord {
+            key: key.as_str().into(),
+            value: value.to_vec(),
+        };
+        rpc_sender
+            .oneshot_request(RpcRequest::DiscoveryRecordPut(record))
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcDiscoveryRecordPutResponse| Ok(warp::reply::json(&reply)),
+            )
+    }
+
+    async fn get_providers(
+        key: String,
+        rpc_sender: RpcSender,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let key = P2pNetworkKadRecordKey::from(key.as_str());
+        rpc_sender
+            .oneshot_request(RpcRequest::DiscoveryProvidersGet(key))
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcDiscoveryProvidersGetResponse| Ok(warp::reply::json(&reply)),
+            )
+    }
+
+    async fn post_start_providing(
+        key: String,
+        rpc_sender: RpcSender,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let key = P2pNetworkKadRecordKey::from(key.as_str());
+        rpc_sender
+            .oneshot_request(RpcRequest::DiscoveryStartProviding(key))
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcDiscoveryStartProvidingResponse| Ok(warp::reply::json(&reply)),
+            )
+    }
 }
 
 fn with_rpc_sender(
@@ -7,7 +7,7 @@ mod service;
 pub use service::*;
 
 mod node;
-pub use node::{Node, NodeBuilder};
+pub use node::{persistence, Node, NodeBuilder};
 
 #[path = "replay.rs"]
 mod replayer;
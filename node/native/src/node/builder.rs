@@ -13,19 +13,24 @@ use node::{
     daemon_json::Daemon,
     p2p::{
         channels::ChannelId, connection::outgoing::P2pConnectionOutgoingInitOpts,
-        identity::SecretKey as P2pSecretKey, P2pLimits, P2pTimeouts,
+        identity::SecretKey as P2pSecretKey, peer::P2pPersistedPeers, P2pLimits, P2pRpcRateLimits,
+        P2pTimeouts,
     },
     service::Recorder,
     snark::{get_srs, get_verifier_index, VerifierIndex, VerifierKind, VerifierSRS},
-    transition_frontier::genesis::GenesisConfig,
-    BlockProducerConfig, GlobalConfig, LedgerConfig, P2pConfig, SnarkConfig, SnarkerConfig,
-    SnarkerStrategy, TransitionFrontierConfig,
+    transition_frontier::{
+        genesis::GenesisConfig, TransitionFrontierSnapshot, TransitionFrontierSnapshotVerified,
+    },
+    BlockProducerConfig, GlobalConfig, LedgerConfig, P2pConfig, SnarkConfig, SnarkPoolConfig,
+    SnarkerConfig, SnarkerStrategy, TransitionFrontierConfig,
 };
+use openmina_core::snark::Snark;
 use openmina_core::{consensus::ConsensusConstants, constants::constraint_constants};
 use openmina_node_common::p2p::TaskSpawner;
+use openmina_node_invariants::{effects_with_invariants, InvariantService};
 use rand::Rng;
 
-use crate::NodeServiceBuilder;
+use crate::{NodeService, NodeServiceBuilder};
 
 use super::Node;
 
@@ -41,11 +46,17 @@ pub struct NodeBuilder {
     initial_peers: Vec<P2pConnectionOutgoingInitOpts>,
     block_producer: Option<BlockProducerConfig>,
     snarker: Option<SnarkerConfig>,
+    snark_pool: SnarkPoolConfig,
+    restored_snarks: Vec<Snark>,
+    restored_user_commands: Vec<v2::MinaBaseUserCommandStableV2>,
+    persisted_peers: P2pPersistedPeers,
+    bootstrap_snapshot: Option<TransitionFrontierSnapshotVerified>,
     service: NodeServiceBuilder,
     verifier_srs: Option<Arc<Mutex<VerifierSRS>>>,
     block_verifier_index: Option<Arc<VerifierIndex>>,
     work_verifier_index: Option<Arc<VerifierIndex>>,
     http_port: Option<u16>,
+    check_invariants: bool,
     daemon_conf: Daemon,
 }
 
@@ -74,11 +85,17 @@ impl NodeBuilder {
             initial_peers: Vec::new(),
             block_producer: None,
             snarker: None,
+            snark_pool: SnarkPoolConfig::default(),
+            restored_snarks: Vec::new(),
+            restored_user_commands: Vec::new(),
+            persisted_peers: P2pPersistedPeers::default(),
+            bootstrap_snapshot: None,
             service: NodeServiceBuilder::new(rng_seed),
             verifier_srs: None,
             block_verifier_index: None,
             work_verifier_index: None,
             http_port: None,
+            check_invariants: false,
             daemon_conf,
         }
     }
@@ -170,6 +187,7 @@ impl NodeBuilder {
             pub_key: key.public_key().into(),
             custom_coinbase_receiver: None,
             proposed_protocol_version: None,
+            shadow: false,
         };
         self.block_producer = Some(config);
         self.service.block_producer_init(key);
@@ -200,6 +218,16 @@ impl NodeBuilder {
         Ok(self)
     }
 
+    /// Run block producer in shadow mode. Blocks will be produced for
+    /// every slot, but never broadcasted.
+    pub fn block_producer_shadow(&mut self) -> anyhow::Result<&mut Self> {
+        let bp = self.block_producer.as_mut().ok_or_else(|| {
+            anyhow::anyhow!("can't enable shadow mode when block producer is not initialized.")
+        })?;
+        bp.shadow = true;
+        Ok(self)
+    }
+
     pub fn custom_block_producer_config(
         &mut self,
         config: BlockProducerConfig,
@@ -228,6 +256,85 @@ impl NodeBuilder {
         self
     }
 
+    pub fn snark_pool_config(&mut self, config: SnarkPoolConfig) -> &mut Self {
+        self.snark_pool = config;
+        self
+    }
+
+    /// Restore pools and known peers persisted in the work dir by
+    /// [`crate::persistence::save`].
+    ///
+    /// Restored data isn't trusted and gets re-validated by the node.
+    pub fn restore_from_work_dir(&mut self, work_dir: impl AsRef<Path>) -> &mut Self {
+        match super::persistence::snark_pool_load(&work_dir) {
+            Ok(snarks) => self.restored_snarks = snarks,
+            Err(err) => {
+                openmina_core::warn!(
+                    openmina_core::log::system_time();
+                    kind = "SnarkPoolRestore",
+                    summary = format!("failed to restore snark pool: {err:#}")
+                );
+            }
+        }
+        match super::persistence::transaction_pool_load(&work_dir) {
+            Ok(commands) => self.restored_user_commands = commands,
+            Err(err) => {
+                openmina_core::warn!(
+                    openmina_core::log::system_time();
+                    kind = "TransactionPoolRestore",
+                    summary = format!("failed to restore transaction pool: {err:#}")
+                );
+            }
+        }
+        match super::persistence::peers_load(&work_dir) {
+            Ok(peers) => self.persisted_peers = peers,
+            Err(err) => {
+                openmina_core::warn!(
+                    openmina_core::log::system_time();
+                    kind = "PeersRestore",
+                    summary = format!("failed to restore known peers: {err:#}")
+                );
+            }
+        }
+        self
+    }
+
+    /// Bootstrap from the snapshot written by `openmina snapshot export`,
+    /// instead of syncing ledgers from peers.
+    ///
+    /// Snapshot is only used if its root matches the root of the best tip
+    /// received from peers, otherwise the node falls back to regular sync.
+    pub fn bootstrap_snapshot(&mut self, path: impl AsRef<Path>) -> anyhow::Result<&mut Self> {
+        let path = path.as_ref();
+        let file = File::open(path).with_context(|| format!("opening snapshot {path:?}"))?;
+        let snapshot = TransitionFrontierSnapshot::read(BufReader::new(file))
+            .with_context(|| format!("reading snapshot {path:?}"))?;
+        let snapshot = snapshot.verify().context("verifying snapshot")?;
+        openmina_core::info!(
+            openmina_core::log::system_time();
+            kind = "BootstrapSnapshot",
+            summary = format!("loaded snapshot with root block {}", snapshot.root_block.hash()),
+            root_height = snapshot.root_block.height(),
+        );
+        self.bootstrap_snapshot = Some(snapshot);
+        Ok(self)
+    }
+
+    /// Write blocks of the best chain, along with their transactions, to
+    /// the SQLite archive at `path`. Blocks become canonical or orphaned
+    /// as the transition frontier root moves.
+    pub fn archive(&mut self, path: impl AsRef<Path>) -> anyhow::Result<&mut Self> {
+        self.service.archive_init(path)?;
+        Ok(self)
+    }
+
+    /// Write each applied block, along with the accounts it accessed and
+    /// the resulting scan state, in the precomputed block format.
+    pub fn precomputed_block_export(&mut self, export: PrecomputedBlockExport) -> &mut Self {
+        self.service.precomputed_block_export(export);
+        self
+    }
+
     /// Set verifier srs. If not set, default will be used.
     pub fn verifier_srs(&mut self, srs: Arc<Mutex<VerifierSRS>>) -> &mut Self {
         self.verifier_srs = Some(srs);
@@ -249,6 +356,12 @@ impl NodeBuilder {
         self
     }
 
+    /// Number of threads verifying block and snark work proofs.
+    pub fn verifier_threads(&mut self, threads: usize) -> &mut Self {
+        self.service.verifier_threads(threads);
+        self
+    }
+
     pub fn record(&mut self, recorder: Recorder) -> &mut Self {
         self.service.record(recorder);
         self
@@ -260,6 +373,13 @@ impl NodeBuilder {
         self
     }
 
+    /// Check lightweight invariants after each action and record
+    /// violations, which can be queried through RPC.
+    pub fn check_invariants(&mut self) -> &mut Self {
+        self.check_invariants = true;
+        self
+    }
+
     pub fn build(self) -> anyhow::Result<Node> {
         let p2p_sec_key = self.p2p_sec_key.unwrap_or_else(P2pSecretKey::rand);
         let initial_peers = if self.initial_peers.is_empty() && !self.p2p_is_seed {
@@ -282,6 +402,22 @@ impl NodeBuilder {
 
         let protocol_constants = self.genesis_config.protocol_constants()?;
 
+        let mut transition_frontier = TransitionFrontierConfig::new(self.genesis_config);
+        transition_frontier.slot_tx_end = self.daemon_conf.slot_tx_end().map(|s| s.as_u32());
+        transition_frontier.slot_chain_end = self.daemon_conf.slot_chain_end().map(|s| s.as_u32());
+        let (bootstrap_ledgers, sync_origin) = match self.bootstrap_snapshot {
+            Some(snapshot) => {
+                transition_frontier.bootstrap_staged_ledger_parts =
+                    Some(snapshot.staged_ledger_parts);
+                // if the network moved on since the snapshot was taken, its
+                // root ledger is still closer to the ones we need to sync
+                // than the genesis ledger.
+                let sync_origin = snapshot.root_block.snarked_ledger_hash().clone();
+                (snapshot.snarked_ledgers, Some(sync_origin))
+            }
+            None => (Vec::new(), None),
+        };
+
         // build config
         let node_config = node::Config {
             global: GlobalConfig {
@@ -301,6 +437,8 @@ impl NodeBuilder {
                     .unwrap_or_default(),
                 timeouts: P2pTimeouts::default(),
                 limits: P2pLimits::default().with_max_peers(Some(100)),
+                rpc_rate_limits: P2pRpcRateLimits::default(),
+                persisted_peers: self.persisted_peers,
             },
             ledger: LedgerConfig {},
             snark: SnarkConfig {
@@ -309,18 +447,19 @@ impl NodeBuilder {
                 work_verifier_index,
                 work_verifier_srs: srs,
             },
-            transition_frontier: TransitionFrontierConfig::new(self.genesis_config),
+            transition_frontier,
             block_producer: self.block_producer,
             tx_pool: ledger::transaction_pool::Config {
                 trust_system: (),
                 pool_max_size: self.daemon_conf.tx_pool_max_size(),
                 slot_tx_end: self.daemon_conf.slot_tx_end(),
             },
+            snark_pool: self.snark_pool,
         };
 
         // build service
         let mut service = self.service;
-        service.ledger_init();
+        service.ledger_init_with_snarked_ledgers(bootstrap_ledgers, sync_origin);
 
         if !self.p2p_is_started {
             service.p2p_init(p2p_sec_key);
@@ -329,10 +468,20 @@ impl NodeBuilder {
         let consensus_consts =
             ConsensusConstants::create(constraint_constants(), &protocol_constants);
 
-        let service = service.build()?;
-        let state = node::State::new(node_config, &consensus_consts, initial_time);
+        let mut service = service.build()?;
+        let effects = if self.check_invariants {
+            service.invariants_state().violations_mut().enabled = true;
+            Some(effects_with_invariants as node::Effects<NodeService>)
+        } else {
+            None
+        };
+        let mut state = node::State::new(node_config, &consensus_consts, initial_time);
+        state.snark_pool.restored_snarks_add(self.restored_snarks);
+        state
+            .transaction_pool
+            .restored_commands_add(self.restored_user_commands);
 
-        Ok(Node::new(self.rng_seed, state, service, None))
+        Ok(Node::new(self.rng_seed, state, service, effects))
     }
 }
 
@@ -1,4 +1,6 @@
 mod builder;
 pub use builder::*;
 
+pub mod persistence;
+
 pub type Node = openmina_node_common::Node<crate::NodeService>;
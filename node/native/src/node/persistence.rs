@@ -0,0 +1,302 @@
+//! Persistence of the node's pools and known peers in the work dir, so that
+//! they survive restarts. Nothing loaded from disk is used as is:
+//! - snarks are verified again, same as the ones received from peers.
+//! - user commands of the transaction pool are verified again and added to
+//!   the pool, same as the ones received from peers, once the node has a
+//!   best tip.
+//! - known peers are only used as dial candidates, same as seeds.
+//!
+//! Transition frontier root ledgers are saved as a snapshot, so that they
+//! can be exported with `openmina ledger export --work-dir` once the node
+//! is stopped.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use mina_p2p_messages::{
+    binprot::{BinProtRead, BinProtWrite},
+    v2,
+};
+use node::{
+    core::snark::Snark,
+    p2p::{peer::P2pPersistedPeers, P2pState},
+    State,
+};
+
+use crate::Node;
+
+const SNARK_POOL_FILE: &str = "snark_pool.bin";
+const TRANSACTION_POOL_FILE: &str = "transaction_pool.bin";
+const PEERS_FILE: &str = "p2p_peers.json";
+const LEDGER_SNAPSHOT_FILE: &str = "ledger_snapshot.bin";
+
+fn snark_pool_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(SNARK_POOL_FILE)
+}
+
+fn transaction_pool_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(TRANSACTION_POOL_FILE)
+}
+
+fn peers_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(PEERS_FILE)
+}
+
+/// Path of the transition frontier snapshot saved by [`save`].
+pub fn ledger_snapshot_path(work_dir: impl AsRef<Path>) -> PathBuf {
+    work_dir.as_ref().join(LEDGER_SNAPSHOT_FILE)
+}
+
+/// Save pools, known peers and root ledgers to the work dir. Called on
+/// shutdown, either on ctrl-c or SIGTERM.
+///
+/// Failure to save one of them doesn't prevent saving the others.
+pub fn save(work_dir: impl AsRef<Path>, node: &Node) -> anyhow::Result<()> {
+    let work_dir = work_dir.as_ref();
+    let state = node.state();
+    std::fs::create_dir_all(work_dir).with_context(|| format!("creating work dir {work_dir:?}"))?;
+    let errors = [
+        snark_pool_save(work_dir, state),
+        transaction_pool_save(work_dir, state),
+        peers_save(work_dir, state),
+        ledger_snapshot_save(work_dir, node),
+    ]
+    .into_iter()
+    .filter_map(Result::err)
+    .collect::<Vec<_>>();
+    for err in &errors {
+        openmina_core::warn!(
+            openmina_core::log::system_time();
+            kind = "NodeStateSave",
+            summary = format!("{err:#}")
+        );
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("failed to save {} part(s) of node state", errors.len());
+    }
+    Ok(())
+}
+
+/// Save known peers and the Kademlia routing table. Called on shutdown and
+/// periodically while the node is running.
+pub fn peers_save(work_dir: impl AsRef<Path>, state: &State) -> anyhow::Result<()> {
+    let Some(p2p) = state.p2p.ready() else {
+        return Ok(());
+    };
+    let peers = persisted_peers(p2p);
+    if peers.is_empty() {
+        // don't overwrite peers from the previous run if we didn't learn any.
+        return Ok(());
+    }
+    peers_write(work_dir.as_ref(), &peers)
+}
+
+fn peers_write(work_dir: &Path, peers: &P2pPersistedPeers) -> anyhow::Result<()> {
+    std::fs::create_dir_all(work_dir).with_context(|| format!("creating work dir {work_dir:?}"))?;
+    write_atomic(&peers_path(work_dir), |w| {
+        serde_json::to_writer(w, peers)?;
+        Ok(())
+    })
+    .context("saving known peers")
+}
+
+fn persisted_peers(p2p: &P2pState) -> P2pPersistedPeers {
+    let kad_entries = p2p
+        .network
+        .scheduler
+        .discovery_state()
+        .map(|discovery_state| {
+            let routing_table = &discovery_state.routing_table;
+            routing_table
+                .buckets
+                .iter()
+                .flat_map(|bucket| bucket.iter())
+                .filter(|entry| entry.key != routing_table.this_key)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    P2pPersistedPeers {
+        address_book: p2p.address_book.clone(),
+        kad_entries,
+    }
+}
+
+fn snark_pool_save(work_dir: &Path, state: &State) -> anyhow::Result<()> {
+    let snarks = state
+        .snark_pool
+        .verified_snarks_to_persist()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    snark_pool_write(work_dir, snarks)
+}
+
+fn snark_pool_write(work_dir: &Path, snarks: Vec<Snark>) -> anyhow::Result<()> {
+    write_atomic(&snark_pool_path(work_dir), |w| Ok(snarks.binprot_write(w)?))
+        .context("saving snark pool")
+}
+
+fn transaction_pool_save(work_dir: &Path, state: &State) -> anyhow::Result<()> {
+    transaction_pool_write(work_dir, state.transaction_pool.user_commands_to_persist())
+}
+
+fn transaction_pool_write(
+    work_dir: &Path,
+    commands: Vec<v2::MinaBaseUserCommandStableV2>,
+) -> anyhow::Result<()> {
+    write_atomic(&transaction_pool_path(work_dir), |w| {
+        Ok(commands.binprot_write(w)?)
+    })
+    .context("saving transaction pool")
+}
+
+/// Load snarks saved by [`save`]. Missing file results in empty list.
+pub fn snark_pool_load(work_dir: impl AsRef<Path>) -> anyhow::Result<Vec<Snark>> {
+    let path = snark_pool_path(work_dir.as_ref());
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let file = File::open(&path).with_context(|| format!("opening {path:?}"))?;
+    Vec::<Snark>::binprot_read(&mut BufReader::new(file))
+        .with_context(|| format!("decoding {path:?}"))
+}
+
+/// Load user commands of the transaction pool saved by [`save`]. Missing
+/// file results in empty list.
+pub fn transaction_pool_load(
+    work_dir: impl AsRef<Path>,
+) -> anyhow::Result<Vec<v2::MinaBaseUserCommandStableV2>> {
+    let path = transaction_pool_path(work_dir.as_ref());
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let file = File::open(&path).with_context(|| format!("opening {path:?}"))?;
+    Vec::<v2::MinaBaseUserCommandStableV2>::binprot_read(&mut BufReader::new(file))
+        .with_context(|| format!("decoding {path:?}"))
+}
+
+/// Load known peers saved by [`peers_save`]. Missing file results in no peers.
+pub fn peers_load(work_dir: impl AsRef<Path>) -> anyhow::Result<P2pPersistedPeers> {
+    let path = peers_path(work_dir.as_ref());
+    if !path.exists() {
+        return Ok(P2pPersistedPeers::default());
+    }
+    let file = File::open(&path).with_context(|| format!("opening {path:?}"))?;
+    serde_json::from_reader(BufReader::new(file)).with_context(|| format!("decoding {path:?}"))
+}
+
+/// Write to a temporary file first and then rename it, so that a crash
+/// in the middle of writing doesn't leave a corrupted file behind.
+fn write_atomic<F>(path: &Path, write: F) -> anyhow::Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> anyhow::Result<()>,
+{
+    let tmp_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    write(&mut writer)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::transaction_logic::UserCommand;
+    use node::core::block::best_tip_fixture;
+    use node::p2p::{
+        connection::outgoing::P2pConnectionOutgoingInitOpts, identity::SecretKey, webrtc,
+    };
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "openmina-persistence-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn peers_round_trip() {
+        let peer_id = SecretKey::from_bytes([1; 32]).public_key().peer_id();
+        let dial_opts = P2pConnectionOutgoingInitOpts::WebRTC {
+            peer_id,
+            signaling: webrtc::SignalingMethod::Http(webrtc::HttpSignalingInfo {
+                host: [127, 0, 0, 1].into(),
+                port: 3000,
+            }),
+        };
+        let mut peers = P2pPersistedPeers::default();
+        peers
+            .address_book
+            .connected(&dial_opts, redux::Timestamp::ZERO);
+
+        let dir = temp_dir("peers");
+        assert!(peers_load(&dir).unwrap().is_empty());
+        peers_write(&dir, &peers).unwrap();
+        let loaded = peers_load(&dir).unwrap();
+        assert_eq!(loaded.address_book.len(), 1);
+        assert_eq!(
+            loaded
+                .address_book
+                .get(&peer_id)
+                .map(|entry| &entry.dial_opts),
+            Some(&dial_opts)
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn snark_pool_round_trip() {
+        let block = best_tip_fixture();
+        let snarks = block
+            .completed_works_iter()
+            .map(|work| Snark::from(work.clone()))
+            .collect::<Vec<_>>();
+        assert!(!snarks.is_empty());
+
+        let dir = temp_dir("snark-pool");
+        assert!(snark_pool_load(&dir).unwrap().is_empty());
+        snark_pool_write(&dir, snarks.clone()).unwrap();
+        let summary = |snarks: &[Snark]| {
+            snarks
+                .iter()
+                .map(|snark| (snark.job_id(), snark.fee.clone(), snark.snarker.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summary(&snark_pool_load(&dir).unwrap()), summary(&snarks));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn transaction_pool_round_trip() {
+        let block = best_tip_fixture();
+        let commands = block
+            .commands_iter()
+            .map(|cmd| cmd.data.clone())
+            .collect::<Vec<_>>();
+        assert!(!commands.is_empty());
+
+        // pool keeps commands as `UserCommand`, so make sure persisted
+        // ones are the same as the ones received from peers.
+        for cmd in &commands {
+            let converted = v2::MinaBaseUserCommandStableV2::from(&UserCommand::from(cmd));
+            assert_eq!(&converted, cmd);
+        }
+
+        let dir = temp_dir("transaction-pool");
+        assert!(transaction_pool_load(&dir).unwrap().is_empty());
+        transaction_pool_write(&dir, commands.clone()).unwrap();
+        assert_eq!(transaction_pool_load(&dir).unwrap(), commands);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
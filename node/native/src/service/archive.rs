@@ -0,0 +1,797 @@
+//! Local archive, writing blocks applied by the node along with their
+//! transactions to an SQLite database.
+//!
+//! Schema roughly mirrors the one of the Mina archive. Public keys, token
+//! ids and hashes are stored in place as base58 strings, instead of being
+//! normalized into separate tables.
+
+use std::path::Path;
+
+use anyhow::Context;
+use ledger::{
+    scan_state::{
+        currency::{Amount, Sgn, Signed},
+        transaction_logic::{
+            signed_command::{self, SignedCommand},
+            zkapp_command::{AccountUpdate, AuthorizationKind, CallForest, ZkAppCommand},
+            Memo, SingleFeeTransfer, Transaction, TransactionStatus, UserCommand, WithStatus,
+        },
+    },
+    staged_ledger::diff::Diff,
+    transaction_pool::transaction_hash::{hash_coinbase, hash_command, hash_fee_transfer},
+    TokenId,
+};
+use mina_signer::CompressedPubKey;
+use node::core::{block::ArcBlockWithHash, channels::mpsc, constants::constraint_constants};
+use rusqlite::{params, Connection, OptionalExtension};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    id INTEGER PRIMARY KEY,
+    state_hash TEXT NOT NULL UNIQUE,
+    parent_id INTEGER REFERENCES blocks(id),
+    parent_hash TEXT NOT NULL,
+    creator TEXT NOT NULL,
+    block_winner TEXT NOT NULL,
+    coinbase_receiver TEXT NOT NULL,
+    snarked_ledger_hash TEXT NOT NULL,
+    staking_epoch_ledger_hash TEXT NOT NULL,
+    next_epoch_ledger_hash TEXT NOT NULL,
+    ledger_hash TEXT NOT NULL,
+    min_window_density INTEGER NOT NULL,
+    total_currency INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    global_slot_since_hard_fork INTEGER NOT NULL,
+    global_slot_since_genesis INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    chain_status TEXT NOT NULL CHECK (chain_status IN ('canonical', 'orphaned', 'pending'))
+);
+CREATE INDEX IF NOT EXISTS idx_blocks_height ON blocks(height);
+CREATE INDEX IF NOT EXISTS idx_blocks_parent_hash ON blocks(parent_hash);
+CREATE INDEX IF NOT EXISTS idx_blocks_chain_status ON blocks(chain_status);
+
+CREATE TABLE IF NOT EXISTS user_commands (
+    id INTEGER PRIMARY KEY,
+    command_type TEXT NOT NULL CHECK (command_type IN ('payment', 'delegation')),
+    fee_payer TEXT NOT NULL,
+    source TEXT NOT NULL,
+    receiver TEXT NOT NULL,
+    nonce INTEGER NOT NULL,
+    amount INTEGER,
+    fee INTEGER NOT NULL,
+    valid_until INTEGER,
+    memo TEXT NOT NULL,
+    hash TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS blocks_user_commands (
+    block_id INTEGER NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+    user_command_id INTEGER NOT NULL REFERENCES user_commands(id) ON DELETE CASCADE,
+    sequence_no INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('applied', 'failed')),
+    failure_reason TEXT,
+    PRIMARY KEY (block_id, user_command_id, sequence_no)
+);
+
+CREATE TABLE IF NOT EXISTS internal_commands (
+    id INTEGER PRIMARY KEY,
+    command_type TEXT NOT NULL
+        CHECK (command_type IN ('fee_transfer_via_coinbase', 'fee_transfer', 'coinbase')),
+    receiver TEXT NOT NULL,
+    fee INTEGER NOT NULL,
+    token TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    UNIQUE (hash, command_type)
+);
+
+CREATE TABLE IF NOT EXISTS blocks_internal_commands (
+    block_id INTEGER NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+    internal_command_id INTEGER NOT NULL REFERENCES internal_commands(id) ON DELETE CASCADE,
+    sequence_no INTEGER NOT NULL,
+    secondary_sequence_no INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('applied', 'failed')),
+    failure_reason TEXT,
+    PRIMARY KEY (block_id, internal_command_id, sequence_no, secondary_sequence_no)
+);
+
+CREATE TABLE IF NOT EXISTS zkapp_commands (
+    id INTEGER PRIMARY KEY,
+    fee_payer TEXT NOT NULL,
+    fee INTEGER NOT NULL,
+    nonce INTEGER NOT NULL,
+    valid_until INTEGER,
+    memo TEXT NOT NULL,
+    hash TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS zkapp_account_updates (
+    id INTEGER PRIMARY KEY,
+    zkapp_command_id INTEGER NOT NULL REFERENCES zkapp_commands(id) ON DELETE CASCADE,
+    sequence_no INTEGER NOT NULL,
+    public_key TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    balance_change INTEGER NOT NULL,
+    increment_nonce BOOLEAN NOT NULL,
+    call_depth INTEGER NOT NULL,
+    use_full_commitment BOOLEAN NOT NULL,
+    implicit_account_creation_fee BOOLEAN NOT NULL,
+    authorization_kind TEXT NOT NULL
+        CHECK (authorization_kind IN ('none_given', 'signature', 'proof')),
+    UNIQUE (zkapp_command_id, sequence_no)
+);
+
+CREATE TABLE IF NOT EXISTS blocks_zkapp_commands (
+    block_id INTEGER NOT NULL REFERENCES blocks(id) ON DELETE CASCADE,
+    zkapp_command_id INTEGER NOT NULL REFERENCES zkapp_commands(id) ON DELETE CASCADE,
+    sequence_no INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('applied', 'failed')),
+    failure_reasons TEXT,
+    PRIMARY KEY (block_id, zkapp_command_id, sequence_no)
+);
+";
+
+/// Base58check version byte of a transaction hash.
+const TRANSACTION_HASH_VERSION: u8 = 0x1D;
+/// Base58check version byte of a user command memo.
+const MEMO_VERSION: u8 = 0x14;
+
+enum ArchiveRequest {
+    BlockApplied(ArcBlockWithHash),
+    BestChainUpdate(Vec<ArcBlockWithHash>),
+}
+
+pub struct ArchiveService {
+    sender: mpsc::UnboundedSender<ArchiveRequest>,
+}
+
+impl ArchiveService {
+    /// Opens (or creates) the database at `path` and starts the thread
+    /// writing to it.
+    pub fn start(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let mut db =
+            ArchiveDb::open(path).with_context(|| format!("opening archive database {path:?}"))?;
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        std::thread::Builder::new()
+            .name("openmina_archive".to_owned())
+            .spawn(move || {
+                while let Some(request) = receiver.blocking_recv() {
+                    match request {
+                        ArchiveRequest::BlockApplied(block) => db.block_archive_or_log(&block),
+                        ArchiveRequest::BestChainUpdate(best_chain) => {
+                            if let Err(err) = db.best_chain_update(&best_chain) {
+                                openmina_core::error!(
+                                    openmina_core::log::system_time();
+                                    kind = "ArchiveBestChainUpdate",
+                                    summary = format!("failed to archive best chain: {err:#}")
+                                );
+                            }
+                        }
+                    }
+                }
+            })
+            .context("spawning archive thread")?;
+
+        Ok(Self { sender })
+    }
+
+    pub fn block_applied(&self, block: ArcBlockWithHash) {
+        let _ = self.sender.send(ArchiveRequest::BlockApplied(block));
+    }
+
+    pub fn best_chain_update(&self, best_chain: Vec<ArcBlockWithHash>) {
+        let _ = self
+            .sender
+            .send(ArchiveRequest::BestChainUpdate(best_chain));
+    }
+}
+
+struct ArchiveDb {
+    conn: Connection,
+}
+
+impl ArchiveDb {
+    fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::init(conn)
+    }
+
+    #[cfg(test)]
+    fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Archives the block with its transactions in a single database
+    /// transaction, unless it's already archived.
+    fn block_archive(&mut self, block: &ArcBlockWithHash) -> anyhow::Result<()> {
+        let tx = self.conn.transaction()?;
+        if block_id(&tx, &block.hash().to_string())?.is_none() {
+            block_add(&tx, block)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// A block failing to be archived is skipped, so that it doesn't
+    /// prevent archiving the ones after it.
+    fn block_archive_or_log(&mut self, block: &ArcBlockWithHash) {
+        if let Err(err) = self.block_archive(block) {
+            openmina_core::error!(
+                openmina_core::log::system_time();
+                kind = "ArchiveBlock",
+                summary = format!("failed to archive block {}: {err:#}", block.hash())
+            );
+        }
+    }
+
+    fn best_chain_update(&mut self, best_chain: &[ArcBlockWithHash]) -> anyhow::Result<()> {
+        let Some(root) = best_chain.first() else {
+            return Ok(());
+        };
+        for block in best_chain {
+            self.block_archive_or_log(block);
+        }
+        let tx = self.conn.transaction()?;
+        chain_status_update(&tx, root)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn block_id(conn: &Connection, state_hash: &str) -> rusqlite::Result<Option<i64>> {
+    conn.query_row(
+        "SELECT id FROM blocks WHERE state_hash = ?1",
+        [state_hash],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+fn block_add(conn: &Connection, block: &ArcBlockWithHash) -> anyhow::Result<()> {
+    let consensus_state = block.consensus_state();
+    let parent_hash = block.pred_hash().to_string();
+    let parent_id = block_id(conn, &parent_hash)?;
+    conn.execute(
+        "INSERT INTO blocks (
+            state_hash, parent_id, parent_hash, creator, block_winner, coinbase_receiver,
+            snarked_ledger_hash, staking_epoch_ledger_hash, next_epoch_ledger_hash, ledger_hash,
+            min_window_density, total_currency, height, global_slot_since_hard_fork,
+            global_slot_since_genesis, timestamp, chain_status
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, 'pending')",
+        params![
+            block.hash().to_string(),
+            parent_id,
+            parent_hash,
+            block.producer().to_string(),
+            consensus_state.block_stake_winner.to_string(),
+            consensus_state.coinbase_receiver.to_string(),
+            block.snarked_ledger_hash().to_string(),
+            block.staking_epoch_ledger_hash().to_string(),
+            block.next_epoch_ledger_hash().to_string(),
+            block.staged_ledger_hash().to_string(),
+            consensus_state.min_window_density.as_u32(),
+            consensus_state.total_currency.as_u64() as i64,
+            block.height(),
+            block.global_slot(),
+            block.global_slot_since_genesis(),
+            (u64::from(block.timestamp()) / 1_000_000) as i64,
+        ],
+    )?;
+    let block_id = conn.last_insert_rowid();
+
+    let coinbase_receiver: CompressedPubKey = (&consensus_state.coinbase_receiver).into();
+    let diff: Diff = (&block.body().staged_ledger_diff).into();
+    let transactions = diff
+        .get_transactions(
+            constraint_constants(),
+            coinbase_receiver,
+            consensus_state.supercharge_coinbase,
+        )
+        .map_err(|err| anyhow::anyhow!("extracting transactions: {err:?}"))?;
+
+    for (sequence_no, WithStatus { data, status }) in transactions.iter().enumerate() {
+        let sequence_no = sequence_no as i64;
+        let (status, failure_reason) = status_columns(status);
+        match data {
+            Transaction::Command(UserCommand::SignedCommand(cmd)) => {
+                let user_command_id = user_command_add(conn, cmd)?;
+                conn.execute(
+                    "INSERT OR IGNORE INTO blocks_user_commands
+                        (block_id, user_command_id, sequence_no, status, failure_reason)
+                    VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        block_id,
+                        user_command_id,
+                        sequence_no,
+                        status,
+                        failure_reason
+                    ],
+                )?;
+            }
+            Transaction::Command(UserCommand::ZkAppCommand(cmd)) => {
+                let zkapp_command_id = zkapp_command_add(conn, cmd)?;
+                conn.execute(
+                    "INSERT OR IGNORE INTO blocks_zkapp_commands
+                        (block_id, zkapp_command_id, sequence_no, status, failure_reasons)
+                    VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        block_id,
+                        zkapp_command_id,
+                        sequence_no,
+                        status,
+                        failure_reason
+                    ],
+                )?;
+            }
+            Transaction::FeeTransfer(fee_transfer) => {
+                for (secondary_sequence_no, single) in fee_transfer.iter().enumerate() {
+                    let token = mina_p2p_messages::v2::TokenIdKeyHash::from(&single.fee_token);
+                    internal_command_add(
+                        conn,
+                        block_id,
+                        (sequence_no, secondary_sequence_no as i64),
+                        "fee_transfer",
+                        &single.receiver_pk,
+                        single.fee.as_u64(),
+                        &token.to_string(),
+                        &hash_to_base58(&hash_fee_transfer(single)),
+                        (status, failure_reason.as_deref()),
+                    )?;
+                }
+            }
+            Transaction::Coinbase(coinbase) => {
+                let default_token = mina_p2p_messages::v2::TokenIdKeyHash::default().to_string();
+                internal_command_add(
+                    conn,
+                    block_id,
+                    (sequence_no, 0),
+                    "coinbase",
+                    &coinbase.receiver,
+                    coinbase.amount.as_u64(),
+                    &default_token,
+                    &hash_to_base58(&hash_coinbase(coinbase)),
+                    (status, failure_reason.as_deref()),
+                )?;
+                if let Some(fee_transfer) = &coinbase.fee_transfer {
+                    let single = SingleFeeTransfer::create(
+                        fee_transfer.receiver_pk.clone(),
+                        fee_transfer.fee,
+                        TokenId::default(),
+                    );
+                    internal_command_add(
+                        conn,
+                        block_id,
+                        (sequence_no, 1),
+                        "fee_transfer_via_coinbase",
+                        &fee_transfer.receiver_pk,
+                        fee_transfer.fee.as_u64(),
+                        &default_token,
+                        &hash_to_base58(&hash_fee_transfer(&single)),
+                        (status, failure_reason.as_deref()),
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn user_command_add(conn: &Connection, cmd: &SignedCommand) -> anyhow::Result<i64> {
+    let hash = command_hash(UserCommand::SignedCommand(Box::new(cmd.clone())));
+    let command_type = match &cmd.payload.body {
+        signed_command::Body::Payment(_) => "payment",
+        signed_command::Body::StakeDelegation(_) => "delegation",
+    };
+    let fee_payer = cmd.fee_payer_pk().into_address();
+    conn.execute(
+        "INSERT OR IGNORE INTO user_commands
+            (command_type, fee_payer, source, receiver, nonce, amount, fee, valid_until, memo, hash)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            command_type,
+            fee_payer,
+            fee_payer,
+            cmd.receiver_pk().into_address(),
+            cmd.nonce().as_u32(),
+            cmd.amount().map(|amount| amount.as_u64() as i64),
+            cmd.fee().as_u64() as i64,
+            cmd.valid_until().as_u32(),
+            memo_to_base58(&cmd.payload.common.memo),
+            hash,
+        ],
+    )?;
+    Ok(conn.query_row(
+        "SELECT id FROM user_commands WHERE hash = ?1",
+        [&hash],
+        |row| row.get(0),
+    )?)
+}
+
+fn zkapp_command_add(conn: &Connection, cmd: &ZkAppCommand) -> anyhow::Result<i64> {
+    let hash = command_hash(UserCommand::ZkAppCommand(Box::new(cmd.clone())));
+    if let Some(id) = conn
+        .query_row(
+            "SELECT id FROM zkapp_commands WHERE hash = ?1",
+            [&hash],
+            |row| row.get(0),
+        )
+        .optional()?
+    {
+        return Ok(id);
+    }
+
+    let fee_payer = &cmd.fee_payer.body;
+    conn.execute(
+        "INSERT INTO zkapp_commands (fee_payer, fee, nonce, valid_until, memo, hash)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            fee_payer.public_key.into_address(),
+            fee_payer.fee.as_u64() as i64,
+            fee_payer.nonce.as_u32(),
+            fee_payer.valid_until.map(|slot| slot.as_u32()),
+            memo_to_base58(&cmd.memo),
+            hash,
+        ],
+    )?;
+    let zkapp_command_id = conn.last_insert_rowid();
+
+    let mut account_updates = Vec::new();
+    account_updates_with_depth(&cmd.account_updates, 0, &mut account_updates);
+    for (sequence_no, (call_depth, account_update)) in account_updates.into_iter().enumerate() {
+        let body = &account_update.body;
+        let authorization_kind = match body.authorization_kind {
+            AuthorizationKind::NoneGiven => "none_given",
+            AuthorizationKind::Signature => "signature",
+            AuthorizationKind::Proof(_) => "proof",
+        };
+        conn.execute(
+            "INSERT INTO zkapp_account_updates (
+                zkapp_command_id, sequence_no, public_key, token_id, balance_change,
+                increment_nonce, call_depth, use_full_commitment,
+                implicit_account_creation_fee, authorization_kind
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                zkapp_command_id,
+                sequence_no as i64,
+                body.public_key.into_address(),
+                mina_p2p_messages::v2::TokenIdKeyHash::from(&body.token_id).to_string(),
+                signed_amount(&body.balance_change),
+                body.increment_nonce,
+                call_depth as i64,
+                body.use_full_commitment,
+                body.implicit_account_creation_fee,
+                authorization_kind,
+            ],
+        )?;
+    }
+
+    Ok(zkapp_command_id)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn internal_command_add(
+    conn: &Connection,
+    block_id: i64,
+    (sequence_no, secondary_sequence_no): (i64, i64),
+    command_type: &str,
+    receiver: &CompressedPubKey,
+    fee: u64,
+    token: &str,
+    hash: &str,
+    (status, failure_reason): (&str, Option<&str>),
+) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO internal_commands (command_type, receiver, fee, token, hash)
+        VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            command_type,
+            receiver.into_address(),
+            fee as i64,
+            token,
+            hash
+        ],
+    )?;
+    let internal_command_id: i64 = conn.query_row(
+        "SELECT id FROM internal_commands WHERE hash = ?1 AND command_type = ?2",
+        params![hash, command_type],
+        |row| row.get(0),
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO blocks_internal_commands (
+            block_id, internal_command_id, sequence_no, secondary_sequence_no, status,
+            failure_reason
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            block_id,
+            internal_command_id,
+            sequence_no,
+            secondary_sequence_no,
+            status,
+            failure_reason,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Marks the root and its archived ancestors as canonical, and the
+/// remaining pending blocks at or below the root's height as orphaned.
+fn chain_status_update(conn: &Connection, root: &ArcBlockWithHash) -> rusqlite::Result<()> {
+    let mut state_hash = Some(root.hash().to_string());
+    while let Some(hash) = state_hash {
+        let updated = conn.execute(
+            "UPDATE blocks SET chain_status = 'canonical'
+            WHERE state_hash = ?1 AND chain_status <> 'canonical'",
+            [&hash],
+        )?;
+        if updated == 0 {
+            break;
+        }
+        state_hash = conn
+            .query_row(
+                "SELECT parent_hash FROM blocks WHERE state_hash = ?1",
+                [&hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+    }
+    conn.execute(
+        "UPDATE blocks SET chain_status = 'orphaned'
+        WHERE chain_status = 'pending' AND height <= ?1",
+        [root.height()],
+    )?;
+    Ok(())
+}
+
+/// Account updates of the call forest in pre-order, along with their call depth.
+fn account_updates_with_depth<'a>(
+    forest: &'a CallForest<AccountUpdate>,
+    depth: usize,
+    acc: &mut Vec<(usize, &'a AccountUpdate)>,
+) {
+    for tree in forest.iter() {
+        acc.push((depth, &tree.elt.account_update));
+        account_updates_with_depth(&tree.elt.calls, depth + 1, acc);
+    }
+}
+
+fn status_columns(status: &TransactionStatus) -> (&'static str, Option<String>) {
+    match status {
+        TransactionStatus::Applied => ("applied", None),
+        TransactionStatus::Failed(failures) => {
+            let reasons = failures
+                .iter()
+                .flatten()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            ("failed", Some(reasons.join(", ")))
+        }
+    }
+}
+
+/// Same hash as the one of the Mina daemon and archive.
+fn command_hash(cmd: UserCommand) -> String {
+    hash_to_base58(&hash_command(cmd.to_valid_unsafe()).hash)
+}
+
+fn hash_to_base58(hash: &[u8; 32]) -> String {
+    // binprot encoded string: length prefix followed by the digest.
+    let mut bytes = Vec::with_capacity(33);
+    bytes.push(hash.len() as u8);
+    bytes.extend_from_slice(hash.as_ref());
+    bs58::encode(bytes)
+        .with_check_version(TRANSACTION_HASH_VERSION)
+        .into_string()
+}
+
+fn memo_to_base58(memo: &Memo) -> String {
+    bs58::encode(memo.as_slice())
+        .with_check_version(MEMO_VERSION)
+        .into_string()
+}
+
+fn signed_amount(value: &Signed<Amount>) -> i64 {
+    let magnitude = value.magnitude.as_u64() as i64;
+    match value.sgn {
+        Sgn::Pos => magnitude,
+        Sgn::Neg => -magnitude,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::{
+        currency::{Fee, Nonce},
+        transaction_logic::zkapp_command::{FeePayer, FeePayerBody},
+    };
+    use mina_signer::Signature;
+    use node::core::block::{best_tip_fixture, child_block};
+
+    use super::*;
+
+    fn count(db: &ArchiveDb, sql: &str) -> i64 {
+        db.conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    fn chain_status(db: &ArchiveDb, block: &ArcBlockWithHash) -> String {
+        db.conn
+            .query_row(
+                "SELECT chain_status FROM blocks WHERE state_hash = ?1",
+                [block.hash().to_string()],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn chain_status_transitions() {
+        let mut db = ArchiveDb::open_in_memory().unwrap();
+        let root = best_tip_fixture();
+        let a1 = child_block(&root, 0);
+        let a2 = child_block(&a1, 0);
+        let b1 = child_block(&root, 1);
+
+        for block in [&root, &a1, &b1, &a2, &a1] {
+            db.block_archive(block).unwrap();
+        }
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM blocks"), 4);
+        assert_eq!(
+            count(
+                &db,
+                "SELECT COUNT(*) FROM blocks WHERE parent_id IS NOT NULL"
+            ),
+            3
+        );
+        assert!([&root, &a1, &b1, &a2]
+            .iter()
+            .all(|block| chain_status(&db, block) == "pending"));
+
+        db.best_chain_update(&[a1.clone(), a2.clone()]).unwrap();
+        assert_eq!(chain_status(&db, &root), "canonical");
+        assert_eq!(chain_status(&db, &a1), "canonical");
+        assert_eq!(chain_status(&db, &b1), "orphaned");
+        assert_eq!(chain_status(&db, &a2), "pending");
+
+        // Blocks of the best chain missing from the archive are added.
+        let a3 = child_block(&a2, 0);
+        db.best_chain_update(&[a2.clone(), a3.clone()]).unwrap();
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM blocks"), 5);
+        assert_eq!(chain_status(&db, &a2), "canonical");
+        assert_eq!(chain_status(&db, &a3), "pending");
+        assert_eq!(chain_status(&db, &b1), "orphaned");
+    }
+
+    #[test]
+    fn block_user_and_internal_commands() {
+        let mut db = ArchiveDb::open_in_memory().unwrap();
+        let block = best_tip_fixture();
+        db.block_archive(&block).unwrap();
+
+        let consensus_state = block.consensus_state();
+        let diff: Diff = (&block.body().staged_ledger_diff).into();
+        let transactions = diff
+            .get_transactions(
+                constraint_constants(),
+                (&consensus_state.coinbase_receiver).into(),
+                consensus_state.supercharge_coinbase,
+            )
+            .unwrap();
+        let user_commands = transactions
+            .iter()
+            .filter(|txn| matches!(txn.data, Transaction::Command(_)))
+            .count() as i64;
+        let coinbases = transactions
+            .iter()
+            .filter(|txn| matches!(txn.data, Transaction::Coinbase(_)))
+            .count() as i64;
+        assert!(user_commands > 0);
+
+        assert_eq!(
+            count(&db, "SELECT COUNT(*) FROM blocks_user_commands"),
+            user_commands
+        );
+        assert_eq!(
+            count(
+                &db,
+                "SELECT COUNT(*) FROM blocks_user_commands
+                WHERE status = 'failed' AND failure_reason IS NOT NULL"
+            ),
+            user_commands
+        );
+        assert_eq!(
+            count(
+                &db,
+                "SELECT COUNT(*) FROM user_commands WHERE command_type = 'payment'"
+            ),
+            count(&db, "SELECT COUNT(*) FROM user_commands")
+        );
+        assert_eq!(
+            count(
+                &db,
+                "SELECT COUNT(*) FROM internal_commands WHERE command_type = 'coinbase'"
+            ),
+            coinbases
+        );
+        let internal_commands = count(&db, "SELECT COUNT(*) FROM internal_commands");
+        assert!(internal_commands > 0);
+        assert!(internal_commands <= count(&db, "SELECT COUNT(*) FROM blocks_internal_commands"));
+        assert_eq!(
+            count(
+                &db,
+                "SELECT COUNT(*) FROM blocks_internal_commands WHERE status <> 'applied'"
+            ),
+            0
+        );
+
+        // Archiving the same block again doesn't duplicate its rows.
+        db.block_archive(&block).unwrap();
+        assert_eq!(
+            count(&db, "SELECT COUNT(*) FROM blocks_user_commands"),
+            user_commands
+        );
+        assert_eq!(
+            count(&db, "SELECT COUNT(*) FROM internal_commands"),
+            internal_commands
+        );
+    }
+
+    #[test]
+    fn zkapp_command_rows() {
+        let db = ArchiveDb::open_in_memory().unwrap();
+        let fee_payer = FeePayer {
+            body: FeePayerBody {
+                public_key: best_tip_fixture().producer().into(),
+                fee: Fee::from_u64(10_000_000),
+                valid_until: None,
+                nonce: Nonce::from_u32(1),
+            },
+            authorization: Signature::dummy(),
+        };
+        let account_update = AccountUpdate::of_fee_payer(fee_payer.clone());
+        let calls = CallForest::new().cons(None, account_update.clone());
+        let cmd = ZkAppCommand {
+            fee_payer,
+            account_updates: CallForest::new().cons(Some(calls), account_update),
+            memo: Memo::empty(),
+        };
+
+        let id = zkapp_command_add(&db.conn, &cmd).unwrap();
+        assert_eq!(zkapp_command_add(&db.conn, &cmd).unwrap(), id);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM zkapp_commands"), 1);
+
+        let mut stmt = db
+            .conn
+            .prepare(
+                "SELECT sequence_no, call_depth, balance_change, authorization_kind
+                FROM zkapp_account_updates WHERE zkapp_command_id = ?1 ORDER BY sequence_no",
+            )
+            .unwrap();
+        let rows = stmt
+            .query_map([id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (0, 0, -10_000_000, "signature".to_owned()),
+                (1, 1, -10_000_000, "signature".to_owned()),
+            ]
+        );
+    }
+}
@@ -1,15 +1,20 @@
+use std::path::Path;
+
+use mina_p2p_messages::v2;
 use node::{
-    account::AccountSecretKey, p2p::identity::SecretKey as P2pSecretKey, service::Recorder,
+    account::AccountSecretKey, ledger::PrecomputedBlockExport,
+    p2p::identity::SecretKey as P2pSecretKey, service::Recorder,
 };
 pub use openmina_node_common::NodeServiceCommonBuildError;
 use openmina_node_common::{
     p2p::TaskSpawner, rpc::RpcSender, EventSender, NodeServiceCommonBuilder,
 };
 
-use crate::{http_server, NodeService, P2pTaskSpawner};
+use crate::{http_server, ArchiveService, NodeService, P2pTaskSpawner};
 
 pub struct NodeServiceBuilder {
     common: NodeServiceCommonBuilder,
+    archive: Option<ArchiveService>,
     pub(super) recorder: Recorder,
     http_server_port: Option<u16>,
 }
@@ -24,6 +29,7 @@ impl NodeServiceBuilder {
     pub fn new(rng_seed: [u8; 32]) -> Self {
         Self {
             common: NodeServiceCommonBuilder::new(rng_seed),
+            archive: None,
             recorder: Recorder::None,
             http_server_port: None,
         }
@@ -42,6 +48,21 @@ impl NodeServiceBuilder {
         self
     }
 
+    pub fn ledger_init_with_snarked_ledgers(
+        &mut self,
+        ledgers: Vec<ledger::Mask>,
+        sync_origin: Option<v2::LedgerHash>,
+    ) -> &mut Self {
+        self.common
+            .ledger_init_with_snarked_ledgers(ledgers, sync_origin);
+        self
+    }
+
+    pub fn precomputed_block_export(&mut self, export: PrecomputedBlockExport) -> &mut Self {
+        self.common.precomputed_block_export(export);
+        self
+    }
+
     pub fn block_producer_init(&mut self, keypair: AccountSecretKey) -> &mut Self {
         self.common.block_producer_init(keypair);
         self
@@ -66,11 +87,22 @@ impl NodeServiceBuilder {
         self
     }
 
+    pub fn verifier_threads(&mut self, threads: usize) -> &mut Self {
+        self.common.verifier_threads(threads);
+        self
+    }
+
     pub fn record(&mut self, recorder: Recorder) -> &mut Self {
         self.recorder = recorder;
         self
     }
 
+    /// Write blocks of the best chain to the SQLite archive at `path`.
+    pub fn archive_init(&mut self, path: impl AsRef<Path>) -> anyhow::Result<&mut Self> {
+        self.archive = Some(ArchiveService::start(path)?);
+        Ok(self)
+    }
+
     pub fn http_server_init(&mut self, port: u16) -> &mut Self {
         if let Some(cur_port) = self.http_server_port {
             panic!("trying to start http server on port `{port}`, when it's already running on port `{cur_port}`");
@@ -94,6 +126,7 @@ impl NodeServiceBuilder {
         Ok(NodeService {
             common,
             snark_worker_sender: None,
+            archive: self.archive,
             recorder: self.recorder,
         })
     }
@@ -1,5 +1,8 @@
 mod rpc;
 
+mod archive;
+pub use archive::*;
+
 mod ext_snark_worker;
 pub use ext_snark_worker::*;
 
@@ -20,6 +20,7 @@ impl RpcService for NodeService {
         respond_block_producer_stats_get,
         RpcBlockProducerStatsGetResponse
     );
+    rpc_service_impl!(respond_verifier_stats_get, RpcVerifierStatsGetResponse);
     rpc_service_impl!(
         respond_message_progress_stats_get,
         RpcMessageProgressResponse
@@ -67,6 +68,22 @@ impl RpcService for NodeService {
         respond_discovery_bootstrap_stats,
         RpcDiscoveryBoostrapStatsResponse
     );
+    rpc_service_impl!(
+        respond_discovery_record_put,
+        node::rpc::RpcDiscoveryRecordPutResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_record_get,
+        node::rpc::RpcDiscoveryRecordGetResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_start_providing,
+        node::rpc::RpcDiscoveryStartProvidingResponse
+    );
+    rpc_service_impl!(
+        respond_discovery_providers_get,
+        node::rpc::RpcDiscoveryProvidersGetResponse
+    );
     rpc_service_impl!(respond_transaction_pool, RpcTransactionPoolResponse);
     rpc_service_impl!(respond_ledger_accounts, RpcLedgerAccountsResponse);
     rpc_service_impl!(respond_transaction_inject, RpcTransactionInjectResponse);
@@ -78,4 +95,14 @@ impl RpcService for NodeService {
         respond_transition_frontier_commands,
         RpcTransitionFrontierUserCommandsResponse
     );
+    rpc_service_impl!(
+        respond_transition_frontier_snapshot_get,
+        RpcTransitionFrontierSnapshotGetResponse
+    );
+    rpc_service_impl!(respond_ledger_export_get, RpcLedgerExportGetResponse);
+    rpc_service_impl!(
+        respond_invariant_violations_get,
+        RpcInvariantViolationsGetResponse
+    );
+    rpc_service_impl!(respond_log_filters, RpcLogFiltersResponse);
 }
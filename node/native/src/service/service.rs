@@ -3,10 +3,12 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
 use mina_p2p_messages::v2::{ProverExtendBlockchainInputStableV2, StateHash};
 use node::{
     block_producer::vrf_evaluator::VrfEvaluatorInput,
     core::{
+        block::ArcBlockWithHash,
         channels::mpsc,
         invariants::{InvariantService, InvariantsState},
         snark::{Snark, SnarkJobId},
@@ -20,12 +22,12 @@ use node::{
     service::{
         BlockProducerService, BlockProducerVrfEvaluatorService, EventSourceService, Recorder,
         SnarkBlockVerifyService, SnarkPoolService, SnarkWorkVerifyService,
-        TransitionFrontierGenesisService,
+        TransitionFrontierArchiveService, TransitionFrontierGenesisService,
     },
     snark::{
         block_verify::{SnarkBlockVerifyId, VerifiableBlockWithHash},
         work_verify::SnarkWorkVerifyId,
-        VerifierIndex, VerifierSRS,
+        SnarkVerifierStats, VerifierIndex, VerifierSRS,
     },
     stats::Stats,
     transition_frontier::genesis::GenesisConfig,
@@ -46,11 +48,12 @@ use sha3::{
     Shake256,
 };
 
-use super::ExternalSnarkWorkerFacade;
+use super::{ArchiveService, ExternalSnarkWorkerFacade};
 
 pub struct NodeService {
     pub(super) common: NodeServiceCommon,
     pub(super) snark_worker_sender: Option<ExternalSnarkWorkerFacade>,
+    pub(super) archive: Option<ArchiveService>,
     pub(super) recorder: Recorder,
 }
 
@@ -129,8 +132,10 @@ impl NodeService {
                     replay_dynamic_effects_lib: dynamic_effects_lib.unwrap_or_default(),
                 }),
                 invariants_state: Default::default(),
+                verifier: Default::default(),
             },
             snark_worker_sender: None,
+            archive: None,
             recorder: Recorder::None,
         }
     }
@@ -165,6 +170,10 @@ impl node::Service for NodeService {
         self.common.stats()
     }
 
+    fn verifier_stats(&mut self) -> Option<SnarkVerifierStats> {
+        Some(self.common.verifier.stats())
+    }
+
     fn recorder(&mut self) -> &mut Recorder {
         &mut self.recorder
     }
@@ -269,14 +278,18 @@ use node::snark::user_command_verify_effectful::SnarkUserCommandVerifyService;
 impl SnarkUserCommandVerifyService for NodeService {
     fn verify_init(
         &mut self,
-        _req_id: node::snark::user_command_verify::SnarkUserCommandVerifyId,
-        _verifier_index: Arc<VerifierIndex>,
-        _verifier_srs: Arc<Mutex<VerifierSRS>>,
-        _commands: mina_p2p_messages::list::List<
-            mina_p2p_messages::v2::MinaBaseUserCommandStableV2,
-        >,
+        req_id: node::snark::user_command_verify::SnarkUserCommandVerifyId,
+        verifier_index: Arc<VerifierIndex>,
+        verifier_srs: Arc<Mutex<VerifierSRS>>,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
     ) {
-        todo!()
+        SnarkUserCommandVerifyService::verify_init(
+            &mut self.common,
+            req_id,
+            verifier_index,
+            verifier_srs,
+            commands,
+        )
     }
 }
 
@@ -308,6 +321,20 @@ impl TransitionFrontierGenesisService for NodeService {
     }
 }
 
+impl TransitionFrontierArchiveService for NodeService {
+    fn archive_block_applied(&mut self, block: &ArcBlockWithHash) {
+        if let Some(archive) = &self.archive {
+            archive.block_applied(block.clone());
+        }
+    }
+
+    fn archive_best_chain_update(&mut self, best_chain: &[ArcBlockWithHash]) {
+        if let Some(archive) = &self.archive {
+            archive.best_chain_update(best_chain.to_vec());
+        }
+    }
+}
+
 impl InvariantService for NodeService {
     fn invariants_state(&mut self) -> &mut InvariantsState {
         InvariantService::invariants_state(&mut self.common)
@@ -93,9 +93,12 @@ pub enum ActionKind {
     BlockProducerBlockInject,
     BlockProducerBlockInjected,
     BlockProducerBlockProduced,
+    BlockProducerBlockProveError,
     BlockProducerBlockProveInit,
+    BlockProducerBlockProveSkip,
     BlockProducerBlockProvePending,
     BlockProducerBlockProveSuccess,
+    BlockProducerBlockShadowed,
     BlockProducerBlockUnprovenBuild,
     BlockProducerStagedLedgerDiffCreateInit,
     BlockProducerStagedLedgerDiffCreatePending,
@@ -172,6 +175,7 @@ pub enum ActionKind {
     P2pChannelsRpcPending,
     P2pChannelsRpcReady,
     P2pChannelsRpcRequestReceived,
+    P2pChannelsRpcRequestRejected,
     P2pChannelsRpcRequestSend,
     P2pChannelsRpcResponsePending,
     P2pChannelsRpcResponseReceived,
@@ -261,9 +265,22 @@ pub enum ActionKind {
     P2pNetworkKadRequestStreamIsCreating,
     P2pNetworkKadRequestStreamReady,
     P2pNetworkKademliaAnswerFindNodeRequest,
+    P2pNetworkKademliaAnswerGetProvidersRequest,
+    P2pNetworkKademliaAnswerGetValueRequest,
+    P2pNetworkKademliaAnswerPutValueRequest,
     P2pNetworkKademliaBootstrapFinished,
+    P2pNetworkKademliaContinueLookup,
+    P2pNetworkKademliaGetProviders,
+    P2pNetworkKademliaGetProvidersFinished,
+    P2pNetworkKademliaGetRecord,
+    P2pNetworkKademliaGetRecordFinished,
+    P2pNetworkKademliaHandleAddProviderRequest,
+    P2pNetworkKademliaPutRecord,
     P2pNetworkKademliaStartBootstrap,
+    P2pNetworkKademliaStartProviding,
     P2pNetworkKademliaUpdateFindNodeRequest,
+    P2pNetworkKademliaUpdateGetProvidersRequest,
+    P2pNetworkKademliaUpdateGetValueRequest,
     P2pNetworkKademliaUpdateRoutingTable,
     P2pNetworkKademliaStreamClose,
     P2pNetworkKademliaStreamIncomingData,
@@ -344,13 +361,27 @@ pub enum ActionKind {
     RpcActionStatsGet,
     RpcBlockProducerStatsGet,
     RpcDiscoveryBoostrapStats,
+    RpcDiscoveryProvidersGetInit,
+    RpcDiscoveryProvidersGetPending,
+    RpcDiscoveryProvidersGetSuccess,
+    RpcDiscoveryRecordGetInit,
+    RpcDiscoveryRecordGetPending,
+    RpcDiscoveryRecordGetSuccess,
+    RpcDiscoveryRecordPut,
     RpcDiscoveryRoutingTable,
+    RpcDiscoveryStartProviding,
     RpcFinish,
     RpcGlobalStateGet,
     RpcHealthCheck,
+    RpcInvariantViolationsGet,
     RpcLedgerAccountsGetInit,
     RpcLedgerAccountsGetPending,
     RpcLedgerAccountsGetSuccess,
+    RpcLedgerExportGetInit,
+    RpcLedgerExportGetPending,
+    RpcLedgerExportGetSuccess,
+    RpcLogFiltersGet,
+    RpcLogFiltersSet,
     RpcMessageProgressGet,
     RpcP2pConnectionIncomingError,
     RpcP2pConnectionIncomingInit,
@@ -380,7 +411,11 @@ pub enum ActionKind {
     RpcTransactionInjectPending,
     RpcTransactionInjectSuccess,
     RpcTransactionPool,
+    RpcTransitionFrontierSnapshotGetInit,
+    RpcTransitionFrontierSnapshotGetPending,
+    RpcTransitionFrontierSnapshotGetSuccess,
     RpcTransitionFrontierUserCommandsGet,
+    RpcVerifierStatsGet,
     SnarkBlockVerifyError,
     SnarkBlockVerifyFinish,
     SnarkBlockVerifyInit,
@@ -406,6 +441,7 @@ pub enum ActionKind {
     SnarkPoolCandidateWorkVerifyError,
     SnarkPoolCandidateWorkVerifyNext,
     SnarkPoolCandidateWorkVerifyPending,
+    SnarkPoolCandidateWorkVerifyRetry,
     SnarkPoolCandidateWorkVerifySuccess,
     SnarkPoolEffectfulSnarkPoolJobsRandomChoose,
     SnarkUserCommandVerifyError,
@@ -430,6 +466,8 @@ pub enum ActionKind {
     TransactionPoolRebroadcast,
     TransactionPoolStartVerify,
     TransactionPoolStartVerifyWithAccounts,
+    TransactionPoolVerifyError,
+    TransactionPoolVerifySuccess,
     TransactionPoolEffectfulFetchAccounts,
     TransitionFrontierGenesisInject,
     TransitionFrontierSynced,
@@ -492,6 +530,7 @@ pub enum ActionKind {
     TransitionFrontierSyncLedgerSnarkedPeersQuery,
     TransitionFrontierSyncLedgerSnarkedPending,
     TransitionFrontierSyncLedgerSnarkedSuccess,
+    TransitionFrontierSyncLedgerStagedPartsFetchFromSnapshot,
     TransitionFrontierSyncLedgerStagedPartsFetchPending,
     TransitionFrontierSyncLedgerStagedPartsFetchSuccess,
     TransitionFrontierSyncLedgerStagedPartsPeerFetchError,
@@ -519,7 +558,7 @@ pub enum ActionKind {
 }
 
 impl ActionKind {
-    pub const COUNT: u16 = 428;
+    pub const COUNT: u16 = 467;
 }
 
 impl std::fmt::Display for ActionKind {
@@ -668,6 +707,8 @@ impl ActionKindGet for TransactionPoolAction {
             Self::StartVerifyWithAccounts { .. } => {
                 ActionKind::TransactionPoolStartVerifyWithAccounts
             }
+            Self::VerifySuccess { .. } => ActionKind::TransactionPoolVerifySuccess,
+            Self::VerifyError { .. } => ActionKind::TransactionPoolVerifyError,
             Self::BestTipChanged { .. } => ActionKind::TransactionPoolBestTipChanged,
             Self::BestTipChangedWithAccounts { .. } => {
                 ActionKind::TransactionPoolBestTipChangedWithAccounts
@@ -739,11 +780,14 @@ impl ActionKindGet for BlockProducerAction {
             }
             Self::BlockUnprovenBuild => ActionKind::BlockProducerBlockUnprovenBuild,
             Self::BlockProveInit => ActionKind::BlockProducerBlockProveInit,
+            Self::BlockProveSkip => ActionKind::BlockProducerBlockProveSkip,
             Self::BlockProvePending => ActionKind::BlockProducerBlockProvePending,
             Self::BlockProveSuccess { .. } => ActionKind::BlockProducerBlockProveSuccess,
+            Self::BlockProveError { .. } => ActionKind::BlockProducerBlockProveError,
             Self::BlockProduced => ActionKind::BlockProducerBlockProduced,
             Self::BlockInject => ActionKind::BlockProducerBlockInject,
             Self::BlockInjected => ActionKind::BlockProducerBlockInjected,
+            Self::BlockShadowed => ActionKind::BlockProducerBlockShadowed,
         }
     }
 }
@@ -756,6 +800,7 @@ impl ActionKindGet for RpcAction {
             Self::ActionStatsGet { .. } => ActionKind::RpcActionStatsGet,
             Self::SyncStatsGet { .. } => ActionKind::RpcSyncStatsGet,
             Self::BlockProducerStatsGet { .. } => ActionKind::RpcBlockProducerStatsGet,
+            Self::VerifierStatsGet { .. } => ActionKind::RpcVerifierStatsGet,
             Self::MessageProgressGet { .. } => ActionKind::RpcMessageProgressGet,
             Self::PeersGet { .. } => ActionKind::RpcPeersGet,
             Self::P2pConnectionOutgoingInit { .. } => ActionKind::RpcP2pConnectionOutgoingInit,
@@ -793,6 +838,18 @@ impl ActionKindGet for RpcAction {
             Self::ReadinessCheck { .. } => ActionKind::RpcReadinessCheck,
             Self::DiscoveryRoutingTable { .. } => ActionKind::RpcDiscoveryRoutingTable,
             Self::DiscoveryBoostrapStats { .. } => ActionKind::RpcDiscoveryBoostrapStats,
+            Self::DiscoveryRecordPut { .. } => ActionKind::RpcDiscoveryRecordPut,
+            Self::DiscoveryRecordGetInit { .. } => ActionKind::RpcDiscoveryRecordGetInit,
+            Self::DiscoveryRecordGetPending { .. } => ActionKind::RpcDiscoveryRecordGetPending,
+            Self::DiscoveryRecordGetSuccess { .. } => ActionKind::RpcDiscoveryRecordGetSuccess,
+            Self::DiscoveryStartProviding { .. } => ActionKind::RpcDiscoveryStartProviding,
+            Self::DiscoveryProvidersGetInit { .. } => ActionKind::RpcDiscoveryProvidersGetInit,
+            Self::DiscoveryProvidersGetPending { .. } => {
+                ActionKind::RpcDiscoveryProvidersGetPending
+            }
+            Self::DiscoveryProvidersGetSuccess { .. } => {
+                ActionKind::RpcDiscoveryProvidersGetSuccess
+            }
             Self::TransactionPool { .. } => ActionKind::RpcTransactionPool,
             Self::LedgerAccountsGetInit { .. } => ActionKind::RpcLedgerAccountsGetInit,
             Self::LedgerAccountsGetPending { .. } => ActionKind::RpcLedgerAccountsGetPending,
@@ -804,6 +861,21 @@ impl ActionKindGet for RpcAction {
             Self::TransitionFrontierUserCommandsGet { .. } => {
                 ActionKind::RpcTransitionFrontierUserCommandsGet
             }
+            Self::TransitionFrontierSnapshotGetInit { .. } => {
+                ActionKind::RpcTransitionFrontierSnapshotGetInit
+            }
+            Self::TransitionFrontierSnapshotGetPending { .. } => {
+                ActionKind::RpcTransitionFrontierSnapshotGetPending
+            }
+            Self::TransitionFrontierSnapshotGetSuccess { .. } => {
+                ActionKind::RpcTransitionFrontierSnapshotGetSuccess
+            }
+            Self::LedgerExportGetInit { .. } => ActionKind::RpcLedgerExportGetInit,
+            Self::LedgerExportGetPending { .. } => ActionKind::RpcLedgerExportGetPending,
+            Self::LedgerExportGetSuccess { .. } => ActionKind::RpcLedgerExportGetSuccess,
+            Self::InvariantViolationsGet { .. } => ActionKind::RpcInvariantViolationsGet,
+            Self::LogFiltersGet { .. } => ActionKind::RpcLogFiltersGet,
+            Self::LogFiltersSet { .. } => ActionKind::RpcLogFiltersSet,
             Self::Finish { .. } => ActionKind::RpcFinish,
         }
     }
@@ -1094,6 +1166,7 @@ impl ActionKindGet for SnarkPoolCandidateAction {
             Self::WorkVerifyNext => ActionKind::SnarkPoolCandidateWorkVerifyNext,
             Self::WorkVerifyPending { .. } => ActionKind::SnarkPoolCandidateWorkVerifyPending,
             Self::WorkVerifyError { .. } => ActionKind::SnarkPoolCandidateWorkVerifyError,
+            Self::WorkVerifyRetry { .. } => ActionKind::SnarkPoolCandidateWorkVerifyRetry,
             Self::WorkVerifySuccess { .. } => ActionKind::SnarkPoolCandidateWorkVerifySuccess,
             Self::PeerPrune { .. } => ActionKind::SnarkPoolCandidatePeerPrune,
         }
@@ -1294,6 +1367,7 @@ impl ActionKindGet for P2pChannelsRpcAction {
             Self::Timeout { .. } => ActionKind::P2pChannelsRpcTimeout,
             Self::ResponseReceived { .. } => ActionKind::P2pChannelsRpcResponseReceived,
             Self::RequestReceived { .. } => ActionKind::P2pChannelsRpcRequestReceived,
+            Self::RequestRejected { .. } => ActionKind::P2pChannelsRpcRequestRejected,
             Self::ResponsePending { .. } => ActionKind::P2pChannelsRpcResponsePending,
             Self::ResponseSend { .. } => ActionKind::P2pChannelsRpcResponseSend,
         }
@@ -1472,6 +1546,31 @@ impl ActionKindGet for P2pNetworkKademliaAction {
             Self::StartBootstrap { .. } => ActionKind::P2pNetworkKademliaStartBootstrap,
             Self::BootstrapFinished => ActionKind::P2pNetworkKademliaBootstrapFinished,
             Self::UpdateRoutingTable { .. } => ActionKind::P2pNetworkKademliaUpdateRoutingTable,
+            Self::AnswerPutValueRequest { .. } => {
+                ActionKind::P2pNetworkKademliaAnswerPutValueRequest
+            }
+            Self::AnswerGetValueRequest { .. } => {
+                ActionKind::P2pNetworkKademliaAnswerGetValueRequest
+            }
+            Self::HandleAddProviderRequest { .. } => {
+                ActionKind::P2pNetworkKademliaHandleAddProviderRequest
+            }
+            Self::AnswerGetProvidersRequest { .. } => {
+                ActionKind::P2pNetworkKademliaAnswerGetProvidersRequest
+            }
+            Self::UpdateGetValueRequest { .. } => {
+                ActionKind::P2pNetworkKademliaUpdateGetValueRequest
+            }
+            Self::UpdateGetProvidersRequest { .. } => {
+                ActionKind::P2pNetworkKademliaUpdateGetProvidersRequest
+            }
+            Self::PutRecord { .. } => ActionKind::P2pNetworkKademliaPutRecord,
+            Self::StartProviding { .. } => ActionKind::P2pNetworkKademliaStartProviding,
+            Self::GetRecord { .. } => ActionKind::P2pNetworkKademliaGetRecord,
+            Self::GetProviders { .. } => ActionKind::P2pNetworkKademliaGetProviders,
+            Self::ContinueLookup { .. } => ActionKind::P2pNetworkKademliaContinueLookup,
+            Self::GetRecordFinished { .. } => ActionKind::P2pNetworkKademliaGetRecordFinished,
+            Self::GetProvidersFinished { .. } => ActionKind::P2pNetworkKademliaGetProvidersFinished,
         }
     }
 }
@@ -1601,6 +1700,9 @@ impl ActionKindGet for TransitionFrontierSyncLedgerStagedAction {
             Self::PartsFetchPending => {
                 ActionKind::TransitionFrontierSyncLedgerStagedPartsFetchPending
             }
+            Self::PartsFetchFromSnapshot { .. } => {
+                ActionKind::TransitionFrontierSyncLedgerStagedPartsFetchFromSnapshot
+            }
             Self::PartsPeerFetchInit => {
                 ActionKind::TransitionFrontierSyncLedgerStagedPartsPeerFetchInit
             }
@@ -50,14 +50,25 @@ pub enum BlockProducerAction {
     },
     BlockUnprovenBuild,
     BlockProveInit,
+    /// Block proof creation is skipped in shadow mode, as the slot wasn't
+    /// actually won and the proof can't be created. Dummy proof is used.
+    BlockProveSkip,
     BlockProvePending,
     BlockProveSuccess {
         proof: Box<MinaBaseProofStableV2>,
     },
+    /// Block proof creation failed while in shadow mode.
+    #[action_event(level = warn, fields(error))]
+    BlockProveError {
+        error: String,
+    },
     BlockProduced,
     #[action_event(level = trace)]
     BlockInject,
     BlockInjected,
+    /// Produced block is kept locally instead of being injected,
+    /// because block producer is in shadow mode.
+    BlockShadowed,
 }
 
 impl redux::EnablingCondition<crate::State> for BlockProducerAction {
@@ -76,8 +87,11 @@ impl redux::EnablingCondition<crate::State> for BlockProducerAction {
                     }
                     let best_tip = state.transition_frontier.best_tip()?;
                     let cur_global_slot = state.cur_global_slot()?;
-                    let next = this.vrf_evaluator.next_won_slot(cur_global_slot, best_tip);
-                    Some(next.is_some())
+                    let next = this
+                        .vrf_evaluator
+                        .next_won_slot(cur_global_slot, best_tip)?;
+                    let config = &state.transition_frontier.config;
+                    Some(!config.is_slot_chain_end_reached(next.global_slot()))
                 })
                 .is_some_and(|v| v),
             BlockProducerAction::WonSlot { won_slot } => state.block_producer.with(false, |this| {
@@ -91,6 +105,10 @@ impl redux::EnablingCondition<crate::State> for BlockProducerAction {
                 this.current.won_slot_should_search()
                     && won_slot.global_slot() >= state.cur_global_slot().unwrap()
                     && won_slot > best_tip
+                    && !state
+                        .transition_frontier
+                        .config
+                        .is_slot_chain_end_reached(won_slot.global_slot())
             }),
             BlockProducerAction::WonSlotWait => state
                 .block_producer
@@ -148,7 +166,21 @@ impl redux::EnablingCondition<crate::State> for BlockProducerAction {
                 matches!(
                     this.current,
                     BlockProducerCurrentState::BlockUnprovenBuilt { .. }
-                )
+                ) && this
+                    .current
+                    .won_slot()
+                    .map_or(false, |won_slot| won_slot.threshold_met)
+            }),
+            BlockProducerAction::BlockProveSkip => state.block_producer.with(false, |this| {
+                this.config.shadow
+                    && matches!(
+                        this.current,
+                        BlockProducerCurrentState::BlockUnprovenBuilt { .. }
+                    )
+                    && this
+                        .current
+                        .won_slot()
+                        .map_or(false, |won_slot| !won_slot.threshold_met)
             }),
             BlockProducerAction::BlockProvePending => state.block_producer.with(false, |this| {
                 matches!(
@@ -164,6 +196,15 @@ impl redux::EnablingCondition<crate::State> for BlockProducerAction {
                     )
                 })
             }
+            BlockProducerAction::BlockProveError { .. } => {
+                state.block_producer.with(false, |this| {
+                    this.config.shadow
+                        && matches!(
+                            this.current,
+                            BlockProducerCurrentState::BlockProvePending { .. }
+                        )
+                })
+            }
             BlockProducerAction::BlockProduced => state.block_producer.with(false, |this| {
                 matches!(
                     this.current,
@@ -171,12 +212,17 @@ impl redux::EnablingCondition<crate::State> for BlockProducerAction {
                 )
             }),
             BlockProducerAction::BlockInject => state.block_producer.with(false, |this| {
-                matches!(this.current, BlockProducerCurrentState::Produced { .. })
+                !this.config.shadow
+                    && matches!(this.current, BlockProducerCurrentState::Produced { .. })
                     && !state.transition_frontier.sync.is_commit_pending()
             }),
             BlockProducerAction::BlockInjected => state.block_producer.with(false, |this| {
                 matches!(this.current, BlockProducerCurrentState::Produced { .. })
             }),
+            BlockProducerAction::BlockShadowed => state.block_producer.with(false, |this| {
+                this.config.shadow
+                    && matches!(this.current, BlockProducerCurrentState::Produced { .. })
+            }),
             BlockProducerAction::WonSlotDiscard { reason } => {
                 let current_reason = state.block_producer.with(None, |bp| {
                     let best_tip = state.transition_frontier.best_tip()?;
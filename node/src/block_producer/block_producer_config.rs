@@ -6,6 +6,12 @@ pub struct BlockProducerConfig {
     pub pub_key: NonZeroCurvePoint,
     pub custom_coinbase_receiver: Option<NonZeroCurvePoint>,
     pub proposed_protocol_version: Option<ProtocolVersionStableV2>,
+    /// Shadow mode. Block producer runs the full production pipeline
+    /// for every slot with the configured (pretend) key, but produced
+    /// blocks are never injected or broadcasted. Instead they are
+    /// compared against canonical blocks in the block producer stats.
+    #[serde(default)]
+    pub shadow: bool,
 }
 
 impl BlockProducerConfig {
@@ -14,6 +20,7 @@ impl BlockProducerConfig {
             pub_key,
             custom_coinbase_receiver: None,
             proposed_protocol_version: None,
+            shadow: false,
         }
     }
 
@@ -75,11 +75,14 @@ pub fn block_producer_effects<S: crate::Service>(
                 transition_frontier_size: best_tip.constants().k.as_u32(),
             });
 
-            if let Some(reason) = store
-                .state()
-                .block_producer
-                .with(None, |bp| bp.current.won_slot_should_discard(&best_tip))
-            {
+            if let Some(reason) = store.state().block_producer.with(None, |bp| {
+                // In shadow mode we don't compete with the best tip, so
+                // finish producing the block to compare it against canonical one.
+                if bp.config.shadow && bp.current.is_producing() {
+                    return None;
+                }
+                bp.current.won_slot_should_discard(&best_tip)
+            }) {
                 store.dispatch(BlockProducerAction::WonSlotDiscard { reason });
             } else {
                 store.dispatch(BlockProducerAction::WonSlotSearch);
@@ -133,15 +136,28 @@ pub fn block_producer_effects<S: crate::Service>(
                 return;
             };
 
-            let completed_snarks = state
-                .snark_pool
-                .completed_snarks_iter()
-                .map(|snark| (snark.job_id(), snark.clone()))
-                .collect();
+            // Blocks after `slot_tx_end` must have an empty diff.
+            let empty_diff = state
+                .transition_frontier
+                .config
+                .is_slot_tx_end_reached(won_slot.global_slot());
+            let completed_snarks = if empty_diff {
+                Default::default()
+            } else {
+                state
+                    .snark_pool
+                    .completed_snarks_iter()
+                    .map(|snark| (snark.job_id(), snark.clone()))
+                    .collect()
+            };
             // TODO(binier)
             let supercharge_coinbase = true;
 
-            let transactions_by_fee = state.block_producer.pending_transactions();
+            let transactions_by_fee = if empty_diff {
+                Vec::new()
+            } else {
+                state.block_producer.pending_transactions()
+            };
 
             if store.dispatch(LedgerWriteAction::Init {
                 request: LedgerWriteRequest::StagedLedgerDiffCreate {
@@ -154,17 +170,18 @@ pub fn block_producer_effects<S: crate::Service>(
                     completed_snarks,
                     supercharge_coinbase,
                     transactions_by_fee,
+                    empty_diff,
                 },
             }) {
                 store.dispatch(BlockProducerAction::StagedLedgerDiffCreatePending);
             }
         }
         BlockProducerAction::StagedLedgerDiffCreatePending => {}
-        BlockProducerAction::StagedLedgerDiffCreateSuccess { .. } => {
+        BlockProducerAction::StagedLedgerDiffCreateSuccess { output } => {
             if let Some(stats) = store.service.stats() {
                 stats
                     .block_producer()
-                    .staged_ledger_diff_create_end(meta.time());
+                    .staged_ledger_diff_create_end(meta.time(), &output.diff_creation_logs);
             }
             store.dispatch(BlockProducerAction::BlockUnprovenBuild);
         }
@@ -183,7 +200,15 @@ pub fn block_producer_effects<S: crate::Service>(
                 }
             }
 
-            store.dispatch(BlockProducerAction::BlockProveInit);
+            if !store.dispatch(BlockProducerAction::BlockProveSkip) {
+                store.dispatch(BlockProducerAction::BlockProveInit);
+            }
+        }
+        BlockProducerAction::BlockProveSkip => {
+            if let Some(stats) = store.service.stats() {
+                stats.block_producer().shadow_proof_create_skipped();
+            }
+            store.dispatch(BlockProducerAction::BlockProduced);
         }
         BlockProducerAction::BlockProveInit => {
             let service = &mut store.service;
@@ -264,8 +289,18 @@ pub fn block_producer_effects<S: crate::Service>(
             }
             store.dispatch(BlockProducerAction::BlockProduced);
         }
+        BlockProducerAction::BlockProveError { error } => {
+            if let Some(stats) = store.service.stats() {
+                stats
+                    .block_producer()
+                    .shadow_proof_create_error(meta.time(), error);
+            }
+            store.dispatch(BlockProducerAction::WonSlotSearch);
+        }
         BlockProducerAction::BlockProduced => {
-            store.dispatch(BlockProducerAction::BlockInject);
+            if !store.dispatch(BlockProducerAction::BlockInject) {
+                store.dispatch(BlockProducerAction::BlockShadowed);
+            }
         }
         BlockProducerAction::BlockInject => {
             let Some((best_tip, root_block, blocks_inbetween)) = None.or_else(|| {
@@ -289,6 +324,18 @@ pub fn block_producer_effects<S: crate::Service>(
         BlockProducerAction::BlockInjected => {
             store.dispatch(BlockProducerAction::WonSlotSearch);
         }
+        BlockProducerAction::BlockShadowed => {
+            if let Some(stats) = store.service.stats() {
+                let bp = &store.state.get().block_producer;
+                if let Some(block) = bp.with(None, |bp| match &bp.current {
+                    BlockProducerCurrentState::Shadowed { block, .. } => Some(block),
+                    _ => None,
+                }) {
+                    stats.block_producer().shadowed(block.hash());
+                }
+            }
+            store.dispatch(BlockProducerAction::WonSlotSearch);
+        }
         BlockProducerAction::WonSlotDiscard { reason } => {
             if let Some(stats) = store.service.stats() {
                 stats.block_producer().discarded(meta.time(), reason);
@@ -1,3 +1,4 @@
+use ledger::dummy::dummy_blockchain_proof;
 use ledger::scan_state::currency::{Amount, Signed};
 use mina_p2p_messages::{
     list::List,
@@ -21,7 +22,8 @@ use openmina_core::constants::constraint_constants;
 
 use super::{
     calc_epoch_seed, to_epoch_and_slot, BlockProducerAction, BlockProducerActionWithMetaRef,
-    BlockProducerCurrentState, BlockProducerEnabled, BlockProducerState, BlockWithoutProof,
+    BlockProducerCurrentState, BlockProducerEnabled, BlockProducerState,
+    BlockProducerWonSlotDiscardReason, BlockWithoutProof,
 };
 
 impl BlockProducerState {
@@ -454,6 +456,25 @@ impl BlockProducerEnabled {
                 }
             }
             BlockProducerAction::BlockProveInit => {}
+            BlockProducerAction::BlockProveSkip => {
+                if let BlockProducerCurrentState::BlockUnprovenBuilt {
+                    won_slot,
+                    chain,
+                    block,
+                    block_hash,
+                    ..
+                } = std::mem::take(&mut self.current)
+                {
+                    self.current = BlockProducerCurrentState::BlockProveSuccess {
+                        time: meta.time(),
+                        won_slot,
+                        chain,
+                        block,
+                        block_hash,
+                        proof: Box::new((*dummy_blockchain_proof()).clone()),
+                    };
+                }
+            }
             BlockProducerAction::BlockProvePending => {
                 if let BlockProducerCurrentState::BlockUnprovenBuilt {
                     won_slot,
@@ -499,6 +520,15 @@ impl BlockProducerEnabled {
                     };
                 }
             }
+            BlockProducerAction::BlockProveError { .. } => {
+                if let Some(won_slot) = self.current.won_slot() {
+                    self.current = BlockProducerCurrentState::WonSlotDiscarded {
+                        time: meta.time(),
+                        won_slot: won_slot.clone(),
+                        reason: BlockProducerWonSlotDiscardReason::ShadowBlockProveFailed,
+                    };
+                }
+            }
             BlockProducerAction::BlockProduced => {
                 if let BlockProducerCurrentState::BlockProveSuccess {
                     won_slot,
@@ -534,6 +564,22 @@ impl BlockProducerEnabled {
                     };
                 }
             }
+            BlockProducerAction::BlockShadowed => {
+                if let BlockProducerCurrentState::Produced {
+                    won_slot,
+                    chain,
+                    block,
+                    ..
+                } = std::mem::take(&mut self.current)
+                {
+                    self.current = BlockProducerCurrentState::Shadowed {
+                        time: meta.time(),
+                        won_slot,
+                        chain,
+                        block,
+                    };
+                }
+            }
         }
     }
 }
@@ -6,6 +6,8 @@ use mina_p2p_messages::v2::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::stats::block_producer::StagedLedgerDiffCreationLog;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StagedLedgerDiffCreateOutput {
     pub diff: StagedLedgerDiffDiffStableV2,
@@ -16,6 +18,7 @@ pub struct StagedLedgerDiffCreateOutput {
     pub pending_coinbase_update: MinaBasePendingCoinbaseUpdateStableV1,
     pub pending_coinbase_witness: MinaBasePendingCoinbaseWitnessStableV2,
     pub stake_proof_sparse_ledger: MinaBaseSparseLedgerBaseStableV2,
+    pub diff_creation_logs: Vec<StagedLedgerDiffCreationLog>,
 }
 
 pub trait BlockProducerService {
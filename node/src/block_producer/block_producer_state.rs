@@ -127,6 +127,14 @@ pub enum BlockProducerCurrentState {
         chain: Vec<ArcBlockWithHash>,
         block: ArcBlockWithHash,
     },
+    /// Block was produced in shadow mode. It won't be injected.
+    Shadowed {
+        time: redux::Timestamp,
+        won_slot: BlockProducerWonSlot,
+        /// Chain that we are extending.
+        chain: Vec<ArcBlockWithHash>,
+        block: ArcBlockWithHash,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -134,6 +142,8 @@ pub enum BlockProducerWonSlotDiscardReason {
     BestTipStakingLedgerDifferent,
     BestTipGlobalSlotHigher,
     BestTipSuperior,
+    /// Block proof creation failed in shadow mode.
+    ShadowBlockProveFailed,
 }
 
 impl BlockProducerState {
@@ -169,6 +179,10 @@ impl BlockProducerState {
         self.with(None, |this| Some(&this.config))
     }
 
+    pub fn is_shadow(&self) -> bool {
+        self.with(false, |this| this.config.shadow)
+    }
+
     pub fn is_me(&self, producer: &v2::NonZeroCurvePoint) -> bool {
         self.with(false, |this| producer == &this.config.pub_key)
     }
@@ -221,7 +235,10 @@ impl BlockProducerState {
 impl BlockProducerCurrentState {
     pub fn won_slot_should_search(&self) -> bool {
         match self {
-            Self::Idle { .. } | Self::WonSlotDiscarded { .. } | Self::Injected { .. } => true,
+            Self::Idle { .. }
+            | Self::WonSlotDiscarded { .. }
+            | Self::Injected { .. }
+            | Self::Shadowed { .. } => true,
             Self::WonSlot { .. }
             | Self::WonSlotWait { .. }
             | Self::WonSlotProduceInit { .. }
@@ -251,7 +268,8 @@ impl BlockProducerCurrentState {
             | Self::BlockProvePending { won_slot, .. }
             | Self::BlockProveSuccess { won_slot, .. }
             | Self::Produced { won_slot, .. }
-            | Self::Injected { won_slot, .. } => Some(won_slot),
+            | Self::Injected { won_slot, .. }
+            | Self::Shadowed { won_slot, .. } => Some(won_slot),
         }
     }
 
@@ -270,7 +288,8 @@ impl BlockProducerCurrentState {
             | Self::BlockProvePending { chain, .. }
             | Self::BlockProveSuccess { chain, .. }
             | Self::Produced { chain, .. }
-            | Self::Injected { chain, .. } => Some(chain),
+            | Self::Injected { chain, .. }
+            | Self::Shadowed { chain, .. } => Some(chain),
         }
     }
 
@@ -333,7 +352,8 @@ impl BlockProducerCurrentState {
             | Self::WonSlotDiscarded { .. }
             | Self::WonSlot { .. }
             | Self::WonSlotWait { .. }
-            | Self::Injected { .. } => false,
+            | Self::Injected { .. }
+            | Self::Shadowed { .. } => false,
             Self::WonSlotProduceInit { .. }
             | Self::WonSlotTransactionsGet { .. }
             | Self::WonSlotTransactionsSuccess { .. }
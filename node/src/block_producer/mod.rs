@@ -40,6 +40,9 @@ pub struct BlockProducerWonSlot {
     pub value_with_threshold: Option<(f64, f64)>,
     // Staking ledger which was used during vrf evaluation.
     pub staking_ledger_hash: v2::LedgerHash,
+    /// Whether the slot was actually won. Only `false` in shadow mode,
+    /// where blocks for such slots are produced, but not proven.
+    pub threshold_met: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -81,6 +84,7 @@ impl BlockProducerWonSlot {
             vrf_output: won_slot.vrf_output.clone(),
             value_with_threshold: won_slot.value_with_threshold,
             staking_ledger_hash: staking_ledger_hash.clone(),
+            threshold_met: won_slot.threshold_met,
         }
     }
 
@@ -11,7 +11,7 @@ use super::BlockProducerVrfEvaluatorStatus;
 use super::SlotPositionInEpoch;
 
 impl BlockProducerVrfEvaluatorAction {
-    pub fn effects<S: Service>(self, _: &ActionMeta, store: &mut Store<S>) {
+    pub fn effects<S: Service>(self, meta: &ActionMeta, store: &mut Store<S>) {
         match self {
             BlockProducerVrfEvaluatorAction::EvaluateSlot { vrf_input } => {
                 store.service.evaluate(vrf_input);
@@ -160,7 +160,22 @@ impl BlockProducerVrfEvaluatorAction {
                     unreachable!()
                 }
             }
-            BlockProducerVrfEvaluatorAction::FinalizeDelegatorTableConstruction { .. } => {
+            BlockProducerVrfEvaluatorAction::FinalizeDelegatorTableConstruction {
+                delegator_table,
+                ..
+            } => {
+                if delegator_table.is_empty() {
+                    let bp = &store.state().block_producer;
+                    openmina_core::warn!(meta.time();
+                        kind = "BlockProducerNoDelegators",
+                        summary = "producer key has no delegators in the staking ledger, no blocks will be produced in this epoch",
+                        producer = bp
+                            .config()
+                            .map(|config| config.pub_key.to_string())
+                            .unwrap_or_default(),
+                        shadow = bp.is_shadow());
+                }
+
                 let Some((
                     current_global_slot,
                     BlockProducerVrfEvaluatorStatus::EpochDelegatorTableSuccess {
@@ -207,8 +222,11 @@ impl BlockProducerVrfEvaluatorAction {
             }
             BlockProducerVrfEvaluatorAction::RecordLastBlockHeightInEpoch { .. } => {}
             BlockProducerVrfEvaluatorAction::ContinueEpochEvaluation { .. } => {
-                if let Some(vrf_evaluator_state) = store.state().block_producer.vrf_evaluator() {
-                    if let Some(vrf_input) = vrf_evaluator_state.construct_vrf_input() {
+                if let Some((vrf_evaluator_state, config)) =
+                    store.state().block_producer.vrf_evaluator_with_config()
+                {
+                    if let Some(vrf_input) = vrf_evaluator_state.construct_vrf_input(config.shadow)
+                    {
                         store.dispatch(BlockProducerVrfEvaluatorAction::EvaluateSlot { vrf_input });
                     }
                 }
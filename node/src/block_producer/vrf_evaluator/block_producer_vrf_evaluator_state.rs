@@ -345,7 +345,7 @@ impl BlockProducerVrfEvaluatorState {
         self.pending_evaluation.clone()
     }
 
-    pub fn construct_vrf_input(&self) -> Option<VrfEvaluatorInput> {
+    pub fn construct_vrf_input(&self, ignore_threshold: bool) -> Option<VrfEvaluatorInput> {
         if let Some(pending_evaluation) = self.current_evaluation() {
             Some(VrfEvaluatorInput::new(
                 pending_evaluation.epoch_data.seed,
@@ -353,6 +353,7 @@ impl BlockProducerVrfEvaluatorState {
                 pending_evaluation.latest_evaluated_slot + 1,
                 pending_evaluation.epoch_data.total_currency,
                 pending_evaluation.epoch_data.ledger,
+                ignore_threshold,
             ))
         } else {
             None
@@ -960,6 +961,7 @@ mod test {
                 global_slot: slot,
                 account_index: AccountIndex(0),
                 value_with_threshold: None,
+                threshold_met: true,
             };
             (
                 slot,
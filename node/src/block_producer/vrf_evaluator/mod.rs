@@ -33,6 +33,10 @@ pub struct VrfEvaluatorInput {
     pub global_slot: u32,
     pub total_currency: u64,
     pub staking_ledger_hash: LedgerHash,
+    /// Treat the slot as won even if the threshold isn't met.
+    ///
+    /// Set when block producer is running in shadow mode.
+    pub ignore_threshold: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -86,6 +90,7 @@ impl VrfEvaluatorInput {
         global_slot: u32,
         total_currency: u64,
         staking_ledger_hash: LedgerHash,
+        ignore_threshold: bool,
     ) -> Self {
         Self {
             epoch_seed,
@@ -93,6 +98,7 @@ impl VrfEvaluatorInput {
             global_slot,
             total_currency,
             staking_ledger_hash,
+            ignore_threshold,
         }
     }
 }
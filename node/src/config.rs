@@ -25,6 +25,8 @@ pub struct Config {
     pub block_producer: Option<BlockProducerConfig>,
     pub global: GlobalConfig,
     pub tx_pool: ledger::transaction_pool::Config,
+    #[serde(default)]
+    pub snark_pool: SnarkPoolConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -45,6 +47,11 @@ pub struct SnarkerConfig {
 pub enum SnarkerStrategy {
     Sequential,
     Random,
+    /// Rank jobs by how likely our work is to be bought, taking into
+    /// account existing commitments and snarks with their fees, job age
+    /// and our estimated proving time. Jobs where we can't undercut the
+    /// existing fee are skipped.
+    Profitable,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -109,7 +116,7 @@ impl BuildEnv {
 }
 
 #[derive(thiserror::Error, Debug)]
-#[error("invalid strategy: {0}! expected one of: seq/sequential/rand/random")]
+#[error("invalid strategy: {0}! expected one of: seq/sequential/rand/random/profit/profitable")]
 pub struct SnarkerStrategyParseError(String);
 
 impl FromStr for SnarkerStrategy {
@@ -119,6 +126,7 @@ impl FromStr for SnarkerStrategy {
         Ok(match s {
             "seq" | "sequential" => SnarkerStrategy::Sequential,
             "rand" | "random" => SnarkerStrategy::Random,
+            "profit" | "profitable" => SnarkerStrategy::Profitable,
             other => return Err(SnarkerStrategyParseError(other.to_owned())),
         })
     }
@@ -12,10 +12,10 @@ use crate::snark::block_verify::SnarkBlockVerifyId;
 pub type ConsensusActionWithMeta = redux::ActionWithMeta<ConsensusAction>;
 pub type ConsensusActionWithMetaRef<'a> = redux::ActionWithMeta<&'a ConsensusAction>;
 
-// NOTE: `debug(hash)` must be used instead of `display(hash)` because
-// for some reason the later breaks CI. `hash = display(&hash)` works too.
+// NOTE: `block_hash = display(&hash)` must be used instead of `display(hash)`
+// because for some reason the later breaks CI.
 #[derive(Serialize, Deserialize, Debug, Clone, ActionEvent)]
-#[action_event(level = debug, fields(debug(hash), debug(error)))]
+#[action_event(level = debug, fields(block_hash = display(&hash), debug(error)))]
 pub enum ConsensusAction {
     #[action_event(level = info)]
     BlockReceived {
@@ -64,7 +64,9 @@ impl redux::EnablingCondition<crate::State> for ConsensusAction {
                     hash: hash.clone(),
                     block: block.clone()
                 };
-                !block.is_genesis() && !state.consensus.blocks.contains_key(hash)
+                !block.is_genesis()
+                    && !state.consensus.blocks.contains_key(hash)
+                    && !state.transition_frontier.config.is_block_after_stop_slot(&block)
             },
             ConsensusAction::BlockChainProofUpdate { hash, .. } => {
                 (state.consensus.best_tip.as_ref() == Some(hash)
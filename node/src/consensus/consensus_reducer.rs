@@ -2,6 +2,7 @@ use openmina_core::{
     block::BlockHash,
     consensus::{is_short_range_fork, long_range_fork_take, short_range_fork_take},
 };
+use p2p::disconnection::{P2pDisconnectionAction, P2pDisconnectionReason};
 use snark::block_verify::{SnarkBlockVerifyAction, SnarkBlockVerifyError};
 
 use crate::{
@@ -91,8 +92,29 @@ impl ConsensusState {
                 let dispatcher = state_context.into_dispatcher();
                 dispatcher.push(ConsensusAction::DetectForkRange { hash });
             }
-            ConsensusAction::BlockSnarkVerifyError { .. } => {
-                // TODO: handle block verification error.
+            ConsensusAction::BlockSnarkVerifyError { hash, .. } => {
+                // Dispatch
+                let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
+                let Some(p2p) = global_state.p2p.ready() else {
+                    return;
+                };
+                // Libp2p peers might have just relayed the block via pubsub
+                // without verifying it, so only peers which sent it to us
+                // as their own best tip are disconnected.
+                let senders = p2p
+                    .peers
+                    .iter()
+                    .filter(|(_, peer)| !peer.is_libp2p())
+                    .filter_map(|(peer_id, peer)| Some((peer_id, peer.status.as_ready()?)))
+                    .filter(|(_, peer)| peer.best_tip.as_ref().map_or(false, |b| b.hash() == hash))
+                    .map(|(peer_id, _)| *peer_id)
+                    .collect::<Vec<_>>();
+                for peer_id in senders {
+                    dispatcher.push(P2pDisconnectionAction::Init {
+                        peer_id,
+                        reason: P2pDisconnectionReason::BlockVerifyError,
+                    });
+                }
             }
             ConsensusAction::DetectForkRange { hash } => {
                 let candidate_hash = hash;
@@ -1,15 +1,19 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use core::str::FromStr;
 use mina_hasher::Fp;
-use mina_p2p_messages::binprot::BinProtWrite;
+use mina_p2p_messages::{
+    binprot::{BinProtRead, BinProtWrite},
+    v2,
+};
 use multihash::{Blake2b256, Hasher};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::fmt::{self, Display, Formatter};
 
 use ledger::{
     scan_state::currency::{Amount, Balance, Magnitude, Nonce, Slot, SlotSpan, TxnVersion},
-    AuthRequired, Permissions, ReceiptChainHash, SetVerificationKey, Timing, TokenId, TokenSymbol,
-    VotingFor, ZkAppAccount, ZkAppUri,
+    AuthRequired, FpExt, Permissions, ReceiptChainHash, SetVerificationKey, Timing, TokenId,
+    TokenSymbol, VerificationKey, VotingFor, ZkAppAccount, ZkAppUri,
 };
 use openmina_node_account::{AccountPublicKey, AccountSecretKey};
 
@@ -18,6 +22,7 @@ use crate::ledger::LEDGER_DEPTH;
 type RawCurrency = String;
 type RawSlot = String;
 
+#[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ledger {
     pub accounts: Option<Vec<Account>>,
@@ -108,6 +113,7 @@ pub fn build_ledger_name(
     format!("{:x?}", hash.finalize())
 }
 
+#[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pk: String,
@@ -116,7 +122,11 @@ pub struct Account {
     delegate: Option<String>,
     token_id: Option<String>,
     token_symbol: Option<String>,
-    #[serde(default, deserialize_with = "string_or_u32_option")]
+    #[serde(
+        default,
+        deserialize_with = "string_or_u32_option",
+        serialize_with = "u32_option_as_string"
+    )]
     nonce: Option<u32>,
     receipt_chain_hash: Option<String>,
     voting_for: Option<String>,
@@ -257,6 +267,38 @@ impl Account {
     }
 }
 
+/// Converts the account into the format used by the Mina daemon for
+/// ledgers in runtime configs and ledger exports.
+///
+/// Note that zkapp verification keys aren't supported yet and are
+/// left out.
+impl From<&ledger::Account> for Account {
+    fn from(account: &ledger::Account) -> Self {
+        let receipt_chain_hash =
+            v2::PendingCoinbaseHash::from(v2::MinaBasePendingCoinbaseHashVersionedStableV1(
+                v2::MinaBasePendingCoinbaseHashBuilderStableV1(account.receipt_chain_hash.0.into()),
+            ));
+        Account {
+            pk: AccountPublicKey::from(account.public_key.clone()).to_string(),
+            sk: None,
+            balance: account.balance.to_mina_string(),
+            delegate: account
+                .delegate
+                .clone()
+                .map(|pk| AccountPublicKey::from(pk).to_string()),
+            token_id: (!account.token_id.is_default()).then(|| account.token_id.0.to_decimal()),
+            token_symbol: (!account.token_symbol.0.is_empty())
+                .then(|| account.token_symbol.0.clone()),
+            nonce: Some(account.nonce.as_u32()),
+            receipt_chain_hash: Some(receipt_chain_hash.to_string()),
+            voting_for: Some(account.voting_for.to_base58check()),
+            timing: AccountTiming::from_timing(&account.timing),
+            permissions: Some(AccountPermissions::from(&account.permissions)),
+            zkapp: account.zkapp.as_deref().map(Zkapp::from),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountTiming {
     initial_minimum_balance: RawCurrency,
@@ -281,12 +323,31 @@ impl AccountTiming {
             vesting_increment,
         })
     }
+
+    fn from_timing(timing: &Timing) -> Option<Self> {
+        match timing {
+            Timing::Untimed => None,
+            Timing::Timed {
+                initial_minimum_balance,
+                cliff_time,
+                cliff_amount,
+                vesting_period,
+                vesting_increment,
+            } => Some(AccountTiming {
+                initial_minimum_balance: initial_minimum_balance.to_mina_string(),
+                cliff_time: GlobalSlotSinceGenesis(cliff_time.as_u32()),
+                cliff_amount: cliff_amount.to_mina_string(),
+                vesting_period: GlobalSlotSpan(vesting_period.as_u32()),
+                vesting_increment: vesting_increment.to_mina_string(),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SetVrfKeyPerm {
     auth: AuthRequired,
-    #[serde(deserialize_with = "string_or_u32")]
+    #[serde(deserialize_with = "string_or_u32", serialize_with = "u32_as_string")]
     txn_version: u32,
 }
 
@@ -321,6 +382,21 @@ where
     }
 }
 
+// Mina daemon encodes 32-bit integers as strings.
+fn u32_as_string<S: Serializer>(value: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+fn u32_option_as_string<S: Serializer>(
+    value: &Option<u32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => u32_as_string(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountPermissions {
     access: Option<AuthRequired>,
@@ -363,14 +439,35 @@ impl AccountPermissions {
     }
 }
 
+impl From<&Permissions<AuthRequired>> for AccountPermissions {
+    fn from(permissions: &Permissions<AuthRequired>) -> Self {
+        AccountPermissions {
+            access: Some(permissions.access),
+            edit_state: Some(permissions.edit_state),
+            send: Some(permissions.send),
+            receive: Some(permissions.receive),
+            set_delegate: Some(permissions.set_delegate),
+            set_permissions: Some(permissions.set_permissions),
+            set_verification_key: SetVrfKeyPerm {
+                auth: permissions.set_verification_key.auth,
+                txn_version: permissions.set_verification_key.txn_version.as_u32(),
+            },
+            set_zkapp_uri: Some(permissions.set_zkapp_uri),
+            edit_action_state: Some(permissions.edit_action_state),
+            set_token_symbol: Some(permissions.set_token_symbol),
+            increment_nonce: Some(permissions.increment_nonce),
+            set_voting_for: Some(permissions.set_voting_for),
+            set_timing: Some(permissions.set_timing),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Zkapp {
     app_state: Vec<String>,
-    // These verification keys currently don't appear in configs, but
-    // they could in the future. We ignore them for now, but should they
-    // appear, serialization is likely to crash and we'll have to update
-    // this type and relevant conversion functions.
-    verification_key: Option<()>,
+    /// Base64 of the binprot encoded verification key, as the daemon
+    /// encodes it.
+    verification_key: Option<String>,
     zkapp_version: u32,
     action_state: Vec<String>,
     last_action_slot: RawSlot,
@@ -417,12 +514,14 @@ impl Zkapp {
             .parse::<u32>()
             .map(Slot::from_u32)
             .map_err(|_| AccountConfigError::MalformedSlot(self.last_action_slot.clone()))?;
-        if self.verification_key.is_some() {
-            return Err(AccountConfigError::VerificationKeyParsingNotSupported);
-        };
+        let verification_key = self
+            .verification_key
+            .as_deref()
+            .map(verification_key_from_base64)
+            .transpose()?;
         Ok(ZkAppAccount {
             app_state,
-            verification_key: None,
+            verification_key,
             zkapp_version: self.zkapp_version,
             action_state,
             last_action_slot,
@@ -433,6 +532,39 @@ impl Zkapp {
     }
 }
 
+fn verification_key_from_base64(vk: &str) -> Result<VerificationKey, AccountConfigError> {
+    let err = || AccountConfigError::MalformedVerificationKey(vk.to_owned());
+    let bytes = BASE64.decode(vk).map_err(|_| err())?;
+    let vk = v2::MinaBaseVerificationKeyWireStableV1::binprot_read(&mut bytes.as_slice())
+        .map_err(|_| err())?;
+    Ok((&vk).into())
+}
+
+fn verification_key_to_base64(vk: &VerificationKey) -> String {
+    let mut bytes = Vec::new();
+    v2::MinaBaseVerificationKeyWireStableV1::from(vk)
+        .binprot_write(&mut bytes)
+        .expect("writing to a vec can't fail");
+    BASE64.encode(bytes)
+}
+
+impl From<&ZkAppAccount> for Zkapp {
+    fn from(zkapp: &ZkAppAccount) -> Self {
+        Zkapp {
+            app_state: zkapp.app_state.iter().map(FpExt::to_decimal).collect(),
+            verification_key: zkapp
+                .verification_key
+                .as_ref()
+                .map(verification_key_to_base64),
+            zkapp_version: zkapp.zkapp_version,
+            action_state: zkapp.action_state.iter().map(FpExt::to_decimal).collect(),
+            last_action_slot: zkapp.last_action_slot.as_u32().to_string(),
+            proved_state: zkapp.proved_state,
+            zkapp_uri: zkapp.zkapp_uri.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum AccountConfigError {
     MalformedCurrencyValue(String),
@@ -443,13 +575,19 @@ pub enum AccountConfigError {
     MalformedSlot(String),
     MalformedFp(String),
     ZkAppStateTooLong(Vec<String>),
-    VerificationKeyParsingNotSupported,
+    MalformedVerificationKey(String),
     DelegateSetOnNonDefaultTokenAccount,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 struct GlobalSlotSinceGenesis(u32);
 
+impl Serialize for GlobalSlotSinceGenesis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ("Since_genesis", self.0.to_string()).serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for GlobalSlotSinceGenesis {
     fn deserialize<D>(deserializer: D) -> Result<GlobalSlotSinceGenesis, D::Error>
     where
@@ -490,9 +628,15 @@ impl<'de> Deserialize<'de> for GlobalSlotSinceGenesis {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 struct GlobalSlotSpan(u32);
 
+impl Serialize for GlobalSlotSpan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ("Global_slot_span", self.0.to_string()).serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for GlobalSlotSpan {
     fn deserialize<D>(deserializer: D) -> Result<GlobalSlotSpan, D::Error>
     where
@@ -552,8 +696,8 @@ impl Display for AccountConfigError {
             Self::ZkAppStateTooLong(app_state) => {
                 write!(f, "zkapp app state too long ('{:?}')", app_state)
             }
-            Self::VerificationKeyParsingNotSupported => {
-                write!(f, "verification key parsing not supported yet!")
+            Self::MalformedVerificationKey(vk) => {
+                write!(f, "malformed verification key ('{}')", vk)
             }
             Self::DelegateSetOnNonDefaultTokenAccount => {
                 write!(f, "delegate set on non-default token account")
@@ -563,3 +707,34 @@ impl Display for AccountConfigError {
 }
 
 impl std::error::Error for AccountConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zkapp_verification_key_round_trip() {
+        let zkapp = ZkAppAccount {
+            verification_key: Some(VerificationKey::dummy()),
+            zkapp_uri: ZkAppUri::from("https://example.com"),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(Zkapp::from(&zkapp)).unwrap();
+        let vk = json["verification_key"]
+            .as_str()
+            .expect("verification key should be a base64 string");
+        assert!(BASE64.decode(vk).is_ok());
+
+        let decoded: Zkapp = serde_json::from_value(json).unwrap();
+        assert_eq!(*decoded.to_zkapp_account().unwrap(), zkapp);
+
+        let malformed = Zkapp {
+            verification_key: Some("not a key".to_owned()),
+            ..decoded
+        };
+        assert!(matches!(
+            malformed.to_zkapp_account(),
+            Err(AccountConfigError::MalformedVerificationKey(_))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use openmina_core::constants::ForkConstants;
 use serde::{Deserialize, Serialize};
 
 mod json_daemon;
@@ -26,6 +27,30 @@ pub struct DaemonJson {
     pub ledger: Option<Ledger>,
     pub genesis: Option<Genesis>,
     pub epoch_data: Option<Epochs>,
+    pub proof: Option<Proof>,
+}
+
+impl DaemonJson {
+    /// Reads only the `proof` section, skipping the ledgers.
+    pub fn read_proof(reader: impl std::io::Read) -> serde_json::Result<Option<Proof>> {
+        #[derive(Deserialize)]
+        struct ProofOnly {
+            proof: Option<Proof>,
+        }
+        serde_json::from_reader::<_, ProofOnly>(reader).map(|v| v.proof)
+    }
+
+    pub fn fork(&self) -> Option<&ForkConstants> {
+        self.proof.as_ref()?.fork.as_ref()
+    }
+}
+
+/// Proof section of the config, only hard fork constants are used, the
+/// rest of constraint constants comes from the network config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proof {
+    /// Chain state the hard fork starts from.
+    pub fork: Option<ForkConstants>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,7 +90,7 @@ mod test {
     use openmina_node_account::AccountPublicKey;
     use std::str::FromStr;
 
-    use crate::daemon_json::DaemonJson;
+    use crate::daemon_json::{Account, DaemonJson};
 
     #[test]
     fn test_daemon_json_read() {
@@ -109,5 +134,46 @@ mod test {
         assert_eq!(daemon.peer_list_url(), None);
         assert_eq!(daemon.slot_tx_end(), None);
         assert_eq!(daemon.slot_chain_end(), None);
+        assert!(daemon_json.proof.unwrap().fork.is_none());
+    }
+
+    #[test]
+    fn test_daemon_json_read_fork() {
+        let json = serde_json::json!({
+            "proof": {
+                "level": "full",
+                "fork": {
+                    "state_hash": "3NKSvjaGSKiQuAt8BP1b1VCpLbJc9RcEFjYCaBYsJJFdrtd6tpaV",
+                    "blockchain_length": 296371,
+                    "global_slot_since_genesis": 445860
+                }
+            }
+        });
+        let proof = DaemonJson::read_proof(json.to_string().as_bytes())
+            .unwrap()
+            .unwrap();
+        let fork = proof.fork.unwrap();
+        assert_eq!(
+            fork,
+            openmina_core::network::devnet::CONSTRAINT_CONSTANTS
+                .fork
+                .unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&proof).unwrap()["fork"],
+            json["proof"]["fork"]
+        );
+    }
+
+    #[test]
+    fn test_account_export_roundtrip() {
+        let test_file = std::fs::File::open("testing/data/daemon.json").unwrap();
+        let daemon_json: DaemonJson = serde_json::from_reader(test_file).unwrap();
+        for account in daemon_json.ledger.unwrap().accounts.unwrap() {
+            let account = account.to_account().unwrap();
+            let exported = serde_json::to_value(Account::from(&account)).unwrap();
+            let imported: Account = serde_json::from_value(exported).unwrap();
+            assert_eq!(imported.to_account().unwrap(), account);
+        }
     }
 }
@@ -35,6 +35,7 @@ impl std::fmt::Display for Event {
                     RpcRequest::ActionStatsGet(query) => write!(f, "ActionStatsGet, {query:?}"),
                     RpcRequest::SyncStatsGet(query) => write!(f, "SyncStatsGet, {query:?}"),
                     RpcRequest::BlockProducerStatsGet => write!(f, "BlockProducerStatsGet"),
+                    RpcRequest::VerifierStatsGet => write!(f, "VerifierStatsGet"),
                     RpcRequest::PeersGet => write!(f, "PeersGet"),
                     RpcRequest::MessageProgressGet => write!(f, "MessageProgressGet"),
                     RpcRequest::P2pConnectionOutgoing(opts) => {
@@ -60,6 +61,16 @@ impl std::fmt::Display for Event {
                     RpcRequest::ReadinessCheck => write!(f, "ReadinessCheck"),
                     RpcRequest::DiscoveryRoutingTable => write!(f, "DiscoveryRoutingTable"),
                     RpcRequest::DiscoveryBoostrapStats => write!(f, "DiscoveryBoostrapStats"),
+                    RpcRequest::DiscoveryRecordPut(record) => {
+                        write!(f, "DiscoveryRecordPut, {}", record.key)
+                    }
+                    RpcRequest::DiscoveryRecordGet(key) => write!(f, "DiscoveryRecordGet, {key}"),
+                    RpcRequest::DiscoveryStartProviding(key) => {
+                        write!(f, "DiscoveryStartProviding, {key}")
+                    }
+                    RpcRequest::DiscoveryProvidersGet(key) => {
+                        write!(f, "DiscoveryProvidersGet, {key}")
+                    }
                     RpcRequest::TransactionPoolGet => write!(f, "TransactionPool"),
                     RpcRequest::LedgerAccountsGet(pub_key) => {
                         write!(f, "LedgerAccountsGet, {pub_key:?}")
@@ -68,6 +79,19 @@ impl std::fmt::Display for Event {
                     RpcRequest::TransitionFrontierUserCommandsGet => {
                         write!(f, "TransitionFrontierUserCommandsGet")
                     }
+                    RpcRequest::TransitionFrontierSnapshotGet => {
+                        write!(f, "TransitionFrontierSnapshotGet")
+                    }
+                    RpcRequest::LedgerExportGet(query) => {
+                        write!(
+                            f,
+                            "LedgerExportGet, {:?}, {:?}",
+                            query.kind, query.public_key
+                        )
+                    }
+                    RpcRequest::InvariantViolationsGet => write!(f, "InvariantViolationsGet"),
+                    RpcRequest::LogFiltersGet => write!(f, "LogFiltersGet"),
+                    RpcRequest::LogFiltersSet(filters) => write!(f, "LogFiltersSet, {filters:?}"),
                 }
             }
             Self::ExternalSnarkWorker(event) => {
@@ -1,6 +1,6 @@
 use p2p::channels::snark::P2pChannelsSnarkAction;
 use p2p::channels::transaction::P2pChannelsTransactionAction;
-use snark::user_command_verify::{SnarkUserCommandVerifyAction, SnarkUserCommandVerifyError};
+use snark::user_command_verify::SnarkUserCommandVerifyAction;
 
 use crate::action::CheckTimeoutsAction;
 use crate::block_producer::vrf_evaluator::BlockProducerVrfEvaluatorAction;
@@ -264,16 +264,14 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                         store.dispatch(SnarkWorkVerifyAction::Success { req_id });
                     }
                 },
-                SnarkEvent::UserCommandVerify(req_id, result) => {
-                    if result.iter().any(|res| res.is_err()) {
-                        store.dispatch(SnarkUserCommandVerifyAction::Error {
-                            req_id,
-                            error: SnarkUserCommandVerifyError::VerificationFailed,
-                        });
-                    } else {
-                        store.dispatch(SnarkUserCommandVerifyAction::Success { req_id });
+                SnarkEvent::UserCommandVerify(req_id, result) => match result {
+                    Err(error) => {
+                        store.dispatch(SnarkUserCommandVerifyAction::Error { req_id, error });
                     }
-                }
+                    Ok(commands) => {
+                        store.dispatch(SnarkUserCommandVerifyAction::Success { req_id, commands });
+                    }
+                },
             },
             Event::Rpc(rpc_id, e) => match *e {
                 RpcRequest::StateGet(filter) => {
@@ -291,6 +289,9 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::BlockProducerStatsGet => {
                     store.dispatch(RpcAction::BlockProducerStatsGet { rpc_id });
                 }
+                RpcRequest::VerifierStatsGet => {
+                    store.dispatch(RpcAction::VerifierStatsGet { rpc_id });
+                }
                 RpcRequest::PeersGet => {
                     store.dispatch(RpcAction::PeersGet { rpc_id });
                 }
@@ -336,6 +337,18 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::DiscoveryBoostrapStats => {
                     store.dispatch(RpcAction::DiscoveryBoostrapStats { rpc_id });
                 }
+                RpcRequest::DiscoveryRecordPut(record) => {
+                    store.dispatch(RpcAction::DiscoveryRecordPut { rpc_id, record });
+                }
+                RpcRequest::DiscoveryRecordGet(key) => {
+                    store.dispatch(RpcAction::DiscoveryRecordGetInit { rpc_id, key });
+                }
+                RpcRequest::DiscoveryStartProviding(key) => {
+                    store.dispatch(RpcAction::DiscoveryStartProviding { rpc_id, key });
+                }
+                RpcRequest::DiscoveryProvidersGet(key) => {
+                    store.dispatch(RpcAction::DiscoveryProvidersGetInit { rpc_id, key });
+                }
                 RpcRequest::TransactionPoolGet => {
                     store.dispatch(RpcAction::TransactionPool { rpc_id });
                 }
@@ -348,6 +361,21 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::TransitionFrontierUserCommandsGet => {
                     store.dispatch(RpcAction::TransitionFrontierUserCommandsGet { rpc_id });
                 }
+                RpcRequest::TransitionFrontierSnapshotGet => {
+                    store.dispatch(RpcAction::TransitionFrontierSnapshotGetInit { rpc_id });
+                }
+                RpcRequest::LedgerExportGet(query) => {
+                    store.dispatch(RpcAction::LedgerExportGetInit { rpc_id, query });
+                }
+                RpcRequest::InvariantViolationsGet => {
+                    store.dispatch(RpcAction::InvariantViolationsGet { rpc_id });
+                }
+                RpcRequest::LogFiltersGet => {
+                    store.dispatch(RpcAction::LogFiltersGet { rpc_id });
+                }
+                RpcRequest::LogFiltersSet(filters) => {
+                    store.dispatch(RpcAction::LogFiltersSet { rpc_id, filters });
+                }
             },
             Event::ExternalSnarkWorker(e) => match e {
                 ExternalSnarkWorkerEvent::Started => {
@@ -384,6 +412,9 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                     }
                 },
                 BlockProducerEvent::BlockProve(block_hash, res) => match res {
+                    Err(error) if store.state().block_producer.is_shadow() => {
+                        store.dispatch(BlockProducerAction::BlockProveError { error });
+                    }
                     Err(err) => todo!(
                         "error while trying to produce block proof for block {block_hash} - {err}"
                     ),
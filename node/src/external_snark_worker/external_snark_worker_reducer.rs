@@ -35,9 +35,12 @@ impl ExternalSnarkWorker {
                 self.state = ExternalSnarkWorkerState::Working(job_id.clone(), summary.clone());
             }
             ExternalSnarkWorkerAction::WorkResult { result } => {
-                let ExternalSnarkWorkerState::Working(job_id, _) = &self.state else {
+                let ExternalSnarkWorkerState::Working(job_id, summary) = &self.state else {
                     return;
                 };
+                if let Some(duration) = meta.time().checked_sub(self.timestamp) {
+                    self.durations.record(summary, duration);
+                }
                 self.state = ExternalSnarkWorkerState::WorkReady(job_id.clone(), result.clone());
             }
             ExternalSnarkWorkerAction::WorkError { error } => {
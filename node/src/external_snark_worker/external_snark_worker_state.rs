@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +14,15 @@ pub struct ExternalSnarkWorkers(pub(crate) ExternalSnarkWorker);
 pub struct ExternalSnarkWorker {
     pub(crate) state: ExternalSnarkWorkerState,
     pub(crate) timestamp: Timestamp,
+    pub(crate) durations: SnarkWorkDurations,
+}
+
+/// Moving averages of how long it took the worker to produce snark work,
+/// per account update. Used to estimate duration of future jobs.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SnarkWorkDurations {
+    tx: Option<Duration>,
+    merge: Option<Duration>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +47,7 @@ impl ExternalSnarkWorkers {
         ExternalSnarkWorkers(ExternalSnarkWorker {
             state: ExternalSnarkWorkerState::None,
             timestamp: now,
+            durations: Default::default(),
         })
     }
 
@@ -61,4 +73,42 @@ impl ExternalSnarkWorkers {
             _ => None,
         }
     }
+
+    /// Estimated time needed for our worker to produce the snark work.
+    ///
+    /// Based on previous runs if there were any, otherwise falls back
+    /// to [`JobSummary::estimated_duration`].
+    pub fn estimated_duration(&self, summary: &JobSummary) -> Duration {
+        self.0
+            .durations
+            .estimate(summary)
+            .unwrap_or_else(|| summary.estimated_duration())
+    }
+}
+
+impl SnarkWorkDurations {
+    pub fn record(&mut self, summary: &JobSummary, duration: Duration) {
+        let (avg, n) = self.avg_mut(summary);
+        let per_update = duration / n;
+        *avg = Some(match *avg {
+            None => per_update,
+            // give more weight to the recent runs.
+            Some(avg) => (avg * 3 + per_update) / 4,
+        });
+    }
+
+    pub fn estimate(&self, summary: &JobSummary) -> Option<Duration> {
+        let (avg, n) = match summary {
+            JobSummary::Tx(n) => (self.tx, *n),
+            JobSummary::Merge(n) => (self.merge, *n),
+        };
+        avg.map(|avg| avg * n.max(1) as u32)
+    }
+
+    fn avg_mut(&mut self, summary: &JobSummary) -> (&mut Option<Duration>, u32) {
+        match summary {
+            JobSummary::Tx(n) => (&mut self.tx, (*n).max(1) as u32),
+            JobSummary::Merge(n) => (&mut self.merge, (*n).max(1) as u32),
+        }
+    }
 }
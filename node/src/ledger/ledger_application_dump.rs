@@ -0,0 +1,496 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use ledger::{
+    scan_state::{
+        currency::{Amount, Signed, Slot},
+        fee_excess::FeeExcess,
+        transaction_logic::{
+            apply_transaction_first_pass, apply_transaction_second_pass,
+            protocol_state::protocol_state_view, Transaction,
+        },
+    },
+    staged_ledger::{
+        diff::Diff,
+        staged_ledger::{SkipVerification, StagedLedger},
+    },
+    verifier::Verifier,
+    Account, AccountId, BaseLedger, Mask,
+};
+use mina_p2p_messages::{
+    binprot::{
+        self,
+        macros::{BinProtRead, BinProtWrite},
+        BinProtRead, BinProtWrite,
+    },
+    v2::{self, MinaBaseStagedLedgerHashStableV1},
+};
+use mina_signer::CompressedPubKey;
+use openmina_core::block::{ArcBlockWithHash, BlockWithHash};
+use openmina_core::constants::constraint_constants;
+use serde::Serialize;
+
+/// Staged ledger and blocks whose application failed, dumped by the node
+/// so that the application can be reproduced both in Rust and OCaml.
+#[derive(BinProtRead, BinProtWrite, Debug, Clone)]
+pub struct LedgerApplicationDump {
+    /// Accounts of the parent staged ledger, ordered by their index.
+    pub accounts: Vec<v2::MinaBaseAccountBinableArgStableV2>,
+    pub scan_state: v2::TransactionSnarkScanStateStableV2,
+    pub pending_coinbase: v2::MinaBasePendingCoinbaseStableV2,
+    pub pred_block: v2::MinaBlockBlockStableV2,
+    /// Blocks applied one after another on top of `pred_block`.
+    pub blocks: Vec<v2::MinaBlockBlockStableV2>,
+}
+
+/// Trace of the dump's re-application.
+#[derive(Serialize, Debug, Clone)]
+pub struct LedgerApplicationReplay {
+    pub blocks: Vec<LedgerApplicationReplayBlock>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LedgerApplicationReplayBlock {
+    pub height: u32,
+    pub hash: v2::StateHash,
+    pub global_slot: u32,
+    pub transactions: Vec<LedgerApplicationReplayTransaction>,
+    /// Fee excess of all the transactions combined, must be zero.
+    pub fee_excess: Option<v2::MinaBaseFeeExcessStableV1>,
+    pub expected_hashes: MinaBaseStagedLedgerHashStableV1,
+    /// Hashes after applying the block through [`StagedLedger::apply`].
+    pub hashes: Option<MinaBaseStagedLedgerHashStableV1>,
+    /// First point where the application diverges from the block.
+    pub divergence: Option<LedgerApplicationDivergence>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LedgerApplicationReplayTransaction {
+    pub index: usize,
+    pub transaction: v2::MinaTransactionTransactionStableV2,
+    /// Status included in the block.
+    pub expected_status: v2::MinaBaseTransactionStatusStableV2,
+    pub status: Option<v2::MinaBaseTransactionStatusStableV2>,
+    pub fee_excess: Option<v2::MinaBaseFeeExcessStableV1>,
+    pub supply_increase: Option<v2::MinaStateBlockchainStateValueStableV2SignedAmount>,
+    pub first_pass: Option<LedgerApplicationReplayPass>,
+    pub second_pass: Option<LedgerApplicationReplayPass>,
+    pub error: Option<String>,
+}
+
+/// Ledger effects of one of the transaction application passes.
+#[derive(Serialize, Debug, Clone)]
+pub struct LedgerApplicationReplayPass {
+    pub ledger_hash_before: v2::LedgerHash,
+    pub ledger_hash_after: v2::LedgerHash,
+    /// Referenced accounts that were changed by the pass.
+    pub accounts: Vec<LedgerApplicationReplayAccountDiff>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LedgerApplicationReplayAccountDiff {
+    pub account_id: AccountId,
+    pub before: Option<Box<Account>>,
+    pub after: Option<Box<Account>>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerApplicationPass {
+    First,
+    Second,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LedgerApplicationDivergence {
+    /// Transactions couldn't be extracted from the staged ledger diff.
+    InvalidDiff {
+        error: String,
+    },
+    TransactionFailed {
+        index: usize,
+        pass: LedgerApplicationPass,
+        error: String,
+    },
+    StatusMismatch {
+        index: usize,
+        expected: v2::MinaBaseTransactionStatusStableV2,
+        got: v2::MinaBaseTransactionStatusStableV2,
+    },
+    NonZeroFeeExcess {
+        fee_excess: v2::MinaBaseFeeExcessStableV1,
+    },
+    ApplicationFailed {
+        error: String,
+    },
+    HashMismatch {
+        fields: Vec<&'static str>,
+    },
+}
+
+impl LedgerApplicationDump {
+    pub fn new(
+        staged_ledger: &StagedLedger,
+        block: &ArcBlockWithHash,
+        pred_block: &ArcBlockWithHash,
+    ) -> Self {
+        Self {
+            accounts: staged_ledger
+                .ledger()
+                .to_list()
+                .iter()
+                .map(v2::MinaBaseAccountBinableArgStableV2::from)
+                .collect(),
+            scan_state: staged_ledger.scan_state().into(),
+            pending_coinbase: staged_ledger.pending_coinbase_collection().into(),
+            pred_block: (*pred_block.block).clone(),
+            blocks: vec![(*block.block).clone()],
+        }
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, binprot::Error> {
+        Self::binprot_read(&mut reader)
+    }
+
+    pub fn store<W: Write>(&self, mut writer: W) -> Result<(), std::io::Error> {
+        self.binprot_write(&mut writer)
+    }
+
+    /// Rebuilds the parent staged ledger.
+    pub fn staged_ledger(&self) -> StagedLedger {
+        let mut root = Mask::new_root(ledger::Database::create(
+            constraint_constants().ledger_depth as u8,
+        ));
+        for account in &self.accounts {
+            let account: Account = account.into();
+            root.get_or_create_account(account.id(), account).unwrap();
+        }
+        StagedLedger::from_parts_unchecked(
+            constraint_constants().clone(),
+            root.make_child(),
+            (&self.scan_state).into(),
+            (&self.pending_coinbase).into(),
+        )
+    }
+
+    /// Re-applies the blocks one by one, tracing ledger effects of each
+    /// transaction. Stops at the first block that diverges.
+    pub fn replay(&self) -> LedgerApplicationReplay {
+        let mut staged_ledger = self.staged_ledger();
+        let mut pred_block = BlockWithHash::new(Arc::new(self.pred_block.clone()));
+        let mut blocks = Vec::new();
+
+        for block in &self.blocks {
+            let block = BlockWithHash::new(Arc::new(block.clone()));
+            let (replay, staged_ledger_after) = replay_block(&staged_ledger, &block, &pred_block);
+            let diverged = replay.divergence.is_some();
+            blocks.push(replay);
+            match staged_ledger_after {
+                Some(staged_ledger_after) if !diverged => staged_ledger = staged_ledger_after,
+                _ => break,
+            }
+            pred_block = block;
+        }
+
+        LedgerApplicationReplay { blocks }
+    }
+}
+
+impl LedgerApplicationReplay {
+    pub fn divergence(
+        &self,
+    ) -> Option<(&LedgerApplicationReplayBlock, &LedgerApplicationDivergence)> {
+        self.blocks
+            .iter()
+            .find_map(|block| Some((block, block.divergence.as_ref()?)))
+    }
+}
+
+fn replay_block(
+    staged_ledger: &StagedLedger,
+    block: &ArcBlockWithHash,
+    pred_block: &ArcBlockWithHash,
+) -> (LedgerApplicationReplayBlock, Option<StagedLedger>) {
+    let constraint_constants = constraint_constants();
+    let global_slot = block.global_slot_since_genesis();
+    let prev_protocol_state = &pred_block.header().protocol_state;
+    let prev_state_view = protocol_state_view(prev_protocol_state);
+
+    let consensus_state = &block.header().protocol_state.body.consensus_state;
+    let coinbase_receiver: CompressedPubKey = (&consensus_state.coinbase_receiver).into();
+    let supercharge_coinbase = consensus_state.supercharge_coinbase;
+
+    let mut replay = LedgerApplicationReplayBlock {
+        height: block.height(),
+        hash: block.hash().clone(),
+        global_slot,
+        transactions: Vec::new(),
+        fee_excess: None,
+        expected_hashes: block.staged_ledger_hashes().clone(),
+        hashes: None,
+        divergence: None,
+    };
+
+    let diff: Diff = (&block.block.body.staged_ledger_diff).into();
+    let transactions = match diff.clone().get_transactions(
+        constraint_constants,
+        coinbase_receiver.clone(),
+        supercharge_coinbase,
+    ) {
+        Ok(transactions) => transactions,
+        Err(err) => {
+            replay.divergence = Some(LedgerApplicationDivergence::InvalidDiff {
+                error: format!("{err:?}"),
+            });
+            return (replay, None);
+        }
+    };
+
+    // Same as `StagedLedger::apply`: first pass of all the transactions,
+    // then second pass of all the partially applied ones.
+    let mut ledger = staged_ledger.ledger().make_child();
+    let mut partials = Vec::with_capacity(transactions.len());
+    let mut fee_excess = Ok(FeeExcess::empty());
+    for (index, transaction) in transactions.iter().enumerate() {
+        let txn = &transaction.data;
+        let txn_fee_excess = txn.fee_excess();
+        fee_excess = fee_excess.and_then(|acc| {
+            FeeExcess::combine(&acc, txn_fee_excess.as_ref().map_err(Clone::clone)?)
+        });
+        let mut trace = LedgerApplicationReplayTransaction {
+            index,
+            transaction: txn.into(),
+            expected_status: (&transaction.status).into(),
+            status: None,
+            fee_excess: txn_fee_excess.as_ref().ok().map(Into::into),
+            supply_increase: None,
+            first_pass: None,
+            second_pass: None,
+            error: None,
+        };
+
+        let (pass, result) = trace_pass(&mut ledger, txn, |ledger| {
+            apply_transaction_first_pass(
+                constraint_constants,
+                Slot::from_u32(global_slot),
+                &prev_state_view,
+                ledger,
+                txn,
+            )
+        });
+        trace.first_pass = Some(pass);
+        match result {
+            Ok(partial) => partials.push(partial),
+            Err(error) => {
+                trace.error = Some(error.clone());
+                replay.transactions.push(trace);
+                replay
+                    .divergence
+                    .get_or_insert(LedgerApplicationDivergence::TransactionFailed {
+                        index,
+                        pass: LedgerApplicationPass::First,
+                        error,
+                    });
+                return (replay, None);
+            }
+        }
+        replay.transactions.push(trace);
+    }
+
+    for (index, partial) in partials.into_iter().enumerate() {
+        let txn = &transactions[index].data;
+        let (pass, result) = trace_pass(&mut ledger, txn, |ledger| {
+            apply_transaction_second_pass(constraint_constants, ledger, partial)
+        });
+        let trace = &mut replay.transactions[index];
+        trace.second_pass = Some(pass);
+        match result {
+            Ok(applied) => {
+                let status = applied.transaction_status();
+                trace.status = Some(status.into());
+                trace.supply_increase = applied
+                    .supply_increase(constraint_constants)
+                    .ok()
+                    .map(|amount: Signed<Amount>| (&amount).into());
+                if status != &transactions[index].status {
+                    replay
+                        .divergence
+                        .get_or_insert(LedgerApplicationDivergence::StatusMismatch {
+                            index,
+                            expected: (&transactions[index].status).into(),
+                            got: status.into(),
+                        });
+                }
+            }
+            Err(error) => {
+                trace.error = Some(error.clone());
+                replay
+                    .divergence
+                    .get_or_insert(LedgerApplicationDivergence::TransactionFailed {
+                        index,
+                        pass: LedgerApplicationPass::Second,
+                        error,
+                    });
+                return (replay, None);
+            }
+        }
+    }
+
+    match fee_excess {
+        Ok(fee_excess) => {
+            replay.fee_excess = Some((&fee_excess).into());
+            if !fee_excess.is_zero() {
+                replay
+                    .divergence
+                    .get_or_insert(LedgerApplicationDivergence::NonZeroFeeExcess {
+                        fee_excess: (&fee_excess).into(),
+                    });
+            }
+        }
+        Err(error) => {
+            replay
+                .divergence
+                .get_or_insert(LedgerApplicationDivergence::ApplicationFailed { error });
+        }
+    }
+
+    let mut staged_ledger = staged_ledger.copy();
+    let result = staged_ledger.apply(
+        Some(SkipVerification::All),
+        constraint_constants,
+        Slot::from_u32(global_slot),
+        diff,
+        (),
+        &Verifier,
+        &prev_state_view,
+        ledger::scan_state::protocol_state::hashes(prev_protocol_state),
+        coinbase_receiver,
+        supercharge_coinbase,
+    );
+    match result {
+        Ok(result) => {
+            let hashes = MinaBaseStagedLedgerHashStableV1::from(&result.hash_after_applying);
+            let fields = hash_mismatches(&replay.expected_hashes, &hashes);
+            if !fields.is_empty() {
+                replay
+                    .divergence
+                    .get_or_insert(LedgerApplicationDivergence::HashMismatch { fields });
+            }
+            replay.hashes = Some(hashes);
+            (replay, Some(staged_ledger))
+        }
+        Err(err) => {
+            replay
+                .divergence
+                .get_or_insert(LedgerApplicationDivergence::ApplicationFailed {
+                    error: format!("{err:?}"),
+                });
+            (replay, None)
+        }
+    }
+}
+
+/// Applies `f` to the ledger and records changes of the accounts
+/// referenced by the transaction.
+fn trace_pass<T>(
+    ledger: &mut Mask,
+    txn: &Transaction,
+    f: impl FnOnce(&mut Mask) -> Result<T, String>,
+) -> (LedgerApplicationReplayPass, Result<T, String>) {
+    let get_account = |ledger: &Mask, account_id: &AccountId| {
+        ledger
+            .location_of_account(account_id)
+            .and_then(|addr| ledger.get(addr))
+    };
+    let account_ids = txn.accounts_referenced();
+    let before = account_ids
+        .iter()
+        .map(|account_id| get_account(ledger, account_id))
+        .collect::<Vec<_>>();
+    let ledger_hash_before = v2::LedgerHash::from_fp(ledger.merkle_root());
+
+    let result = f(ledger);
+
+    let accounts = account_ids
+        .into_iter()
+        .zip(before)
+        .filter_map(|(account_id, before)| {
+            let after = get_account(ledger, &account_id);
+            (before != after).then_some(LedgerApplicationReplayAccountDiff {
+                account_id,
+                before,
+                after,
+            })
+        })
+        .collect();
+    let pass = LedgerApplicationReplayPass {
+        ledger_hash_before,
+        ledger_hash_after: v2::LedgerHash::from_fp(ledger.merkle_root()),
+        accounts,
+    };
+    (pass, result)
+}
+
+fn hash_mismatches(
+    expected: &MinaBaseStagedLedgerHashStableV1,
+    got: &MinaBaseStagedLedgerHashStableV1,
+) -> Vec<&'static str> {
+    [
+        (
+            "ledger_hash",
+            expected.non_snark.ledger_hash == got.non_snark.ledger_hash,
+        ),
+        (
+            "aux_hash",
+            expected.non_snark.aux_hash == got.non_snark.aux_hash,
+        ),
+        (
+            "pending_coinbase_aux",
+            expected.non_snark.pending_coinbase_aux == got.non_snark.pending_coinbase_aux,
+        ),
+        (
+            "pending_coinbase_hash",
+            expected.pending_coinbase_hash == got.pending_coinbase_hash,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, matches)| (!matches).then_some(field))
+    .collect()
+}
+
+impl fmt::Display for LedgerApplicationPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First => write!(f, "first pass"),
+            Self::Second => write!(f, "second pass"),
+        }
+    }
+}
+
+impl fmt::Display for LedgerApplicationDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDiff { error } => write!(f, "invalid staged ledger diff: {error}"),
+            Self::TransactionFailed { index, pass, error } => {
+                write!(f, "transaction #{index} failed in {pass}: {error}")
+            }
+            Self::StatusMismatch {
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "transaction #{index} status mismatch, expected: {expected:?}, got: {got:?}"
+            ),
+            Self::NonZeroFeeExcess { fee_excess } => {
+                write!(f, "non-zero fee excess: {fee_excess:?}")
+            }
+            Self::ApplicationFailed { error } => write!(f, "application failed: {error}"),
+            Self::HashMismatch { fields } => {
+                write!(f, "staged ledger hash mismatch: {}", fields.join(", "))
+            }
+        }
+    }
+}
@@ -270,6 +270,36 @@ fn next_read_requests_init<S: redux::Service>(store: &mut Store<S>) {
             return;
         }
     }
+
+    let snapshot_rpcs = store
+        .state()
+        .rpc
+        .transition_frontier_snapshot_rpc_ids()
+        .filter(|(_, status)| status.is_init())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    for rpc_id in snapshot_rpcs {
+        store.dispatch(RpcAction::TransitionFrontierSnapshotGetInit { rpc_id });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
+
+    let ledger_export_rpcs = store
+        .state()
+        .rpc
+        .ledger_export_rpc_ids()
+        .filter(|(.., status)| status.is_init())
+        .map(|(id, query, _)| (id, query.clone()))
+        .collect::<Vec<_>>();
+
+    for (rpc_id, query) in ledger_export_rpcs {
+        store.dispatch(RpcAction::LedgerExportGetInit { rpc_id, query });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
 }
 
 fn find_peers_with_ledger_rpc(
@@ -437,5 +467,11 @@ fn propagate_read_response<S: redux::Service>(
         (_, LedgerReadResponse::AccountsForRpc(rpc_id, accounts)) => {
             store.dispatch(RpcAction::LedgerAccountsGetSuccess { rpc_id, accounts });
         }
+        (_, LedgerReadResponse::TransitionFrontierSnapshot(rpc_id, snapshot)) => {
+            store.dispatch(RpcAction::TransitionFrontierSnapshotGetSuccess { rpc_id, snapshot });
+        }
+        (_, LedgerReadResponse::LedgerExport(rpc_id, export)) => {
+            store.dispatch(RpcAction::LedgerExportGetSuccess { rpc_id, export });
+        }
     }
 }
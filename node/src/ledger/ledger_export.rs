@@ -0,0 +1,122 @@
+use ledger::{Account, AccountId, BaseLedger, FpExt, Mask, MerklePath, TokenId};
+use mina_p2p_messages::v2;
+use openmina_core::block::ArcBlockWithHash;
+use serde::{Deserialize, Serialize};
+
+use crate::account::AccountPublicKey;
+
+/// Ledgers that can be exported from the node.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerExportKind {
+    /// Staking epoch ledger of the best tip.
+    Staking,
+    /// Next epoch ledger of the best tip.
+    Next,
+    /// Snarked ledger of the transition frontier root.
+    Snarked,
+    /// Staged ledger of the best tip.
+    Staged,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LedgerExportQuery {
+    pub kind: LedgerExportKind,
+    /// Export only the default token account of this public key.
+    pub public_key: Option<AccountPublicKey>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LedgerExport {
+    pub ledger_hash: v2::LedgerHash,
+    /// Accounts ordered by their index in the ledger.
+    pub accounts: Vec<LedgerExportAccount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LedgerExportAccount {
+    pub index: u64,
+    pub account: Account,
+    /// Path from the account up to the ledger root. Only included
+    /// when a single account is exported.
+    pub merkle_path: Option<Vec<LedgerExportMerklePathElem>>,
+}
+
+/// Hash of the sibling node, as a decimal field element, in the same
+/// shape as `merklePath` returned by the Mina daemon.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerExportMerklePathElem {
+    Left(String),
+    Right(String),
+}
+
+impl LedgerExportKind {
+    pub fn ledger_hash<'a>(
+        self,
+        root: &'a ArcBlockWithHash,
+        best_tip: &'a ArcBlockWithHash,
+    ) -> &'a v2::LedgerHash {
+        match self {
+            Self::Staking => best_tip.staking_epoch_ledger_hash(),
+            Self::Next => best_tip.next_epoch_ledger_hash(),
+            Self::Snarked => root.snarked_ledger_hash(),
+            Self::Staged => best_tip.staged_ledger_hash(),
+        }
+    }
+}
+
+impl LedgerExport {
+    /// Exports all accounts of the ledger or, if `public_key` is set,
+    /// just its default token account along with the merkle path.
+    pub fn from_mask(
+        mut mask: Mask,
+        public_key: Option<&AccountPublicKey>,
+    ) -> Result<Self, String> {
+        let ledger_hash = v2::LedgerHash::from_fp(mask.merkle_root());
+
+        let Some(public_key) = public_key else {
+            let mut accounts = Vec::new();
+            mask.iter(|account| {
+                accounts.push(LedgerExportAccount {
+                    index: accounts.len() as u64,
+                    account: account.clone(),
+                    merkle_path: None,
+                });
+            });
+            return Ok(Self {
+                ledger_hash,
+                accounts,
+            });
+        };
+
+        let account_id = AccountId::new(public_key.clone().into(), TokenId::default());
+        let (index, account) = mask
+            .index_of_account(account_id)
+            .and_then(|index| Some((index, mask.get_at_index(index)?)))
+            .ok_or_else(|| format!("account {public_key} not found in ledger {ledger_hash}"))?;
+        let merkle_path = mask
+            .merkle_path_at_index(index)
+            .iter()
+            .map(LedgerExportMerklePathElem::from)
+            .collect();
+
+        Ok(Self {
+            ledger_hash,
+            accounts: vec![LedgerExportAccount {
+                index: index.as_u64(),
+                account: *account,
+                merkle_path: Some(merkle_path),
+            }],
+        })
+    }
+}
+
+impl From<&MerklePath> for LedgerExportMerklePathElem {
+    fn from(value: &MerklePath) -> Self {
+        match value {
+            MerklePath::Left(hash) => Self::Left(hash.to_decimal()),
+            MerklePath::Right(hash) => Self::Right(hash.to_decimal()),
+        }
+    }
+}
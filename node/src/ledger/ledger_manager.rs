@@ -1,8 +1,14 @@
+use ledger::scan_state::scan_state::transaction_snark::OneOrTwo;
+use ledger::scan_state::scan_state::AvailableJobMessage;
 use ledger::staged_ledger::staged_ledger::StagedLedger;
-use mina_p2p_messages::v2::{LedgerHash, MinaBaseAccountBinableArgStableV2};
+use mina_p2p_messages::v2::{
+    LedgerHash, MinaBaseAccountBinableArgStableV2, MinaStateProtocolStateValueStableV2, StateHash,
+};
+use openmina_core::block::ArcBlockWithHash;
 use openmina_core::channels::mpsc;
+use openmina_core::thread;
 use std::collections::BTreeMap;
-use std::thread;
+use std::sync::Arc;
 
 use super::ledger_service::LedgerCtx;
 use super::read::{LedgerReadId, LedgerReadRequest, LedgerReadResponse};
@@ -11,6 +17,7 @@ use super::LedgerService;
 use crate::account::AccountPublicKey;
 use crate::ledger::LedgerAddress;
 use crate::transition_frontier::sync::ledger::snarked::TransitionFrontierSyncLedgerSnarkedService;
+use crate::transition_frontier::TransitionFrontierSnapshot;
 use ledger::{Account, AccountId, Mask};
 use mina_signer::CompressedPubKey;
 
@@ -52,6 +59,9 @@ pub(super) enum LedgerRequest {
     GetMask {
         ledger_hash: LedgerHash,
     }, // expected response: LedgerMask
+    GetScanStateAvailableJobs {
+        staged_ledger_hash: LedgerHash,
+    }, // expected response: ScanStateAvailableJobs
     InsertGenesisLedger {
         mask: Mask,
     },
@@ -59,6 +69,10 @@ pub(super) enum LedgerRequest {
         staged_ledger_hash: LedgerHash,
         result: Result<StagedLedger, String>,
     },
+    TransitionFrontierSnapshotGet {
+        root_block: ArcBlockWithHash,
+        protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
+    }, // expected response: TransitionFrontierSnapshot
 }
 
 #[derive(Debug)]
@@ -69,11 +83,13 @@ pub enum LedgerResponse {
     AccountsSet(Result<LedgerHash, String>),
     AccountsGet(Result<Vec<Account>, String>),
     LedgerMask(Option<(Mask, bool)>),
+    ScanStateAvailableJobs(Option<Vec<OneOrTwo<AvailableJobMessage>>>),
     #[allow(clippy::type_complexity)]
     ProducersWithDelegatesMap(
         Option<BTreeMap<AccountPublicKey, Vec<(ledger::AccountIndex, AccountPublicKey, u64)>>>,
     ),
     SnarkedLedgerContentsCopied(Result<bool, String>),
+    TransitionFrontierSnapshot(Result<TransitionFrontierSnapshot, String>),
     Success, // operation was performed and result stored; nothing to return.
 }
 
@@ -118,6 +134,7 @@ impl LedgerRequest {
                     completed_snarks,
                     supercharge_coinbase,
                     transactions_by_fee,
+                    empty_diff,
                 } => {
                     let pred_block_hash = pred_block.hash().clone();
                     let global_slot_since_genesis = global_slot.clone();
@@ -130,6 +147,7 @@ impl LedgerRequest {
                         completed_snarks,
                         supercharge_coinbase,
                         transactions_by_fee,
+                        empty_diff,
                     );
                     LedgerWriteResponse::StagedLedgerDiffCreate {
                         pred_block_hash,
@@ -212,6 +230,18 @@ impl LedgerRequest {
                         let res = ledger_ctx.get_accounts_for_rpc(ledger_hash, public_key);
                         LedgerReadResponse::AccountsForRpc(rpc_id, res)
                     }
+                    LedgerReadRequest::TransitionFrontierSnapshot(rpc_id, data) => {
+                        let res = ledger_ctx
+                            .transition_frontier_snapshot(&data.root_block, data.protocol_states)
+                            .map(Arc::new);
+                        LedgerReadResponse::TransitionFrontierSnapshot(rpc_id, res)
+                    }
+                    LedgerReadRequest::LedgerExport(rpc_id, ledger_hash, public_key) => {
+                        let res = ledger_ctx
+                            .ledger_export(&ledger_hash, public_key.as_ref())
+                            .map(Arc::new);
+                        LedgerReadResponse::LedgerExport(rpc_id, res)
+                    }
                 },
             ),
             LedgerRequest::AccountsSet {
@@ -253,6 +283,10 @@ impl LedgerRequest {
             LedgerRequest::GetMask { ledger_hash } => {
                 LedgerResponse::LedgerMask(ledger_ctx.mask(&ledger_hash))
             }
+            LedgerRequest::GetScanStateAvailableJobs { staged_ledger_hash } => {
+                let res = ledger_ctx.scan_state_available_jobs(&staged_ledger_hash);
+                LedgerResponse::ScanStateAvailableJobs(res)
+            }
             LedgerRequest::GetProducersWithDelegates {
                 ledger_hash,
                 filter,
@@ -287,6 +321,13 @@ impl LedgerRequest {
                 let res = ledger_ctx.get_accounts(ledger_hash, account_ids);
                 LedgerResponse::AccountsGet(Ok(res))
             }
+            LedgerRequest::TransitionFrontierSnapshotGet {
+                root_block,
+                protocol_states,
+            } => {
+                let res = ledger_ctx.transition_frontier_snapshot(&root_block, protocol_states);
+                LedgerResponse::TransitionFrontierSnapshot(res)
+            }
         }
     }
 }
@@ -337,7 +378,7 @@ impl LedgerManager {
             }
             ledger_ctx
         };
-        let join_handle = std::thread::Builder::new()
+        let join_handle = thread::Builder::new()
             .name("ledger-manager".into())
             .spawn(ledger_manager_loop)
             .expect("Failed: ledger manager");
@@ -358,10 +399,17 @@ impl LedgerManager {
         self.caller.call_sync(request)
     }
 
+    #[cfg(not(target_family = "wasm"))]
     pub async fn wait_for_stop(self) -> std::thread::Result<LedgerCtx> {
         self.join_handle.join()
     }
 
+    /// Blocking on the join would freeze the web worker's event loop.
+    #[cfg(target_family = "wasm")]
+    pub async fn wait_for_stop(self) -> std::thread::Result<LedgerCtx> {
+        self.join_handle.join_async().await
+    }
+
     pub fn insert_genesis_ledger(&self, mask: Mask) {
         self.call(LedgerRequest::InsertGenesisLedger { mask });
     }
@@ -375,6 +423,19 @@ impl LedgerManager {
         }
     }
 
+    /// Jobs available in the scan state of the given staged ledger.
+    pub fn scan_state_available_jobs(
+        &self,
+        staged_ledger_hash: &LedgerHash,
+    ) -> Option<Vec<OneOrTwo<AvailableJobMessage>>> {
+        match self.call_sync(LedgerRequest::GetScanStateAvailableJobs {
+            staged_ledger_hash: staged_ledger_hash.clone(),
+        }) {
+            Ok(LedgerResponse::ScanStateAvailableJobs(jobs)) => jobs,
+            _ => panic!("scan_state_available_jobs failed"),
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     pub fn producers_with_delegates(
         &self,
@@ -390,6 +451,24 @@ impl LedgerManager {
             _ => panic!("producers_with_delegates failed"),
         }
     }
+
+    /// Collects transition frontier root ledgers, same as the snapshot
+    /// rpc, but without going through the state machine. Used to persist
+    /// ledgers on shutdown.
+    pub fn transition_frontier_snapshot(
+        &self,
+        root_block: ArcBlockWithHash,
+        protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
+    ) -> Result<TransitionFrontierSnapshot, String> {
+        match self.call_sync(LedgerRequest::TransitionFrontierSnapshotGet {
+            root_block,
+            protocol_states,
+        }) {
+            Ok(LedgerResponse::TransitionFrontierSnapshot(res)) => res,
+            Ok(res) => Err(format_response_error("transition_frontier_snapshot", res)),
+            Err(err) => Err(format!("ledger manager stopped: {err}")),
+        }
+    }
 }
 
 impl LedgerCaller {
@@ -0,0 +1,540 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ledger::{
+    scan_state::transaction_logic::zkapp_command::AccessedOrNot,
+    staged_ledger::{diff::Diff, staged_ledger::StagedLedger},
+    AccountId, BaseLedger, Mask,
+};
+use mina_p2p_messages::{
+    list::List,
+    v2::{self, StateBodyHash, StateHash},
+};
+use mina_signer::CompressedPubKey;
+use openmina_core::block::{ArcBlockWithHash, BlockWithHash};
+use openmina_core::channels::mpsc;
+use openmina_core::constants::constraint_constants;
+use openmina_core::{thread, NetworkConfig};
+use serde::{Deserialize, Serialize};
+
+/// Block in the Mina daemon's precomputed block format, along with the
+/// ledger effects of applying it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PrecomputedBlock {
+    pub version: u32,
+    pub data: PrecomputedBlockData,
+}
+
+/// Fields specific to the precomputed block format are encoded as the
+/// daemon does, the rest with the json encoding of the p2p types.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PrecomputedBlockData {
+    pub scheduled_time: v2::BlockTimeTimeStableV1,
+    pub protocol_state: v2::MinaStateProtocolStateValueStableV2,
+    /// Base64 of the binprot encoded proof.
+    #[serde(with = "proof_base64")]
+    pub protocol_state_proof: v2::MinaBaseProofStableV2,
+    pub staged_ledger_diff: v2::StagedLedgerDiffDiffStableV2,
+    pub delta_transition_chain_proof: (StateHash, List<StateBodyHash>),
+    #[serde(with = "protocol_version_json")]
+    pub protocol_version: v2::ProtocolVersionStableV2,
+    #[serde(default, with = "protocol_version_opt_json")]
+    pub proposed_protocol_version: Option<v2::ProtocolVersionStableV2>,
+    /// Accounts accessed by the block's transactions, with their index
+    /// and state after the block was applied.
+    pub accounts_accessed: Vec<(u64, v2::MinaBaseAccountBinableArgStableV2)>,
+    /// Accounts that didn't exist before the block, with the account
+    /// creation fee paid for each, in mina.
+    #[serde(with = "accounts_created_json")]
+    pub accounts_created: Vec<(v2::MinaBaseAccountIdStableV2, v2::CurrencyFeeStableV1)>,
+    /// Tokens of the accessed accounts, with their owner. The default
+    /// token has no owner.
+    pub tokens_used: Vec<(v2::TokenIdKeyHash, Option<v2::MinaBaseAccountIdStableV2>)>,
+    /// Scan state of the staged ledger after the block was applied.
+    /// Not part of the daemon's format, so absent when importing
+    /// blocks exported by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_state: Option<v2::TransactionSnarkScanStateStableV2>,
+}
+
+/// Where the node writes precomputed blocks of the blocks it applies.
+#[derive(Debug, Clone)]
+pub enum PrecomputedBlockExport {
+    /// Each block in its own `<network>-<height>-<hash>.json` file.
+    Dir(PathBuf),
+    /// All blocks appended to a single file, one json per line.
+    Log(PathBuf),
+}
+
+impl PrecomputedBlock {
+    pub const VERSION: u32 = 3;
+
+    /// Builds the precomputed block of `block`, which was applied on top
+    /// of `pred_ledger`, resulting in `staged_ledger`.
+    pub fn new(
+        block: &ArcBlockWithHash,
+        staged_ledger: &StagedLedger,
+        pred_ledger: &Mask,
+    ) -> Result<Self, String> {
+        let header = block.header();
+        let consensus_state = &header.protocol_state.body.consensus_state;
+        let coinbase_receiver: CompressedPubKey = (&consensus_state.coinbase_receiver).into();
+        let diff: Diff = (&block.body().staged_ledger_diff).into();
+        let transactions = diff
+            .get_transactions(
+                constraint_constants(),
+                coinbase_receiver,
+                consensus_state.supercharge_coinbase,
+            )
+            .map_err(|err| format!("{err:?}"))?;
+
+        let mut accessed = BTreeSet::new();
+        let account_ids = transactions
+            .iter()
+            .flat_map(|txn| txn.data.account_access_statuses(&txn.status))
+            .filter(|(_, status)| matches!(status, AccessedOrNot::Accessed))
+            .map(|(account_id, _)| account_id)
+            .filter(|account_id| accessed.insert(account_id.clone()))
+            .collect::<Vec<_>>();
+
+        let ledger = staged_ledger.ledger();
+        let accounts_accessed = account_ids
+            .iter()
+            .map(|account_id| {
+                let index = ledger
+                    .index_of_account(account_id.clone())
+                    .ok_or_else(|| format!("accessed account missing: {account_id:?}"))?;
+                let account = ledger
+                    .get_at_index(index)
+                    .ok_or_else(|| format!("account missing at index: {index:?}"))?;
+                Ok((index.0, (&*account).into()))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let account_creation_fee =
+            v2::CurrencyFeeStableV1(v2::UnsignedExtendedUInt64Int64ForVersionTagsStableV1(
+                constraint_constants().account_creation_fee.into(),
+            ));
+        let accounts_created = account_ids
+            .iter()
+            .filter(|account_id| pred_ledger.location_of_account(account_id).is_none())
+            .map(|account_id| (account_id.clone().into(), account_creation_fee.clone()))
+            .collect();
+
+        let tokens_used = account_ids
+            .iter()
+            .map(|AccountId { token_id, .. }| token_id.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|token_id| {
+                let owner = if token_id.is_default() {
+                    None
+                } else {
+                    ledger.token_owner(token_id.clone()).map(Into::into)
+                };
+                ((&token_id).into(), owner)
+            })
+            .collect();
+
+        Ok(Self {
+            version: Self::VERSION,
+            data: PrecomputedBlockData {
+                scheduled_time: header
+                    .protocol_state
+                    .body
+                    .blockchain_state
+                    .timestamp
+                    .clone(),
+                protocol_state: header.protocol_state.clone(),
+                protocol_state_proof: header.protocol_state_proof.clone(),
+                staged_ledger_diff: block.body().staged_ledger_diff.clone(),
+                delta_transition_chain_proof: header.delta_block_chain_proof.clone(),
+                protocol_version: header.current_protocol_version.clone(),
+                proposed_protocol_version: header.proposed_protocol_version_opt.clone(),
+                accounts_accessed,
+                accounts_created,
+                tokens_used,
+                scan_state: Some(staged_ledger.scan_state().into()),
+            },
+        })
+    }
+
+    pub fn block(&self) -> ArcBlockWithHash {
+        let data = &self.data;
+        BlockWithHash::new(Arc::new(v2::MinaBlockBlockStableV2 {
+            header: v2::MinaBlockHeaderStableV2 {
+                protocol_state: data.protocol_state.clone(),
+                protocol_state_proof: data.protocol_state_proof.clone(),
+                delta_block_chain_proof: data.delta_transition_chain_proof.clone(),
+                current_protocol_version: data.protocol_version.clone(),
+                proposed_protocol_version_opt: data.proposed_protocol_version.clone(),
+            },
+            body: v2::StagedLedgerDiffBodyStableV1 {
+                staged_ledger_diff: data.staged_ledger_diff.clone(),
+            },
+        }))
+    }
+}
+
+impl PrecomputedBlockExport {
+    fn init(&self) -> std::io::Result<()> {
+        match self {
+            Self::Dir(dir) => fs::create_dir_all(dir),
+            Self::Log(path) => match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => fs::create_dir_all(dir),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    fn write(
+        &self,
+        block: &ArcBlockWithHash,
+        precomputed: &PrecomputedBlock,
+    ) -> std::io::Result<()> {
+        match self {
+            Self::Dir(dir) => {
+                let file_name = format!(
+                    "{}-{}-{}.json",
+                    NetworkConfig::global().name,
+                    block.height(),
+                    block.hash()
+                );
+                let file = fs::File::create(dir.join(file_name))?;
+                serde_json::to_writer(file, precomputed)?;
+            }
+            Self::Log(path) => {
+                let mut line = serde_json::to_vec(precomputed)?;
+                line.push(b'\n');
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?
+                    .write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes precomputed blocks on its own thread, so that block application
+/// isn't blocked on encoding and file system writes.
+pub struct PrecomputedBlockWriter {
+    sender: mpsc::UnboundedSender<(ArcBlockWithHash, PrecomputedBlock)>,
+}
+
+impl PrecomputedBlockWriter {
+    pub fn start(export: PrecomputedBlockExport) -> Self {
+        let (sender, mut receiver) =
+            mpsc::unbounded_channel::<(ArcBlockWithHash, PrecomputedBlock)>();
+        thread::Builder::new()
+            .name("openmina_precomputed_blocks".to_owned())
+            .spawn(move || {
+                if let Err(error) = export.init() {
+                    openmina_core::error!(openmina_core::log::system_time();
+                        kind = "PrecomputedBlockWriter",
+                        summary = format!("failed to initialize export {export:?}: {error}"),
+                    );
+                    return;
+                }
+                while let Some((block, precomputed)) = receiver.blocking_recv() {
+                    if let Err(error) = export.write(&block, &precomputed) {
+                        openmina_core::warn!(openmina_core::log::system_time();
+                            kind = "PrecomputedBlockWriter",
+                            summary = format!("failed to export block {}: {error}", block.hash()),
+                        );
+                    }
+                }
+            })
+            .expect("Failed: precomputed block writer");
+        Self { sender }
+    }
+
+    pub fn write(&self, block: ArcBlockWithHash, precomputed: PrecomputedBlock) {
+        let _ = self.sender.send((block, precomputed));
+    }
+}
+
+mod proof_base64 {
+    use base64::{engine::general_purpose::URL_SAFE, Engine};
+    use mina_p2p_messages::{
+        binprot::{BinProtRead, BinProtWrite},
+        v2::MinaBaseProofStableV2,
+    };
+    use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        proof: &MinaBaseProofStableV2,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::new();
+        proof.binprot_write(&mut bytes).map_err(S::Error::custom)?;
+        serializer.serialize_str(&URL_SAFE.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<MinaBaseProofStableV2, D::Error> {
+        let base64 = String::deserialize(deserializer)?;
+        let bytes = URL_SAFE.decode(base64).map_err(D::Error::custom)?;
+        MinaBaseProofStableV2::binprot_read(&mut bytes.as_slice()).map_err(D::Error::custom)
+    }
+}
+
+/// Protocol version as a record of numbers, instead of the
+/// `transaction.network.patch` string used by the p2p types.
+#[derive(Serialize, Deserialize)]
+struct ProtocolVersionJson {
+    transaction: u64,
+    network: u64,
+    patch: u64,
+}
+
+impl From<&v2::ProtocolVersionStableV2> for ProtocolVersionJson {
+    fn from(value: &v2::ProtocolVersionStableV2) -> Self {
+        Self {
+            transaction: value.transaction.as_u64(),
+            network: value.network.as_u64(),
+            patch: value.patch.as_u64(),
+        }
+    }
+}
+
+impl From<ProtocolVersionJson> for v2::ProtocolVersionStableV2 {
+    fn from(value: ProtocolVersionJson) -> Self {
+        Self {
+            transaction: value.transaction.into(),
+            network: value.network.into(),
+            patch: value.patch.into(),
+        }
+    }
+}
+
+mod protocol_version_json {
+    use mina_p2p_messages::v2::ProtocolVersionStableV2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ProtocolVersionJson;
+
+    pub fn serialize<S: Serializer>(
+        version: &ProtocolVersionStableV2,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ProtocolVersionJson::from(version).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ProtocolVersionStableV2, D::Error> {
+        ProtocolVersionJson::deserialize(deserializer).map(Into::into)
+    }
+}
+
+mod protocol_version_opt_json {
+    use mina_p2p_messages::v2::ProtocolVersionStableV2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ProtocolVersionJson;
+
+    pub fn serialize<S: Serializer>(
+        version: &Option<ProtocolVersionStableV2>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        version
+            .as_ref()
+            .map(ProtocolVersionJson::from)
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<ProtocolVersionStableV2>, D::Error> {
+        Option::<ProtocolVersionJson>::deserialize(deserializer).map(|v| v.map(Into::into))
+    }
+}
+
+/// Fees as decimal mina strings, e.g. `"0.001"`.
+mod accounts_created_json {
+    use ledger::scan_state::currency::Fee;
+    use mina_p2p_messages::v2::{CurrencyFeeStableV1, MinaBaseAccountIdStableV2};
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        accounts: &[(MinaBaseAccountIdStableV2, CurrencyFeeStableV1)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        accounts
+            .iter()
+            .map(|(account_id, fee)| (account_id, Fee::from(fee).to_mina_string()))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(MinaBaseAccountIdStableV2, CurrencyFeeStableV1)>, D::Error> {
+        Vec::<(MinaBaseAccountIdStableV2, String)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(account_id, fee)| {
+                let fee = Fee::of_mina_string(&fee)
+                    .ok_or_else(|| D::Error::custom(format!("invalid fee `{fee}`")))?;
+                Ok((account_id, (&fee).into()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use base64::{engine::general_purpose::URL_SAFE, Engine};
+    use ledger::{scan_state::currency::Fee, TokenId};
+    use mina_p2p_messages::binprot::BinProtRead;
+    use openmina_core::block::best_tip_fixture;
+
+    use super::*;
+
+    fn precomputed(block: &ArcBlockWithHash) -> PrecomputedBlock {
+        let header = block.header();
+        let producer = AccountId::new(block.producer().into(), TokenId::default());
+        PrecomputedBlock {
+            version: PrecomputedBlock::VERSION,
+            data: PrecomputedBlockData {
+                scheduled_time: header
+                    .protocol_state
+                    .body
+                    .blockchain_state
+                    .timestamp
+                    .clone(),
+                protocol_state: header.protocol_state.clone(),
+                protocol_state_proof: header.protocol_state_proof.clone(),
+                staged_ledger_diff: block.body().staged_ledger_diff.clone(),
+                delta_transition_chain_proof: header.delta_block_chain_proof.clone(),
+                protocol_version: header.current_protocol_version.clone(),
+                proposed_protocol_version: header.proposed_protocol_version_opt.clone(),
+                accounts_accessed: vec![],
+                accounts_created: vec![(
+                    producer.into(),
+                    v2::CurrencyFeeStableV1(v2::UnsignedExtendedUInt64Int64ForVersionTagsStableV1(
+                        1_000_000_000.into(),
+                    )),
+                )],
+                tokens_used: vec![(v2::TokenIdKeyHash::default(), None)],
+                scan_state: None,
+            },
+        }
+    }
+
+    #[test]
+    fn daemon_json_encoding() {
+        let block = best_tip_fixture();
+        let json = serde_json::to_value(precomputed(&block)).unwrap();
+        let data = &json["data"];
+
+        let proof = data["protocol_state_proof"]
+            .as_str()
+            .expect("proof should be a base64 string");
+        let proof = URL_SAFE.decode(proof).unwrap();
+        let proof = v2::MinaBaseProofStableV2::binprot_read(&mut proof.as_slice()).unwrap();
+        assert_eq!(proof, block.header().protocol_state_proof);
+
+        let version = &block.header().current_protocol_version;
+        assert_eq!(
+            data["protocol_version"],
+            serde_json::json!({
+                "transaction": version.transaction.as_u64(),
+                "network": version.network.as_u64(),
+                "patch": version.patch.as_u64(),
+            })
+        );
+        assert_eq!(data["accounts_created"][0][1], "1");
+        assert!(data.get("scan_state").is_none());
+
+        let decoded: PrecomputedBlock = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.block().hash(), block.hash());
+        assert!(decoded.data.scan_state.is_none());
+    }
+
+    #[test]
+    fn fee_mina_string() {
+        for (fee, s) in [
+            (0, "0"),
+            (1_000_000_000, "1"),
+            (1_500_000, "0.0015"),
+            (12_000_000_001, "12.000000001"),
+        ] {
+            let fee = Fee::from_u64(fee);
+            assert_eq!(fee.to_mina_string(), s);
+            assert_eq!(Fee::of_mina_string(s), Some(fee));
+        }
+        assert_eq!(
+            Fee::of_mina_string("1.5"),
+            Some(Fee::from_u64(1_500_000_000))
+        );
+        assert_eq!(Fee::of_mina_string("0.0000000001"), None);
+        assert_eq!(Fee::of_mina_string("1.-5"), None);
+        assert_eq!(Fee::of_mina_string(""), None);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "openmina-precomputed-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn export_dir() {
+        let block = best_tip_fixture();
+        let dir = temp_dir("dir");
+        let export = PrecomputedBlockExport::Dir(dir.join("blocks"));
+        export.init().unwrap();
+        export.write(&block, &precomputed(&block)).unwrap();
+
+        let path = dir.join("blocks").join(format!(
+            "{}-{}-{}.json",
+            NetworkConfig::global().name,
+            block.height(),
+            block.hash()
+        ));
+        let json = fs::read_to_string(path).unwrap();
+        let decoded: PrecomputedBlock = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.block().hash(), block.hash());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn export_log_with_writer() {
+        let block = best_tip_fixture();
+        let dir = temp_dir("log");
+        let path = dir.join("blocks.jsonl");
+        let writer = PrecomputedBlockWriter::start(PrecomputedBlockExport::Log(path.clone()));
+        writer.write(block.clone(), precomputed(&block));
+        writer.write(block.clone(), precomputed(&block));
+
+        let start = std::time::Instant::now();
+        let lines = loop {
+            let lines = fs::read_to_string(&path).unwrap_or_default();
+            let written = lines.ends_with('\n') && lines.lines().count() == 2;
+            if written || start.elapsed() > Duration::from_secs(10) {
+                break lines;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let hashes = lines
+            .lines()
+            .map(|line| {
+                let decoded: PrecomputedBlock = serde_json::from_str(line).unwrap();
+                decoded.block().hash().clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, vec![block.hash().clone(); 2]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -8,7 +8,10 @@ use super::ledger_manager::{LedgerManager, LedgerRequest};
 use ledger::{
     scan_state::{
         currency::Slot,
-        scan_state::{AvailableJobMessage, JobValueBase, JobValueMerge, JobValueWithIndex, Pass},
+        scan_state::{
+            transaction_snark::OneOrTwo, AvailableJobMessage, JobValueBase, JobValueMerge,
+            JobValueWithIndex, Pass,
+        },
         transaction_logic::{
             local_state::LocalState,
             protocol_state::{protocol_state_view, ProtocolStateView},
@@ -18,7 +21,7 @@ use ledger::{
     },
     sparse_ledger::SparseLedger,
     staged_ledger::{
-        diff::Diff,
+        diff::{with_valid_signatures_and_proofs, Diff},
         staged_ledger::{SkipVerification, StagedLedger},
         validate_block::block_body_hash,
     },
@@ -54,12 +57,14 @@ use crate::transition_frontier::sync::{
     ledger::staged::StagedLedgerAuxAndPendingCoinbasesValid,
     TransitionFrontierRootSnarkedLedgerUpdates,
 };
+use crate::transition_frontier::{TransitionFrontierSnapshot, TransitionFrontierSnapshotLedger};
 
-use super::write::CommitResult;
+use super::write::{BlockApplyResult, CommitResult};
 
 use super::{
     ledger_empty_hash_at_depth, read::LedgerReadResponse, write::LedgerWriteResponse,
-    LedgerAddress, LedgerEvent, LEDGER_DEPTH,
+    LedgerAddress, LedgerApplicationDump, LedgerEvent, LedgerExport, PrecomputedBlock,
+    PrecomputedBlockExport, PrecomputedBlockWriter, LEDGER_DEPTH,
 };
 use super::{
     read::{LedgerReadId, LedgerReadRequest},
@@ -75,10 +80,16 @@ pub struct LedgerCtx {
     snarked_ledgers: BTreeMap<LedgerHash, Mask>,
     /// Additional snarked ledgers specified at startup (loaded from disk)
     additional_snarked_ledgers: BTreeMap<LedgerHash, Mask>,
+    /// One of the additional snarked ledgers to start the initial ledger
+    /// sync from, when none of them matches the target, e.g. root snarked
+    /// ledger of the bootstrap snapshot. It's closer to the synced ledgers
+    /// than the genesis one, so less needs to be fetched from peers.
+    sync_origin_snarked_ledger: Option<LedgerHash>,
     staged_ledgers: BTreeMap<LedgerHash, StagedLedger>,
     sync: LedgerSyncState,
     event_sender:
         Option<openmina_core::channels::mpsc::UnboundedSender<crate::event_source::Event>>,
+    precomputed_block_writer: Option<PrecomputedBlockWriter>,
 }
 
 #[derive(Default)]
@@ -131,6 +142,11 @@ impl LedgerCtx {
         self.event_sender = Some(event_sender);
     }
 
+    /// Write a precomputed block of each applied block.
+    pub fn set_precomputed_block_export(&mut self, export: PrecomputedBlockExport) {
+        self.precomputed_block_writer = Some(PrecomputedBlockWriter::start(export));
+    }
+
     pub(super) fn send_event(&self, event: LedgerEvent) {
         if let Some(tx) = self.event_sender.as_ref() {
             let _ = tx.send(event.into());
@@ -145,6 +161,19 @@ impl LedgerCtx {
         self.send_event(LedgerEvent::Read(id, resp))
     }
 
+    /// Adds snarked ledger which can be used as a starting point for the
+    /// ledger sync, e.g. the one loaded from the bootstrap snapshot.
+    pub fn insert_additional_snarked_ledger(&mut self, mut mask: Mask) {
+        let hash = merkle_root(&mut mask);
+        self.additional_snarked_ledgers.insert(hash, mask);
+    }
+
+    /// Use the additional snarked ledger as a starting point of the
+    /// initial ledger sync instead of the genesis one.
+    pub fn set_sync_origin_snarked_ledger(&mut self, hash: LedgerHash) {
+        self.sync_origin_snarked_ledger = Some(hash);
+    }
+
     pub fn insert_genesis_ledger(&mut self, mut mask: Mask) {
         let hash = merkle_root(&mut mask);
         let staged_ledger =
@@ -187,6 +216,21 @@ impl LedgerCtx {
         }
     }
 
+    pub fn ledger_export(
+        &self,
+        ledger_hash: &LedgerHash,
+        public_key: Option<&AccountPublicKey>,
+    ) -> Result<LedgerExport, String> {
+        let (mask, _) = self
+            .mask(ledger_hash)
+            .ok_or_else(|| format!("ledger {ledger_hash} not found"))?;
+        let export = LedgerExport::from_mask(mask, public_key)?;
+        if &export.ledger_hash != ledger_hash {
+            return Err(format!("ledger {ledger_hash} isn't synced yet"));
+        }
+        Ok(export)
+    }
+
     // TODO(tizoc): explain when `is_synced` is `true` and when it is `false`. Also use something else than a boolean.
     /// Returns a tuple of `(mask, is_synced)` for a [Mask] with the specified `hash` if it exists or `None` otherwise.
     pub fn mask(&self, hash: &LedgerHash) -> Option<(Mask, bool)> {
@@ -225,9 +269,20 @@ impl LedgerCtx {
             return Ok(false);
         }
 
+        // Ledger with the target hash may already be available,
+        // e.g. when it was loaded from the bootstrap snapshot.
         let origin = self
             .snarked_ledgers
-            .get(&origin_snarked_ledger_hash)
+            .get(&target_snarked_ledger_hash)
+            .or_else(|| {
+                self.additional_snarked_ledgers
+                    .get(&target_snarked_ledger_hash)
+            })
+            .or_else(|| {
+                let hash = self.sync_origin_snarked_ledger.as_ref()?;
+                self.additional_snarked_ledgers.get(hash)
+            })
+            .or_else(|| self.snarked_ledgers.get(&origin_snarked_ledger_hash))
             .or_else(|| {
                 // If it doesn't exist in completed ledgers, it may be
                 // an in-progress ledger from a previous attempt that we can reuse
@@ -525,7 +580,7 @@ impl LedgerCtx {
         &mut self,
         block: ArcBlockWithHash,
         pred_block: ArcBlockWithHash,
-    ) -> Result<(), String> {
+    ) -> Result<BlockApplyResult, String> {
         openmina_core::info!(openmina_core::log::system_time();
             kind = "LedgerService::block_apply",
             summary = format!("{}, {} <- {}", block.height(), block.hash(), block.pred_hash()),
@@ -592,12 +647,50 @@ impl LedgerCtx {
             panic!("staged ledger hash mismatch. found: {ledger_hashes:#?}, expected: {expected_ledger_hashes:#?}");
         }
 
+        if self.precomputed_block_writer.is_some() {
+            self.precomputed_block_export(&block, &pred_block, &staged_ledger);
+        }
+
+        let snarked_ledger_hash = match &result.ledger_proof {
+            Some((proof, _)) => LedgerHash::from_fp(proof.statement().target.first_pass_ledger),
+            None => pred_block.snarked_ledger_hash().clone(),
+        };
+
         let ledger_hash = block.staged_ledger_hash();
         self.sync
             .staged_ledgers
             .insert(ledger_hash.clone(), staged_ledger);
 
-        Ok(())
+        Ok(BlockApplyResult {
+            staged_ledger_hashes: ledger_hashes,
+            snarked_ledger_hash,
+        })
+    }
+
+    fn precomputed_block_export(
+        &mut self,
+        block: &ArcBlockWithHash,
+        pred_block: &ArcBlockWithHash,
+        staged_ledger: &StagedLedger,
+    ) {
+        let Some(pred_ledger) = self
+            .staged_ledger_mut(pred_block.staged_ledger_hash())
+            .map(|l| l.ledger())
+        else {
+            return;
+        };
+        let Some(writer) = self.precomputed_block_writer.as_ref() else {
+            return;
+        };
+        match PrecomputedBlock::new(block, staged_ledger, &pred_ledger) {
+            Ok(precomputed) => writer.write(block.clone(), precomputed),
+            Err(error) => {
+                openmina_core::warn!(openmina_core::log::system_time();
+                    kind = "LedgerService::precomputed_block_export",
+                    summary = format!("failed to export block {}: {error}", block.hash()),
+                );
+            }
+        }
     }
 
     pub fn commit(
@@ -608,6 +701,8 @@ impl LedgerCtx {
         new_root: &ArcBlockWithHash,
         new_best_tip: &ArcBlockWithHash,
     ) -> CommitResult {
+        // initial sync is done, later ones start from the ledgers we have.
+        self.sync_origin_snarked_ledger = None;
         openmina_core::debug!(openmina_core::log::system_time();
             kind = "LedgerService::commit",
             summary = format!("commit {}, {}", new_best_tip.height(), new_best_tip.hash()),
@@ -706,13 +801,7 @@ impl LedgerCtx {
             .unwrap_or_default();
 
         let available_jobs = self
-            .staged_ledger_mut(new_best_tip.staged_ledger_hash())
-            .map(|l| {
-                l.scan_state()
-                    .all_job_pairs_iter()
-                    .map(|job| job.map(|single| AvailableJobMessage::from(single)))
-                    .collect()
-            })
+            .scan_state_available_jobs(new_best_tip.staged_ledger_hash())
             .unwrap_or_default();
 
         CommitResult {
@@ -721,6 +810,18 @@ impl LedgerCtx {
         }
     }
 
+    pub fn scan_state_available_jobs(
+        &mut self,
+        staged_ledger_hash: &LedgerHash,
+    ) -> Option<Vec<OneOrTwo<AvailableJobMessage>>> {
+        self.staged_ledger_mut(staged_ledger_hash).map(|l| {
+            l.scan_state()
+                .all_job_pairs_iter()
+                .map(|job| job.map(|single| AvailableJobMessage::from(single)))
+                .collect()
+        })
+    }
+
     pub fn get_num_accounts(
         &mut self,
         ledger_hash: v2::LedgerHash,
@@ -821,6 +922,64 @@ impl LedgerCtx {
         )
     }
 
+    /// Collects transition frontier root ledgers, so that other nodes
+    /// can bootstrap from them without syncing ledgers from peers.
+    pub fn transition_frontier_snapshot(
+        &mut self,
+        root_block: &ArcBlockWithHash,
+        protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
+    ) -> Result<TransitionFrontierSnapshot, String> {
+        let root_snarked_ledger_hash = root_block.snarked_ledger_hash();
+        let ledger_hashes = [
+            root_snarked_ledger_hash,
+            root_block.staking_epoch_ledger_hash(),
+            root_block.next_epoch_ledger_hash(),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+
+        let snarked_ledgers = ledger_hashes
+            .into_iter()
+            .filter_map(|ledger_hash| {
+                let (mask, _) = self.mask(ledger_hash).filter(|(_, is_synced)| *is_synced)?;
+                let accounts = mask.fold(Vec::new(), |mut accounts, account| {
+                    accounts.push(v2::MinaBaseAccountBinableArgStableV2::from(account));
+                    accounts
+                });
+                Some(TransitionFrontierSnapshotLedger {
+                    ledger_hash: ledger_hash.clone(),
+                    accounts,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !snarked_ledgers
+            .iter()
+            .any(|ledger| &ledger.ledger_hash == root_snarked_ledger_hash)
+        {
+            return Err(format!(
+                "root snarked ledger {root_snarked_ledger_hash} not found"
+            ));
+        }
+
+        let staged_ledger_parts = self
+            .staged_ledger_aux_and_pending_coinbase(
+                root_block.staged_ledger_hash().clone(),
+                protocol_states,
+            )
+            .ok_or_else(|| {
+                format!(
+                    "root staged ledger {} or its protocol states not found",
+                    root_block.staged_ledger_hash()
+                )
+            })?;
+
+        Ok(TransitionFrontierSnapshot {
+            root_block: (*root_block.block).clone(),
+            snarked_ledgers,
+            staged_ledger_parts: (*staged_ledger_parts).clone(),
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn staged_ledger_diff_create(
         &mut self,
@@ -832,6 +991,7 @@ impl LedgerCtx {
         completed_snarks: BTreeMap<SnarkJobId, Snark>,
         supercharge_coinbase: bool,
         transactions_by_fee: Vec<valid::UserCommand>,
+        empty_diff: bool,
     ) -> Result<StagedLedgerDiffCreateOutput, String> {
         let mut staged_ledger = self
             .staged_ledger_mut(pred_block.staged_ledger_hash())
@@ -851,22 +1011,30 @@ impl LedgerCtx {
         let protocol_state_view = protocol_state_view(&pred_block.header().protocol_state);
 
         // TODO(binier): include `invalid_txns` in output.
-        let (pre_diff, _invalid_txns) = staged_ledger
-            .create_diff(
-                constraint_constants(),
-                (&global_slot_since_genesis).into(),
-                Some(true),
-                (&coinbase_receiver).into(),
-                (),
-                &protocol_state_view,
-                transactions_by_fee,
-                |stmt| {
-                    let job_id = SnarkJobId::from(stmt);
-                    completed_snarks.get(&job_id).map(Into::into)
-                },
-                supercharge_coinbase,
+        let (pre_diff, _invalid_txns, diff_creation_logs) = if empty_diff {
+            (
+                with_valid_signatures_and_proofs::Diff::empty(),
+                vec![],
+                vec![],
             )
-            .map_err(|err| format!("{err:?}"))?;
+        } else {
+            staged_ledger
+                .create_diff(
+                    constraint_constants(),
+                    (&global_slot_since_genesis).into(),
+                    Some(true),
+                    (&coinbase_receiver).into(),
+                    (),
+                    &protocol_state_view,
+                    transactions_by_fee,
+                    |stmt| {
+                        let job_id = SnarkJobId::from(stmt);
+                        completed_snarks.get(&job_id).map(Into::into)
+                    },
+                    supercharge_coinbase,
+                )
+                .map_err(|err| format!("{err:?}"))?
+        };
 
         // TODO(binier): maybe here, check if block reward is above threshold.
         // https://github.com/minaprotocol/mina/blob/b3d418a8c0ae4370738886c2b26f0ec7bdb49303/src/lib/block_producer/block_producer.ml#L222
@@ -907,6 +1075,7 @@ impl LedgerCtx {
                 &producer,
                 &delegator,
             ),
+            diff_creation_logs: diff_creation_logs.iter().map(Into::into).collect(),
         })
     }
 
@@ -1154,6 +1323,7 @@ pub trait LedgerService: redux::Service {
 
 /// Save staged ledger and block to file, when the application fail.
 /// So we can easily reproduce the application both in Rust and OCaml, to compare them.
+/// See `openmina replay block-application`.
 /// - https://github.com/openmina/openmina/blob/8e68037aafddd43842a54c8439baeafee4c6e1eb/ledger/src/staged_ledger/staged_ledger.rs#L5959
 /// - TODO: Find OCaml link, I remember having the same test in OCaml but I can't find where
 fn dump_application_to_file(
@@ -1161,40 +1331,11 @@ fn dump_application_to_file(
     block: ArcBlockWithHash,
     pred_block: ArcBlockWithHash,
 ) -> std::io::Result<String> {
-    use mina_p2p_messages::binprot::{
-        self,
-        macros::{BinProtRead, BinProtWrite},
-    };
-
-    #[derive(BinProtRead, BinProtWrite)]
-    struct ApplyContext {
-        accounts: Vec<v2::MinaBaseAccountBinableArgStableV2>,
-        scan_state: v2::TransactionSnarkScanStateStableV2,
-        pending_coinbase: v2::MinaBasePendingCoinbaseStableV2,
-        pred_block: v2::MinaBlockBlockStableV2,
-        blocks: Vec<v2::MinaBlockBlockStableV2>,
-    }
-
-    let cs = &block.block.header.protocol_state.body.consensus_state;
-    let block_height = cs.blockchain_length.as_u32();
+    let apply_context = LedgerApplicationDump::new(staged_ledger, &block, &pred_block);
 
-    let apply_context = ApplyContext {
-        accounts: staged_ledger
-            .ledger()
-            .to_list()
-            .iter()
-            .map(v2::MinaBaseAccountBinableArgStableV2::from)
-            .collect::<Vec<_>>(),
-        scan_state: staged_ledger.scan_state().into(),
-        pending_coinbase: staged_ledger.pending_coinbase_collection().into(),
-        pred_block: (*pred_block.block).clone(),
-        blocks: vec![(*block.block).clone()],
-    };
-
-    use mina_p2p_messages::binprot::BinProtWrite;
-    let filename = format!("/tmp/failed_application_ctx_{}.binprot", block_height);
+    let filename = format!("/tmp/failed_application_ctx_{}.binprot", block.height());
     let mut file = std::fs::File::create(&filename)?;
-    apply_context.binprot_write(&mut file)?;
+    apply_context.store(&mut file)?;
     file.sync_all()?;
 
     Ok(filename)
@@ -1208,6 +1349,39 @@ mod tests {
 
     use super::*;
 
+    fn mask_with_accounts(accounts: &[Account]) -> (LedgerHash, Mask) {
+        let mut mask = Mask::new_root(Database::create(35));
+        for account in accounts {
+            mask.get_or_create_account(account.id(), account.clone())
+                .unwrap();
+        }
+        (merkle_root(&mut mask), mask)
+    }
+
+    #[test]
+    fn ledger_sync_starts_from_sync_origin() {
+        let accounts = [Account::rand(), Account::rand()];
+        let (genesis_hash, genesis) = mask_with_accounts(&accounts[..1]);
+        let (snapshot_hash, snapshot) = mask_with_accounts(&accounts);
+        let target: LedgerHash = "jx5YAT36bv62M8mPcREYYfZWXaKqqMzDCP8wmc21uf4CfDKAHCr"
+            .parse()
+            .unwrap();
+
+        let mut ctx = LedgerCtx::default();
+        ctx.snarked_ledgers.insert(genesis_hash.clone(), genesis);
+        ctx.insert_additional_snarked_ledger(snapshot);
+
+        let synced_from = |ctx: &mut LedgerCtx| {
+            ctx.copy_snarked_ledger_contents_for_sync(genesis_hash.clone(), target.clone(), true)
+                .unwrap();
+            merkle_root(ctx.sync.snarked_ledgers.get_mut(&target).unwrap())
+        };
+        assert_eq!(synced_from(&mut ctx), genesis_hash);
+
+        ctx.set_sync_origin_snarked_ledger(snapshot_hash.clone());
+        assert_eq!(synced_from(&mut ctx), snapshot_hash);
+    }
+
     #[test]
     fn test_ledger_hash() {
         IntoIterator::into_iter([(
@@ -20,6 +20,15 @@ pub use ledger_effects::*;
 
 mod ledger_service;
 pub use ledger_service::*;
+
+mod ledger_export;
+pub use ledger_export::*;
+
+mod ledger_application_dump;
+pub use ledger_application_dump::*;
+
+mod ledger_precomputed_block;
+pub use ledger_precomputed_block::*;
 pub mod ledger_manager;
 
 pub use ledger::AccountIndex as LedgerAccountIndex;
@@ -12,13 +12,15 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use mina_p2p_messages::v2;
+use openmina_core::block::ArcBlockWithHash;
 use serde::{Deserialize, Serialize};
 
 use crate::account::AccountPublicKey;
 use crate::block_producer::vrf_evaluator::DelegatorTable;
-use crate::ledger::LedgerAddress;
+use crate::ledger::{LedgerAddress, LedgerExport};
 use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
 use crate::rpc::RpcScanStateSummaryScanStateJob;
+use crate::transition_frontier::TransitionFrontierSnapshot;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
 pub enum LedgerReadKind {
@@ -30,6 +32,8 @@ pub enum LedgerReadKind {
     GetStagedLedgerAuxAndPendingCoinbases,
     ScanStateSummary,
     AccountsForRpc,
+    TransitionFrontierSnapshot,
+    LedgerExport,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -45,6 +49,8 @@ pub enum LedgerReadRequest {
     // rpcs
     ScanStateSummary(v2::LedgerHash),
     AccountsForRpc(RpcId, v2::LedgerHash, Option<AccountPublicKey>),
+    TransitionFrontierSnapshot(RpcId, LedgerReadTransitionFrontierSnapshot),
+    LedgerExport(RpcId, v2::LedgerHash, Option<AccountPublicKey>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -60,6 +66,8 @@ pub enum LedgerReadResponse {
     // rpcs
     ScanStateSummary(Vec<Vec<RpcScanStateSummaryScanStateJob>>),
     AccountsForRpc(RpcId, Vec<Account>),
+    TransitionFrontierSnapshot(RpcId, Result<Arc<TransitionFrontierSnapshot>, String>),
+    LedgerExport(RpcId, Result<Arc<LedgerExport>, String>),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -68,6 +76,12 @@ pub struct LedgerReadStagedLedgerAuxAndPendingCoinbases {
     pub protocol_states: BTreeMap<v2::StateHash, v2::MinaStateProtocolStateValueStableV2>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LedgerReadTransitionFrontierSnapshot {
+    pub root_block: ArcBlockWithHash,
+    pub protocol_states: BTreeMap<v2::StateHash, v2::MinaStateProtocolStateValueStableV2>,
+}
+
 impl LedgerReadRequest {
     pub fn kind(&self) -> LedgerReadKind {
         match self {
@@ -81,6 +95,8 @@ impl LedgerReadRequest {
             }
             Self::ScanStateSummary(..) => LedgerReadKind::ScanStateSummary,
             Self::AccountsForRpc(..) => LedgerReadKind::AccountsForRpc,
+            Self::TransitionFrontierSnapshot(..) => LedgerReadKind::TransitionFrontierSnapshot,
+            Self::LedgerExport(..) => LedgerReadKind::LedgerExport,
         }
     }
 
@@ -99,6 +115,10 @@ impl LedgerReadRequest {
             Self::ScanStateSummary(..) => 100,
             // TODO(adonagy): not sure
             Self::AccountsForRpc(..) => 10,
+            // Copies whole ledgers.
+            Self::TransitionFrontierSnapshot(..) => 200,
+            Self::LedgerExport(_, _, None) => 100,
+            Self::LedgerExport(_, _, Some(_)) => 1,
         };
         cost.max(1)
     }
@@ -117,6 +137,8 @@ impl LedgerReadResponse {
             }
             Self::ScanStateSummary(..) => LedgerReadKind::ScanStateSummary,
             Self::AccountsForRpc(..) => LedgerReadKind::AccountsForRpc,
+            Self::TransitionFrontierSnapshot(..) => LedgerReadKind::TransitionFrontierSnapshot,
+            Self::LedgerExport(..) => LedgerReadKind::LedgerExport,
         }
     }
 }
@@ -126,3 +148,9 @@ impl PartialEq for LedgerReadStagedLedgerAuxAndPendingCoinbases {
         self.ledger_hash == other.ledger_hash
     }
 }
+
+impl PartialEq for LedgerReadTransitionFrontierSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.root_block.hash() == other.root_block.hash()
+    }
+}
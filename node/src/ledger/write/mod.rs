@@ -44,6 +44,8 @@ pub enum LedgerWriteRequest {
         completed_snarks: BTreeMap<SnarkJobId, Snark>,
         supercharge_coinbase: bool,
         transactions_by_fee: Vec<valid::UserCommand>,
+        /// Create a diff without any transactions, snark work or coinbase.
+        empty_diff: bool,
     },
     BlockApply {
         block: ArcBlockWithHash,
@@ -71,7 +73,7 @@ pub enum LedgerWriteResponse {
     },
     BlockApply {
         block_hash: v2::StateHash,
-        result: Result<(), String>,
+        result: Result<BlockApplyResult, String>,
     },
     Commit {
         best_tip_hash: v2::StateHash,
@@ -79,6 +81,15 @@ pub enum LedgerWriteResponse {
     },
 }
 
+/// Ledger hashes produced by applying the block.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BlockApplyResult {
+    pub staged_ledger_hashes: v2::MinaBaseStagedLedgerHashStableV1,
+    /// Target of the ledger proof emitted by the block, or the parent's
+    /// snarked ledger if none was emitted.
+    pub snarked_ledger_hash: v2::LedgerHash,
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct CommitResult {
     pub available_jobs: Vec<OneOrTwo<AvailableJobMessage>>,
@@ -537,6 +537,7 @@ pub fn node_p2p_effects<S: Service>(store: &mut Store<S>, action: P2pActionWithM
                     P2pChannelsRpcAction::Init { .. } => {}
                     P2pChannelsRpcAction::Pending { .. } => {}
                     P2pChannelsRpcAction::RequestSend { .. } => {}
+                    P2pChannelsRpcAction::RequestRejected { .. } => {}
                     P2pChannelsRpcAction::ResponsePending { .. } => {}
                     P2pChannelsRpcAction::ResponseSend { .. } => {}
                 }
@@ -563,7 +564,36 @@ pub fn node_p2p_effects<S: Service>(store: &mut Store<S>, action: P2pActionWithM
         }
         P2pAction::Network(_action) => {
             #[cfg(feature = "p2p-libp2p")]
-            _action.effects(&meta, store);
+            {
+                if let p2p::P2pNetworkAction::Kad(p2p::P2pNetworkKadAction::System(action)) =
+                    &_action
+                {
+                    match action {
+                        p2p::P2pNetworkKademliaAction::GetRecordFinished {
+                            record,
+                            rpc_id: Some(rpc_id),
+                            ..
+                        } => {
+                            store.dispatch(RpcAction::DiscoveryRecordGetSuccess {
+                                rpc_id: *rpc_id,
+                                record: Ok(record.clone()),
+                            });
+                        }
+                        p2p::P2pNetworkKademliaAction::GetProvidersFinished {
+                            providers,
+                            rpc_id: Some(rpc_id),
+                            ..
+                        } => {
+                            store.dispatch(RpcAction::DiscoveryProvidersGetSuccess {
+                                rpc_id: *rpc_id,
+                                providers: Ok(providers.clone()),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                _action.effects(&meta, store);
+            }
         }
     }
 }
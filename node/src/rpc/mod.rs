@@ -1,9 +1,11 @@
 mod rpc_state;
 use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use ledger::scan_state::currency::{Amount, Balance, Fee, Nonce, Slot};
 use ledger::scan_state::transaction_logic::signed_command::SignedCommandPayload;
+use ledger::scan_state::transaction_logic::transaction_union_payload::TransactionUnionPayload;
 use ledger::scan_state::transaction_logic::{self, signed_command, Memo};
 use ledger::transaction_pool::{diff, ValidCommandWithHash};
 use ledger::Account;
@@ -13,8 +15,9 @@ use mina_p2p_messages::v2::{
     MinaBaseUserCommandStableV2, MinaTransactionTransactionStableV2,
     SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse, StateHash, TransactionHash,
 };
-use openmina_node_account::AccountPublicKey;
+use openmina_node_account::{AccountPublicKey, AccountSecretKey};
 use p2p::bootstrap::P2pNetworkKadBootstrapStats;
+use p2p::{P2pNetworkKadEntry, P2pNetworkKadRecord, P2pNetworkKadRecordKey};
 pub use rpc_state::*;
 
 mod rpc_actions;
@@ -39,16 +42,21 @@ use openmina_core::snark::SnarkJobId;
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
+use crate::core::invariants::InvariantViolations;
+use crate::core::log::LogFilters;
 use crate::external_snark_worker::{
     ExternalSnarkWorkerError, ExternalSnarkWorkerWorkError, SnarkWorkSpecError,
 };
+use crate::ledger::{LedgerExport, LedgerExportQuery};
 use crate::p2p::connection::incoming::P2pConnectionIncomingInitOpts;
 use crate::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
 use crate::p2p::PeerId;
+use crate::snark::SnarkVerifierStats;
 use crate::snark_pool::{JobCommitment, JobSummary};
 use crate::stats::actions::{ActionStatsForBlock, ActionStatsSnapshot};
 use crate::stats::block_producer::{BlockProductionAttempt, BlockProductionAttemptWonSlot};
 use crate::stats::sync::SyncStatsSnapshot;
+use crate::transition_frontier::TransitionFrontierSnapshot;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum RpcRequest {
@@ -57,6 +65,7 @@ pub enum RpcRequest {
     ActionStatsGet(ActionStatsQuery),
     SyncStatsGet(SyncStatsQuery),
     BlockProducerStatsGet,
+    VerifierStatsGet,
     MessageProgressGet,
     PeersGet,
     P2pConnectionOutgoing(P2pConnectionOutgoingInitOpts),
@@ -72,10 +81,19 @@ pub enum RpcRequest {
     ReadinessCheck,
     DiscoveryRoutingTable,
     DiscoveryBoostrapStats,
+    DiscoveryRecordPut(P2pNetworkKadRecord),
+    DiscoveryRecordGet(P2pNetworkKadRecordKey),
+    DiscoveryStartProviding(P2pNetworkKadRecordKey),
+    DiscoveryProvidersGet(P2pNetworkKadRecordKey),
     TransactionPoolGet,
     LedgerAccountsGet(Option<AccountPublicKey>),
     TransactionInject(Vec<RpcInjectPayment>),
     TransitionFrontierUserCommandsGet,
+    TransitionFrontierSnapshotGet,
+    LedgerExportGet(LedgerExportQuery),
+    InvariantViolationsGet,
+    LogFiltersGet,
+    LogFiltersSet(LogFilters),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -90,26 +108,66 @@ pub struct RpcInjectPayment {
     signature_field: BigInt,
     signature_scalar: BigInt,
 }
+impl RpcInjectPayment {
+    /// Payment signed by `from` for the network the node is running on.
+    pub fn new_signed(
+        from: &AccountSecretKey,
+        to: AccountPublicKey,
+        amount: u64,
+        fee: u64,
+        nonce: u32,
+        valid_until: u32,
+        memo: String,
+    ) -> Self {
+        use mina_signer::Signer;
+
+        let mut payment = Self {
+            fee,
+            amount,
+            to,
+            from: from.public_key(),
+            memo,
+            nonce,
+            valid_until,
+            signature_field: BigInt::zero(),
+            signature_scalar: BigInt::zero(),
+        };
+        let payload = TransactionUnionPayload::of_user_command_payload(&payment.payload());
+        let network_id = match openmina_core::NetworkConfig::global().network_id {
+            openmina_core::network::NetworkId::TESTNET => mina_signer::NetworkId::TESTNET,
+            openmina_core::network::NetworkId::MAINNET => mina_signer::NetworkId::MAINNET,
+        };
+        let signature = mina_signer::create_legacy(network_id).sign(&from.clone().into(), &payload);
+        payment.signature_field = signature.rx.into();
+        payment.signature_scalar = signature.s.into();
+        payment
+    }
+
+    fn payload(&self) -> SignedCommandPayload {
+        SignedCommandPayload::create(
+            Fee::from_u64(self.fee),
+            self.from.clone().into(),
+            Nonce::from_u32(self.nonce),
+            Some(Slot::from_u32(self.valid_until)),
+            Memo::from_str(&self.memo).unwrap(),
+            signed_command::Body::Payment(signed_command::PaymentPayload {
+                receiver_pk: self.to.clone().into(),
+                amount: Amount::from_u64(self.amount),
+            }),
+        )
+    }
+}
+
 // MinaBaseUserCommandStableV2
 impl From<RpcInjectPayment> for MinaBaseUserCommandStableV2 {
     fn from(value: RpcInjectPayment) -> Self {
         let signature = mina_signer::Signature {
-            rx: value.signature_field.into(),
-            s: value.signature_scalar.into(),
+            rx: value.signature_field.clone().into(),
+            s: value.signature_scalar.clone().into(),
         };
         println!("Signature: {signature}");
         let sc = signed_command::SignedCommand {
-            payload: SignedCommandPayload::create(
-                Fee::from_u64(value.fee),
-                value.from.clone().into(),
-                Nonce::from_u32(value.nonce),
-                Some(Slot::from_u32(value.valid_until)),
-                Memo::from_str(&value.memo).unwrap(),
-                signed_command::Body::Payment(signed_command::PaymentPayload {
-                    receiver_pk: value.to.into(),
-                    amount: Amount::from_u64(value.amount),
-                }),
-            ),
+            payload: value.payload(),
             signer: value.from.into(),
             signature,
         };
@@ -321,6 +379,7 @@ pub type RpcStatusGetResponse = Option<RpcNodeStatus>;
 pub type RpcActionStatsGetResponse = Option<ActionStatsResponse>;
 pub type RpcSyncStatsGetResponse = Option<Vec<SyncStatsSnapshot>>;
 pub type RpcBlockProducerStatsGetResponse = Option<RpcBlockProducerStats>;
+pub type RpcVerifierStatsGetResponse = Option<SnarkVerifierStats>;
 pub type RpcPeersGetResponse = Vec<RpcPeerInfo>;
 pub type RpcP2pConnectionOutgoingResponse = Result<(), String>;
 pub type RpcScanStateSummaryGetResponse = Option<RpcScanStateSummary>;
@@ -330,6 +389,10 @@ pub type RpcSnarkerConfigGetResponse = Option<RpcSnarkerConfig>;
 pub type RpcTransactionPoolResponse = Vec<ValidCommandWithHash>;
 pub type RpcLedgerAccountsResponse = Vec<AccountSlim>;
 pub type RpcTransitionFrontierUserCommandsResponse = Vec<MinaBaseUserCommandStableV2>;
+pub type RpcTransitionFrontierSnapshotGetResponse = Result<Arc<TransitionFrontierSnapshot>, String>;
+pub type RpcLedgerExportGetResponse = Result<Arc<LedgerExport>, String>;
+pub type RpcInvariantViolationsGetResponse = InvariantViolations;
+pub type RpcLogFiltersResponse = LogFilters;
 
 // TODO(adonagy): rework this to handle all the possible user commands (enum..)
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -526,6 +589,10 @@ pub type RpcReadinessCheckResponse = Result<(), String>;
 
 pub type RpcDiscoveryRoutingTableResponse = Option<discovery::RpcDiscoveryRoutingTable>;
 pub type RpcDiscoveryBoostrapStatsResponse = Option<P2pNetworkKadBootstrapStats>;
+pub type RpcDiscoveryRecordPutResponse = Result<(), String>;
+pub type RpcDiscoveryRecordGetResponse = Result<Option<P2pNetworkKadRecord>, String>;
+pub type RpcDiscoveryStartProvidingResponse = Result<(), String>;
+pub type RpcDiscoveryProvidersGetResponse = Result<Vec<P2pNetworkKadEntry>, String>;
 
 pub mod discovery {
     use p2p::{
@@ -1,15 +1,21 @@
+use std::sync::Arc;
+
 use ledger::transaction_pool::{diff, ValidCommandWithHash};
 use ledger::Account;
 use openmina_core::block::ArcBlockWithHash;
+use openmina_core::log::LogFilters;
 use openmina_core::snark::SnarkJobId;
 use openmina_core::ActionEvent;
 use openmina_node_account::AccountPublicKey;
 use serde::{Deserialize, Serialize};
 
 use crate::external_snark_worker::SnarkWorkId;
+use crate::ledger::{LedgerExport, LedgerExportQuery};
 use crate::p2p::connection::incoming::P2pConnectionIncomingInitOpts;
 use crate::p2p::connection::outgoing::{P2pConnectionOutgoingError, P2pConnectionOutgoingInitOpts};
 use crate::p2p::connection::P2pConnectionResponse;
+use crate::p2p::{P2pNetworkKadEntry, P2pNetworkKadRecord, P2pNetworkKadRecordKey};
+use crate::transition_frontier::TransitionFrontierSnapshot;
 
 use super::{
     ActionStatsQuery, RpcId, RpcInjectPayment, RpcScanStateSummaryGetQuery,
@@ -41,6 +47,9 @@ pub enum RpcAction {
     BlockProducerStatsGet {
         rpc_id: RpcId,
     },
+    VerifierStatsGet {
+        rpc_id: RpcId,
+    },
 
     MessageProgressGet {
         rpc_id: RpcId,
@@ -137,6 +146,44 @@ pub enum RpcAction {
     DiscoveryBoostrapStats {
         rpc_id: RpcId,
     },
+    #[action_event(level = info)]
+    DiscoveryRecordPut {
+        rpc_id: RpcId,
+        record: P2pNetworkKadRecord,
+    },
+    #[action_event(level = info)]
+    DiscoveryRecordGetInit {
+        rpc_id: RpcId,
+        key: P2pNetworkKadRecordKey,
+    },
+    #[action_event(level = info)]
+    DiscoveryRecordGetPending {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info)]
+    DiscoveryRecordGetSuccess {
+        rpc_id: RpcId,
+        record: Result<Option<P2pNetworkKadRecord>, String>,
+    },
+    #[action_event(level = info)]
+    DiscoveryStartProviding {
+        rpc_id: RpcId,
+        key: P2pNetworkKadRecordKey,
+    },
+    #[action_event(level = info)]
+    DiscoveryProvidersGetInit {
+        rpc_id: RpcId,
+        key: P2pNetworkKadRecordKey,
+    },
+    #[action_event(level = info)]
+    DiscoveryProvidersGetPending {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info)]
+    DiscoveryProvidersGetSuccess {
+        rpc_id: RpcId,
+        providers: Result<Vec<P2pNetworkKadEntry>, String>,
+    },
 
     TransactionPool {
         rpc_id: RpcId,
@@ -178,6 +225,44 @@ pub enum RpcAction {
     TransitionFrontierUserCommandsGet {
         rpc_id: RpcId,
     },
+    #[action_event(level = info)]
+    TransitionFrontierSnapshotGetInit {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info)]
+    TransitionFrontierSnapshotGetPending {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info)]
+    TransitionFrontierSnapshotGetSuccess {
+        rpc_id: RpcId,
+        snapshot: Result<Arc<TransitionFrontierSnapshot>, String>,
+    },
+    #[action_event(level = info)]
+    LedgerExportGetInit {
+        rpc_id: RpcId,
+        query: LedgerExportQuery,
+    },
+    #[action_event(level = info)]
+    LedgerExportGetPending {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info)]
+    LedgerExportGetSuccess {
+        rpc_id: RpcId,
+        export: Result<Arc<LedgerExport>, String>,
+    },
+    InvariantViolationsGet {
+        rpc_id: RpcId,
+    },
+    LogFiltersGet {
+        rpc_id: RpcId,
+    },
+    #[action_event(level = info, fields(debug(filters)))]
+    LogFiltersSet {
+        rpc_id: RpcId,
+        filters: LogFilters,
+    },
 
     Finish {
         rpc_id: RpcId,
@@ -192,6 +277,7 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
             RpcAction::ActionStatsGet { .. } => true,
             RpcAction::SyncStatsGet { .. } => true,
             RpcAction::BlockProducerStatsGet { .. } => true,
+            RpcAction::VerifierStatsGet { .. } => true,
             RpcAction::MessageProgressGet { .. } => true,
             RpcAction::PeersGet { .. } => true,
             RpcAction::P2pConnectionOutgoingInit { rpc_id, .. } => {
@@ -261,6 +347,30 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
             RpcAction::ReadinessCheck { .. } => true,
             RpcAction::DiscoveryRoutingTable { .. } => true,
             RpcAction::DiscoveryBoostrapStats { .. } => true,
+            RpcAction::DiscoveryRecordPut { .. } => true,
+            RpcAction::DiscoveryRecordGetInit { .. } => true,
+            RpcAction::DiscoveryRecordGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::DiscoveryRecordGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::DiscoveryStartProviding { .. } => true,
+            RpcAction::DiscoveryProvidersGetInit { .. } => true,
+            RpcAction::DiscoveryProvidersGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::DiscoveryProvidersGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
             RpcAction::TransactionPool { .. } => true,
             RpcAction::LedgerAccountsGetInit { .. } => {
                 state.transition_frontier.best_tip().is_some()
@@ -293,6 +403,31 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
                 .get(rpc_id)
                 .map_or(false, |v| v.status.is_pending()),
             RpcAction::TransitionFrontierUserCommandsGet { .. } => true,
+            RpcAction::TransitionFrontierSnapshotGetInit { .. } => true,
+            RpcAction::TransitionFrontierSnapshotGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::TransitionFrontierSnapshotGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::LedgerExportGetInit { .. } => true,
+            RpcAction::LedgerExportGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::LedgerExportGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::InvariantViolationsGet { .. } => true,
+            RpcAction::LogFiltersGet { .. } => true,
+            RpcAction::LogFiltersSet { .. } => true,
             RpcAction::Finish { rpc_id } => state
                 .rpc
                 .requests
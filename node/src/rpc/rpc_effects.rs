@@ -9,10 +9,13 @@ use openmina_core::block::ArcBlockWithHash;
 
 use crate::block_producer::BlockProducerWonSlot;
 use crate::external_snark_worker::available_job_to_snark_worker_spec;
-use crate::ledger::read::{LedgerReadAction, LedgerReadRequest};
+use crate::ledger::read::{
+    LedgerReadAction, LedgerReadRequest, LedgerReadTransitionFrontierSnapshot,
+};
 use crate::p2p::connection::incoming::P2pConnectionIncomingAction;
 use crate::p2p::connection::outgoing::P2pConnectionOutgoingAction;
 use crate::p2p::connection::P2pConnectionResponse;
+use crate::p2p::{P2pNetworkKadRecord, P2pNetworkKademliaAction};
 use crate::rpc::{AccountSlim, PeerConnectionStatus, RpcPeerInfo, RpcTransactionInjectResponse};
 use crate::snark_pool::SnarkPoolAction;
 use crate::transition_frontier::sync::ledger::TransitionFrontierSyncLedgerState;
@@ -149,6 +152,10 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
             });
             let _ = store.service.respond_block_producer_stats_get(rpc_id, resp);
         }
+        RpcAction::VerifierStatsGet { rpc_id } => {
+            let resp = store.service.verifier_stats();
+            let _ = store.service.respond_verifier_stats_get(rpc_id, resp);
+        }
         RpcAction::MessageProgressGet { rpc_id } => {
             // TODO: move to stats
             let p2p = p2p_ready!(store.state().p2p, meta.time());
@@ -640,6 +647,83 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                 meta.time()
             );
         }
+        RpcAction::DiscoveryRecordPut { rpc_id, record } => {
+            let P2pNetworkKadRecord { key, value } = record;
+            let response = if store.dispatch(P2pNetworkKademliaAction::PutRecord { key, value }) {
+                Ok(())
+            } else {
+                Err("discovery is not ready, the record is too large or too many records are published".to_owned())
+            };
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_discovery_record_put(rpc_id, response),
+                meta.time()
+            );
+        }
+        RpcAction::DiscoveryRecordGetInit { rpc_id, key } => {
+            // the record might be found locally right away, so the request
+            // should be pending before the lookup starts.
+            if store.dispatch(RpcAction::DiscoveryRecordGetPending { rpc_id })
+                && !store.dispatch(P2pNetworkKademliaAction::GetRecord {
+                    key,
+                    rpc_id: Some(rpc_id),
+                })
+            {
+                store.dispatch(RpcAction::DiscoveryRecordGetSuccess {
+                    rpc_id,
+                    record: Err(
+                        "discovery is not ready or the key is already being looked up".to_owned(),
+                    ),
+                });
+            }
+        }
+        RpcAction::DiscoveryRecordGetPending { .. } => {}
+        RpcAction::DiscoveryRecordGetSuccess { rpc_id, record } => {
+            respond_or_log!(
+                store.service().respond_discovery_record_get(rpc_id, record),
+                meta.time()
+            );
+            store.dispatch(RpcAction::Finish { rpc_id });
+        }
+        RpcAction::DiscoveryStartProviding { rpc_id, key } => {
+            let response = if store.dispatch(P2pNetworkKademliaAction::StartProviding { key }) {
+                Ok(())
+            } else {
+                Err("discovery is not ready or too many keys are provided".to_owned())
+            };
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_discovery_start_providing(rpc_id, response),
+                meta.time()
+            );
+        }
+        RpcAction::DiscoveryProvidersGetInit { rpc_id, key } => {
+            if store.dispatch(RpcAction::DiscoveryProvidersGetPending { rpc_id })
+                && !store.dispatch(P2pNetworkKademliaAction::GetProviders {
+                    key,
+                    rpc_id: Some(rpc_id),
+                })
+            {
+                store.dispatch(RpcAction::DiscoveryProvidersGetSuccess {
+                    rpc_id,
+                    providers: Err(
+                        "discovery is not ready or the key is already being looked up".to_owned(),
+                    ),
+                });
+            }
+        }
+        RpcAction::DiscoveryProvidersGetPending { .. } => {}
+        RpcAction::DiscoveryProvidersGetSuccess { rpc_id, providers } => {
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_discovery_providers_get(rpc_id, providers),
+                meta.time()
+            );
+            store.dispatch(RpcAction::Finish { rpc_id });
+        }
         RpcAction::TransactionPool { rpc_id } => {
             let response = store.state().transaction_pool.get_all_transactions();
 
@@ -698,6 +782,14 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
         }
         RpcAction::TransactionInjectInit { rpc_id, commands } => {
             store.dispatch(RpcAction::TransactionInjectPending { rpc_id });
+            if store.state().transaction_pool.best_tip_hash().is_none() {
+                // no ledger to verify the commands against yet
+                store.dispatch(RpcAction::TransactionInjectFailure {
+                    rpc_id,
+                    response: vec![],
+                });
+                return;
+            }
             // sort the commadns by nonce
 
             store.dispatch(TransactionPoolAction::StartVerify {
@@ -742,6 +834,99 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                 meta.time()
             )
         }
+        RpcAction::TransitionFrontierSnapshotGetInit { rpc_id } => {
+            let Some((root_block, protocol_states)) = None.or_else(|| {
+                let transition_frontier = &store.state().transition_frontier;
+                Some((
+                    transition_frontier.root()?.clone(),
+                    transition_frontier.needed_protocol_states.clone(),
+                ))
+            }) else {
+                if store.dispatch(RpcAction::TransitionFrontierSnapshotGetPending { rpc_id }) {
+                    store.dispatch(RpcAction::TransitionFrontierSnapshotGetSuccess {
+                        rpc_id,
+                        snapshot: Err("transition frontier isn't synced yet".to_owned()),
+                    });
+                }
+                return;
+            };
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::TransitionFrontierSnapshot(
+                    rpc_id,
+                    LedgerReadTransitionFrontierSnapshot {
+                        root_block,
+                        protocol_states,
+                    },
+                ),
+            }) {
+                store.dispatch(RpcAction::TransitionFrontierSnapshotGetPending { rpc_id });
+            }
+        }
+        RpcAction::TransitionFrontierSnapshotGetPending { .. } => {}
+        RpcAction::TransitionFrontierSnapshotGetSuccess { rpc_id, snapshot } => {
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_transition_frontier_snapshot_get(rpc_id, snapshot),
+                meta.time()
+            );
+            store.dispatch(RpcAction::Finish { rpc_id });
+        }
+        RpcAction::LedgerExportGetInit { rpc_id, query } => {
+            let transition_frontier = &store.state().transition_frontier;
+            let ledger_hash = transition_frontier
+                .root()
+                .zip(transition_frontier.best_tip())
+                .map(|(root, best_tip)| query.kind.ledger_hash(root, best_tip).clone());
+            let Some(ledger_hash) = ledger_hash else {
+                if store.dispatch(RpcAction::LedgerExportGetPending { rpc_id }) {
+                    store.dispatch(RpcAction::LedgerExportGetSuccess {
+                        rpc_id,
+                        export: Err("transition frontier isn't synced yet".to_owned()),
+                    });
+                }
+                return;
+            };
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::LedgerExport(rpc_id, ledger_hash, query.public_key),
+            }) {
+                store.dispatch(RpcAction::LedgerExportGetPending { rpc_id });
+            }
+        }
+        RpcAction::LedgerExportGetPending { .. } => {}
+        RpcAction::LedgerExportGetSuccess { rpc_id, export } => {
+            respond_or_log!(
+                store.service().respond_ledger_export_get(rpc_id, export),
+                meta.time()
+            );
+            store.dispatch(RpcAction::Finish { rpc_id });
+        }
+        RpcAction::InvariantViolationsGet { rpc_id } => {
+            let violations = store.service.invariants_state().violations().clone();
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_invariant_violations_get(rpc_id, violations),
+                meta.time()
+            );
+        }
+        RpcAction::LogFiltersGet { rpc_id } => {
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_log_filters(rpc_id, openmina_core::log::log_filters()),
+                meta.time()
+            );
+        }
+        RpcAction::LogFiltersSet { rpc_id, filters } => {
+            openmina_core::log::set_log_filters(&filters);
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_log_filters(rpc_id, openmina_core::log::log_filters()),
+                meta.time()
+            );
+        }
         RpcAction::Finish { .. } => {}
     }
 }
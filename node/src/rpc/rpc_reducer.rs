@@ -12,6 +12,7 @@ impl RpcState {
             RpcAction::ActionStatsGet { .. } => {}
             RpcAction::SyncStatsGet { .. } => {}
             RpcAction::BlockProducerStatsGet { .. } => {}
+            RpcAction::VerifierStatsGet { .. } => {}
             RpcAction::MessageProgressGet { .. } => {}
             RpcAction::PeersGet { .. } => {}
             RpcAction::P2pConnectionOutgoingInit { rpc_id, opts } => {
@@ -105,6 +106,58 @@ impl RpcState {
             RpcAction::ReadinessCheck { .. } => {}
             RpcAction::DiscoveryRoutingTable { .. } => {}
             RpcAction::DiscoveryBoostrapStats { .. } => {}
+            RpcAction::DiscoveryRecordPut { .. } => {}
+            RpcAction::DiscoveryRecordGetInit { rpc_id, key } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::DiscoveryRecordGet(key.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::DiscoveryRecordGetPending { rpc_id }
+            | RpcAction::DiscoveryProvidersGetPending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::DiscoveryRecordGetSuccess { rpc_id, record } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = match record {
+                    Ok(_) => RpcRequestStatus::Success { time: meta.time() },
+                    Err(error) => RpcRequestStatus::Error {
+                        time: meta.time(),
+                        error: error.clone(),
+                    },
+                };
+            }
+            RpcAction::DiscoveryStartProviding { .. } => {}
+            RpcAction::DiscoveryProvidersGetInit { rpc_id, key } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::DiscoveryProvidersGet(key.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::DiscoveryProvidersGetSuccess { rpc_id, providers } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = match providers {
+                    Ok(_) => RpcRequestStatus::Success { time: meta.time() },
+                    Err(error) => RpcRequestStatus::Error {
+                        time: meta.time(),
+                        error: error.clone(),
+                    },
+                };
+            }
+            RpcAction::InvariantViolationsGet { .. } => {}
+            RpcAction::LogFiltersGet { .. } => {}
+            RpcAction::LogFiltersSet { .. } => {}
             RpcAction::Finish { rpc_id } => {
                 self.requests.remove(rpc_id);
             }
@@ -159,6 +212,58 @@ impl RpcState {
                 };
             }
             RpcAction::TransitionFrontierUserCommandsGet { .. } => {}
+            RpcAction::TransitionFrontierSnapshotGetInit { rpc_id } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::TransitionFrontierSnapshotGet,
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::TransitionFrontierSnapshotGetPending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::TransitionFrontierSnapshotGetSuccess { rpc_id, snapshot } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = match snapshot {
+                    Ok(_) => RpcRequestStatus::Success { time: meta.time() },
+                    Err(error) => RpcRequestStatus::Error {
+                        time: meta.time(),
+                        error: error.clone(),
+                    },
+                };
+            }
+            RpcAction::LedgerExportGetInit { rpc_id, query } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::LedgerExportGet(query.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::LedgerExportGetPending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::LedgerExportGetSuccess { rpc_id, export } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = match export {
+                    Ok(_) => RpcRequestStatus::Success { time: meta.time() },
+                    Err(error) => RpcRequestStatus::Error {
+                        time: meta.time(),
+                        error: error.clone(),
+                    },
+                };
+            }
         }
     }
 }
@@ -6,13 +6,17 @@ use crate::State;
 
 use super::{
     RpcActionStatsGetResponse, RpcBlockProducerStatsGetResponse, RpcDiscoveryBoostrapStatsResponse,
-    RpcDiscoveryRoutingTableResponse, RpcHealthCheckResponse, RpcId, RpcLedgerAccountsResponse,
-    RpcMessageProgressResponse, RpcP2pConnectionOutgoingResponse, RpcPeersGetResponse,
-    RpcReadinessCheckResponse, RpcScanStateSummaryGetResponse, RpcSnarkPoolGetResponse,
-    RpcSnarkPoolJobGetResponse, RpcSnarkerJobCommitResponse, RpcSnarkerJobSpecResponse,
-    RpcSnarkerWorkersResponse, RpcStatusGetResponse, RpcSyncStatsGetResponse,
-    RpcTransactionInjectFailure, RpcTransactionInjectResponse, RpcTransactionPoolResponse,
-    RpcTransitionFrontierUserCommandsResponse,
+    RpcDiscoveryProvidersGetResponse, RpcDiscoveryRecordGetResponse, RpcDiscoveryRecordPutResponse,
+    RpcDiscoveryRoutingTableResponse, RpcDiscoveryStartProvidingResponse, RpcHealthCheckResponse,
+    RpcId, RpcInvariantViolationsGetResponse, RpcLedgerAccountsResponse,
+    RpcLedgerExportGetResponse, RpcLogFiltersResponse, RpcMessageProgressResponse,
+    RpcP2pConnectionOutgoingResponse, RpcPeersGetResponse, RpcReadinessCheckResponse,
+    RpcScanStateSummaryGetResponse, RpcSnarkPoolGetResponse, RpcSnarkPoolJobGetResponse,
+    RpcSnarkerJobCommitResponse, RpcSnarkerJobSpecResponse, RpcSnarkerWorkersResponse,
+    RpcStatusGetResponse, RpcSyncStatsGetResponse, RpcTransactionInjectFailure,
+    RpcTransactionInjectResponse, RpcTransactionPoolResponse,
+    RpcTransitionFrontierSnapshotGetResponse, RpcTransitionFrontierUserCommandsResponse,
+    RpcVerifierStatsGetResponse,
 };
 
 #[derive(Error, Serialize, Deserialize, Debug, Clone)]
@@ -65,6 +69,11 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcBlockProducerStatsGetResponse,
     ) -> Result<(), RespondError>;
+    fn respond_verifier_stats_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcVerifierStatsGetResponse,
+    ) -> Result<(), RespondError>;
     fn respond_message_progress_stats_get(
         &mut self,
         rpc_id: RpcId,
@@ -140,6 +149,26 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcDiscoveryBoostrapStatsResponse,
     ) -> Result<(), RespondError>;
+    fn respond_discovery_record_put(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcDiscoveryRecordPutResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_discovery_record_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcDiscoveryRecordGetResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_discovery_start_providing(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcDiscoveryStartProvidingResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_discovery_providers_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcDiscoveryProvidersGetResponse,
+    ) -> Result<(), RespondError>;
     fn respond_readiness_check(
         &mut self,
         rpc_id: RpcId,
@@ -170,4 +199,24 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcTransitionFrontierUserCommandsResponse,
     ) -> Result<(), RespondError>;
+    fn respond_transition_frontier_snapshot_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcTransitionFrontierSnapshotGetResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_ledger_export_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcLedgerExportGetResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_invariant_violations_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcInvariantViolationsGetResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_log_filters(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcLogFiltersResponse,
+    ) -> Result<(), RespondError>;
 }
@@ -5,6 +5,8 @@ use openmina_core::block::ArcBlockWithHash;
 use openmina_node_account::AccountPublicKey;
 use serde::{Deserialize, Serialize};
 
+use crate::ledger::LedgerExportQuery;
+
 use super::{RpcId, RpcRequest};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -77,6 +79,24 @@ impl RpcState {
             })
     }
 
+    pub fn transition_frontier_snapshot_rpc_ids(
+        &self,
+    ) -> impl Iterator<Item = (RpcId, &RpcRequestStatus)> + '_ {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::TransitionFrontierSnapshotGet))
+            .map(|(id, req)| (*id, &req.status))
+    }
+
+    pub fn ledger_export_rpc_ids(
+        &self,
+    ) -> impl Iterator<Item = (RpcId, &LedgerExportQuery, &RpcRequestStatus)> + '_ {
+        self.requests.iter().filter_map(|(id, req)| match &req.req {
+            RpcRequest::LedgerExportGet(query) => Some((*id, query, &req.status)),
+            _ => None,
+        })
+    }
+
     pub fn accounts_request_rpc_ids(
         &self,
     ) -> impl Iterator<Item = (RpcId, Option<AccountPublicKey>, &RpcRequestStatus)> + '_ {
@@ -1,5 +1,6 @@
 pub use crate::block_producer::vrf_evaluator::BlockProducerVrfEvaluatorService;
 pub use crate::block_producer::BlockProducerService;
+pub use crate::core::invariants::InvariantService;
 pub use crate::event_source::EventSourceService;
 use crate::external_snark_worker::ExternalSnarkWorkerService;
 pub use crate::ledger::LedgerService;
@@ -11,9 +12,11 @@ pub use crate::snark::work_verify_effectful::SnarkWorkVerifyService;
 pub use crate::snark_pool::SnarkPoolService;
 pub use crate::transition_frontier::genesis_effectful::TransitionFrontierGenesisService;
 pub use crate::transition_frontier::sync::ledger::snarked::TransitionFrontierSyncLedgerSnarkedService;
+pub use crate::transition_frontier::TransitionFrontierArchiveService;
 pub use redux::TimeService;
 use snark::user_command_verify_effectful::SnarkUserCommandVerifyService;
 
+use crate::snark::SnarkVerifierStats;
 use crate::stats::Stats;
 
 pub trait Service:
@@ -30,13 +33,16 @@ pub trait Service:
     + LedgerService
     + TransitionFrontierGenesisService
     + TransitionFrontierSyncLedgerSnarkedService
+    + TransitionFrontierArchiveService
     + SnarkPoolService
     + SnarkUserCommandVerifyService
     + BlockProducerVrfEvaluatorService
     + BlockProducerService
     + ExternalSnarkWorkerService
     + RpcService
+    + InvariantService
 {
     fn stats(&mut self) -> Option<&mut Stats>;
+    fn verifier_stats(&mut self) -> Option<SnarkVerifierStats>;
     fn recorder(&mut self) -> &mut Recorder;
 }
@@ -1,4 +1,4 @@
-use snark::work_verify::SnarkWorkVerifyAction;
+use snark::work_verify::{SnarkWorkVerifyAction, SnarkWorkVerifyError};
 
 use crate::{snark_pool::candidate::SnarkPoolCandidateAction, Service, Store};
 
@@ -14,11 +14,18 @@ pub fn snark_effects<S: Service>(store: &mut Store<S>, action: SnarkActionWithMe
         }
         SnarkAction::WorkVerify(a) => match a {
             // TODO(tizoc): handle this logic with the on_error callback passed on the Init action
-            SnarkWorkVerifyAction::Error { req_id, .. } => {
+            SnarkWorkVerifyAction::Error { req_id, error } => {
                 let req = store.state().snark.work_verify.jobs.get(req_id);
                 let Some(req) = req else { return };
                 let sender = req.sender().parse().unwrap();
 
+                if matches!(error, SnarkWorkVerifyError::Overloaded) {
+                    store.dispatch(SnarkPoolCandidateAction::WorkVerifyRetry {
+                        peer_id: sender,
+                        verify_id: req_id,
+                    });
+                    return;
+                }
                 store.dispatch(SnarkPoolCandidateAction::WorkVerifyError {
                     peer_id: sender,
                     verify_id: req_id,
@@ -44,6 +44,12 @@ pub enum SnarkPoolCandidateAction {
         peer_id: PeerId,
         verify_id: SnarkWorkVerifyId,
     },
+    /// Work wasn't verified because the verifier was overloaded, verify
+    /// it again later.
+    WorkVerifyRetry {
+        peer_id: PeerId,
+        verify_id: SnarkWorkVerifyId,
+    },
     WorkVerifySuccess {
         peer_id: PeerId,
         verify_id: SnarkWorkVerifyId,
@@ -121,6 +127,10 @@ impl redux::EnablingCondition<crate::State> for SnarkPoolCandidateAction {
                 // TODO(binier)
                 true
             }
+            SnarkPoolCandidateAction::WorkVerifyRetry { peer_id, verify_id } => state
+                .snark_pool
+                .candidates
+                .peer_work_pending_verify(peer_id, *verify_id),
             SnarkPoolCandidateAction::WorkVerifySuccess { .. } => {
                 // TODO(binier)
                 true
@@ -141,6 +141,9 @@ impl SnarkPoolCandidatesState {
                     reason: P2pDisconnectionReason::SnarkPoolVerifyError,
                 });
             }
+            SnarkPoolCandidateAction::WorkVerifyRetry { peer_id, verify_id } => {
+                state.verify_retry(meta.time(), peer_id, *verify_id);
+            }
             SnarkPoolCandidateAction::WorkVerifySuccess {
                 peer_id,
                 verify_id,
@@ -258,6 +258,37 @@ impl SnarkPoolCandidatesState {
         }
     }
 
+    pub fn peer_work_pending_verify(&self, peer_id: &PeerId, verify_id: SnarkWorkVerifyId) -> bool {
+        self.by_peer.get(peer_id).map_or(false, |peer_jobs| {
+            peer_jobs
+                .values()
+                .any(|job_state| job_state.pending_verify_id() == Some(verify_id))
+        })
+    }
+
+    /// Moves work pending verification with `verify_id` back to received,
+    /// so that it's picked for verification again.
+    pub fn verify_retry(
+        &mut self,
+        time: Timestamp,
+        peer_id: &PeerId,
+        verify_id: SnarkWorkVerifyId,
+    ) {
+        if let Some(peer_jobs) = self.by_peer.get_mut(peer_id) {
+            for job_state in peer_jobs
+                .values_mut()
+                .filter(|job_state| job_state.pending_verify_id() == Some(verify_id))
+            {
+                if let SnarkPoolCandidateState::WorkVerifyPending { work, .. } = job_state {
+                    *job_state = SnarkPoolCandidateState::WorkReceived {
+                        time,
+                        work: work.clone(),
+                    };
+                }
+            }
+        }
+    }
+
     pub fn peer_remove(&mut self, peer_id: PeerId) {
         if let Some(works) = self.by_peer.remove(&peer_id) {
             for job_id in works.into_keys() {
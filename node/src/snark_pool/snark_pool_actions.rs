@@ -5,6 +5,7 @@ use openmina_core::ActionEvent;
 use serde::{Deserialize, Serialize};
 
 use crate::p2p::PeerId;
+use crate::SnarkerStrategy;
 
 use super::candidate::SnarkPoolCandidateAction;
 use super::SnarkWork;
@@ -57,15 +58,25 @@ impl redux::EnablingCondition<crate::State> for SnarkPoolAction {
                 .map_or(false, |v| v.auto_commit),
             SnarkPoolAction::CommitmentCreateMany { .. } => state.config.snarker.is_some(),
             SnarkPoolAction::CommitmentCreate { job_id } => {
-                state.config.snarker.is_some() && state.snark_pool.should_create_commitment(job_id)
+                state.config.snarker.as_ref().map_or(false, |config| {
+                    state.snark_pool.should_create_commitment(job_id)
+                        || (matches!(config.strategy, SnarkerStrategy::Profitable)
+                            && state.snark_pool.can_undercut(job_id, &config.fee))
+                })
+            }
+            SnarkPoolAction::CommitmentAdd { commitment, sender } => {
+                let is_within_quota = sender == &state.p2p.my_id()
+                    || state
+                        .snark_pool
+                        .is_within_commitment_quota(sender, &commitment.job_id);
+                is_within_quota
+                    && state.snark_pool.get(&commitment.job_id).map_or(false, |s| {
+                        match s.commitment.as_ref() {
+                            Some(cur) => commitment > &cur.commitment,
+                            None => true,
+                        }
+                    })
             }
-            SnarkPoolAction::CommitmentAdd { commitment, .. } => state
-                .snark_pool
-                .get(&commitment.job_id)
-                .map_or(false, |s| match s.commitment.as_ref() {
-                    Some(cur) => commitment > &cur.commitment,
-                    None => true,
-                }),
             SnarkPoolAction::WorkAdd { snark, .. } => {
                 state
                     .snark_pool
@@ -1,4 +1,20 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SnarkPoolConfig {}
+pub struct SnarkPoolConfig {
+    /// Maximum number of snarks kept for jobs which are no longer in the
+    /// scan state. When exceeded, snarks with the highest fee get evicted.
+    pub max_stale_snarks: usize,
+    /// Maximum number of commitments a single peer can hold at a time.
+    /// Doesn't apply to our own commitments.
+    pub max_commitments_per_peer: usize,
+}
+
+impl Default for SnarkPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_stale_snarks: 1024,
+            max_commitments_per_peer: 128,
+        }
+    }
+}
@@ -5,7 +5,8 @@ use p2p::channels::{
     snark::P2pChannelsSnarkAction, snark_job_commitment::P2pChannelsSnarkJobCommitmentAction,
 };
 
-use crate::{snark_pool::JobCommitment, ExternalSnarkWorkerAction, SnarkerStrategy};
+use crate::snark_pool::{candidate::SnarkPoolCandidateAction, JobCommitment};
+use crate::{ExternalSnarkWorkerAction, SnarkerStrategy};
 
 use super::{
     JobState, SnarkPoolAction, SnarkPoolActionWithMetaRef, SnarkPoolEffectfulAction,
@@ -42,15 +43,17 @@ impl SnarkPoolState {
 
                 state.retain(|id| jobs_map.remove(id).map(|(order, _)| order));
                 for (id, (order, job)) in jobs_map {
+                    let snark = state.stale_snark_take(&id);
                     state.insert(JobState {
                         time: meta.time(),
                         id,
                         job,
                         commitment: None,
-                        snark: None,
+                        snark,
                         order,
                     });
                 }
+                state.stale_snarks_evict();
 
                 let orphaned_snarks = orphaned_snarks
                     .iter()
@@ -70,9 +73,32 @@ impl SnarkPoolState {
                 }
 
                 state.candidates_prune();
+                let restored_snarks = state.restored_snarks_take();
 
                 // Dispatch
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
+                // Restored snarks go through the same verification as
+                // the ones received from peers.
+                let my_id = global_state.p2p.my_id();
+                let (restored_snarks, dropped_snarks): (Vec<_>, Vec<_>) = restored_snarks
+                    .into_iter()
+                    .partition(|(job_id, _)| global_state.snark_pool.contains(job_id));
+                if !dropped_snarks.is_empty() {
+                    openmina_core::log::info!(
+                        meta.time();
+                        kind = "SnarkPoolRestoredSnarksDropped",
+                        summary = format!(
+                            "dropped {} restored snark(s) for jobs no longer in the scan state",
+                            dropped_snarks.len()
+                        )
+                    );
+                }
+                for (_, work) in restored_snarks {
+                    dispatcher.push(SnarkPoolCandidateAction::WorkReceived {
+                        peer_id: my_id,
+                        work,
+                    });
+                }
                 if let Some(job_id) = global_state.external_snark_worker.working_job_id() {
                     if !global_state.snark_pool.contains(job_id) {
                         // job is no longer needed.
@@ -94,6 +120,20 @@ impl SnarkPoolState {
                         .snark_pool
                         .available_jobs_with_highest_priority(available_workers);
                     match snarker_config.strategy {
+                        SnarkerStrategy::Profitable => {
+                            let worker = &global_state.external_snark_worker;
+                            let job_ids = global_state
+                                .snark_pool
+                                .profitable_jobs(
+                                    &snarker_config.fee,
+                                    |summary| worker.estimated_duration(summary),
+                                    available_workers,
+                                )
+                                .into_iter()
+                                .map(|job| job.id.clone())
+                                .collect();
+                            dispatcher.push(SnarkPoolAction::CommitmentCreateMany { job_ids });
+                        }
                         SnarkerStrategy::Sequential => {
                             let job_ids = jobs
                                 .into_iter()
@@ -1,7 +1,9 @@
+use std::cmp::Reverse;
 use std::time::Duration;
 use std::{collections::BTreeMap, fmt, ops::RangeBounds};
 
 use ledger::scan_state::scan_state::{transaction_snark::OneOrTwo, AvailableJobMessage};
+use mina_p2p_messages::v2::CurrencyFeeStableV1;
 use openmina_core::snark::{Snark, SnarkInfo, SnarkJobCommitment, SnarkJobId};
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
@@ -17,6 +19,12 @@ pub struct SnarkPoolState {
     counter: u64,
     list: BTreeMap<u64, JobState>,
     by_ledger_hash_index: BTreeMap<SnarkJobId, u64>,
+    /// Verified snarks for jobs which got removed from the scan state.
+    /// Reused if the job shows up again.
+    stale_snarks: BTreeMap<SnarkJobId, SnarkWork>,
+    /// Snarks restored from disk, not yet verified. Consumed on the
+    /// next jobs update.
+    restored_snarks: BTreeMap<SnarkJobId, Snark>,
     pub candidates: SnarkPoolCandidatesState,
     pub(super) last_check_timeouts: Timestamp,
 }
@@ -55,17 +63,19 @@ pub enum JobSummary {
 
 impl Default for SnarkPoolState {
     fn default() -> Self {
-        Self::new()
+        Self::new(SnarkPoolConfig::default())
     }
 }
 
 impl SnarkPoolState {
-    pub fn new() -> Self {
+    pub fn new(config: SnarkPoolConfig) -> Self {
         Self {
-            config: SnarkPoolConfig {},
+            config,
             counter: 0,
             list: Default::default(),
             by_ledger_hash_index: Default::default(),
+            stale_snarks: Default::default(),
+            restored_snarks: Default::default(),
             candidates: SnarkPoolCandidatesState::new(),
             last_check_timeouts: Timestamp::ZERO,
         }
@@ -111,15 +121,21 @@ impl SnarkPoolState {
         self.list.get_mut(index)?.commitment.take()
     }
 
+    /// Retain jobs for which `get_new_job_order` returns new order.
+    ///
+    /// Snarks of removed jobs are moved to the stale snarks.
     pub fn retain<F>(&mut self, mut get_new_job_order: F)
     where
         F: FnMut(&SnarkJobId) -> Option<usize>,
     {
         let list = &mut self.list;
+        let stale_snarks = &mut self.stale_snarks;
         self.by_ledger_hash_index
             .retain(|id, index| match get_new_job_order(id) {
                 None => {
-                    list.remove(index);
+                    if let Some(snark) = list.remove(index).and_then(|job| job.snark) {
+                        stale_snarks.insert(id.clone(), snark);
+                    }
                     false
                 }
                 Some(order) => {
@@ -133,6 +149,91 @@ impl SnarkPoolState {
             });
     }
 
+    /// Evict stale snarks with the highest fee until we are within
+    /// [`SnarkPoolConfig::max_stale_snarks`] limit.
+    pub fn stale_snarks_evict(&mut self) {
+        let Some(n) = self
+            .stale_snarks
+            .len()
+            .checked_sub(self.config.max_stale_snarks)
+            .filter(|n| *n > 0)
+        else {
+            return;
+        };
+        let mut snarks = self
+            .stale_snarks
+            .iter()
+            .map(|(id, snark)| (snark.work.fee.0.as_u64(), id))
+            .collect::<Vec<_>>();
+        snarks.sort_by_key(|(fee, _)| Reverse(*fee));
+        let evicted = snarks
+            .into_iter()
+            .take(n)
+            .map(|(_, id)| id.clone())
+            .collect::<Vec<_>>();
+        for id in evicted {
+            self.stale_snarks.remove(&id);
+        }
+    }
+
+    pub fn stale_snark_take(&mut self, id: &SnarkJobId) -> Option<SnarkWork> {
+        self.stale_snarks.remove(id)
+    }
+
+    /// Add snarks, restored from disk, which will be verified once
+    /// their jobs are in the pool.
+    pub fn restored_snarks_add(&mut self, snarks: impl IntoIterator<Item = Snark>) {
+        for snark in snarks {
+            let id = snark.job_id();
+            let take = self
+                .restored_snarks
+                .get(&id)
+                .map_or(true, |cur| &snark > cur);
+            if take {
+                self.restored_snarks.insert(id, snark);
+            }
+        }
+    }
+
+    pub fn restored_snarks_take(&mut self) -> BTreeMap<SnarkJobId, Snark> {
+        std::mem::take(&mut self.restored_snarks)
+    }
+
+    /// Verified snarks, both for the jobs in the scan state and stale
+    /// ones, with the cheapest ones first.
+    ///
+    /// Limited to [`SnarkPoolConfig::max_stale_snarks`] stale snarks, so
+    /// that what's persisted fits within the limits when restored.
+    pub fn verified_snarks_to_persist(&self) -> Vec<&Snark> {
+        let mut stale = self
+            .stale_snarks
+            .values()
+            .map(|snark| &snark.work)
+            .collect::<Vec<_>>();
+        stale.sort_by_key(|snark| snark.fee.0.as_u64());
+        stale.truncate(self.config.max_stale_snarks);
+        self.completed_snarks_iter().chain(stale).collect()
+    }
+
+    /// Number of commitments currently held by the peer.
+    pub fn peer_commitments_count(&self, peer_id: &PeerId) -> usize {
+        self.jobs_iter()
+            .filter_map(|job| job.commitment.as_ref())
+            .filter(|commitment| &commitment.sender == peer_id)
+            .count()
+    }
+
+    /// Whether the peer can add a commitment for the job without
+    /// exceeding [`SnarkPoolConfig::max_commitments_per_peer`] quota.
+    /// Replacing peer's own commitment is always allowed.
+    pub fn is_within_commitment_quota(&self, peer_id: &PeerId, job_id: &SnarkJobId) -> bool {
+        let replaces_own = self
+            .get(job_id)
+            .and_then(|job| job.commitment.as_ref())
+            .map_or(false, |commitment| &commitment.sender == peer_id);
+        replaces_own || self.peer_commitments_count(peer_id) < self.config.max_commitments_per_peer
+    }
+
     pub fn range<R>(&self, range: R) -> impl '_ + DoubleEndedIterator<Item = (u64, &'_ JobState)>
     where
         R: RangeBounds<u64>,
@@ -144,6 +245,12 @@ impl SnarkPoolState {
         self.get(job_id).map_or(false, |s| s.is_available())
     }
 
+    /// Whether our work with the given `fee` would be preferred over the
+    /// existing commitment and snark for the job.
+    pub fn can_undercut(&self, job_id: &SnarkJobId, fee: &CurrencyFeeStableV1) -> bool {
+        self.get(job_id).map_or(false, |s| s.can_undercut(fee))
+    }
+
     pub fn is_commitment_timed_out(&self, id: &SnarkJobId, time_now: Timestamp) -> bool {
         self.by_ledger_hash_index.get(id).map_or(false, |i| {
             self.is_commitment_timed_out_by_index(i, time_now)
@@ -200,6 +307,36 @@ impl SnarkPoolState {
             })
     }
 
+    /// Find up to `n` jobs that are the most profitable for us to work on.
+    ///
+    /// Jobs with existing commitment or snark are only considered if we
+    /// can undercut the fee and are expected to deliver before our own
+    /// commitment would time out. Uncontested jobs come first, then
+    /// older ones, since finishing them unblocks the scan state sooner.
+    pub fn profitable_jobs<F>(
+        &self,
+        fee: &CurrencyFeeStableV1,
+        mut estimated_duration: F,
+        n: usize,
+    ) -> Vec<&JobState>
+    where
+        F: FnMut(&JobSummary) -> Duration,
+    {
+        let mut jobs = self
+            .jobs_iter()
+            .filter_map(|job| {
+                if job.is_available() {
+                    return Some(((false, job.order), job));
+                }
+                let summary = job.summary();
+                let in_time = estimated_duration(&summary) <= summary.estimated_duration();
+                (in_time && job.can_undercut(fee)).then_some(((true, job.order), job))
+            })
+            .collect::<Vec<_>>();
+        jobs.sort_by_key(|(key, _)| *key);
+        jobs.into_iter().take(n).map(|(_, job)| job).collect()
+    }
+
     pub fn completed_snarks_iter(&self) -> impl '_ + Iterator<Item = &'_ Snark> {
         self.list
             .iter()
@@ -230,6 +367,8 @@ impl fmt::Debug for SnarkPoolState {
         f.debug_struct("JobCommitments")
             .field("counter", &self.counter)
             .field("len", &self.list.len())
+            .field("stale_snarks_len", &self.stale_snarks.len())
+            .field("restored_snarks_len", &self.restored_snarks.len())
             .finish()
     }
 }
@@ -239,6 +378,21 @@ impl JobState {
         self.commitment.is_none() && self.snark.is_none()
     }
 
+    /// Whether our work with the given `fee` would have lower fee than
+    /// both existing commitment and snark for this job.
+    pub fn can_undercut(&self, fee: &CurrencyFeeStableV1) -> bool {
+        let fee = fee.0.as_u64();
+        let commitment_fee = self
+            .commitment
+            .as_ref()
+            .map(|v| v.commitment.fee.0.as_u64());
+        let snark_fee = self.snark.as_ref().map(|v| v.work.fee.0.as_u64());
+        [commitment_fee, snark_fee]
+            .into_iter()
+            .flatten()
+            .all(|existing| fee < existing)
+    }
+
     pub fn commitment_msg(&self) -> Option<&SnarkJobCommitment> {
         self.commitment.as_ref().map(|v| &v.commitment)
     }
@@ -301,6 +455,8 @@ mod ser {
         config: SnarkPoolConfig,
         counter: u64,
         list: BTreeMap<u64, JobState>,
+        stale_snarks: BTreeMap<SnarkJobId, SnarkWork>,
+        restored_snarks: BTreeMap<SnarkJobId, Snark>,
         candidates: SnarkPoolCandidatesState,
         last_check_timeouts: Timestamp,
     }
@@ -310,10 +466,12 @@ mod ser {
         where
             S: serde::Serializer,
         {
-            let mut s = serializer.serialize_struct("SnarkPool", 5)?;
+            let mut s = serializer.serialize_struct("SnarkPool", 7)?;
             s.serialize_field("config", &self.config)?;
             s.serialize_field("counter", &self.counter)?;
             s.serialize_field("list", &self.list)?;
+            s.serialize_field("stale_snarks", &self.stale_snarks)?;
+            s.serialize_field("restored_snarks", &self.restored_snarks)?;
             s.serialize_field("candidates", &self.candidates)?;
             s.serialize_field("last_check_timeouts", &self.last_check_timeouts)?;
             s.end()
@@ -331,9 +489,208 @@ mod ser {
                 counter: v.counter,
                 list: v.list,
                 by_ledger_hash_index,
+                stale_snarks: v.stale_snarks,
+                restored_snarks: v.restored_snarks,
                 candidates: v.candidates,
                 last_check_timeouts: v.last_check_timeouts,
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mina_hasher::Fp;
+    use mina_p2p_messages::v2::{self, LedgerHash};
+    use openmina_core::block::best_tip_fixture;
+
+    use super::*;
+
+    fn snark(fee: u64) -> Snark {
+        let block = best_tip_fixture();
+        let mut snark = Snark::from(block.completed_works_iter().next().unwrap().clone());
+        snark.fee = v2::CurrencyFeeStableV1(fee.into());
+        snark
+    }
+
+    fn job_id(i: u64) -> SnarkJobId {
+        let hash = LedgerHash::from_fp(Fp::from(i));
+        format!("{hash}_{hash}-{hash}_{hash}").parse().unwrap()
+    }
+
+    fn job(id: u64, order: usize, commitment_fee: Option<u64>, snark_fee: Option<u64>) -> JobState {
+        let work = snark(0);
+        let merge_job = |proof: &v2::LedgerProofProdStableV2| {
+            v2::TransactionSnarkScanStateLedgerProofWithSokMessageStableV2(
+                proof.clone(),
+                v2::MinaBaseSokMessageStableV1 {
+                    fee: work.fee.clone(),
+                    prover: work.snarker.clone(),
+                },
+            )
+        };
+        let (left, right) = match &*work.proofs {
+            v2::TransactionSnarkWorkTStableV2Proofs::One(v) => (merge_job(v), merge_job(v)),
+            v2::TransactionSnarkWorkTStableV2Proofs::Two((v1, v2)) => {
+                (merge_job(v1), merge_job(v2))
+            }
+        };
+        let sender = PeerId::from_bytes([0; 32]);
+        JobState {
+            time: Timestamp::ZERO,
+            id: job_id(id),
+            job: OneOrTwo::One(AvailableJobMessage::Merge { left, right }),
+            commitment: commitment_fee.map(|fee| JobCommitment {
+                commitment: SnarkJobCommitment::new(
+                    0,
+                    job_id(id),
+                    v2::CurrencyFeeStableV1(fee.into()),
+                    work.snarker.clone(),
+                ),
+                received_t: Timestamp::ZERO,
+                sender,
+            }),
+            snark: snark_fee.map(|fee| SnarkWork {
+                work: snark(fee),
+                received_t: Timestamp::ZERO,
+                sender,
+            }),
+            order,
+        }
+    }
+
+    fn profitable_job_ids(
+        pool: &SnarkPoolState,
+        fee: u64,
+        estimated_duration: Duration,
+        n: usize,
+    ) -> Vec<SnarkJobId> {
+        pool.profitable_jobs(
+            &v2::CurrencyFeeStableV1(fee.into()),
+            |_| estimated_duration,
+            n,
+        )
+        .into_iter()
+        .map(|job| job.id.clone())
+        .collect()
+    }
+
+    fn pool_with_stale_snarks(max_stale_snarks: usize, fees: &[u64]) -> SnarkPoolState {
+        let mut pool = SnarkPoolState::new(SnarkPoolConfig {
+            max_stale_snarks,
+            ..Default::default()
+        });
+        for (i, fee) in fees.iter().enumerate() {
+            let work = SnarkWork {
+                work: snark(*fee),
+                received_t: Timestamp::ZERO,
+                sender: PeerId::from_bytes([0; 32]),
+            };
+            pool.stale_snarks.insert(job_id(i as u64), work);
+        }
+        pool
+    }
+
+    fn stale_fees(pool: &SnarkPoolState) -> Vec<u64> {
+        let mut fees = pool
+            .stale_snarks
+            .values()
+            .map(|snark| snark.work.fee.0.as_u64())
+            .collect::<Vec<_>>();
+        fees.sort();
+        fees
+    }
+
+    #[test]
+    fn can_undercut_only_lower_fee() {
+        let fee = |fee: u64| v2::CurrencyFeeStableV1(fee.into());
+        assert!(job(0, 0, None, None).can_undercut(&fee(10)));
+        assert!(job(0, 0, Some(11), None).can_undercut(&fee(10)));
+        assert!(!job(0, 0, Some(10), None).can_undercut(&fee(10)));
+        assert!(!job(0, 0, None, Some(10)).can_undercut(&fee(10)));
+        assert!(!job(0, 0, Some(20), Some(5)).can_undercut(&fee(10)));
+    }
+
+    #[test]
+    fn profitable_jobs_uncontested_first_then_by_order() {
+        let mut pool = SnarkPoolState::new(Default::default());
+        pool.insert(job(0, 3, None, None));
+        pool.insert(job(1, 0, Some(10), None));
+        pool.insert(job(2, 1, None, Some(3)));
+        pool.insert(job(3, 2, Some(20), Some(4)));
+        pool.insert(job(4, 1, None, None));
+        pool.insert(job(5, 2, None, Some(6)));
+
+        // jobs 2 and 3 have cheaper snarks than ours.
+        assert_eq!(
+            profitable_job_ids(&pool, 5, Duration::ZERO, 10),
+            vec![job_id(4), job_id(0), job_id(1), job_id(5)]
+        );
+        assert_eq!(
+            profitable_job_ids(&pool, 5, Duration::ZERO, 2),
+            vec![job_id(4), job_id(0)]
+        );
+    }
+
+    #[test]
+    fn profitable_jobs_estimate_only_filters_contested() {
+        let mut pool = SnarkPoolState::new(Default::default());
+        pool.insert(job(0, 2, None, None));
+        pool.insert(job(1, 0, Some(10), None));
+        pool.insert(job(2, 1, None, None));
+
+        let job_duration = pool.get(&job_id(1)).unwrap().estimated_duration();
+        assert_eq!(
+            profitable_job_ids(&pool, 5, job_duration, 10),
+            vec![job_id(2), job_id(0), job_id(1)]
+        );
+        // too slow to deliver before the commitment would time out, but
+        // uncontested jobs keep their order.
+        assert_eq!(
+            profitable_job_ids(&pool, 5, job_duration + Duration::from_secs(1), 10),
+            vec![job_id(2), job_id(0)]
+        );
+    }
+
+    #[test]
+    fn stale_snarks_evict_highest_fee_first() {
+        let mut pool = pool_with_stale_snarks(2, &[30, 10, 40, 20]);
+        pool.stale_snarks_evict();
+        assert_eq!(stale_fees(&pool), vec![10, 20]);
+
+        // within the limit, nothing to evict.
+        pool.stale_snarks_evict();
+        assert_eq!(stale_fees(&pool), vec![10, 20]);
+    }
+
+    #[test]
+    fn stale_snark_take_reuses_snark() {
+        let mut pool = pool_with_stale_snarks(2, &[10, 20]);
+        let snark = pool.stale_snark_take(&job_id(1)).unwrap();
+        assert_eq!(snark.work.fee.0.as_u64(), 20);
+        assert!(pool.stale_snark_take(&job_id(1)).is_none());
+        assert_eq!(stale_fees(&pool), vec![10]);
+    }
+
+    #[test]
+    fn verified_snarks_to_persist_cheapest_stale_within_limit() {
+        let mut pool = pool_with_stale_snarks(3, &[30, 10, 20]);
+        pool.config.max_stale_snarks = 2;
+        let fees = pool
+            .verified_snarks_to_persist()
+            .into_iter()
+            .map(|snark| snark.fee.0.as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(fees, vec![10, 20]);
+    }
+
+    #[test]
+    fn restored_snarks_add_keeps_cheapest_per_job() {
+        let mut pool = SnarkPoolState::new(Default::default());
+        pool.restored_snarks_add([snark(20), snark(10), snark(30)]);
+        let restored = pool.restored_snarks_take();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.values().next().unwrap().fee.0.as_u64(), 10);
+        assert!(pool.restored_snarks_take().is_empty());
+    }
+}
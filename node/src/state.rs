@@ -184,7 +184,7 @@ impl State {
         Self {
             p2p: P2p::Pending(config.p2p),
             ledger: LedgerState::new(config.ledger),
-            snark_pool: SnarkPoolState::new(),
+            snark_pool: SnarkPoolState::new(config.snark_pool),
             snark: SnarkState::new(config.snark),
             consensus: ConsensusState::new(),
             transition_frontier: TransitionFrontierState::new(config.transition_frontier),
@@ -233,6 +233,14 @@ impl State {
         Some(best_tip.global_slot() + (slots as u32))
     }
 
+    /// Whether the current slot is past `slot_tx_end`, so new commands
+    /// must be rejected.
+    pub fn is_slot_tx_end_reached(&self) -> bool {
+        self.cur_global_slot().map_or(false, |slot| {
+            self.transition_frontier.config.is_slot_tx_end_reached(slot)
+        })
+    }
+
     pub fn current_epoch(&self) -> Option<u32> {
         // TODO: Should not be hardcoded
         const SLOTS_PER_EPOCH: u32 = 7140;
@@ -1,5 +1,8 @@
 use std::collections::VecDeque;
 
+use ledger::scan_state::currency::Fee;
+use ledger::staged_ledger::diff::AtMostTwo;
+use ledger::staged_ledger::diff_creation_log::{self, Partition, Reason};
 use ledger::AccountIndex;
 use mina_p2p_messages::v2;
 use serde::{Deserialize, Serialize};
@@ -23,6 +26,12 @@ pub struct BlockProductionAttempt {
     pub times: BlockProductionTimes,
     #[serde(flatten)]
     pub status: BlockProductionStatus,
+    /// Comparison against the canonical block for the same slot.
+    ///
+    /// Only set for blocks produced in shadow mode.
+    pub shadow_comparison: Option<ShadowBlockComparison>,
+    /// How the staged ledger diff was filled. Set once the diff is created.
+    pub diff_creation: Option<StagedLedgerDiffCreation>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -69,6 +78,15 @@ pub enum BlockProductionStatus {
     Discarded {
         discard_reason: BlockProducerWonSlotDiscardReason,
     },
+    /// Produced in shadow mode, but proof creation failed.
+    ShadowProofCreateError {
+        error: String,
+    },
+    /// Produced in shadow mode for a slot that wasn't actually won,
+    /// so the proof can't be created and a dummy one is used.
+    ShadowProofCreateSkipped,
+    /// Produced in shadow mode. Block won't be injected.
+    Shadowed,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -89,6 +107,98 @@ pub struct ProducedBlockTransactions {
     pub zkapps: u16,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreation {
+    /// User commands left out of the block because not enough snark
+    /// work was available to include them.
+    pub commands_dropped_no_work: u64,
+    /// User commands left out of the block because the scan state had
+    /// no space left for them.
+    pub commands_dropped_no_space: u64,
+    /// Logs of the scan state partitions filled by the diff.
+    pub logs: Vec<StagedLedgerDiffCreationLog>,
+}
+
+/// Staged ledger diff creation log of one scan state partition.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreationLog {
+    pub summary: DiffCreationSummary,
+    /// Resources remaining after each step of diff creation.
+    pub detail: Vec<DiffCreationDetail>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiffCreationSummary {
+    pub partition: Partition,
+    pub start_resources: DiffCreationResources,
+    pub available_slots: u64,
+    pub required_work_count: u64,
+    pub discarded_commands: DiffCreationDiscardedCommands,
+    pub discarded_completed_work: DiffCreationDiscardedCompletedWork,
+    pub end_resources: DiffCreationResources,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiffCreationResources {
+    pub completed_work: DiffCreationCountAndFees,
+    pub commands: DiffCreationCountAndFees,
+    pub coinbase_work_fees: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct DiffCreationCountAndFees {
+    pub count: u64,
+    pub fees: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct DiffCreationDiscardedCommands {
+    pub insufficient_work: u64,
+    pub insufficient_space: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct DiffCreationDiscardedCompletedWork {
+    pub insufficient_fees: u64,
+    pub extra_work: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiffCreationDetail {
+    pub reason: Reason,
+    pub commands: DiffCreationCountAndFees,
+    pub completed_work: DiffCreationCountAndFees,
+    pub coinbase_work_fees: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShadowBlockComparison {
+    /// Canonical block for the same slot. `None` if no block was
+    /// included in the canonical chain for that slot.
+    pub canonical: Option<ShadowCanonicalBlock>,
+    /// Difference between the shadow block and the canonical one
+    /// (`shadow - canonical`).
+    pub diff: Option<ShadowBlockDiff>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShadowCanonicalBlock {
+    pub producer: v2::NonZeroCurvePoint,
+    #[serde(flatten)]
+    pub block: ProducedBlock,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ShadowBlockDiff {
+    pub payments: i32,
+    pub delegations: i32,
+    pub zkapps: i32,
+    pub completed_works_count: i64,
+    pub coinbase: i64,
+    pub fees: i64,
+    pub snark_fees: i64,
+}
+
 impl BlockProducerStats {
     fn latest_attempt_block_hash_matches(&self, hash: &BlockHash) -> bool {
         self.attempts
@@ -116,6 +226,10 @@ impl BlockProducerStats {
             .rev()
             .take_while(|v| v.won_slot.global_slot >= root_block.global_slot())
             .for_each(|attempt| {
+                if attempt.status.is_shadow() {
+                    attempt.compare_with_canonical(best_tip, chain);
+                    return;
+                }
                 let Some(block) = attempt.block.as_ref() else {
                     return;
                 };
@@ -185,6 +299,8 @@ impl BlockProducerStats {
                 discarded: None,
             },
             status: BlockProductionStatus::Scheduled,
+            shadow_comparison: None,
+            diff_creation: None,
         });
     }
 
@@ -202,13 +318,19 @@ impl BlockProducerStats {
         );
     }
 
-    pub fn staged_ledger_diff_create_end(&mut self, time: redux::Timestamp) {
+    pub fn staged_ledger_diff_create_end(
+        &mut self,
+        time: redux::Timestamp,
+        diff_creation_logs: &[StagedLedgerDiffCreationLog],
+    ) {
         self.update(
             "staged_ledger_diff_create_end",
             move |attempt| match attempt.status {
                 BlockProductionStatus::StagedLedgerDiffCreatePending => {
                     attempt.status = BlockProductionStatus::StagedLedgerDiffCreateSuccess;
                     attempt.times.staged_ledger_diff_create_end = Some(time);
+                    attempt.diff_creation =
+                        Some(StagedLedgerDiffCreation::new(diff_creation_logs.to_vec()));
                     true
                 }
                 _ => false,
@@ -255,6 +377,47 @@ impl BlockProducerStats {
         });
     }
 
+    pub fn shadow_proof_create_error(&mut self, time: redux::Timestamp, error: String) {
+        self.update("shadow_proof_create_error", move |attempt| {
+            match attempt.status {
+                BlockProductionStatus::ProofCreatePending => {
+                    attempt.status = BlockProductionStatus::ShadowProofCreateError { error };
+                    attempt.times.proof_create_end = Some(time);
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+
+    pub fn shadow_proof_create_skipped(&mut self) {
+        self.update(
+            "shadow_proof_create_skipped",
+            move |attempt| match attempt.status {
+                BlockProductionStatus::Produced => {
+                    attempt.status = BlockProductionStatus::ShadowProofCreateSkipped;
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn shadowed(&mut self, hash: &BlockHash) {
+        if !self.latest_attempt_block_hash_matches(hash) {
+            return;
+        }
+
+        self.update("shadowed", move |attempt| match attempt.status {
+            BlockProductionStatus::ProofCreateSuccess
+            | BlockProductionStatus::ShadowProofCreateSkipped => {
+                attempt.status = BlockProductionStatus::Shadowed;
+                true
+            }
+            _ => false,
+        });
+    }
+
     pub fn block_apply_start(&mut self, time: redux::Timestamp, hash: &BlockHash) {
         let is_our_block = self
             .attempts
@@ -316,6 +479,139 @@ impl BlockProducerStats {
     }
 }
 
+impl BlockProductionStatus {
+    pub fn is_shadow(&self) -> bool {
+        matches!(
+            self,
+            Self::Shadowed | Self::ShadowProofCreateSkipped | Self::ShadowProofCreateError { .. }
+        )
+    }
+}
+
+impl BlockProductionAttempt {
+    /// Compare the block produced in shadow mode against the canonical
+    /// block for the same slot, once the canonical chain has moved past it.
+    fn compare_with_canonical<T: AsRef<Block>>(
+        &mut self,
+        best_tip: &BlockWithHash<T>,
+        chain: &[BlockWithHash<T>],
+    ) {
+        let slot = self.won_slot.global_slot;
+        if self.shadow_comparison.is_some() || best_tip.global_slot() < slot {
+            return;
+        }
+        let Some(block) = self.block.as_ref() else {
+            return;
+        };
+
+        let canonical = std::iter::once(best_tip)
+            .chain(chain.iter().rev())
+            .find(|b| b.global_slot() == slot)
+            .map(|b| ShadowCanonicalBlock {
+                producer: b.producer().clone(),
+                block: ProducedBlock::new(b.hash(), b.height(), b.body()),
+            });
+        let diff = canonical
+            .as_ref()
+            .map(|canonical| ShadowBlockDiff::new(block, &canonical.block));
+
+        self.shadow_comparison = Some(ShadowBlockComparison { canonical, diff });
+    }
+}
+
+impl ShadowBlockDiff {
+    fn new(shadow: &ProducedBlock, canonical: &ProducedBlock) -> Self {
+        let (s, c) = (&shadow.transactions, &canonical.transactions);
+        Self {
+            payments: s.payments as i32 - c.payments as i32,
+            delegations: s.delegations as i32 - c.delegations as i32,
+            zkapps: s.zkapps as i32 - c.zkapps as i32,
+            completed_works_count: shadow.completed_works_count as i64
+                - canonical.completed_works_count as i64,
+            coinbase: shadow.coinbase as i64 - canonical.coinbase as i64,
+            fees: shadow.fees as i64 - canonical.fees as i64,
+            snark_fees: shadow.snark_fees as i64 - canonical.snark_fees as i64,
+        }
+    }
+}
+
+impl StagedLedgerDiffCreation {
+    fn new(logs: Vec<StagedLedgerDiffCreationLog>) -> Self {
+        // Commands discarded from the first partition are retried in the
+        // second one, so the last log has the commands finally dropped.
+        let dropped = logs
+            .last()
+            .map(|log| log.summary.discarded_commands)
+            .unwrap_or_default();
+        Self {
+            commands_dropped_no_work: dropped.insufficient_work,
+            commands_dropped_no_space: dropped.insufficient_space,
+            logs,
+        }
+    }
+}
+
+impl From<&diff_creation_log::DiffCreationLog> for StagedLedgerDiffCreationLog {
+    fn from(log: &diff_creation_log::DiffCreationLog) -> Self {
+        let summary = &log.summary;
+        Self {
+            summary: DiffCreationSummary {
+                partition: summary.partition,
+                start_resources: (&summary.start_resources).into(),
+                available_slots: summary.available_slots,
+                required_work_count: summary.required_work_count,
+                discarded_commands: DiffCreationDiscardedCommands {
+                    insufficient_work: summary.discarded_commands.insufficient_work,
+                    insufficient_space: summary.discarded_commands.insufficient_space,
+                },
+                discarded_completed_work: DiffCreationDiscardedCompletedWork {
+                    insufficient_fees: summary.discarded_completed_work.insufficient_fees,
+                    extra_work: summary.discarded_completed_work.extra_work,
+                },
+                end_resources: (&summary.end_resources).into(),
+            },
+            detail: log
+                .detail
+                .0
+                .iter()
+                .map(|line| DiffCreationDetail {
+                    reason: line.reason,
+                    commands: line.commands.into(),
+                    completed_work: line.completed_work.into(),
+                    coinbase_work_fees: coinbase_work_fees(&line.coinbase),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&diff_creation_log::summary::Resources> for DiffCreationResources {
+    fn from(resources: &diff_creation_log::summary::Resources) -> Self {
+        Self {
+            completed_work: resources.completed_work.into(),
+            commands: resources.commands.into(),
+            coinbase_work_fees: coinbase_work_fees(&resources.coinbase_work_fees),
+        }
+    }
+}
+
+impl From<diff_creation_log::CountAndFee> for DiffCreationCountAndFees {
+    fn from((count, fees): diff_creation_log::CountAndFee) -> Self {
+        Self {
+            count,
+            fees: fees.as_u64(),
+        }
+    }
+}
+
+fn coinbase_work_fees(fees: &AtMostTwo<Fee>) -> Vec<u64> {
+    match fees {
+        AtMostTwo::Zero | AtMostTwo::One(None) | AtMostTwo::Two(None) => vec![],
+        AtMostTwo::One(Some(fee)) | AtMostTwo::Two(Some((fee, None))) => vec![fee.as_u64()],
+        AtMostTwo::Two(Some((fee1, Some(fee2)))) => vec![fee1.as_u64(), fee2.as_u64()],
+    }
+}
+
 impl From<&BlockProducerWonSlot> for BlockProductionAttemptWonSlot {
     fn from(won_slot: &BlockProducerWonSlot) -> Self {
         Self {
@@ -328,43 +624,45 @@ impl From<&BlockProducerWonSlot> for BlockProductionAttemptWonSlot {
     }
 }
 
-impl From<(&BlockHash, &BlockWithoutProof)> for ProducedBlock {
-    fn from((block_hash, block): (&BlockHash, &BlockWithoutProof)) -> Self {
+impl ProducedBlock {
+    fn new(hash: &BlockHash, height: u32, body: &v2::StagedLedgerDiffBodyStableV1) -> Self {
         Self {
-            hash: block_hash.clone(),
-            height: block
-                .protocol_state
-                .body
-                .consensus_state
-                .blockchain_length
-                .as_u32(),
-            transactions: block.into(),
-            completed_works_count: block.body.completed_works_count(),
-            coinbase: block.body.coinbase_sum(),
-            fees: block.body.fees_sum(),
-            snark_fees: block.body.snark_fees_sum(),
+            hash: hash.clone(),
+            height,
+            transactions: body.into(),
+            completed_works_count: body.completed_works_count(),
+            coinbase: body.coinbase_sum(),
+            fees: body.fees_sum(),
+            snark_fees: body.snark_fees_sum(),
         }
     }
 }
 
-impl From<&BlockWithoutProof> for ProducedBlockTransactions {
-    fn from(block: &BlockWithoutProof) -> Self {
-        block
+impl From<(&BlockHash, &BlockWithoutProof)> for ProducedBlock {
+    fn from((block_hash, block): (&BlockHash, &BlockWithoutProof)) -> Self {
+        let height = block
+            .protocol_state
             .body
-            .commands_iter()
-            .fold(Self::default(), |mut res, cmd| {
-                match &cmd.data {
-                    v2::MinaBaseUserCommandStableV2::SignedCommand(v) => match &v.payload.body {
-                        v2::MinaBaseSignedCommandPayloadBodyStableV2::Payment(_) => {
-                            res.payments += 1
-                        }
-                        v2::MinaBaseSignedCommandPayloadBodyStableV2::StakeDelegation(_) => {
-                            res.delegations += 1
-                        }
-                    },
-                    v2::MinaBaseUserCommandStableV2::ZkappCommand(_) => res.zkapps += 1,
-                }
-                res
-            })
+            .consensus_state
+            .blockchain_length
+            .as_u32();
+        Self::new(block_hash, height, &block.body)
+    }
+}
+
+impl From<&v2::StagedLedgerDiffBodyStableV1> for ProducedBlockTransactions {
+    fn from(body: &v2::StagedLedgerDiffBodyStableV1) -> Self {
+        body.commands_iter().fold(Self::default(), |mut res, cmd| {
+            match &cmd.data {
+                v2::MinaBaseUserCommandStableV2::SignedCommand(v) => match &v.payload.body {
+                    v2::MinaBaseSignedCommandPayloadBodyStableV2::Payment(_) => res.payments += 1,
+                    v2::MinaBaseSignedCommandPayloadBodyStableV2::StakeDelegation(_) => {
+                        res.delegations += 1
+                    }
+                },
+                v2::MinaBaseUserCommandStableV2::ZkappCommand(_) => res.zkapps += 1,
+            }
+            res
+        })
     }
 }
@@ -1,7 +1,7 @@
 use ledger::{
     scan_state::{
         currency::{Amount, Nonce},
-        transaction_logic::{verifiable, UserCommand, WithStatus},
+        transaction_logic::{valid, verifiable, UserCommand, WithStatus},
     },
     transaction_pool::{
         diff::{self, DiffVerified},
@@ -15,7 +15,12 @@ use openmina_core::{
 };
 use p2p::channels::transaction::P2pChannelsTransactionAction;
 use redux::callback;
-use snark::{user_command_verify::SnarkUserCommandVerifyId, VerifierIndex, VerifierSRS};
+use snark::{
+    user_command_verify::{
+        SnarkUserCommandVerifyAction, SnarkUserCommandVerifyError, SnarkUserCommandVerifyId,
+    },
+    VerifierIndex, VerifierSRS,
+};
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     sync::{Arc, Mutex},
@@ -29,27 +34,15 @@ use crate::{BlockProducerAction, RpcAction};
 
 type PendingId = u32;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TransactionPoolState {
     pool: ledger::transaction_pool::TransactionPool,
     pending_actions: BTreeMap<PendingId, TransactionPoolAction>,
     pending_id: PendingId,
     best_tip_hash: Option<v2::LedgerHash>,
-    /// For debug only
-    #[serde(skip)]
-    file: Option<std::fs::File>,
-}
-
-impl Clone for TransactionPoolState {
-    fn clone(&self) -> Self {
-        Self {
-            pool: self.pool.clone(),
-            pending_actions: self.pending_actions.clone(),
-            pending_id: self.pending_id,
-            best_tip_hash: self.best_tip_hash.clone(),
-            file: None,
-        }
-    }
+    /// Commands restored from disk, not yet verified. Consumed once the
+    /// first best tip is known.
+    restored_commands: Vec<v2::MinaBaseUserCommandStableV2>,
 }
 
 impl TransactionPoolState {
@@ -59,7 +52,7 @@ impl TransactionPoolState {
             pending_actions: Default::default(),
             pending_id: 0,
             best_tip_hash: None,
-            file: None,
+            restored_commands: Vec::new(),
         }
     }
 
@@ -67,6 +60,36 @@ impl TransactionPoolState {
         self.pool.size()
     }
 
+    pub fn pool(&self) -> &ledger::transaction_pool::TransactionPool {
+        &self.pool
+    }
+
+    /// Ledger hash of the best tip, commands can't be verified before
+    /// the first one is known.
+    pub fn best_tip_hash(&self) -> Option<&v2::LedgerHash> {
+        self.best_tip_hash.as_ref()
+    }
+
+    /// Add commands, restored from disk, which will be verified and
+    /// added to the pool, same as the ones received from peers, once the
+    /// first best tip is known.
+    pub fn restored_commands_add(
+        &mut self,
+        commands: impl IntoIterator<Item = v2::MinaBaseUserCommandStableV2>,
+    ) {
+        self.restored_commands.extend(commands);
+    }
+
+    /// Commands in the pool, ordered by nonce for each sender, so that
+    /// they can be added back in the same order once restored.
+    pub fn user_commands_to_persist(&self) -> Vec<v2::MinaBaseUserCommandStableV2> {
+        self.pool
+            .get_all_transactions()
+            .into_iter()
+            .map(|cmd| v2::MinaBaseUserCommandStableV2::from(&cmd.data.forget_check()))
+            .collect()
+    }
+
     pub fn transactions(&mut self, limit: usize) -> Vec<ValidCommandWithHash> {
         self.pool.transactions(limit)
     }
@@ -100,27 +123,13 @@ impl TransactionPoolState {
         // TODO
     }
 
-    pub fn reducer(mut state: crate::Substate<Self>, action: &TransactionPoolAction) {
-        // Uncoment following block to save actions to `/tmp/pool.bin`
-        // {
-        //     let substate = state.get_substate_mut().unwrap();
-        //     if substate.file.is_none() {
-        //         let mut file = std::fs::File::create("/tmp/pool.bin").unwrap();
-        //         postcard::to_io(&state.get_state(), &mut file).unwrap();
-        //         let substate = state.get_substate_mut().unwrap();
-        //         substate.file = Some(file);
-        //     }
-        // }
-
-        let substate = state.get_substate_mut().unwrap();
-        if let Some(file) = substate.file.as_mut() {
-            postcard::to_io(action, file).unwrap();
-        };
-
+    pub fn reducer(state: crate::Substate<Self>, action: &TransactionPoolAction) {
         Self::handle_action(state, action)
     }
 
     fn handle_action(mut state: crate::Substate<Self>, action: &TransactionPoolAction) {
+        let is_slot_tx_end_reached = state.get_state().is_slot_tx_end_reached();
+        let verify_req_id = state.get_state().snark.user_command_verify.next_req_id();
         let substate = state.get_substate_mut().unwrap();
 
         match action {
@@ -159,9 +168,41 @@ impl TransactionPoolState {
                 let commands = commands.iter().map(UserCommand::from).collect::<Vec<_>>();
                 let diff = diff::Diff { list: commands };
 
-                let valids = substate.pool.verify(diff, accounts).unwrap(); // TODO: Handle invalids
+                let prevalidated = substate.pool.prevalidate(diff, accounts);
+
+                let dispatcher = state.into_dispatcher();
+                match prevalidated {
+                    Ok(commands) if commands.is_empty() => {
+                        dispatcher.push(TransactionPoolAction::VerifySuccess {
+                            valids: vec![],
+                            from_rpc: *from_rpc,
+                        });
+                    }
+                    Ok(commands) => {
+                        dispatcher.push(SnarkUserCommandVerifyAction::Init {
+                            req_id: verify_req_id,
+                            commands,
+                            from_rpc: *from_rpc,
+                            on_success: callback!(on_user_command_verify_success((_req_id: SnarkUserCommandVerifyId, valids: Vec<valid::UserCommand>, from_rpc: Option<RpcId>)) -> crate::Action {
+                                TransactionPoolAction::VerifySuccess { valids, from_rpc }
+                            }),
+                            on_error: callback!(on_user_command_verify_error((_req_id: SnarkUserCommandVerifyId, error: SnarkUserCommandVerifyError, from_rpc: Option<RpcId>)) -> crate::Action {
+                                TransactionPoolAction::VerifyError { error: error.to_string(), from_rpc }
+                            }),
+                        });
+                    }
+                    Err(error) => {
+                        dispatcher.push(TransactionPoolAction::VerifyError {
+                            error,
+                            from_rpc: *from_rpc,
+                        });
+                    }
+                }
+            }
+            TransactionPoolAction::VerifySuccess { valids, from_rpc } => {
                 let valids = valids
-                    .into_iter()
+                    .iter()
+                    .cloned()
                     .map(transaction_hash::hash_command)
                     .collect::<Vec<_>>();
                 let best_tip_hash = substate.best_tip_hash.clone().unwrap();
@@ -175,9 +216,26 @@ impl TransactionPoolState {
                     from_rpc: *from_rpc,
                 });
             }
+            TransactionPoolAction::VerifyError { error, from_rpc } => {
+                openmina_core::log::warn!(
+                    openmina_core::log::system_time();
+                    kind = "TransactionPoolVerifyError",
+                    summary = "failed to verify user commands",
+                    error = error
+                );
+                if let Some(rpc_id) = from_rpc {
+                    state
+                        .into_dispatcher()
+                        .push(RpcAction::TransactionInjectFailure {
+                            rpc_id: *rpc_id,
+                            response: vec![],
+                        });
+                }
+            }
             TransactionPoolAction::BestTipChanged { best_tip_hash } => {
                 let account_ids = substate.pool.get_accounts_to_revalidate_on_new_best_tip();
                 substate.best_tip_hash = Some(best_tip_hash.clone());
+                let restored_commands = std::mem::take(&mut substate.restored_commands);
 
                 let dispatcher = state.into_dispatcher();
                 dispatcher.push(TransactionPoolEffectfulAction::FetchAccounts {
@@ -189,6 +247,12 @@ impl TransactionPoolState {
                     pending_id: None,
                     from_rpc: None,
                 });
+                if !restored_commands.is_empty() {
+                    dispatcher.push(TransactionPoolAction::StartVerify {
+                        commands: restored_commands.into_iter().collect(),
+                        from_rpc: None,
+                    });
+                }
             }
             TransactionPoolAction::BestTipChangedWithAccounts { accounts } => {
                 substate.pool.on_new_best_tip(accounts);
@@ -230,6 +294,23 @@ impl TransactionPoolState {
                     panic!()
                 };
 
+                if is_slot_tx_end_reached {
+                    if let Some(rpc_id) = from_rpc {
+                        let rejected = diff
+                            .list
+                            .into_iter()
+                            .map(|cmd| (cmd, diff::Error::AfterSlotTxEnd))
+                            .collect();
+                        state
+                            .into_dispatcher()
+                            .push(RpcAction::TransactionInjectFailure {
+                                rpc_id,
+                                response: rejected,
+                            });
+                    }
+                    return;
+                }
+
                 // Note(adonagy): Action for rebroadcast, in his action we can use forget_check
                 match substate.pool.unsafe_apply(&diff, accounts, is_sender_local) {
                     Ok((ApplyDecision::Accept, accepted, rejected)) => {
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use ledger::{
+    scan_state::transaction_logic::valid,
     transaction_pool::{
         diff::{self, BestTipDiff, DiffVerified},
         ValidCommandWithHash,
@@ -31,6 +32,14 @@ pub enum TransactionPoolAction {
         pending_id: PendingId,
         from_rpc: Option<RpcId>,
     },
+    VerifySuccess {
+        valids: Vec<valid::UserCommand>,
+        from_rpc: Option<RpcId>,
+    },
+    VerifyError {
+        error: String,
+        from_rpc: Option<RpcId>,
+    },
     BestTipChanged {
         best_tip_hash: LedgerHash,
     },
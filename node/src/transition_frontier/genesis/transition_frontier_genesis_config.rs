@@ -21,7 +21,9 @@ use mina_p2p_messages::{
     },
     v2::{self, PROTOCOL_CONSTANTS},
 };
-use openmina_core::constants::{constraint_constants, DEFAULT_GENESIS_TIMESTAMP_MILLISECONDS};
+use openmina_core::constants::{
+    constraint_constants, ForkConstants, DEFAULT_GENESIS_TIMESTAMP_MILLISECONDS,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -89,6 +91,11 @@ pub enum GenesisConfigError {
         expected: v2::LedgerHash,
         computed: v2::LedgerHash,
     },
+    #[error("hard fork constants in config ({config:?}) don't match the network's ({network:?})")]
+    ForkMismatch {
+        config: Option<ForkConstants>,
+        network: Option<ForkConstants>,
+    },
     #[error("account error: {0}")]
     Account(#[from] AccountConfigError),
     #[error("error loading genesis config from precomputed data: {0}")]
@@ -241,6 +248,16 @@ impl GenesisConfig {
                 // (mask, load_result)
             }
             Self::DaemonJson(config) => {
+                // Fork constants are global, they must be set from this
+                // config before the node is started.
+                if let Some(fork) = config.fork() {
+                    if constraint_constants().fork.as_ref() != Some(fork) {
+                        return Err(GenesisConfigError::ForkMismatch {
+                            config: Some(fork.clone()),
+                            network: constraint_constants().fork.clone(),
+                        });
+                    }
+                }
                 let mut masks = Vec::new();
                 let constants = config
                     .genesis
@@ -5,6 +5,12 @@ pub mod sync;
 mod transition_frontier_config;
 pub use transition_frontier_config::*;
 
+mod transition_frontier_snapshot;
+pub use transition_frontier_snapshot::*;
+
+mod transition_frontier_archive_service;
+pub use transition_frontier_archive_service::*;
+
 mod transition_frontier_state;
 pub use transition_frontier_state::*;
 
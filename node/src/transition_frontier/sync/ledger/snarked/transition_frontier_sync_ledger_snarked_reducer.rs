@@ -3,6 +3,7 @@ use std::iter;
 use mina_p2p_messages::v2::MinaLedgerSyncLedgerQueryStableV1;
 use p2p::{
     channels::rpc::{P2pChannelsRpcAction, P2pRpcRequest},
+    disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
     PeerId,
 };
 use redux::ActionMeta;
@@ -266,10 +267,13 @@ impl TransitionFrontierSyncLedgerSnarkedState {
                     },
                 );
             }
-            TransitionFrontierSyncLedgerSnarkedAction::NumAccountsRejected { .. } => {
+            TransitionFrontierSyncLedgerSnarkedAction::NumAccountsRejected { sender, .. } => {
                 // TODO(tizoc): should this be reflected in the state somehow?
-                // TODO(tizoc): we do nothing here, but the peer must be punished somehow
                 let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionAction::Init {
+                    peer_id: *sender,
+                    reason: P2pDisconnectionReason::TransitionFrontierSyncLedgerInvalid,
+                });
                 dispatcher.push(TransitionFrontierSyncLedgerSnarkedAction::PeersQuery);
             }
             TransitionFrontierSyncLedgerSnarkedAction::NumAccountsSuccess {
@@ -542,10 +546,13 @@ impl TransitionFrontierSyncLedgerSnarkedState {
                         .push(TransitionFrontierSyncLedgerSnarkedAction::MerkleTreeSyncSuccess);
                 }
             }
-            TransitionFrontierSyncLedgerSnarkedAction::ChildHashesRejected { .. } => {
+            TransitionFrontierSyncLedgerSnarkedAction::ChildHashesRejected { sender, .. } => {
                 // TODO(tizoc): should this be reflected in the state somehow?
-                // TODO(tizoc): we do nothing here, but the peer must be punished somehow
                 let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionAction::Init {
+                    peer_id: *sender,
+                    reason: P2pDisconnectionReason::TransitionFrontierSyncLedgerInvalid,
+                });
                 dispatcher.push(TransitionFrontierSyncLedgerSnarkedAction::PeersQuery);
             }
             TransitionFrontierSyncLedgerSnarkedAction::ChildAccountsReceived { .. } => {}
@@ -577,10 +584,13 @@ impl TransitionFrontierSyncLedgerSnarkedState {
                         .push(TransitionFrontierSyncLedgerSnarkedAction::MerkleTreeSyncSuccess);
                 }
             }
-            TransitionFrontierSyncLedgerSnarkedAction::ChildAccountsRejected { .. } => {
+            TransitionFrontierSyncLedgerSnarkedAction::ChildAccountsRejected { sender, .. } => {
                 // TODO(tizoc): should this be reflected in the state somehow?
-                // TODO(tizoc): we do nothing here, but the peer must be punished somehow
                 let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionAction::Init {
+                    peer_id: *sender,
+                    reason: P2pDisconnectionReason::TransitionFrontierSyncLedgerInvalid,
+                });
                 dispatcher.push(TransitionFrontierSyncLedgerSnarkedAction::PeersQuery);
             }
             TransitionFrontierSyncLedgerSnarkedAction::Success => {
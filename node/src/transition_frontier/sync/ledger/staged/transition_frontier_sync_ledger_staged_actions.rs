@@ -22,6 +22,11 @@ pub type TransitionFrontierSyncLedgerStagedActionWithMetaRef<'a> =
 #[action_event(level = info)]
 pub enum TransitionFrontierSyncLedgerStagedAction {
     PartsFetchPending,
+    /// Use staged ledger parts loaded from the bootstrap snapshot
+    /// instead of fetching them from peers.
+    PartsFetchFromSnapshot {
+        parts: Arc<StagedLedgerAuxAndPendingCoinbases>,
+    },
     PartsPeerFetchInit,
     PartsPeerFetchPending {
         peer_id: PeerId,
@@ -74,6 +79,18 @@ impl redux::EnablingCondition<crate::State> for TransitionFrontierSyncLedgerStag
                     }
                     _ => false,
                 }),
+            TransitionFrontierSyncLedgerStagedAction::PartsFetchFromSnapshot { .. } => state
+                .transition_frontier
+                .sync
+                .ledger()
+                .and_then(|s| s.staged())
+                .map_or(false, |s| {
+                    matches!(
+                        s,
+                        TransitionFrontierSyncLedgerStagedState::PartsFetchPending { attempts, .. }
+                            if attempts.is_empty()
+                    )
+                }),
             TransitionFrontierSyncLedgerStagedAction::PartsPeerFetchInit => state
                 .transition_frontier
                 .sync
@@ -24,8 +24,52 @@ impl TransitionFrontierSyncLedgerStagedState {
         match action {
             TransitionFrontierSyncLedgerStagedAction::PartsFetchPending => {
                 // handled in parent. TODO(refactor) check this
+                let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
+                let snapshot_parts = &global_state
+                    .transition_frontier
+                    .config
+                    .bootstrap_staged_ledger_parts;
+                if let Some(parts) = snapshot_parts {
+                    dispatcher.push(
+                        TransitionFrontierSyncLedgerStagedAction::PartsFetchFromSnapshot {
+                            parts: parts.clone(),
+                        },
+                    );
+                } else {
+                    dispatcher.push(TransitionFrontierSyncLedgerStagedAction::PartsPeerFetchInit);
+                }
+            }
+            TransitionFrontierSyncLedgerStagedAction::PartsFetchFromSnapshot { parts } => {
+                let Self::PartsFetchPending { target, .. } = state else {
+                    return;
+                };
+                let validated = StagedLedgerAuxAndPendingCoinbasesValidated::validate(
+                    parts,
+                    &target.staged.hashes,
+                );
+                let parts = match validated {
+                    StagedLedgerAuxAndPendingCoinbasesValidated::Valid(parts) => parts,
+                    StagedLedgerAuxAndPendingCoinbasesValidated::Invalid(_) => {
+                        // Snapshot is outdated or for a different chain.
+                        openmina_core::warn!(meta.time();
+                            kind = "BootstrapSnapshot",
+                            summary = "snapshot staged ledger doesn't match the sync target, fetching it from peers",
+                            target_block = target.staged.block_hash.to_string());
+                        let dispatcher = state_context.into_dispatcher();
+                        dispatcher
+                            .push(TransitionFrontierSyncLedgerStagedAction::PartsPeerFetchInit);
+                        return;
+                    }
+                };
+                *state = Self::PartsFetchSuccess {
+                    time: meta.time(),
+                    target: target.clone(),
+                    parts,
+                };
+
+                // Dispatch
                 let dispatcher = state_context.into_dispatcher();
-                dispatcher.push(TransitionFrontierSyncLedgerStagedAction::PartsPeerFetchInit);
+                dispatcher.push(TransitionFrontierSyncLedgerStagedAction::ReconstructInit);
             }
             TransitionFrontierSyncLedgerStagedAction::PartsPeerFetchInit => {
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
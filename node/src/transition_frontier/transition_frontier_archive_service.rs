@@ -0,0 +1,15 @@
+use openmina_core::block::ArcBlockWithHash;
+
+pub trait TransitionFrontierArchiveService: redux::Service {
+    /// Archive a block once it's applied, as pending. Blocks that don't
+    /// end up in the best chain are archived too.
+    fn archive_block_applied(&mut self, block: &ArcBlockWithHash);
+
+    /// Archive blocks of the new best chain, starting with the transition
+    /// frontier root.
+    ///
+    /// Blocks that were already archived are skipped. Root and its
+    /// archived ancestors become canonical, while the rest of the archived
+    /// blocks at or below the root's height become orphaned.
+    fn archive_best_chain_update(&mut self, best_chain: &[ArcBlockWithHash]);
+}
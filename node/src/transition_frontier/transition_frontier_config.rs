@@ -1,16 +1,53 @@
 use std::sync::Arc;
 
+use openmina_core::block::ArcBlockWithHash;
 use serde::{Deserialize, Serialize};
 
+use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
+
 use super::genesis::TransitionFrontierGenesisConfig;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransitionFrontierConfig {
     pub genesis: Arc<TransitionFrontierGenesisConfig>,
+    /// Staged ledger parts loaded from the bootstrap snapshot. Used instead
+    /// of fetching them from peers, if they match the sync target.
+    #[serde(default)]
+    pub bootstrap_staged_ledger_parts: Option<Arc<StagedLedgerAuxAndPendingCoinbases>>,
+    /// Global slot since hard fork from which blocks carry no transactions
+    /// and the transaction pool rejects new commands (`daemon.slot_tx_end`).
+    #[serde(default)]
+    pub slot_tx_end: Option<u32>,
+    /// Global slot since hard fork from which blocks are neither produced
+    /// nor accepted (`daemon.slot_chain_end`).
+    #[serde(default)]
+    pub slot_chain_end: Option<u32>,
 }
 
 impl TransitionFrontierConfig {
     pub fn new(genesis: Arc<TransitionFrontierGenesisConfig>) -> Self {
-        TransitionFrontierConfig { genesis }
+        TransitionFrontierConfig {
+            genesis,
+            bootstrap_staged_ledger_parts: None,
+            slot_tx_end: None,
+            slot_chain_end: None,
+        }
+    }
+
+    pub fn is_slot_tx_end_reached(&self, global_slot: u32) -> bool {
+        self.slot_tx_end.map_or(false, |end| global_slot >= end)
+    }
+
+    pub fn is_slot_chain_end_reached(&self, global_slot: u32) -> bool {
+        self.slot_chain_end.map_or(false, |end| global_slot >= end)
+    }
+
+    /// Whether the block is past the hard fork stop slots, i.e. it is
+    /// after `slot_chain_end`, or after `slot_tx_end` with a non-empty
+    /// staged ledger diff.
+    pub fn is_block_after_stop_slot(&self, block: &ArcBlockWithHash) -> bool {
+        let global_slot = block.global_slot();
+        self.is_slot_chain_end_reached(global_slot)
+            || (self.is_slot_tx_end_reached(global_slot) && !block.body().is_empty())
     }
 }
@@ -191,13 +191,15 @@ pub fn transition_frontier_effects<S: crate::Service>(
                     }
                 }
                 TransitionFrontierSyncAction::BlocksNextApplySuccess { ref hash } => {
+                    let sync = &store.state.get().transition_frontier.sync;
                     if let Some(stats) = store.service.stats() {
-                        if let Some(state) =
-                            store.state.get().transition_frontier.sync.block_state(hash)
-                        {
+                        if let Some(state) = sync.block_state(hash) {
                             stats.syncing_block_update(state);
                         }
                     }
+                    if let Some(block) = sync.block_state(hash).and_then(|state| state.block()) {
+                        store.service.archive_block_applied(block);
+                    }
                 }
                 TransitionFrontierSyncAction::BlocksSuccess => {
                     store.dispatch(TransitionFrontierSyncAction::CommitInit);
@@ -278,6 +280,7 @@ fn synced_effects<S: crate::Service>(
     if let Some(stats) = store.service.stats() {
         stats.new_best_chain(meta.time(), best_chain);
     }
+    store.service.archive_best_chain_update(best_chain);
 
     let chain_diff = chain_diff.clone();
 
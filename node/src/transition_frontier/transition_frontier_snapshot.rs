@@ -0,0 +1,134 @@
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use ledger::{BaseLedger, Mask};
+use mina_p2p_messages::{
+    binprot::{
+        self,
+        macros::{BinProtRead, BinProtWrite},
+        BinProtRead, BinProtWrite,
+    },
+    v2,
+};
+use openmina_core::block::{ArcBlockWithHash, BlockWithHash};
+use openmina_core::constants::constraint_constants;
+use serde::{Deserialize, Serialize};
+
+use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
+
+use super::sync::ledger::staged::StagedLedgerAuxAndPendingCoinbasesValidated;
+
+/// Transition frontier root exported by a synced node.
+///
+/// Contains everything needed to bootstrap another node without
+/// syncing ledgers from peers. Contents aren't trusted by the node
+/// loading the snapshot, see [`TransitionFrontierSnapshot::verify`].
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct TransitionFrontierSnapshot {
+    pub root_block: v2::MinaBlockBlockStableV2,
+    /// Root snarked ledger and, if available, epoch ledgers.
+    pub snarked_ledgers: Vec<TransitionFrontierSnapshotLedger>,
+    /// Staged ledger aux, pending coinbases and protocol states needed
+    /// for the root staged ledger reconstruction.
+    pub staged_ledger_parts: StagedLedgerAuxAndPendingCoinbases,
+}
+
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct TransitionFrontierSnapshotLedger {
+    pub ledger_hash: v2::LedgerHash,
+    /// Accounts ordered by their index in the ledger.
+    pub accounts: Vec<v2::MinaBaseAccountBinableArgStableV2>,
+}
+
+/// Snapshot whose contents match the hashes they claim to have.
+///
+/// Whether root block is a part of the canonical chain is checked
+/// later, once the node receives best tip from peers.
+pub struct TransitionFrontierSnapshotVerified {
+    pub root_block: ArcBlockWithHash,
+    pub snarked_ledgers: Vec<Mask>,
+    pub staged_ledger_parts: Arc<StagedLedgerAuxAndPendingCoinbases>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TransitionFrontierSnapshotError {
+    #[error("ledger hash mismatch, expected: {expected}, computed: {computed}")]
+    LedgerHashMismatch {
+        expected: v2::LedgerHash,
+        computed: v2::LedgerHash,
+    },
+    #[error("root snarked ledger {0} is missing")]
+    RootSnarkedLedgerMissing(v2::LedgerHash),
+    #[error("staged ledger parts don't match root block's staged ledger hash")]
+    StagedLedgerPartsInvalid,
+}
+
+impl TransitionFrontierSnapshot {
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, binprot::Error> {
+        Self::binprot_read(&mut reader)
+    }
+
+    pub fn store<W: Write>(&self, mut writer: W) -> Result<(), std::io::Error> {
+        self.binprot_write(&mut writer)
+    }
+
+    pub fn root_block_hash(&self) -> v2::StateHash {
+        self.root_block.hash()
+    }
+
+    /// Rebuilds ledgers and checks that their hashes, as well as the hash
+    /// of the staged ledger parts, match the ones claimed by the snapshot.
+    pub fn verify(
+        self,
+    ) -> Result<TransitionFrontierSnapshotVerified, TransitionFrontierSnapshotError> {
+        let root_block = BlockWithHash::new(Arc::new(self.root_block));
+
+        let snarked_ledgers = self
+            .snarked_ledgers
+            .into_iter()
+            .map(|snarked_ledger| {
+                let mut mask = Mask::new_root(ledger::Database::create(
+                    constraint_constants().ledger_depth as u8,
+                ));
+                for account in snarked_ledger.accounts {
+                    let account: ledger::Account = (&account).into();
+                    mask.get_or_create_account(account.id(), account).unwrap();
+                }
+                let computed: v2::LedgerHash =
+                    v2::MinaBaseLedgerHash0StableV1(mask.merkle_root().into()).into();
+                if computed != snarked_ledger.ledger_hash {
+                    return Err(TransitionFrontierSnapshotError::LedgerHashMismatch {
+                        expected: snarked_ledger.ledger_hash,
+                        computed,
+                    });
+                }
+                Ok((computed, mask))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let root_snarked_ledger_hash = root_block.snarked_ledger_hash();
+        if !snarked_ledgers
+            .iter()
+            .any(|(hash, _)| hash == root_snarked_ledger_hash)
+        {
+            return Err(TransitionFrontierSnapshotError::RootSnarkedLedgerMissing(
+                root_snarked_ledger_hash.clone(),
+            ));
+        }
+
+        let staged_ledger_parts = Arc::new(self.staged_ledger_parts);
+        let validated = StagedLedgerAuxAndPendingCoinbasesValidated::validate(
+            &staged_ledger_parts,
+            root_block.staged_ledger_hashes(),
+        );
+        if !validated.is_valid() {
+            return Err(TransitionFrontierSnapshotError::StagedLedgerPartsInvalid);
+        }
+
+        Ok(TransitionFrontierSnapshotVerified {
+            root_block,
+            snarked_ledgers: snarked_ledgers.into_iter().map(|(_, mask)| mask).collect(),
+            staged_ledger_parts,
+        })
+    }
+}
@@ -27,21 +27,23 @@ use node::core::channels::mpsc;
 use node::core::log::system_time;
 use node::core::requests::RpcId;
 use node::core::warn;
-use node::p2p::{P2pConnectionEvent, P2pEvent, P2pLimits, PeerId};
+use node::p2p::{P2pConnectionEvent, P2pEvent, P2pLimits, P2pRpcRateLimits, PeerId};
 use node::snark::{VerifierIndex, VerifierSRS};
 use node::{
     event_source::Event,
     p2p::{channels::ChannelId, identity::SecretKey as P2pSecretKey},
     service::{Recorder, Service},
     snark::{get_srs, get_verifier_index, VerifierKind},
-    BuildEnv, Config, GlobalConfig, LedgerConfig, P2pConfig, SnarkConfig, State,
+    BuildEnv, Config, GlobalConfig, LedgerConfig, P2pConfig, SnarkConfig, SnarkPoolConfig, State,
     TransitionFrontierConfig,
 };
 use openmina_node_invariants::{InvariantResult, Invariants};
 use openmina_node_native::http_server;
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::node::{DaemonJson, NonDeterministicEvent, OcamlStep, TestPeerId};
+use crate::node::{
+    adversary_effects, partition_effects, DaemonJson, NonDeterministicEvent, OcamlStep, TestPeerId,
+};
 use crate::{
     network_debugger::Debugger,
     node::{
@@ -287,18 +289,25 @@ impl Cluster {
                 peer_discovery: true,
                 timeouts: testing_config.timeouts,
                 limits: P2pLimits::default().with_max_peers(Some(testing_config.max_peers)),
+                rpc_rate_limits: P2pRpcRateLimits::default(),
+                persisted_peers: Default::default(),
                 initial_time: testing_config
                     .initial_time
                     .checked_sub(redux::Timestamp::ZERO)
                     .unwrap_or_default(),
             },
-            transition_frontier: TransitionFrontierConfig::new(testing_config.genesis),
+            transition_frontier: TransitionFrontierConfig {
+                slot_tx_end: testing_config.slot_tx_end,
+                slot_chain_end: testing_config.slot_chain_end,
+                ..TransitionFrontierConfig::new(testing_config.genesis)
+            },
             block_producer: block_producer_config,
             tx_pool: ledger::transaction_pool::Config {
                 trust_system: (),
                 pool_max_size: 3000,
                 slot_tx_end: None,
             },
+            snark_pool: SnarkPoolConfig::default(),
         };
 
         let mut service_builder = NodeServiceBuilder::new(rng_seed);
@@ -355,6 +364,7 @@ impl Cluster {
         if self.config.is_replay() {
             service.set_replay();
         }
+        service.set_adversary(testing_config.adversary);
 
         let state = node::State::new(config, &consensus_consts, testing_config.initial_time);
         fn effects(store: &mut node::Store<NodeTestingService>, action: node::ActionWithMeta) {
@@ -380,6 +390,9 @@ impl Cluster {
                 }
             }
 
+            if partition_effects(store, &action) || adversary_effects(store, &action) {
+                return;
+            }
             node::effects(store, action)
         }
         let mut store = node::Store::new(
@@ -508,6 +521,38 @@ impl Cluster {
         self.nodes.get_mut(node_id.index())
     }
 
+    /// Split rust nodes into `groups` which can't communicate with each
+    /// other. Nodes not in any of the groups form one more group.
+    ///
+    /// Replaces the current partition, empty `groups` heal it.
+    pub fn partition(&mut self, groups: &[Vec<ClusterNodeId>]) {
+        let group_of = |node_id: ClusterNodeId| {
+            groups
+                .iter()
+                .position(|group| group.contains(&node_id))
+                .unwrap_or(groups.len())
+        };
+        let peers = self
+            .nodes_iter()
+            .map(|(node_id, node)| (group_of(node_id), node.peer_id()))
+            .collect::<Vec<_>>();
+
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            let group = group_of(ClusterNodeId::new_unchecked(i));
+            let partitioned_peers = peers
+                .iter()
+                .filter(|(peer_group, _)| *peer_group != group)
+                .map(|(_, peer_id)| *peer_id)
+                .collect();
+            node.set_partitioned_peers(partitioned_peers);
+        }
+    }
+
+    pub fn next_rpc_id(&mut self) -> RpcId {
+        self.rpc_counter += 1;
+        RpcId::new_unchecked(usize::MAX, self.rpc_counter)
+    }
+
     pub fn ocaml_node(&self, node_id: ClusterOcamlNodeId) -> Option<&OcamlNode> {
         self.ocaml_nodes
             .get(node_id.index())
@@ -804,8 +849,7 @@ impl Cluster {
                     ListenerNode::Custom(addr) => addr.clone(),
                 };
 
-                self.rpc_counter += 1;
-                let rpc_id = RpcId::new_unchecked(usize::MAX, self.rpc_counter);
+                let rpc_id = self.next_rpc_id();
                 let dialer = self
                     .nodes
                     .get_mut(dialer.index())
@@ -814,6 +858,10 @@ impl Cluster {
                 let req = node::rpc::RpcRequest::P2pConnectionOutgoing(listener_addr);
                 dialer.dispatch_event(Event::Rpc(rpc_id, Box::new(req)))
             }
+            ScenarioStep::PartitionNodes { groups } => {
+                self.partition(&groups);
+                true
+            }
             ScenarioStep::CheckTimeouts { node_id } => {
                 let node = self
                     .nodes
@@ -5,7 +5,8 @@ use std::{path::PathBuf, time::Duration};
 
 use ledger::BaseLedger;
 use node::account::{AccountPublicKey, AccountSecretKey};
-use node::{event_source::Event, ledger::LedgerService, ActionKind, State};
+use node::stats::block_producer::BlockProductionAttempt;
+use node::{event_source::Event, ledger::LedgerService, rpc::RpcId, ActionKind, State};
 use rand::{rngs::StdRng, SeedableRng};
 use time::OffsetDateTime;
 
@@ -48,6 +49,15 @@ impl<'a> ClusterRunner<'a> {
         self.cluster.node_mut(node_id)
     }
 
+    /// Block production attempts recorded in rust node's stats.
+    pub fn block_production_attempts(
+        &mut self,
+        node_id: ClusterNodeId,
+    ) -> Option<Vec<BlockProductionAttempt>> {
+        self.node_mut(node_id)
+            .map(|node| node.block_production_attempts())
+    }
+
     pub fn ocaml_node(&self, node_id: ClusterOcamlNodeId) -> Option<&OcamlNode> {
         self.cluster.ocaml_node(node_id)
     }
@@ -106,6 +116,10 @@ impl<'a> ClusterRunner<'a> {
         self.cluster.get_account_sec_key(pub_key)
     }
 
+    pub fn next_rpc_id(&mut self) -> RpcId {
+        self.cluster.next_rpc_id()
+    }
+
     pub fn add_rust_node(&mut self, testing_config: RustNodeTestingConfig) -> ClusterNodeId {
         let step = ScenarioStep::AddNode {
             config: testing_config.into(),
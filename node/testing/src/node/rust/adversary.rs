@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use mina_p2p_messages::bigint::BigInt;
+use mina_p2p_messages::gossip::GossipNetMessageV2;
+use mina_p2p_messages::v2::{
+    self, CurrencyFeeStableV1, LedgerProofProdStableV2, MinaBlockBlockStableV2,
+    PicklesProofProofsVerified2ReprStableV2, TransactionSnarkWorkTStableV2Proofs,
+};
+use node::account::AccountSecretKey;
+use node::core::snark::SnarkJobCommitment;
+use node::external_snark_worker::ExternalSnarkWorkerEvent;
+use node::ledger::read::LedgerReadResponse;
+use node::ledger::LedgerEvent;
+use node::p2p::channels::best_tip::{BestTipPropagationChannelMsg, P2pChannelsBestTipAction};
+use node::p2p::channels::snark_job_commitment::{
+    P2pChannelsSnarkJobCommitmentAction, SnarkJobCommitmentPropagationChannelMsg,
+};
+use node::p2p::channels::{MsgId, P2pChannelsAction, P2pChannelsService};
+use node::p2p::peer::P2pPeerAction;
+use node::p2p::{
+    P2pAction, P2pNetworkNoiseAction, P2pNetworkPnetAction, P2pNetworkPubsubAction, PeerId,
+};
+use node::{event_source::Event, Action, ActionWithMeta, Store};
+use serde::{Deserialize, Serialize};
+
+use crate::service::NodeTestingService;
+
+/// Number of fake snarkers used by [`AdversaryPersona::FloodSnarkCommitments`].
+const FAKE_SNARKERS: usize = 8;
+
+/// Value of `ft_eval1` in invalid proofs. Small enough to be a field element.
+const INVALID_PROOF_MARKER: [u8; 32] = [0x0a; 32];
+
+/// Malicious behaviour of a Rust node in the cluster.
+///
+/// Adversary is a regular openmina node, so it syncs and connects like
+/// any other node. Personas only change what it sends to its peers.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdversaryPersona {
+    /// Instead of its best tip, propagates a copy of it with a shifted
+    /// timestamp and an invalid protocol state proof.
+    InvalidBlockProofs,
+    /// Produces snark work with invalid transaction proofs, so it's
+    /// propagated to peers as any other snark from own snark worker.
+    InvalidSnarkProofs,
+    /// For each block produced by this node, propagates a second block
+    /// for the same slot, which differs only in timestamp.
+    Equivocate,
+    /// Answers `AnswerSyncLedgerQuery` with wrong ledger hashes.
+    WrongLedgerHashes,
+    /// Never answers staged ledger aux and pending coinbases requests.
+    WithholdStagedLedgerParts,
+    /// Answers snark job commitment requests with more commitments than
+    /// requested, made up for available jobs by fake snarkers.
+    FloodSnarkCommitments,
+    /// Sends a noise frame that fails decryption to each libp2p peer, once
+    /// it is connected.
+    MalformedNoiseFrames,
+    /// Sends a yamux frame with an unknown version to each libp2p peer,
+    /// once it is connected.
+    MalformedYamuxFrames,
+}
+
+/// Effects of an adversary node, run before the honest ones.
+///
+/// Returns `true` if honest effects for the action must be skipped.
+pub fn adversary_effects(store: &mut Store<NodeTestingService>, action: &ActionWithMeta) -> bool {
+    let is = |persona| store.service.adversary().contains(&persona);
+    let invalid_block_proofs = is(AdversaryPersona::InvalidBlockProofs);
+    let equivocate = is(AdversaryPersona::Equivocate);
+    let flood_snark_commitments = is(AdversaryPersona::FloodSnarkCommitments);
+    let malformed_noise_frames = is(AdversaryPersona::MalformedNoiseFrames);
+    let malformed_yamux_frames = is(AdversaryPersona::MalformedYamuxFrames);
+    let timestamp_ms = action.meta().time_as_nanos() / 1_000_000;
+
+    let Action::P2p(action) = action.action() else {
+        return false;
+    };
+    match action {
+        P2pAction::Channels(P2pChannelsAction::BestTip(
+            P2pChannelsBestTipAction::ResponseSend { peer_id, best_tip },
+        )) => {
+            let equivocate = equivocate && store.state().block_producer.is_me(best_tip.producer());
+            if !invalid_block_proofs && !equivocate {
+                return false;
+            }
+            if invalid_block_proofs {
+                // Shifted timestamp, so that peers haven't seen the block yet.
+                let mut block = shift_timestamp((*best_tip.block).clone(), 2);
+                invalidate_proof(&mut block.header.protocol_state_proof.0);
+                send_block(store, *peer_id, block);
+            }
+            if equivocate {
+                let block = shift_timestamp((*best_tip.block).clone(), 1);
+                send_block(store, *peer_id, block);
+            }
+            invalid_block_proofs
+        }
+        P2pAction::Channels(P2pChannelsAction::SnarkJobCommitment(
+            P2pChannelsSnarkJobCommitmentAction::RequestReceived { peer_id, .. },
+        )) if flood_snark_commitments => {
+            let job_ids = store
+                .state()
+                .snark_pool
+                .available_jobs_iter()
+                .map(|job| job.id.clone())
+                .collect::<Vec<_>>();
+            if job_ids.is_empty() {
+                return false;
+            }
+            // Deterministic keys from the end, so they don't clash with
+            // the ones used by scenarios.
+            let snarkers = (0..FAKE_SNARKERS)
+                .map(|i| AccountSecretKey::max_deterministic_count() - 1 - i)
+                .map(|i| {
+                    AccountSecretKey::deterministic(i as u64)
+                        .public_key()
+                        .into()
+                })
+                .collect::<Vec<v2::NonZeroCurvePoint>>();
+            // Regardless of the requested limit.
+            let count = u8::MAX;
+            let commitments = job_ids
+                .iter()
+                .cycle()
+                .zip(snarkers.iter().cycle())
+                .take(count as usize)
+                .map(|(job_id, snarker)| {
+                    SnarkJobCommitment::new(
+                        timestamp_ms,
+                        job_id.clone(),
+                        CurrencyFeeStableV1(0_u64.into()),
+                        snarker.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let msg = SnarkJobCommitmentPropagationChannelMsg::WillSend { count };
+            store
+                .service
+                .channel_send(*peer_id, MsgId::first(), msg.into());
+            for commitment in commitments {
+                let msg = SnarkJobCommitmentPropagationChannelMsg::Commitment(commitment);
+                store
+                    .service
+                    .channel_send(*peer_id, MsgId::first(), msg.into());
+            }
+            true
+        }
+        P2pAction::Peer(P2pPeerAction::Ready { peer_id, .. })
+            if malformed_noise_frames || malformed_yamux_frames =>
+        {
+            let Some(addr) = store
+                .state()
+                .p2p
+                .ready()
+                .filter(|p2p| p2p.is_libp2p_peer(peer_id))
+                .and_then(|p2p| p2p.network.scheduler.find_peer(peer_id))
+                .map(|(addr, _)| *addr)
+            else {
+                return false;
+            };
+            if malformed_yamux_frames {
+                // Version `0xff`, the rest of the header is left zeroed.
+                let mut frame = vec![0; 12];
+                frame[0] = 0xff;
+                let data = frame.into();
+                store.dispatch(P2pAction::from(P2pNetworkNoiseAction::OutgoingData {
+                    addr,
+                    data,
+                }));
+            }
+            if malformed_noise_frames {
+                // Length prefixed chunk which can't be decrypted.
+                let mut frame = 32_u16.to_be_bytes().to_vec();
+                frame.extend_from_slice(&[0xab; 32]);
+                let data = frame.into();
+                store.dispatch(P2pAction::from(P2pNetworkPnetAction::OutgoingData {
+                    addr,
+                    data,
+                }));
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Corrupts events produced by the service of an adversary node, before
+/// they reach its state machine.
+pub fn adversary_event(personas: &[AdversaryPersona], event: Event) -> Event {
+    let wrong_hash = || v2::MinaBaseLedgerHash0StableV1(BigInt::zero()).into();
+
+    match event {
+        Event::ExternalSnarkWorker(ExternalSnarkWorkerEvent::WorkResult(proofs))
+            if personas.contains(&AdversaryPersona::InvalidSnarkProofs) =>
+        {
+            let invalidate = |proof: &LedgerProofProdStableV2| {
+                let mut proof = proof.clone();
+                invalidate_proof(&mut proof.0.proof.0);
+                proof
+            };
+            let proofs = match &*proofs {
+                TransactionSnarkWorkTStableV2Proofs::One(p) => {
+                    TransactionSnarkWorkTStableV2Proofs::One(invalidate(p))
+                }
+                TransactionSnarkWorkTStableV2Proofs::Two((p1, p2)) => {
+                    TransactionSnarkWorkTStableV2Proofs::Two((invalidate(p1), invalidate(p2)))
+                }
+            };
+            Event::ExternalSnarkWorker(ExternalSnarkWorkerEvent::WorkResult(Arc::new(proofs)))
+        }
+        Event::Ledger(LedgerEvent::Read(id, response)) => {
+            let response = match response {
+                LedgerReadResponse::GetNumAccounts(Some((num_accounts, _)))
+                    if personas.contains(&AdversaryPersona::WrongLedgerHashes) =>
+                {
+                    LedgerReadResponse::GetNumAccounts(Some((num_accounts, wrong_hash())))
+                }
+                LedgerReadResponse::GetChildHashesAtAddr(Some((left, _)))
+                    if personas.contains(&AdversaryPersona::WrongLedgerHashes) =>
+                {
+                    LedgerReadResponse::GetChildHashesAtAddr(Some((left, wrong_hash())))
+                }
+                LedgerReadResponse::GetStagedLedgerAuxAndPendingCoinbases(Some(_))
+                    if personas.contains(&AdversaryPersona::WithholdStagedLedgerParts) =>
+                {
+                    LedgerReadResponse::GetStagedLedgerAuxAndPendingCoinbases(None)
+                }
+                response => response,
+            };
+            Event::Ledger(LedgerEvent::Read(id, response))
+        }
+        event => event,
+    }
+}
+
+/// Marks proof as invalid.
+///
+/// Unless the cluster uses `ProofKind::Full`, proofs aren't verified, so
+/// testing service rejects proofs marked this way instead, see
+/// [`is_invalid_proof`].
+fn invalidate_proof(proof: &mut PicklesProofProofsVerified2ReprStableV2) {
+    proof.prev_evals.ft_eval1 = BigInt::from_bytes(INVALID_PROOF_MARKER);
+}
+
+/// Whether proof was sent by [`AdversaryPersona::InvalidBlockProofs`] or
+/// [`AdversaryPersona::InvalidSnarkProofs`].
+pub fn is_invalid_proof(proof: &PicklesProofProofsVerified2ReprStableV2) -> bool {
+    proof.prev_evals.ft_eval1 == BigInt::from_bytes(INVALID_PROOF_MARKER)
+}
+
+fn shift_timestamp(mut block: MinaBlockBlockStableV2, by_ms: u64) -> MinaBlockBlockStableV2 {
+    let timestamp = &mut block.header.protocol_state.body.blockchain_state.timestamp;
+    timestamp.0 = (timestamp.0 .0.as_u64() + by_ms).into();
+    block
+}
+
+fn send_block(
+    store: &mut Store<NodeTestingService>,
+    peer_id: PeerId,
+    block: MinaBlockBlockStableV2,
+) {
+    let is_libp2p_peer = store
+        .state()
+        .p2p
+        .ready()
+        .map_or(false, |p2p| p2p.is_libp2p_peer(&peer_id));
+    if is_libp2p_peer {
+        let message = Box::new(GossipNetMessageV2::NewState(block));
+        store.dispatch(P2pNetworkPubsubAction::Broadcast { message });
+    } else {
+        let msg = BestTipPropagationChannelMsg::BestTip(Arc::new(block));
+        store
+            .service
+            .channel_send(peer_id, MsgId::first(), msg.into());
+    }
+}
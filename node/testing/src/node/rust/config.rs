@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::scenario::ListenerNode;
 
+use super::AdversaryPersona;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum TestPeerId {
     /// NOTE This option results a deterministic private key derived from the
@@ -34,6 +36,14 @@ pub struct RustNodeTestingConfig {
     pub timeouts: P2pTimeouts,
     pub libp2p_port: Option<u16>,
     pub recorder: Recorder,
+    /// Malicious behaviour of the node, honest if empty.
+    #[serde(default)]
+    pub adversary: Vec<AdversaryPersona>,
+    /// Hard fork stop slots, see [`node::TransitionFrontierConfig`].
+    #[serde(default)]
+    pub slot_tx_end: Option<u32>,
+    #[serde(default)]
+    pub slot_chain_end: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -62,6 +72,9 @@ impl RustNodeTestingConfig {
             timeouts: P2pTimeouts::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         }
     }
 
@@ -78,6 +91,9 @@ impl RustNodeTestingConfig {
             timeouts: P2pTimeouts::without_rpc(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         }
     }
 
@@ -111,6 +127,17 @@ impl RustNodeTestingConfig {
         self
     }
 
+    pub fn with_adversary(mut self, persona: AdversaryPersona) -> Self {
+        self.adversary.push(persona);
+        self
+    }
+
+    pub fn with_stop_slots(mut self, slot_tx_end: u32, slot_chain_end: u32) -> Self {
+        self.slot_tx_end = Some(slot_tx_end);
+        self.slot_chain_end = Some(slot_chain_end);
+        self
+    }
+
     pub fn with_daemon_json<P: AsRef<Path>>(mut self, daemon_json: P) -> Self {
         self.genesis = Arc::new(GenesisConfig::DaemonJson(
             serde_json::from_reader(&mut File::open(daemon_json).expect("daemon json file"))
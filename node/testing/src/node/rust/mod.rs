@@ -4,13 +4,23 @@ pub use config::*;
 mod event;
 pub use event::*;
 
+mod adversary;
+pub use adversary::*;
+
+mod partition;
+pub use partition::*;
+
+use std::collections::BTreeSet;
+
 use node::event_source::EventSourceAction;
 use node::p2p::connection::outgoing::{
     P2pConnectionOutgoingInitLibp2pOpts, P2pConnectionOutgoingInitOpts,
 };
+use node::p2p::disconnection::{P2pDisconnectionAction, P2pDisconnectionReason};
 use node::p2p::webrtc::SignalingMethod;
 use node::p2p::PeerId;
-use node::service::P2pDisconnectionService;
+use node::service::{P2pDisconnectionService, Service};
+use node::stats::block_producer::BlockProductionAttempt;
 use node::{Action, CheckTimeoutsAction, State, Store};
 use redux::EnablingCondition;
 use temp_dir::TempDir;
@@ -53,6 +63,14 @@ impl Node {
         &mut self.store.service
     }
 
+    /// Block production attempts recorded in node's stats.
+    pub fn block_production_attempts(&mut self) -> Vec<BlockProductionAttempt> {
+        self.service_mut()
+            .stats()
+            .map(|stats| stats.block_producer().collect_attempts())
+            .unwrap_or_default()
+    }
+
     pub fn set_dyn_effects(&mut self, effects: DynEffects) {
         self.service_mut().set_dyn_effects(effects)
     }
@@ -190,4 +208,24 @@ impl Node {
     pub fn p2p_disconnect(&mut self, peer_id: PeerId) {
         self.service_mut().disconnect(peer_id)
     }
+
+    /// Isolate the node from `peers`, disconnecting the ones it is
+    /// connected to. Replaces previously set peers, empty set heals the
+    /// partition.
+    pub fn set_partitioned_peers(&mut self, peers: BTreeSet<PeerId>) {
+        let to_disconnect = self
+            .state()
+            .p2p
+            .ready_peers()
+            .into_iter()
+            .filter(|peer_id| peers.contains(peer_id))
+            .collect::<Vec<_>>();
+        self.service_mut().set_partitioned_peers(peers);
+        for peer_id in to_disconnect {
+            self.dispatch(P2pDisconnectionAction::Init {
+                peer_id,
+                reason: P2pDisconnectionReason::Timeout,
+            });
+        }
+    }
 }
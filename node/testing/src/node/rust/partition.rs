@@ -0,0 +1,38 @@
+use node::p2p::channels::rpc::P2pChannelsRpcAction;
+use node::p2p::channels::P2pChannelsAction;
+use node::p2p::disconnection::{P2pDisconnectionAction, P2pDisconnectionReason};
+use node::p2p::peer::P2pPeerAction;
+use node::p2p::P2pAction;
+use node::{Action, ActionWithMeta, Store};
+
+use crate::service::NodeTestingService;
+
+/// Effects isolating the node from peers on the other side of a simulated
+/// network partition, run before the honest ones.
+///
+/// Such peers get disconnected as soon as they are ready, with a timeout
+/// like a real partition would cause, and blocks received from them are
+/// ignored.
+///
+/// Returns `true` if honest effects for the action must be skipped.
+pub fn partition_effects(store: &mut Store<NodeTestingService>, action: &ActionWithMeta) -> bool {
+    let Action::P2p(action) = action.action() else {
+        return false;
+    };
+    match action {
+        P2pAction::Peer(P2pPeerAction::Ready { peer_id, .. })
+            if store.service.is_partitioned_from(peer_id) =>
+        {
+            store.dispatch(P2pDisconnectionAction::Init {
+                peer_id: *peer_id,
+                reason: P2pDisconnectionReason::Timeout,
+            });
+            true
+        }
+        P2pAction::Peer(P2pPeerAction::BestTipUpdate { peer_id, .. })
+        | P2pAction::Channels(P2pChannelsAction::Rpc(P2pChannelsRpcAction::Ready { peer_id })) => {
+            store.service.is_partitioned_from(peer_id)
+        }
+        _ => false,
+    }
+}
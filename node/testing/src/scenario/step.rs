@@ -35,6 +35,13 @@ pub enum ScenarioStep {
         dialer: ClusterNodeId,
         listener: ListenerNode,
     },
+    /// Split rust nodes into groups which can't communicate with each other.
+    ///
+    /// Nodes not in any of the groups form one more group. Replaces the
+    /// current partition, empty `groups` heal it.
+    PartitionNodes {
+        groups: Vec<Vec<ClusterNodeId>>,
+    },
     CheckTimeouts {
         node_id: ClusterNodeId,
     },
@@ -35,8 +35,12 @@ use self::multi_node::vrf_epoch_bounds_evaluation::MultiNodeVrfEpochBoundsEvalua
 use self::p2p::pubsub::P2pReceiveBlock;
 use self::record_replay::block_production::RecordReplayBlockProduction;
 use self::record_replay::bootstrap::RecordReplayBootstrap;
+use self::simulation::long_range_fork::SimulationLongRangeFork;
+use self::simulation::shadow_block_production::SimulationShadowBlockProduction;
+use self::simulation::short_range_fork::SimulationShortRangeFork;
 use self::simulation::small::SimulationSmall;
 use self::simulation::small_forever_real_time::SimulationSmallForeverRealTime;
+use self::simulation::stop_slots::SimulationStopSlots;
 use self::solo_node::sync_to_genesis::SoloNodeSyncToGenesis;
 use self::solo_node::sync_to_genesis_custom::SoloNodeSyncToGenesisCustom;
 use self::solo_node::{
@@ -63,6 +67,10 @@ pub enum Scenarios {
     MultiNodeBasicConnectivityPeerDiscovery(MultiNodeBasicConnectivityPeerDiscovery),
     SimulationSmall(SimulationSmall),
     SimulationSmallForeverRealTime(SimulationSmallForeverRealTime),
+    SimulationShortRangeFork(SimulationShortRangeFork),
+    SimulationLongRangeFork(SimulationLongRangeFork),
+    SimulationStopSlots(SimulationStopSlots),
+    SimulationShadowBlockProduction(SimulationShadowBlockProduction),
     P2pReceiveBlock(P2pReceiveBlock),
     RecordReplayBootstrap(RecordReplayBootstrap),
     RecordReplayBlockProduction(RecordReplayBlockProduction),
@@ -86,6 +94,10 @@ impl Scenarios {
             Self::MultiNodeBasicConnectivityPeerDiscovery(_) => cfg!(feature = "p2p-webrtc"),
             Self::SimulationSmall(_) => true,
             Self::SimulationSmallForeverRealTime(_) => true,
+            Self::SimulationShortRangeFork(_) => true,
+            Self::SimulationLongRangeFork(_) => true,
+            Self::SimulationStopSlots(_) => true,
+            Self::SimulationShadowBlockProduction(_) => true,
             _ => false,
         }
     }
@@ -143,6 +155,10 @@ impl Scenarios {
             }
             Self::SimulationSmall(_) => SimulationSmall::DOCS,
             Self::SimulationSmallForeverRealTime(_) => SimulationSmallForeverRealTime::DOCS,
+            Self::SimulationShortRangeFork(_) => SimulationShortRangeFork::DOCS,
+            Self::SimulationLongRangeFork(_) => SimulationLongRangeFork::DOCS,
+            Self::SimulationStopSlots(_) => SimulationStopSlots::DOCS,
+            Self::SimulationShadowBlockProduction(_) => SimulationShadowBlockProduction::DOCS,
             Self::P2pReceiveBlock(_) => P2pReceiveBlock::DOCS,
             Self::RecordReplayBootstrap(_) => RecordReplayBootstrap::DOCS,
             Self::RecordReplayBlockProduction(_) => RecordReplayBlockProduction::DOCS,
@@ -178,6 +194,10 @@ impl Scenarios {
             Self::MultiNodeBasicConnectivityPeerDiscovery(v) => v.run(runner).await,
             Self::SimulationSmall(v) => v.run(runner).await,
             Self::SimulationSmallForeverRealTime(v) => v.run(runner).await,
+            Self::SimulationShortRangeFork(v) => v.run(runner).await,
+            Self::SimulationLongRangeFork(v) => v.run(runner).await,
+            Self::SimulationStopSlots(v) => v.run(runner).await,
+            Self::SimulationShadowBlockProduction(v) => v.run(runner).await,
             Self::P2pReceiveBlock(v) => v.run(runner).await,
             Self::RecordReplayBootstrap(v) => v.run(runner).await,
             Self::RecordReplayBlockProduction(v) => v.run(runner).await,
@@ -45,6 +45,7 @@ impl MultiNodeVrfGetCorrectLedgers {
                     pub_key: sec_key.public_key().into(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    shadow: false,
                 },
                 sec_key,
             }),
@@ -52,6 +53,9 @@ impl MultiNodeVrfGetCorrectLedgers {
             timeouts: P2pTimeouts::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         });
 
         tokio::time::sleep(Duration::from_secs(2)).await;
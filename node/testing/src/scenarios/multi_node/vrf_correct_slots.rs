@@ -51,6 +51,7 @@ impl MultiNodeVrfGetCorrectSlots {
                     pub_key: sec_key.public_key().into(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    shadow: false,
                 },
                 sec_key,
             }),
@@ -58,6 +59,9 @@ impl MultiNodeVrfGetCorrectSlots {
             timeouts: P2pTimeouts::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         });
 
         tokio::time::sleep(Duration::from_secs(2)).await;
@@ -45,6 +45,9 @@ impl MultiNodeVrfEpochBoundsEvaluation {
             timeouts: P2pTimeouts::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         };
 
         let producer_node = runner.add_rust_node(RustNodeTestingConfig {
@@ -53,6 +56,7 @@ impl MultiNodeVrfEpochBoundsEvaluation {
                     pub_key: sec_key.public_key().into(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    shadow: false,
                 },
                 sec_key: sec_key.clone(),
             }),
@@ -0,0 +1,386 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{
+    BlockTimeTimeStableV1, TransactionSnarkWorkTStableV2Proofs, PROTOCOL_CONSTANTS,
+};
+use node::p2p::disconnection::P2pDisconnectionReason;
+use node::p2p::{P2pPeerStatus, P2pTimeouts, PeerId};
+use node::transition_frontier::genesis::{GenesisConfig, NonStakers};
+
+use crate::{
+    cluster::ClusterNodeId,
+    node::{is_invalid_proof, AdversaryPersona, Node, RustNodeTestingConfig},
+    scenarios::{
+        connect_rust_nodes, get_peer_state, peer_is_ready, wait_for_connection_event,
+        wait_for_nodes_listening_on_localhost, ClusterRunner, Driver, RunCfgAdvanceTime,
+    },
+    simulator::{Simulator, SimulatorAdversary, SimulatorConfig, SimulatorRunUntil},
+};
+
+/// Honest node should disconnect a peer sending a noise frame that can't be decrypted.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryMalformedNoiseFrames;
+
+impl AdversaryMalformedNoiseFrames {
+    pub async fn run(self, runner: ClusterRunner<'_>) {
+        honest_node_disconnects_adversary(runner, AdversaryPersona::MalformedNoiseFrames).await
+    }
+}
+
+/// Honest node should disconnect a peer sending a yamux frame with an unknown version.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryMalformedYamuxFrames;
+
+impl AdversaryMalformedYamuxFrames {
+    pub async fn run(self, runner: ClusterRunner<'_>) {
+        honest_node_disconnects_adversary(runner, AdversaryPersona::MalformedYamuxFrames).await
+    }
+}
+
+async fn honest_node_disconnects_adversary(runner: ClusterRunner<'_>, persona: AdversaryPersona) {
+    let mut driver = Driver::new(runner);
+
+    let testing_config = RustNodeTestingConfig::devnet_default().with_timeouts(P2pTimeouts {
+        best_tip_with_proof: None,
+        ..Default::default()
+    });
+    let (honest, _) = driver.add_rust_node(testing_config.clone());
+    let (adversary, adversary_peer_id) =
+        driver.add_rust_node(testing_config.with_adversary(persona));
+
+    assert!(
+        wait_for_nodes_listening_on_localhost(
+            &mut driver,
+            Duration::from_secs(30),
+            [honest, adversary]
+        )
+        .await
+        .unwrap(),
+        "nodes should be listening"
+    );
+
+    connect_rust_nodes(driver.inner_mut(), adversary, honest).await;
+
+    let disconnected = wait_for_connection_event(
+        &mut driver,
+        Duration::from_secs(60),
+        |node_id: ClusterNodeId, peer_id: &PeerId, peer_status: &P2pPeerStatus| {
+            node_id == honest
+                && peer_id == &adversary_peer_id
+                && (peer_status.is_error()
+                    || matches!(peer_status, P2pPeerStatus::Disconnected { .. }))
+        },
+    )
+    .await
+    .unwrap();
+    assert!(
+        disconnected,
+        "honest node should disconnect the adversary, but it is {:?}",
+        get_peer_state(driver.inner(), honest, &adversary_peer_id)
+    );
+    assert!(
+        !peer_is_ready(driver.inner(), honest, &adversary_peer_id),
+        "adversary should not be a ready peer of the honest node"
+    );
+}
+
+/// Honest nodes should reject blocks with invalid proofs, propagated by
+/// the adversary instead of its best tip.
+///
+/// Run until slot **20**, then until honest nodes agree on the best tip,
+/// none of which may contain an invalid proof. WebRTC peers get
+/// disconnected. Libp2p ones don't, as pubsub relays blocks before they
+/// are verified, so an honest relay can't be told apart from the adversary.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryInvalidBlockProofs;
+
+impl AdversaryInvalidBlockProofs {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::InvalidBlockProofs],
+            block_producer: false,
+            snark_worker: false,
+            late_nodes: 0,
+            late_nodes_slot: 0,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 20).await;
+        if cfg!(feature = "p2p-webrtc") {
+            assert_adversary_disconnected(&simulator, |reason| {
+                matches!(reason, P2pDisconnectionReason::BlockVerifyError)
+            });
+        }
+    }
+}
+
+/// Honest nodes should reject snark work with invalid proofs, produced by
+/// the adversary, and disconnect it.
+///
+/// Run until slot **60**, then until honest nodes agree on the best tip,
+/// none of which may contain an invalid proof.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary snark worker: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryInvalidSnarkProofs;
+
+impl AdversaryInvalidSnarkProofs {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::InvalidSnarkProofs],
+            block_producer: false,
+            snark_worker: true,
+            late_nodes: 0,
+            late_nodes_slot: 0,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 60).await;
+        assert_adversary_disconnected(&simulator, |reason| {
+            matches!(reason, P2pDisconnectionReason::SnarkPoolVerifyError)
+        });
+    }
+}
+
+/// Honest nodes should stay in consensus while the adversary produces two
+/// blocks for each slot it wins.
+///
+/// Run until slot **40**, then until honest nodes agree on the best tip,
+/// which may contain at most one block per slot. Both blocks are valid, so
+/// the adversary isn't disconnected.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary **whale** block producer: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryEquivocation;
+
+impl AdversaryEquivocation {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::Equivocate],
+            block_producer: true,
+            snark_worker: false,
+            late_nodes: 0,
+            late_nodes_slot: 0,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 40).await;
+
+        for (node_id, node) in honest_nodes_iter(&simulator, &runner) {
+            let best_chain = &node.state().transition_frontier.best_chain;
+            let slots = best_chain
+                .iter()
+                .map(|block| block.global_slot())
+                .collect::<BTreeSet<_>>();
+            assert_eq!(
+                slots.len(),
+                best_chain.len(),
+                "node_{node_id}: best chain contains multiple blocks for the same slot"
+            );
+        }
+    }
+}
+
+/// Nodes joining later should sync from honest peers and disconnect the
+/// adversary, answering ledger queries with wrong hashes.
+///
+/// Run until slot **20**, with **2** late nodes joining at slot **10**,
+/// connected only to the adversary until they reject its answers, then
+/// until all honest nodes agree on the best tip.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryWrongLedgerHashes;
+
+impl AdversaryWrongLedgerHashes {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::WrongLedgerHashes],
+            block_producer: false,
+            snark_worker: false,
+            late_nodes: 2,
+            late_nodes_slot: 10,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 20).await;
+
+        let disconnected_by = simulator
+            .adversary_disconnections()
+            .into_iter()
+            .filter(|d| {
+                matches!(
+                    d.reason,
+                    P2pDisconnectionReason::TransitionFrontierSyncLedgerInvalid
+                )
+            })
+            .map(|d| d.node_id)
+            .collect::<BTreeSet<_>>();
+        for node_id in simulator.late_node_ids() {
+            assert!(
+                disconnected_by.contains(node_id),
+                "late node_{node_id} didn't disconnect the adversary, disconnections: {:?}",
+                simulator.adversary_disconnections()
+            );
+        }
+    }
+}
+
+/// Nodes joining later should sync from honest peers, while the adversary
+/// never serves staged ledger parts.
+///
+/// Run until slot **550**, with **2** late nodes joining at slot **500**,
+/// once the root block moved past genesis, so that its staged ledger
+/// needs to be fetched. They are connected only to the adversary until
+/// they fail to fetch it, then until all honest nodes agree on the best
+/// tip. Withholding data isn't distinguishable from having pruned it, so
+/// the adversary isn't disconnected.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryWithholdStagedLedgerParts;
+
+impl AdversaryWithholdStagedLedgerParts {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::WithholdStagedLedgerParts],
+            block_producer: false,
+            snark_worker: false,
+            late_nodes: 2,
+            late_nodes_slot: 500,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 550).await;
+
+        for node_id in simulator.late_node_ids() {
+            let state = runner.node(*node_id).unwrap().state();
+            let root = state.transition_frontier.root().unwrap();
+            assert!(
+                !root.is_genesis(),
+                "late node_{node_id} synced with genesis root, staged ledger wasn't fetched"
+            );
+        }
+    }
+}
+
+/// Honest nodes should disconnect the adversary, sending more snark job
+/// commitments than requested.
+///
+/// Run until slot **30**, then until honest nodes agree on the best tip.
+///
+/// - **whale** block producers: **2**.
+/// - seed nodes: **1**.
+/// - snark workers: **1**.
+/// - adversary: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct AdversaryFloodSnarkCommitments;
+
+impl AdversaryFloodSnarkCommitments {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let adversary = SimulatorAdversary {
+            personas: vec![AdversaryPersona::FloodSnarkCommitments],
+            block_producer: false,
+            snark_worker: false,
+            late_nodes: 0,
+            late_nodes_slot: 0,
+        };
+        let simulator = simulate_adversary(&mut runner, adversary, 30).await;
+        assert_adversary_disconnected(&simulator, |reason| {
+            matches!(reason, P2pDisconnectionReason::P2pChannelMsgUnexpected(_))
+        });
+    }
+}
+
+/// Simulates honest nodes together with the adversary, until the given
+/// slot and then until honest nodes agree on the best tip.
+///
+/// Honest nodes must not have a block or snark work with an invalid proof
+/// in their best chain.
+async fn simulate_adversary(
+    runner: &mut ClusterRunner<'_>,
+    adversary: SimulatorAdversary,
+    run_until_slot: u32,
+) -> Simulator {
+    let initial_time = redux::Timestamp::global_now();
+    let mut constants = PROTOCOL_CONSTANTS.clone();
+    constants.genesis_state_timestamp =
+        BlockTimeTimeStableV1((u64::from(initial_time) / 1_000_000).into());
+    let genesis_cfg = GenesisConfig::Counts {
+        whales: 3,
+        fish: 0,
+        non_stakers: NonStakers::None,
+        constants,
+    };
+    let cfg = SimulatorConfig {
+        genesis: genesis_cfg.into(),
+        seed_nodes: 1,
+        normal_nodes: 0,
+        snark_workers: 1,
+        block_producers: 2,
+        shadow_block_producers: 0,
+        advance_time: RunCfgAdvanceTime::Rand(10..=200),
+        run_until: SimulatorRunUntil::Slot(run_until_slot),
+        run_until_timeout: Duration::from_secs(30 * 60),
+        recorder: Default::default(),
+        slot_tx_end: None,
+        slot_chain_end: None,
+        partitions: Vec::new(),
+        adversary: Some(adversary),
+    };
+    let mut simulator = Simulator::new(initial_time, cfg);
+    simulator.run(runner).await;
+    simulator
+        .run_until_honest_nodes_converged(runner, Duration::from_secs(5 * 60))
+        .await;
+
+    for (node_id, node) in honest_nodes_iter(&simulator, runner) {
+        for block in &node.state().transition_frontier.best_chain {
+            assert!(
+                !is_invalid_proof(&block.header().protocol_state_proof.0),
+                "node_{node_id}: block {} with invalid proof in the best chain",
+                block.hash()
+            );
+            let invalid_work = block.completed_works_iter().any(|work| match &work.proofs {
+                TransactionSnarkWorkTStableV2Proofs::One(p) => is_invalid_proof(&p.0.proof.0),
+                TransactionSnarkWorkTStableV2Proofs::Two((p1, p2)) => {
+                    is_invalid_proof(&p1.0.proof.0) || is_invalid_proof(&p2.0.proof.0)
+                }
+            });
+            assert!(
+                !invalid_work,
+                "node_{node_id}: block {} with invalid snark work in the best chain",
+                block.hash()
+            );
+        }
+    }
+    simulator
+}
+
+fn honest_nodes_iter<'a>(
+    simulator: &'a Simulator,
+    runner: &'a ClusterRunner<'_>,
+) -> impl 'a + Iterator<Item = (ClusterNodeId, &'a Node)> {
+    runner
+        .nodes_iter()
+        .filter(|(node_id, _)| Some(*node_id) != simulator.adversary_node_id())
+}
+
+fn assert_adversary_disconnected<F>(simulator: &Simulator, is_expected: F)
+where
+    F: Fn(&P2pDisconnectionReason) -> bool,
+{
+    let disconnections = simulator.adversary_disconnections();
+    assert!(
+        disconnections.iter().any(|d| is_expected(&d.reason)),
+        "honest nodes didn't disconnect the adversary for the expected reason, disconnections: {disconnections:?}"
+    );
+}
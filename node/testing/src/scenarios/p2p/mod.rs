@@ -1,3 +1,4 @@
+pub mod adversary;
 pub mod basic_connection_handling;
 pub mod basic_incoming_connections;
 pub mod basic_outgoing_connections;
@@ -33,10 +33,15 @@ impl RecordReplayBlockProduction {
             normal_nodes: 1,
             snark_workers: 1,
             block_producers: 3,
+            shadow_block_producers: 0,
             advance_time: RunCfgAdvanceTime::Rand(1..=200),
             run_until: SimulatorRunUntil::BlockchainLength(10),
             run_until_timeout: Duration::from_secs(10 * 60),
             recorder: Recorder::StateWithInputActions,
+            slot_tx_end: None,
+            slot_chain_end: None,
+            partitions: Vec::new(),
+            adversary: None,
         };
         let mut simulator = Simulator::new(initial_time, cfg);
         simulator.run(&mut runner).await;
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{BlockTimeTimeStableV1, PROTOCOL_CONSTANTS};
+use node::transition_frontier::genesis::{GenesisConfig, NonStakers};
+
+use crate::{
+    scenarios::{ClusterRunner, RunCfgAdvanceTime},
+    simulator::{
+        Simulator, SimulatorConfig, SimulatorForkRange, SimulatorPartition, SimulatorRunUntil,
+    },
+};
+
+/// Long range fork simulation.
+///
+/// Block producers are split into two groups, each with one whale, from
+/// slot **4000** of the first epoch, before its lock checkpoint, to slot
+/// **7340** in the second epoch. Once the split heals, nodes on the losing
+/// chain must switch with a long range fork decision, re-inject its
+/// payments into the transaction pool and agree on the best tip within
+/// **20** slots.
+///
+/// - **whale** block producers: **2**.
+/// - **fish** block producers: **4**.
+/// - seed nodes: **1**.
+/// - normal nodes: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct SimulationLongRangeFork;
+
+impl SimulationLongRangeFork {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let initial_time = redux::Timestamp::global_now();
+        let mut constants = PROTOCOL_CONSTANTS.clone();
+        constants.genesis_state_timestamp =
+            BlockTimeTimeStableV1((u64::from(initial_time) / 1_000_000).into());
+        let genesis_cfg = GenesisConfig::Counts {
+            whales: 2,
+            fish: 4,
+            non_stakers: NonStakers::Count(4),
+            constants,
+        };
+        let cfg = SimulatorConfig {
+            genesis: genesis_cfg.into(),
+            seed_nodes: 1,
+            normal_nodes: 1,
+            snark_workers: 1,
+            block_producers: 6,
+            shadow_block_producers: 0,
+            advance_time: RunCfgAdvanceTime::Rand(10..=200),
+            run_until: SimulatorRunUntil::Epoch(2),
+            run_until_timeout: Duration::from_secs(60 * 60),
+            recorder: Default::default(),
+            slot_tx_end: None,
+            slot_chain_end: None,
+            partitions: vec![SimulatorPartition {
+                start_slot: 4000,
+                slots: 3340,
+                block_producer_groups: vec![vec![0, 2, 3], vec![1, 4, 5]],
+                payments_per_group: 3,
+                fork_range: SimulatorForkRange::Long,
+                converge_within_slots: 20,
+            }],
+            adversary: None,
+        };
+        let mut simulator = Simulator::new(initial_time, cfg);
+        simulator.run(&mut runner).await;
+    }
+}
@@ -1,2 +1,6 @@
+pub mod long_range_fork;
+pub mod shadow_block_production;
+pub mod short_range_fork;
 pub mod small;
 pub mod small_forever_real_time;
+pub mod stop_slots;
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{BlockTimeTimeStableV1, PROTOCOL_CONSTANTS};
+use node::{
+    stats::block_producer::BlockProductionStatus,
+    transition_frontier::genesis::{GenesisConfig, NonStakers},
+};
+
+use crate::{
+    scenarios::{ClusterRunner, RunCfgAdvanceTime},
+    simulator::{Simulator, SimulatorConfig, SimulatorRunUntil},
+};
+
+/// Shadow block production simulation.
+///
+/// Run until slot **20**. Shadow block producer must produce blocks
+/// without injecting them, proving the ones for the slots it actually won,
+/// so that the proving duration is recorded in its stats.
+///
+/// - **whale** block producers: **1**.
+/// - **whale** shadow block producers: **1**.
+/// - seed nodes: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct SimulationShadowBlockProduction;
+
+impl SimulationShadowBlockProduction {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let initial_time = redux::Timestamp::global_now();
+        let mut constants = PROTOCOL_CONSTANTS.clone();
+        constants.genesis_state_timestamp =
+            BlockTimeTimeStableV1((u64::from(initial_time) / 1_000_000).into());
+        let genesis_cfg = GenesisConfig::Counts {
+            whales: 2,
+            fish: 0,
+            non_stakers: NonStakers::None,
+            constants,
+        };
+        let cfg = SimulatorConfig {
+            genesis: genesis_cfg.into(),
+            seed_nodes: 1,
+            normal_nodes: 0,
+            snark_workers: 0,
+            block_producers: 1,
+            shadow_block_producers: 1,
+            advance_time: RunCfgAdvanceTime::Rand(10..=200),
+            run_until: SimulatorRunUntil::Slot(20),
+            run_until_timeout: Duration::from_secs(30 * 60),
+            recorder: Default::default(),
+            slot_tx_end: None,
+            slot_chain_end: None,
+            partitions: Vec::new(),
+            adversary: None,
+        };
+        let mut simulator = Simulator::new(initial_time, cfg);
+        simulator.run(&mut runner).await;
+
+        let shadow_node_id = runner
+            .nodes_iter()
+            .find(|(_, node)| {
+                node.config()
+                    .block_producer
+                    .as_ref()
+                    .map_or(false, |bp| bp.config.shadow)
+            })
+            .map(|(node_id, _)| node_id)
+            .expect("shadow block producer node not found");
+        let shadow_pub_key = runner
+            .node(shadow_node_id)
+            .unwrap()
+            .config()
+            .block_producer
+            .as_ref()
+            .unwrap()
+            .config
+            .pub_key
+            .clone();
+
+        for (node_id, node) in runner.nodes_iter() {
+            let best_chain = &node.state().transition_frontier.best_chain;
+            assert!(
+                best_chain
+                    .iter()
+                    .all(|block| block.producer() != &shadow_pub_key),
+                "node_{node_id}: block produced in shadow mode was injected"
+            );
+        }
+
+        let attempts = runner
+            .block_production_attempts(shadow_node_id)
+            .expect("shadow block producer node not found");
+        let proven = attempts
+            .iter()
+            .filter(|attempt| matches!(attempt.status, BlockProductionStatus::Shadowed))
+            .filter_map(|attempt| {
+                let start = attempt.times.proof_create_start?;
+                let end = attempt.times.proof_create_end?;
+                Some(end.checked_sub(start).unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+        eprintln!(
+            "shadow block production attempts: {}, proven: {}, proving durations: {proven:?}",
+            attempts.len(),
+            proven.len()
+        );
+        assert!(
+            !proven.is_empty(),
+            "no block was proven in shadow mode, attempts: {attempts:?}"
+        );
+    }
+}
@@ -37,10 +37,15 @@ impl SimulationSmall {
             normal_nodes: 2,
             snark_workers: 1,
             block_producers: 6,
+            shadow_block_producers: 0,
             advance_time: RunCfgAdvanceTime::Rand(10..=200),
             run_until: SimulatorRunUntil::Epoch(3),
             run_until_timeout: Duration::from_secs(30 * 60),
             recorder: Default::default(),
+            slot_tx_end: None,
+            slot_chain_end: None,
+            partitions: Vec::new(),
+            adversary: None,
         };
         let mut simulator = Simulator::new(initial_time, cfg);
         simulator.run(&mut runner).await;
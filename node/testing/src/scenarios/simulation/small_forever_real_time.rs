@@ -37,10 +37,15 @@ impl SimulationSmallForeverRealTime {
             normal_nodes: 2,
             snark_workers: 1,
             block_producers: 3,
+            shadow_block_producers: 0,
             advance_time: RunCfgAdvanceTime::Real,
             run_until: SimulatorRunUntil::Forever,
             run_until_timeout: Duration::MAX,
             recorder: Default::default(),
+            slot_tx_end: None,
+            slot_chain_end: None,
+            partitions: Vec::new(),
+            adversary: None,
         };
         let mut simulator = Simulator::new(initial_time, cfg);
         simulator.run(&mut runner).await;
@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{BlockTimeTimeStableV1, PROTOCOL_CONSTANTS};
+use node::{
+    event_source::Event,
+    rpc::{RpcInjectPayment, RpcRequest},
+    transition_frontier::genesis::{GenesisConfig, NonStakers},
+    ActionKind,
+};
+
+use crate::{
+    cluster::ClusterNodeId,
+    scenario::ScenarioStep,
+    scenarios::{ClusterRunner, RunCfg, RunCfgAdvanceTime},
+    simulator::{Simulator, SimulatorConfig, SimulatorRunUntil},
+};
+
+const SLOT_TX_END: u32 = 10;
+const SLOT_CHAIN_END: u32 = 20;
+
+/// Hard fork stop slots simulation.
+///
+/// Nodes are configured with `slot_tx_end` **10** and `slot_chain_end`
+/// **20** and run until slot **25**. Blocks from slot 10 must have empty
+/// staged ledger diffs, no block may be produced from slot 20 and payments
+/// injected after slot 10 must be rejected by the transaction pool.
+///
+/// - **whale** block producers: **2**.
+/// - **fish** block producers: **2**.
+/// - seed nodes: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct SimulationStopSlots;
+
+impl SimulationStopSlots {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let initial_time = redux::Timestamp::global_now();
+        let mut constants = PROTOCOL_CONSTANTS.clone();
+        constants.genesis_state_timestamp =
+            BlockTimeTimeStableV1((u64::from(initial_time) / 1_000_000).into());
+        let genesis_cfg = GenesisConfig::Counts {
+            whales: 2,
+            fish: 2,
+            non_stakers: NonStakers::Count(2),
+            constants,
+        };
+        let cfg = SimulatorConfig {
+            genesis: genesis_cfg.into(),
+            seed_nodes: 1,
+            normal_nodes: 0,
+            snark_workers: 1,
+            block_producers: 4,
+            shadow_block_producers: 0,
+            advance_time: RunCfgAdvanceTime::Rand(10..=200),
+            run_until: SimulatorRunUntil::Slot(SLOT_CHAIN_END + 5),
+            run_until_timeout: Duration::from_secs(30 * 60),
+            recorder: Default::default(),
+            slot_tx_end: Some(SLOT_TX_END),
+            slot_chain_end: Some(SLOT_CHAIN_END),
+            partitions: Vec::new(),
+            adversary: None,
+        };
+        let mut simulator = Simulator::new(initial_time, cfg);
+        simulator.run(&mut runner).await;
+
+        for (node_id, node) in runner.nodes_iter() {
+            let best_chain = &node.state().transition_frontier.best_chain;
+            let best_tip = best_chain.last().expect("node must have a best tip");
+            assert!(
+                best_tip.global_slot() < SLOT_CHAIN_END,
+                "node_{node_id}: best tip {} is at slot {}, after slot_chain_end",
+                best_tip.hash(),
+                best_tip.global_slot()
+            );
+            for block in best_chain
+                .iter()
+                .filter(|block| block.global_slot() >= SLOT_TX_END)
+            {
+                assert!(
+                    block.body().is_empty(),
+                    "node_{node_id}: block {} at slot {} isn't empty, after slot_tx_end",
+                    block.hash(),
+                    block.global_slot()
+                );
+            }
+        }
+
+        let bp_pub_keys = runner
+            .nodes_iter()
+            .filter_map(|(_, node)| {
+                let sec_key = &node.config().block_producer.as_ref()?.sec_key;
+                Some(sec_key.public_key())
+            })
+            .collect::<BTreeSet<_>>();
+        let (sec_key, account) = runner
+            .accounts_with_sec_keys(ClusterNodeId::new_unchecked(0))
+            .find(|(sec_key, _)| !bp_pub_keys.contains(&sec_key.public_key()))
+            .expect("no account to send payment from");
+        let payment = RpcInjectPayment::new_signed(
+            &sec_key,
+            sec_key.public_key(),
+            1_000_000_000,
+            10_000_000,
+            account.nonce.as_u32(),
+            u32::MAX,
+            String::new(),
+        );
+
+        let node_id = ClusterNodeId::new_unchecked(0);
+        let pool_size = runner
+            .node(node_id)
+            .unwrap()
+            .state()
+            .transaction_pool
+            .size();
+        let rpc_id = runner.next_rpc_id();
+        let event = Event::Rpc(
+            rpc_id,
+            Box::new(RpcRequest::TransactionInject(vec![payment])),
+        );
+        runner
+            .exec_step(ScenarioStep::ManualEvent {
+                node_id,
+                event: Box::new(event),
+            })
+            .await
+            .unwrap();
+        runner
+            .run(
+                RunCfg::default()
+                    .timeout(Duration::from_secs(60))
+                    .action_handler(|_, _, _, action| {
+                        matches!(
+                            action.action().kind(),
+                            ActionKind::RpcTransactionInjectFailure
+                        )
+                    }),
+            )
+            .await
+            .expect("payment after slot_tx_end must be rejected");
+        assert_eq!(
+            runner
+                .node(node_id)
+                .unwrap()
+                .state()
+                .transaction_pool
+                .size(),
+            pool_size,
+            "payment after slot_tx_end must not be added to the pool"
+        );
+    }
+}
@@ -55,6 +55,9 @@ impl SoloNodeSyncToGenesis {
             timeouts: Default::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         });
 
         runner
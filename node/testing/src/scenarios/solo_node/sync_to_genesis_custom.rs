@@ -71,6 +71,9 @@ impl SoloNodeSyncToGenesisCustom {
             timeouts: P2pTimeouts::default(),
             libp2p_port: None,
             recorder: Default::default(),
+            adversary: Vec::new(),
+            slot_tx_end: None,
+            slot_chain_end: None,
         });
 
         runner
@@ -1,6 +1,6 @@
 mod rpc_service;
 
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::sync::Mutex;
 use std::time::Duration;
 use std::{collections::BTreeMap, sync::Arc};
@@ -8,11 +8,11 @@ use std::{collections::BTreeMap, sync::Arc};
 use ledger::dummy::dummy_transaction_proof;
 use ledger::proofs::transaction::ProofError;
 use ledger::scan_state::scan_state::transaction_snark::SokMessage;
+use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
 use ledger::Mask;
-use mina_p2p_messages::list::List;
 use mina_p2p_messages::string::ByteString;
 use mina_p2p_messages::v2::{
-    self, CurrencyFeeStableV1, LedgerHash, LedgerProofProdStableV2, MinaBaseProofStableV2,
+    CurrencyFeeStableV1, LedgerHash, LedgerProofProdStableV2, MinaBaseProofStableV2,
     MinaStateSnarkedLedgerStateWithSokStableV2, NonZeroCurvePoint,
     ProverExtendBlockchainInputStableV2, SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single,
     StateHash, TransactionSnarkStableV2, TransactionSnarkWorkTStableV2Proofs,
@@ -20,6 +20,7 @@ use mina_p2p_messages::v2::{
 use node::account::AccountPublicKey;
 use node::block_producer::vrf_evaluator::VrfEvaluatorInput;
 use node::block_producer::BlockProducerEvent;
+use node::core::block::ArcBlockWithHash;
 use node::core::channels::mpsc;
 use node::core::snark::{Snark, SnarkJobId};
 use node::external_snark_worker::ExternalSnarkWorkerEvent;
@@ -27,14 +28,15 @@ use node::p2p::service_impl::webrtc_with_libp2p::P2pServiceWebrtcWithLibp2p;
 use node::p2p::P2pCryptoService;
 use node::recorder::Recorder;
 use node::service::{
-    BlockProducerService, BlockProducerVrfEvaluatorService, TransitionFrontierGenesisService,
+    BlockProducerService, BlockProducerVrfEvaluatorService, TransitionFrontierArchiveService,
+    TransitionFrontierGenesisService,
 };
 use node::snark::block_verify::{
-    SnarkBlockVerifyId, SnarkBlockVerifyService, VerifiableBlockWithHash,
+    SnarkBlockVerifyError, SnarkBlockVerifyId, SnarkBlockVerifyService, VerifiableBlockWithHash,
 };
 use node::snark::user_command_verify::SnarkUserCommandVerifyId;
 use node::snark::user_command_verify_effectful::SnarkUserCommandVerifyService;
-use node::snark::work_verify::{SnarkWorkVerifyId, SnarkWorkVerifyService};
+use node::snark::work_verify::{SnarkWorkVerifyError, SnarkWorkVerifyId, SnarkWorkVerifyService};
 use node::snark::{SnarkEvent, VerifierIndex, VerifierSRS};
 use node::snark_pool::SnarkPoolService;
 use node::stats::Stats;
@@ -53,7 +55,7 @@ use openmina_node_native::NodeService;
 use redux::Instant;
 
 use crate::cluster::{ClusterNodeId, ProofKind};
-use crate::node::NonDeterministicEvent;
+use crate::node::{adversary_event, is_invalid_proof, AdversaryPersona, NonDeterministicEvent};
 
 pub type DynEffects = Box<dyn FnMut(&State, &NodeTestingService, &ActionWithMeta) + Send>;
 
@@ -132,6 +134,10 @@ pub struct NodeTestingService {
     pending_events: PendingEvents,
     //pending_events: PendingRequests<PendingEventIdType, Event>,
     dyn_effects: Option<DynEffects>,
+    /// Malicious behaviour of the node, honest if empty.
+    adversary: Vec<AdversaryPersona>,
+    /// Peers on the other side of a simulated network partition.
+    partitioned_peers: BTreeSet<PeerId>,
 
     snarker_sok_digest: Option<ByteString>,
     /// Once dropped, it will cause all threads associated to shutdown.
@@ -149,6 +155,8 @@ impl NodeTestingService {
             monotonic_time: Instant::now(),
             pending_events: PendingEvents::new(),
             dyn_effects: None,
+            adversary: Vec::new(),
+            partitioned_peers: Default::default(),
             snarker_sok_digest: None,
             _shutdown,
         }
@@ -182,6 +190,24 @@ impl NodeTestingService {
         self
     }
 
+    pub fn adversary(&self) -> &[AdversaryPersona] {
+        &self.adversary
+    }
+
+    pub fn set_adversary(&mut self, personas: Vec<AdversaryPersona>) -> &mut Self {
+        self.adversary = personas;
+        self
+    }
+
+    pub fn is_partitioned_from(&self, peer_id: &PeerId) -> bool {
+        self.partitioned_peers.contains(peer_id)
+    }
+
+    pub fn set_partitioned_peers(&mut self, peers: BTreeSet<PeerId>) -> &mut Self {
+        self.partitioned_peers = peers;
+        self
+    }
+
     pub fn advance_time(&mut self, by_nanos: u64) {
         self.monotonic_time += Duration::from_nanos(by_nanos);
     }
@@ -217,6 +243,7 @@ impl NodeTestingService {
                     eprintln!("dropping non-deterministic event: {event:?}");
                     continue;
                 }
+                let event = adversary_event(&self.adversary, event);
                 self.pending_events.add(event);
             }
         }
@@ -244,6 +271,7 @@ impl NodeTestingService {
                 }
             }
         };
+        let event = adversary_event(&self.adversary, event);
         let id = self.pending_events.add(event);
         Some((id, self.pending_events.get(id).unwrap()))
     }
@@ -271,6 +299,10 @@ impl node::Service for NodeTestingService {
         self.real.stats()
     }
 
+    fn verifier_stats(&mut self) -> Option<node::snark::SnarkVerifierStats> {
+        self.real.verifier_stats()
+    }
+
     fn recorder(&mut self) -> &mut Recorder {
         self.real.recorder()
     }
@@ -322,6 +354,16 @@ impl TransitionFrontierGenesisService for NodeTestingService {
     }
 }
 
+impl TransitionFrontierArchiveService for NodeTestingService {
+    fn archive_block_applied(&mut self, block: &ArcBlockWithHash) {
+        TransitionFrontierArchiveService::archive_block_applied(&mut self.real, block);
+    }
+
+    fn archive_best_chain_update(&mut self, best_chain: &[ArcBlockWithHash]) {
+        TransitionFrontierArchiveService::archive_best_chain_update(&mut self.real, best_chain);
+    }
+}
+
 impl P2pServiceWebrtc for NodeTestingService {
     type Event = Event;
 
@@ -370,10 +412,16 @@ impl SnarkBlockVerifyService for NodeTestingService {
     ) {
         match self.proof_kind() {
             ProofKind::Dummy | ProofKind::ConstraintsChecked => {
+                let proof = &block.header_ref().protocol_state_proof;
+                let res = if is_invalid_proof(&proof.0) {
+                    Err(SnarkBlockVerifyError::VerificationFailed)
+                } else {
+                    Ok(())
+                };
                 let _ = self
                     .real
                     .event_sender()
-                    .send(SnarkEvent::BlockVerify(req_id, Ok(())).into());
+                    .send(SnarkEvent::BlockVerify(req_id, res).into());
             }
             ProofKind::Full => SnarkBlockVerifyService::verify_init(
                 &mut self.real,
@@ -392,7 +440,7 @@ impl SnarkUserCommandVerifyService for NodeTestingService {
         req_id: SnarkUserCommandVerifyId,
         verifier_index: Arc<VerifierIndex>,
         verifier_srs: Arc<Mutex<VerifierSRS>>,
-        commands: List<v2::MinaBaseUserCommandStableV2>,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
     ) {
         SnarkUserCommandVerifyService::verify_init(
             &mut self.real,
@@ -414,10 +462,22 @@ impl SnarkWorkVerifyService for NodeTestingService {
     ) {
         match self.proof_kind() {
             ProofKind::Dummy | ProofKind::ConstraintsChecked => {
+                let is_invalid = work.iter().any(|snark| {
+                    let proofs = match &*snark.proofs {
+                        TransactionSnarkWorkTStableV2Proofs::One(p) => vec![p],
+                        TransactionSnarkWorkTStableV2Proofs::Two((p1, p2)) => vec![p1, p2],
+                    };
+                    proofs.iter().any(|p| is_invalid_proof(&p.0.proof.0))
+                });
+                let res = if is_invalid {
+                    Err(SnarkWorkVerifyError::VerificationFailed)
+                } else {
+                    Ok(())
+                };
                 let _ = self
                     .real
                     .event_sender()
-                    .send(SnarkEvent::WorkVerify(req_id, Ok(())).into());
+                    .send(SnarkEvent::WorkVerify(req_id, res).into());
             }
             ProofKind::Full => SnarkWorkVerifyService::verify_init(
                 &mut self.real,
@@ -19,6 +19,10 @@ impl RpcService for super::NodeTestingService {
         respond_block_producer_stats_get,
         node::rpc::RpcBlockProducerStatsGetResponse
     );
+    to_real!(
+        respond_verifier_stats_get,
+        node::rpc::RpcVerifierStatsGetResponse
+    );
 
     to_real!(
         respond_action_stats_get,
@@ -77,6 +81,22 @@ impl RpcService for super::NodeTestingService {
         respond_discovery_bootstrap_stats,
         node::rpc::RpcDiscoveryBoostrapStatsResponse
     );
+    to_real!(
+        respond_discovery_record_put,
+        node::rpc::RpcDiscoveryRecordPutResponse
+    );
+    to_real!(
+        respond_discovery_record_get,
+        node::rpc::RpcDiscoveryRecordGetResponse
+    );
+    to_real!(
+        respond_discovery_start_providing,
+        node::rpc::RpcDiscoveryStartProvidingResponse
+    );
+    to_real!(
+        respond_discovery_providers_get,
+        node::rpc::RpcDiscoveryProvidersGetResponse
+    );
     to_real!(
         respond_transaction_pool,
         node::rpc::RpcTransactionPoolResponse
@@ -97,4 +117,17 @@ impl RpcService for super::NodeTestingService {
         respond_transition_frontier_commands,
         node::rpc::RpcTransitionFrontierUserCommandsResponse,
     );
+    to_real!(
+        respond_transition_frontier_snapshot_get,
+        node::rpc::RpcTransitionFrontierSnapshotGetResponse,
+    );
+    to_real!(
+        respond_ledger_export_get,
+        node::rpc::RpcLedgerExportGetResponse,
+    );
+    to_real!(
+        respond_invariant_violations_get,
+        node::rpc::RpcInvariantViolationsGetResponse,
+    );
+    to_real!(respond_log_filters, node::rpc::RpcLogFiltersResponse);
 }
@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{
+    CurrencyFeeStableV1, UnsignedExtendedUInt64Int64ForVersionTagsStableV1,
+};
+use node::p2p::disconnection::{P2pDisconnectionAction, P2pDisconnectionReason};
+use node::p2p::{P2pAction, PeerId};
+use node::{
+    Action, ActionKind, ActionWithMeta, BlockProducerConfig, SnarkerConfig, SnarkerStrategy, State,
+};
+
+use crate::{
+    cluster::ClusterNodeId,
+    node::{RustNodeBlockProducerTestingConfig, RustNodeTestingConfig},
+    scenarios::{ClusterRunner, DynEffectsData, RunCfg},
+    service::NodeTestingService,
+};
+
+use super::{partition::connect_nodes, Simulator};
+
+/// Adversary disconnected by an honest node.
+#[derive(Debug, Clone)]
+pub struct AdversaryDisconnection {
+    pub node_id: ClusterNodeId,
+    pub reason: P2pDisconnectionReason,
+}
+
+pub(super) fn record_adversary_disconnections(
+    adversary: Option<PeerId>,
+    disconnections: DynEffectsData<Vec<AdversaryDisconnection>>,
+) -> impl 'static + Send + FnMut(ClusterNodeId, &State, &NodeTestingService, &ActionWithMeta) -> bool
+{
+    move |node_id: ClusterNodeId, _: &State, _: &NodeTestingService, action: &ActionWithMeta| {
+        let Action::P2p(P2pAction::Disconnection(P2pDisconnectionAction::Init { peer_id, reason })) =
+            action.action()
+        else {
+            return false;
+        };
+        if Some(*peer_id) == adversary {
+            disconnections.inner().push(AdversaryDisconnection {
+                node_id,
+                reason: reason.clone(),
+            });
+        }
+        false
+    }
+}
+
+impl Simulator {
+    pub fn adversary_node_id(&self) -> Option<ClusterNodeId> {
+        self.adversary
+    }
+
+    /// Honest nodes which joined once the adversary was set up.
+    pub fn late_node_ids(&self) -> &[ClusterNodeId] {
+        &self.late_nodes
+    }
+
+    pub fn adversary_disconnections(&self) -> Vec<AdversaryDisconnection> {
+        self.adversary_disconnections.inner().clone()
+    }
+
+    pub(super) fn adversary_peer_id(&self, runner: &ClusterRunner<'_>) -> Option<PeerId> {
+        Some(runner.node(self.adversary?)?.peer_id())
+    }
+
+    pub(super) fn late_nodes_done(&self) -> bool {
+        self.config.adversary.as_ref().map_or(true, |adversary| {
+            self.late_nodes.len() >= adversary.late_nodes
+        })
+    }
+
+    pub(super) async fn set_up_adversary_node(&mut self, runner: &mut ClusterRunner<'_>) {
+        let Some(adversary) = self.config.adversary.clone() else {
+            return;
+        };
+        eprintln!("setting up adversary node: {:?}", adversary.personas);
+
+        let mut config = RustNodeTestingConfig {
+            max_peers: 100,
+            initial_peers: self.seed_node_dial_addrs(runner),
+            adversary: adversary.personas.clone(),
+            ..self.seed_config_async(runner).await
+        };
+
+        if adversary.block_producer {
+            let (sec_key, stake) = runner
+                .block_producer_sec_keys(ClusterNodeId::new_unchecked(0))
+                .into_iter()
+                .nth(self.config.block_producers + self.config.shadow_block_producers)
+                .expect("no block producer account left for the adversary");
+            eprintln!(
+                "adversary block producer({}) stake: {stake} mina",
+                sec_key.public_key()
+            );
+            config.block_producer = Some(RustNodeBlockProducerTestingConfig {
+                config: BlockProducerConfig {
+                    pub_key: sec_key.public_key().into(),
+                    custom_coinbase_receiver: None,
+                    proposed_protocol_version: None,
+                    shadow: false,
+                },
+                sec_key,
+            });
+        }
+
+        if adversary.snark_worker {
+            let used_pub_keys = runner
+                .nodes_iter()
+                .flat_map(|(_, node)| {
+                    let config = node.config();
+                    let bp = config
+                        .block_producer
+                        .as_ref()
+                        .map(|bp| bp.sec_key.public_key());
+                    let snarker = config
+                        .snark_worker
+                        .as_ref()
+                        .map(|snarker| snarker.public_key.clone());
+                    bp.into_iter().chain(snarker)
+                })
+                .chain(
+                    config
+                        .block_producer
+                        .as_ref()
+                        .map(|bp| bp.sec_key.public_key()),
+                )
+                .collect::<BTreeSet<_>>();
+            let (sec_key, _) = runner
+                .accounts_with_sec_keys(ClusterNodeId::new_unchecked(0))
+                .find(|(sec_key, _)| !used_pub_keys.contains(&sec_key.public_key()))
+                .expect("no snark worker account left for the adversary");
+            config.snark_worker = Some(SnarkerConfig {
+                public_key: sec_key.public_key(),
+                fee: CurrencyFeeStableV1(UnsignedExtendedUInt64Int64ForVersionTagsStableV1(
+                    10_000_000.into(),
+                )),
+                strategy: SnarkerStrategy::Sequential,
+                auto_commit: true,
+            });
+        }
+
+        self.adversary = Some(runner.add_rust_node(config));
+        self.wait_for_all_nodes_synced(runner).await;
+    }
+
+    /// Adds late nodes once their slot comes.
+    ///
+    /// They are connected to the seeds only once each of them either
+    /// disconnected the adversary, failed to fetch staged ledger parts from
+    /// it or synced.
+    pub(super) async fn update_late_nodes(&mut self, runner: &mut ClusterRunner<'_>) {
+        if self.late_nodes_done() {
+            return;
+        }
+        let Some(adversary) = self.config.adversary.clone() else {
+            return;
+        };
+        let Some(adversary_node_id) = self.adversary else {
+            return;
+        };
+        let Some(cur_slot) = runner
+            .nodes_iter()
+            .next()
+            .and_then(|(_, node)| node.state().cur_global_slot())
+        else {
+            return;
+        };
+        if cur_slot < adversary.late_nodes_slot {
+            return;
+        }
+        eprintln!("setting up late nodes: {}", adversary.late_nodes);
+
+        let node_config = RustNodeTestingConfig {
+            max_peers: 100,
+            initial_peers: vec![adversary_node_id.into()],
+            ..self.seed_config_async(runner).await
+        };
+        let late_nodes = (0..adversary.late_nodes)
+            .map(|_| runner.add_rust_node(node_config.clone()))
+            .collect::<Vec<_>>();
+
+        let adversary_peer_id = runner.node(adversary_node_id).unwrap().peer_id();
+        let done = DynEffectsData::new(BTreeSet::new());
+        let start_t = redux::Instant::now();
+        while done.inner().len() < late_nodes.len() {
+            assert!(
+                start_t.elapsed() < Duration::from_secs(300),
+                "late nodes didn't try to sync from the adversary"
+            );
+            let done = done.clone();
+            let late_nodes = late_nodes.clone();
+            let mut record_disconnections = record_adversary_disconnections(
+                Some(adversary_peer_id),
+                self.adversary_disconnections.clone(),
+            );
+            let _ = runner
+                .run(
+                    RunCfg::default()
+                        .advance_time(self.config.advance_time.clone())
+                        .timeout(Duration::from_secs(10))
+                        .action_handler(move |node_id, state, service, action| {
+                            record_disconnections(node_id, state, service, action);
+                            if !late_nodes.contains(&node_id) {
+                                return false;
+                            }
+                            let is_done = match action.action() {
+                                Action::P2p(P2pAction::Disconnection(
+                                    P2pDisconnectionAction::Init { peer_id, .. },
+                                )) => *peer_id == adversary_peer_id,
+                                action => matches!(
+                                    action.kind(),
+                                    ActionKind::TransitionFrontierSyncLedgerStagedPartsPeerFetchError
+                                        | ActionKind::TransitionFrontierSynced
+                                ),
+                            };
+                            is_done && done.inner().insert(node_id)
+                        }),
+                )
+                .await;
+        }
+
+        let seeds = self
+            .seed_nodes_iter(runner)
+            .map(|(node_id, _)| node_id)
+            .collect::<Vec<_>>();
+        for node_id in &late_nodes {
+            for seed_id in &seeds {
+                connect_nodes(runner, *node_id, *seed_id).await;
+            }
+        }
+        self.late_nodes = late_nodes;
+        self.wait_for_all_nodes_synced(runner).await;
+    }
+
+    /// Runs until all nodes except the adversary agree on the best tip.
+    pub async fn run_until_honest_nodes_converged(
+        &mut self,
+        runner: &mut ClusterRunner<'_>,
+        timeout: Duration,
+    ) {
+        let start_t = redux::Instant::now();
+        loop {
+            let mut best_tips = runner
+                .nodes_iter()
+                .filter(|(node_id, _)| Some(*node_id) != self.adversary)
+                .map(|(_, node)| {
+                    node.state()
+                        .transition_frontier
+                        .best_tip()
+                        .map(|b| b.hash().clone())
+                });
+            let first = best_tips.next().flatten();
+            if first.is_some() && best_tips.all(|best_tip| best_tip == first) {
+                return;
+            }
+            assert!(
+                start_t.elapsed() < timeout,
+                "honest nodes didn't converge within {timeout:?}"
+            );
+
+            tokio::task::yield_now().await;
+            let _ = runner
+                .run(
+                    RunCfg::default()
+                        .advance_time(self.config.advance_time.clone())
+                        .timeout(Duration::ZERO),
+                )
+                .await;
+        }
+    }
+}
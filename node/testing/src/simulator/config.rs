@@ -3,7 +3,10 @@ use std::{sync::Arc, time::Duration};
 use node::transition_frontier::genesis::GenesisConfig;
 use serde::{Deserialize, Serialize};
 
-use crate::{node::Recorder, scenarios::RunCfgAdvanceTime};
+use crate::{
+    node::{AdversaryPersona, Recorder},
+    scenarios::RunCfgAdvanceTime,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SimulatorConfig {
@@ -12,10 +15,25 @@ pub struct SimulatorConfig {
     pub normal_nodes: usize,
     pub snark_workers: usize,
     pub block_producers: usize,
+    /// Block producers in shadow mode, using keys of the accounts with
+    /// the largest stake after the ones of `block_producers`.
+    #[serde(default)]
+    pub shadow_block_producers: usize,
     pub advance_time: RunCfgAdvanceTime,
     pub run_until: SimulatorRunUntil,
     pub run_until_timeout: Duration,
     pub recorder: Recorder,
+    /// Hard fork stop slots, see [`node::TransitionFrontierConfig`].
+    #[serde(default)]
+    pub slot_tx_end: Option<u32>,
+    #[serde(default)]
+    pub slot_chain_end: Option<u32>,
+    /// Splits of block producers into isolated groups, applied in order.
+    #[serde(default)]
+    pub partitions: Vec<SimulatorPartition>,
+    /// Malicious node joining once honest nodes are set up.
+    #[serde(default)]
+    pub adversary: Option<SimulatorAdversary>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -23,4 +41,55 @@ pub enum SimulatorRunUntil {
     Forever,
     Epoch(u32),
     BlockchainLength(u32),
+    /// Until the current global slot reaches the given one.
+    Slot(u32),
+}
+
+/// Network partition between block producers, letting each group build its
+/// own chain until the split heals.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SimulatorPartition {
+    /// Global slot at which block producers get split.
+    pub start_slot: u32,
+    /// Number of slots after which the split heals.
+    pub slots: u32,
+    /// Indexes of block producers (largest stake first) in each group.
+    ///
+    /// The rest of the nodes, including seeds, join the first group.
+    pub block_producer_groups: Vec<Vec<usize>>,
+    /// Payments sent to each group while it is isolated.
+    ///
+    /// Once nodes converge, payments must be either in their best chain
+    /// or back in their transaction pool.
+    pub payments_per_group: u32,
+    /// Fork decision with which nodes must switch to the winning chain
+    /// once the split heals.
+    pub fork_range: SimulatorForkRange,
+    /// Number of slots after the split heals, within which all nodes must
+    /// agree on the best tip.
+    pub converge_within_slots: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulatorForkRange {
+    /// `ConsensusShortRangeForkDecision`.
+    Short,
+    /// `ConsensusLongRangeForkDecision`.
+    Long,
+}
+
+/// Malicious node, set up after all honest ones and connected to seeds.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SimulatorAdversary {
+    pub personas: Vec<AdversaryPersona>,
+    /// Whether adversary produces blocks, using the key of the account with
+    /// the largest stake after the ones of honest block producers.
+    pub block_producer: bool,
+    /// Whether adversary produces snark work.
+    pub snark_worker: bool,
+    /// Honest nodes joining at `late_nodes_slot`. They are connected only to
+    /// the adversary, until it fails to serve them or gets disconnected,
+    /// and only then to the seeds.
+    pub late_nodes: usize,
+    pub late_nodes_slot: u32,
 }
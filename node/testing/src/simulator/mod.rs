@@ -1,8 +1,14 @@
 mod config;
 pub use config::*;
+
+mod adversary;
+mod partition;
+use adversary::record_adversary_disconnections;
+pub use adversary::AdversaryDisconnection;
 use mina_p2p_messages::v2::{
     CurrencyFeeStableV1, UnsignedExtendedUInt64Int64ForVersionTagsStableV1,
 };
+use partition::{record_fork_choices, ForkChoice, PartitionStage};
 
 use std::{collections::BTreeSet, time::Duration};
 
@@ -12,12 +18,19 @@ use crate::{
     cluster::ClusterNodeId,
     node::{Node, RustNodeBlockProducerTestingConfig, RustNodeTestingConfig},
     scenario::ListenerNode,
-    scenarios::{ClusterRunner, RunCfg},
+    scenarios::{ClusterRunner, DynEffectsData, RunCfg},
 };
 
 pub struct Simulator {
     initial_time: redux::Timestamp,
     config: SimulatorConfig,
+    /// Index of the current partition in `config.partitions`.
+    partition_i: usize,
+    partition_stage: PartitionStage,
+    fork_choices: DynEffectsData<Vec<ForkChoice>>,
+    adversary: Option<ClusterNodeId>,
+    late_nodes: Vec<ClusterNodeId>,
+    adversary_disconnections: DynEffectsData<Vec<AdversaryDisconnection>>,
 }
 
 impl Simulator {
@@ -25,6 +38,12 @@ impl Simulator {
         Self {
             initial_time,
             config,
+            partition_i: 0,
+            partition_stage: PartitionStage::Pending,
+            fork_choices: DynEffectsData::new(Vec::new()),
+            adversary: None,
+            late_nodes: Vec::new(),
+            adversary_disconnections: DynEffectsData::new(Vec::new()),
         }
     }
 
@@ -45,6 +64,9 @@ impl Simulator {
             timeouts: Default::default(),
             libp2p_port: None,
             recorder: self.config.recorder.clone(),
+            adversary: Vec::new(),
+            slot_tx_end: self.config.slot_tx_end,
+            slot_chain_end: self.config.slot_chain_end,
         }
     }
 
@@ -55,11 +77,16 @@ impl Simulator {
                 && state.transition_frontier.best_tip().is_some()
         };
         while !runner.nodes_iter().all(|(_, node)| is_synced(node.state())) {
+            let mut record_disconnections = record_adversary_disconnections(
+                self.adversary_peer_id(runner),
+                self.adversary_disconnections.clone(),
+            );
             runner
                 .run(
                     RunCfg::default()
                         .timeout(Duration::from_secs(300))
-                        .action_handler(move |_, _, _, action| {
+                        .action_handler(move |node_id, state, service, action| {
+                            record_disconnections(node_id, state, service, action);
                             matches!(
                                 action.action().kind(),
                                 ActionKind::TransitionFrontierGenesisInject
@@ -165,9 +192,10 @@ impl Simulator {
     async fn set_up_block_producer_nodes(&mut self, runner: &mut ClusterRunner<'_>) {
         let block_producers = runner.block_producer_sec_keys(ClusterNodeId::new_unchecked(0));
 
-        assert!(self.config.block_producers <= block_producers.len());
+        let shadow_block_producers = self.config.shadow_block_producers;
+        assert!(self.config.block_producers + shadow_block_producers <= block_producers.len());
         eprintln!(
-            "setting up rust block producer nodes: {}/{}",
+            "setting up rust block producer nodes: {}/{}, in shadow mode: {shadow_block_producers}",
             self.config.block_producers,
             block_producers.len()
         );
@@ -178,12 +206,14 @@ impl Simulator {
             ..self.seed_config_async(runner).await
         };
 
-        for (sec_key, stake) in block_producers
+        for (i, (sec_key, stake)) in block_producers
             .into_iter()
-            .take(self.config.block_producers)
+            .take(self.config.block_producers + shadow_block_producers)
+            .enumerate()
         {
+            let shadow = i >= self.config.block_producers;
             eprintln!(
-                "block producer({}) stake: {stake} mina",
+                "block producer({}) stake: {stake} mina, shadow: {shadow}",
                 sec_key.public_key()
             );
             let config = RustNodeTestingConfig {
@@ -192,6 +222,7 @@ impl Simulator {
                         pub_key: sec_key.public_key().into(),
                         custom_coinbase_receiver: None,
                         proposed_protocol_version: None,
+                        shadow,
                     },
                     sec_key,
                 }),
@@ -208,6 +239,7 @@ impl Simulator {
         self.set_up_normal_nodes(runner).await;
         self.set_up_snark_worker_nodes(runner).await;
         self.set_up_block_producer_nodes(runner).await;
+        self.set_up_adversary_node(runner).await;
 
         let run_until = self.config.run_until.clone();
         let advance_time = self.config.advance_time.clone();
@@ -217,13 +249,24 @@ impl Simulator {
 
         while start_t.elapsed() < self.config.run_until_timeout {
             tokio::task::yield_now().await;
+            let mut record_fork_choices = record_fork_choices(self.fork_choices.clone());
+            let mut record_disconnections = record_adversary_disconnections(
+                self.adversary_peer_id(runner),
+                self.adversary_disconnections.clone(),
+            );
             let _ = runner
                 .run(
                     RunCfg::default()
                         .advance_time(advance_time.clone())
-                        .timeout(Duration::ZERO),
+                        .timeout(Duration::ZERO)
+                        .action_handler(move |node_id, state, service, action| {
+                            record_fork_choices(node_id, state, service, action)
+                                | record_disconnections(node_id, state, service, action)
+                        }),
                 )
                 .await;
+            self.update_partition(runner).await;
+            self.update_late_nodes(runner).await;
 
             let printed_elapsed_time = {
                 let state = runner.nodes_iter().next().unwrap().1.state();
@@ -262,8 +305,12 @@ impl Simulator {
                             consensus_state.epoch_count.as_u32() >= *epoch
                         }
                         SimulatorRunUntil::BlockchainLength(height) => best_tip.height() >= *height,
+                        SimulatorRunUntil::Slot(slot) => node
+                            .state()
+                            .cur_global_slot()
+                            .map_or(false, |cur| cur >= *slot),
                     };
-                    if stop {
+                    if stop && self.partitions_done() && self.late_nodes_done() {
                         return;
                     }
                 }
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{MinaBaseUserCommandStableV2, StateHash, TransactionHash};
+use node::consensus::{ConsensusAction, ConsensusBlockStatus};
+use node::event_source::Event;
+use node::rpc::{RpcInjectPayment, RpcRequest};
+use node::{Action, ActionWithMeta, State};
+
+use crate::{
+    cluster::ClusterNodeId,
+    scenario::ScenarioStep,
+    scenarios::{ClusterRunner, DynEffectsData, RunCfg},
+    service::NodeTestingService,
+};
+
+use super::{Simulator, SimulatorForkRange, SimulatorPartition};
+
+pub(super) enum PartitionStage {
+    Pending,
+    Split {
+        groups: Vec<Vec<ClusterNodeId>>,
+        payments: Vec<Vec<TransactionHash>>,
+    },
+    Healed {
+        groups: Vec<Vec<ClusterNodeId>>,
+        payments: Vec<Vec<TransactionHash>>,
+        slot: u32,
+        time: redux::Timestamp,
+        real_time: redux::Instant,
+        /// Best tips of the nodes when the split healed.
+        best_tips: BTreeMap<ClusterNodeId, StateHash>,
+    },
+}
+
+/// Fork decision which made a node take a new best tip.
+#[derive(Debug, Clone)]
+pub(super) struct ForkChoice {
+    node_id: ClusterNodeId,
+    range: SimulatorForkRange,
+    candidate: StateHash,
+    /// Best tip replaced by the candidate.
+    previous: StateHash,
+}
+
+pub(super) fn record_fork_choices(
+    choices: DynEffectsData<Vec<ForkChoice>>,
+) -> impl 'static + Send + FnMut(ClusterNodeId, &State, &NodeTestingService, &ActionWithMeta) -> bool
+{
+    move |node_id: ClusterNodeId, state: &State, _: &NodeTestingService, action: &ActionWithMeta| {
+        let (range, hash) = match action.action() {
+            Action::Consensus(ConsensusAction::ShortRangeForkResolve { hash }) => {
+                (SimulatorForkRange::Short, hash)
+            }
+            Action::Consensus(ConsensusAction::LongRangeForkResolve { hash }) => {
+                (SimulatorForkRange::Long, hash)
+            }
+            _ => return false,
+        };
+        let previous = match state.consensus.blocks.get(hash).map(|block| &block.status) {
+            Some(ConsensusBlockStatus::ShortRangeForkResolve {
+                compared_with: Some(previous),
+                decision,
+                ..
+            }) if decision.use_as_best_tip() => previous,
+            Some(ConsensusBlockStatus::LongRangeForkResolve {
+                compared_with,
+                decision,
+                ..
+            }) if decision.use_as_best_tip() => compared_with,
+            _ => return false,
+        };
+        choices.inner().push(ForkChoice {
+            node_id,
+            range,
+            candidate: hash.clone(),
+            previous: previous.clone(),
+        });
+        false
+    }
+}
+
+impl Simulator {
+    pub(super) fn partitions_done(&self) -> bool {
+        self.partition_i >= self.config.partitions.len()
+    }
+
+    /// Split, heal and check convergence of the current partition,
+    /// depending on the global slot.
+    pub(super) async fn update_partition(&mut self, runner: &mut ClusterRunner<'_>) {
+        let Some(partition) = self.config.partitions.get(self.partition_i).cloned() else {
+            return;
+        };
+        let Some(cur_slot) = runner
+            .nodes_iter()
+            .next()
+            .and_then(|(_, node)| node.state().cur_global_slot())
+        else {
+            return;
+        };
+
+        match std::mem::replace(&mut self.partition_stage, PartitionStage::Pending) {
+            PartitionStage::Pending if cur_slot >= partition.start_slot => {
+                self.partition_stage = split(runner, &partition).await;
+            }
+            PartitionStage::Split { groups, payments }
+                if cur_slot >= partition.start_slot + partition.slots =>
+            {
+                self.fork_choices.inner().clear();
+                self.partition_stage = heal(runner, groups, payments).await;
+            }
+            PartitionStage::Healed {
+                groups,
+                payments,
+                slot,
+                time,
+                real_time,
+                best_tips,
+            } => {
+                if !is_converged(runner) {
+                    assert!(
+                        cur_slot <= slot + partition.converge_within_slots,
+                        "nodes didn't converge within {} slots after the split healed",
+                        partition.converge_within_slots
+                    );
+                    self.partition_stage = PartitionStage::Healed {
+                        groups,
+                        payments,
+                        slot,
+                        time,
+                        real_time,
+                        best_tips,
+                    };
+                    return;
+                }
+                let virtual_elapsed = runner
+                    .nodes_iter()
+                    .next()
+                    .and_then(|(_, node)| node.state().time().checked_sub(time))
+                    .unwrap_or_default();
+                eprintln!(
+                    "[partition] nodes converged after {} slots, real: {:?}, virtual: {virtual_elapsed:?}",
+                    cur_slot - slot,
+                    real_time.elapsed()
+                );
+
+                // let ledger service finish pending transaction pool updates.
+                let _ = runner
+                    .run(RunCfg::default().timeout(Duration::from_secs(1)))
+                    .await;
+
+                let fork_choices = self.fork_choices.inner().clone();
+                check_fork_choices(runner, &partition, &best_tips, &fork_choices);
+                check_payments(runner, &groups, &payments);
+
+                self.partition_i += 1;
+            }
+            stage => self.partition_stage = stage,
+        }
+    }
+}
+
+async fn split(runner: &mut ClusterRunner<'_>, partition: &SimulatorPartition) -> PartitionStage {
+    let block_producers = runner
+        .nodes_iter()
+        .filter(|(_, node)| node.config().block_producer.is_some())
+        .map(|(node_id, _)| node_id)
+        .collect::<Vec<_>>();
+    let mut groups = partition
+        .block_producer_groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|i| block_producers[*i])
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let rest = runner
+        .nodes_iter()
+        .map(|(node_id, _)| node_id)
+        .filter(|node_id| !groups.iter().any(|group| group.contains(node_id)))
+        .collect::<Vec<_>>();
+    groups[0].extend(rest);
+
+    eprintln!("[partition] splitting nodes into groups: {groups:?}");
+    runner
+        .exec_step(ScenarioStep::PartitionNodes {
+            groups: groups.clone(),
+        })
+        .await
+        .unwrap();
+    for group in &groups {
+        for node_id in &group[1..] {
+            connect_nodes(runner, *node_id, group[0]).await;
+        }
+    }
+
+    let payments = send_payments(runner, &groups, partition.payments_per_group).await;
+    PartitionStage::Split { groups, payments }
+}
+
+async fn heal(
+    runner: &mut ClusterRunner<'_>,
+    groups: Vec<Vec<ClusterNodeId>>,
+    payments: Vec<Vec<TransactionHash>>,
+) -> PartitionStage {
+    eprintln!("[partition] healing the split");
+    runner
+        .exec_step(ScenarioStep::PartitionNodes { groups: Vec::new() })
+        .await
+        .unwrap();
+    for group in &groups[1..] {
+        connect_nodes(runner, group[0], groups[0][0]).await;
+    }
+
+    let best_tips = runner
+        .nodes_iter()
+        .filter_map(|(node_id, node)| {
+            let best_tip = node.state().transition_frontier.best_tip()?;
+            Some((node_id, best_tip.hash().clone()))
+        })
+        .collect();
+    let state = runner.nodes_iter().next().unwrap().1.state();
+    PartitionStage::Healed {
+        groups,
+        payments,
+        slot: state.cur_global_slot().unwrap_or_default(),
+        time: state.time(),
+        real_time: redux::Instant::now(),
+        best_tips,
+    }
+}
+
+pub(super) async fn connect_nodes(
+    runner: &mut ClusterRunner<'_>,
+    dialer: ClusterNodeId,
+    listener: ClusterNodeId,
+) {
+    let listener_peer_id = runner.node(listener).unwrap().peer_id();
+    let dialer_state = runner.node(dialer).unwrap().state();
+    if dialer_state.p2p.get_ready_peer(&listener_peer_id).is_some() {
+        return;
+    }
+    runner
+        .exec_step(ScenarioStep::ConnectNodes {
+            dialer,
+            listener: listener.into(),
+        })
+        .await
+        .unwrap();
+}
+
+/// Sends payments from a separate account to the first node of each group.
+async fn send_payments(
+    runner: &mut ClusterRunner<'_>,
+    groups: &[Vec<ClusterNodeId>],
+    count: u32,
+) -> Vec<Vec<TransactionHash>> {
+    if count == 0 {
+        return vec![Vec::new(); groups.len()];
+    }
+    let bp_pub_keys = runner
+        .nodes_iter()
+        .filter_map(|(_, node)| {
+            let sec_key = &node.config().block_producer.as_ref()?.sec_key;
+            Some(sec_key.public_key())
+        })
+        .collect::<BTreeSet<_>>();
+    let senders = runner
+        .accounts_with_sec_keys(ClusterNodeId::new_unchecked(0))
+        .filter(|(sec_key, _)| !bp_pub_keys.contains(&sec_key.public_key()))
+        .take(groups.len())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        senders.len(),
+        groups.len(),
+        "not enough accounts to send payments from"
+    );
+
+    let mut hashes = Vec::with_capacity(groups.len());
+    for (group, (sec_key, account)) in groups.iter().zip(senders) {
+        let payments = (0..count)
+            .map(|i| {
+                RpcInjectPayment::new_signed(
+                    &sec_key,
+                    sec_key.public_key(),
+                    1_000_000_000,
+                    10_000_000,
+                    account.nonce.as_u32() + i,
+                    u32::MAX,
+                    String::new(),
+                )
+            })
+            .collect::<Vec<_>>();
+        hashes.push(
+            payments
+                .iter()
+                .map(|payment| MinaBaseUserCommandStableV2::from(payment.clone()).hash())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+        );
+
+        let rpc_id = runner.next_rpc_id();
+        let event = Event::Rpc(rpc_id, Box::new(RpcRequest::TransactionInject(payments)));
+        runner
+            .exec_step(ScenarioStep::ManualEvent {
+                node_id: group[0],
+                event: Box::new(event),
+            })
+            .await
+            .unwrap();
+    }
+    hashes
+}
+
+fn is_converged(runner: &ClusterRunner<'_>) -> bool {
+    let mut best_tips = runner.nodes_iter().map(|(_, node)| {
+        node.state()
+            .transition_frontier
+            .best_tip()
+            .map(|b| b.hash())
+    });
+    let first = best_tips.next().flatten();
+    first.is_some() && best_tips.all(|best_tip| best_tip == first)
+}
+
+fn best_chain_hashes(state: &State) -> BTreeSet<&StateHash> {
+    state
+        .transition_frontier
+        .best_chain
+        .iter()
+        .map(|block| block.hash())
+        .collect()
+}
+
+/// Nodes whose chain lost must have switched to the winning chain with
+/// the expected fork decision.
+fn check_fork_choices(
+    runner: &ClusterRunner<'_>,
+    partition: &SimulatorPartition,
+    best_tips: &BTreeMap<ClusterNodeId, StateHash>,
+    fork_choices: &[ForkChoice],
+) {
+    let mut reorged = 0;
+    for (node_id, best_tip) in best_tips {
+        let best_chain = best_chain_hashes(runner.node(*node_id).unwrap().state());
+        if best_chain.contains(best_tip) {
+            continue;
+        }
+        reorged += 1;
+
+        let switch = fork_choices
+            .iter()
+            .filter(|choice| choice.node_id == *node_id)
+            .find(|choice| {
+                best_chain.contains(&choice.candidate) && !best_chain.contains(&choice.previous)
+            })
+            .unwrap_or_else(|| {
+                panic!("node_{node_id} switched to the winning chain without a fork decision")
+            });
+        eprintln!("[partition] node_{node_id} switched chains: {switch:?}");
+        assert_eq!(
+            switch.range, partition.fork_range,
+            "node_{node_id} switched chains with unexpected fork decision"
+        );
+    }
+    assert!(reorged > 0, "partition didn't result in competing chains");
+}
+
+/// Payments sent to a group must be either in the best chain of its nodes
+/// or, if the chain they were included in lost, back in the pool.
+fn check_payments(
+    runner: &ClusterRunner<'_>,
+    groups: &[Vec<ClusterNodeId>],
+    payments: &[Vec<TransactionHash>],
+) {
+    for (group, payments) in groups.iter().zip(payments) {
+        for node_id in group {
+            let state = runner.node(*node_id).unwrap().state();
+            let included = state
+                .transition_frontier
+                .best_chain
+                .iter()
+                .flat_map(|block| block.commands_iter())
+                .filter_map(|cmd| cmd.data.hash().ok());
+            let pooled = state
+                .transaction_pool
+                .get_all_transactions()
+                .into_iter()
+                .filter_map(|cmd| {
+                    MinaBaseUserCommandStableV2::from(&cmd.data.forget_check())
+                        .hash()
+                        .ok()
+                });
+            let known = included.chain(pooled).collect::<BTreeSet<_>>();
+
+            for hash in payments {
+                assert!(
+                    known.contains(hash),
+                    "node_{node_id}: payment {hash} is neither in the best chain nor in the transaction pool"
+                );
+            }
+        }
+    }
+}
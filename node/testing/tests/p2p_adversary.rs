@@ -0,0 +1,55 @@
+#[cfg(feature = "p2p-webrtc")]
+use openmina_node_testing::scenarios::p2p::adversary::AdversaryFloodSnarkCommitments;
+use openmina_node_testing::scenarios::p2p::adversary::{
+    AdversaryEquivocation, AdversaryInvalidBlockProofs, AdversaryInvalidSnarkProofs,
+    AdversaryMalformedNoiseFrames, AdversaryMalformedYamuxFrames,
+    AdversaryWithholdStagedLedgerParts, AdversaryWrongLedgerHashes,
+};
+
+mod common;
+
+scenario_test!(
+    malformed_noise_frames,
+    AdversaryMalformedNoiseFrames,
+    AdversaryMalformedNoiseFrames
+);
+
+scenario_test!(
+    malformed_yamux_frames,
+    AdversaryMalformedYamuxFrames,
+    AdversaryMalformedYamuxFrames
+);
+
+scenario_test!(
+    invalid_block_proofs,
+    AdversaryInvalidBlockProofs,
+    AdversaryInvalidBlockProofs
+);
+
+scenario_test!(
+    invalid_snark_proofs,
+    AdversaryInvalidSnarkProofs,
+    AdversaryInvalidSnarkProofs
+);
+
+scenario_test!(equivocation, AdversaryEquivocation, AdversaryEquivocation);
+
+scenario_test!(
+    wrong_ledger_hashes,
+    AdversaryWrongLedgerHashes,
+    AdversaryWrongLedgerHashes
+);
+
+scenario_test!(
+    withhold_staged_ledger_parts,
+    AdversaryWithholdStagedLedgerParts,
+    AdversaryWithholdStagedLedgerParts
+);
+
+// Snark job commitments are only propagated over WebRTC.
+scenario_test!(
+    #[cfg(feature = "p2p-webrtc")]
+    flood_snark_commitments,
+    AdversaryFloodSnarkCommitments,
+    AdversaryFloodSnarkCommitments
+);
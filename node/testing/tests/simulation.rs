@@ -0,0 +1,23 @@
+use openmina_node_testing::scenarios::simulation::long_range_fork::SimulationLongRangeFork;
+use openmina_node_testing::scenarios::simulation::shadow_block_production::SimulationShadowBlockProduction;
+use openmina_node_testing::scenarios::simulation::short_range_fork::SimulationShortRangeFork;
+
+mod common;
+
+scenario_test!(
+    shadow_block_production,
+    SimulationShadowBlockProduction,
+    SimulationShadowBlockProduction
+);
+
+scenario_test!(
+    short_range_fork,
+    SimulationShortRangeFork,
+    SimulationShortRangeFork
+);
+
+scenario_test!(
+    long_range_fork,
+    SimulationLongRangeFork,
+    SimulationLongRangeFork
+);
@@ -0,0 +1,160 @@
+//! Openmina node running in the browser.
+//!
+//! The node runs in its own web worker and only connects to WebRTC peers.
+//! Ledgers are kept in memory, so the node bootstraps from the network on
+//! every start. JavaScript interacts with it through [`rpc::RpcSender`],
+//! returned by [`run`].
+#![cfg(target_family = "wasm")]
+
+mod node;
+pub use node::{Node, NodeBuilder};
+
+mod service;
+pub use service::*;
+
+pub mod rpc;
+
+use std::sync::Arc;
+
+use ::node::{
+    account::AccountSecretKey, core::channels::oneshot, core::thread, daemon_json::DaemonJson,
+    p2p::connection::outgoing::P2pConnectionOutgoingInitOpts,
+    transition_frontier::genesis::GenesisConfig,
+};
+use openmina_core::NetworkConfig;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+/// Configuration passed from JavaScript to [`run`].
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct WebNodeConfig {
+    /// Network to join, `devnet` if not set.
+    pub network: Option<String>,
+    /// Genesis config in the daemon json format. Only the devnet one is
+    /// bundled with the node, so it's required for other networks.
+    pub genesis_config: Option<String>,
+    /// WebRTC peers, in the `/<peer_id>/<signaling>` format.
+    pub peers: Vec<String>,
+    /// Secret key of the block producer, if the node should produce blocks.
+    pub block_producer: Option<String>,
+    /// Number of web workers verifying block and snark work proofs.
+    pub verifier_threads: Option<usize>,
+}
+
+#[wasm_bindgen(start)]
+fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Starts the node in a new web worker and returns its rpc sender once
+/// the node is built.
+#[wasm_bindgen]
+pub async fn run(config: JsValue) -> Result<rpc::RpcSender, JsError> {
+    let config = js_to_value::<WebNodeConfig>(config)?;
+    let peers = webrtc_peers_parse(&config.peers)?;
+    let block_producer = config
+        .block_producer
+        .as_deref()
+        .map(str::parse::<AccountSecretKey>)
+        .transpose()
+        .map_err(|err| JsError::new(&format!("invalid block producer key: {err}")))?;
+
+    let network = config.network.as_deref().unwrap_or("devnet");
+    network_init(network)?;
+    let genesis_config = genesis_config(network, config.genesis_config.as_deref())?;
+
+    let (rpc_sender_tx, rpc_sender_rx) = oneshot::channel();
+    thread::Builder::new()
+        .name("openmina_node".to_owned())
+        .spawn_async(move || async move {
+            let mut builder = NodeBuilder::new(None, genesis_config);
+            builder.initial_peers(peers);
+            if let Some(key) = block_producer {
+                builder.block_producer(key);
+            }
+            if let Some(threads) = config.verifier_threads {
+                builder.verifier_threads(threads);
+            }
+            let mut node = match builder.build() {
+                Ok(node) => node,
+                Err(err) => {
+                    let _ = rpc_sender_tx.send(Err(err.to_string()));
+                    return;
+                }
+            };
+            let _ = rpc_sender_tx.send(Ok(node.rpc()));
+            node.run_forever().await;
+        })
+        .map_err(|err| JsError::new(&format!("failed to spawn node worker: {err}")))?;
+
+    let rpc_sender = rpc_sender_rx
+        .await
+        .map_err(|_| JsError::new("node worker stopped before the node was built"))?
+        .map_err(|err| JsError::new(&err))?;
+    Ok(rpc::RpcSender::new(rpc_sender))
+}
+
+/// Initializes the global network config. Nodes started again in the same
+/// page share it, so they must join the same network.
+fn network_init(network: &str) -> Result<(), JsError> {
+    let config = NetworkConfig::by_name(network).map_err(|err| JsError::new(&err))?;
+    match config.init_global() {
+        Ok(()) => Ok(()),
+        Err(_) if NetworkConfig::global().name == network => Ok(()),
+        Err(_) => Err(JsError::new(&format!(
+            "node already initialized for network `{}`",
+            NetworkConfig::global().name
+        ))),
+    }
+}
+
+fn genesis_config(network: &str, daemon_json: Option<&str>) -> Result<Arc<GenesisConfig>, JsError> {
+    match daemon_json {
+        Some(json) => {
+            let config = serde_json::from_str::<DaemonJson>(json)
+                .map_err(|err| JsError::new(&format!("invalid genesis config: {err}")))?;
+            Ok(Arc::new(GenesisConfig::DaemonJson(Box::new(config))))
+        }
+        None if network == "devnet" => Ok(::node::config::DEVNET_CONFIG.clone()),
+        None => Err(JsError::new(&format!(
+            "genesis config is required for network `{network}`"
+        ))),
+    }
+}
+
+/// Parses WebRTC peer addresses. Libp2p peers are rejected, as the
+/// browser can't open raw TCP connections to them.
+pub fn webrtc_peers_parse(peers: &[String]) -> Result<Vec<P2pConnectionOutgoingInitOpts>, JsError> {
+    if peers.is_empty() {
+        return Err(JsError::new("at least one WebRTC peer is required"));
+    }
+    peers
+        .iter()
+        .map(|peer| {
+            let opts = peer
+                .parse::<P2pConnectionOutgoingInitOpts>()
+                .map_err(|err| JsError::new(&format!("invalid peer `{peer}`: {err}")))?;
+            if opts.is_libp2p() {
+                return Err(JsError::new(&format!(
+                    "libp2p peer `{peer}` isn't supported in the browser"
+                )));
+            }
+            Ok(opts)
+        })
+        .collect()
+}
+
+fn js_to_value<T: serde::de::DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    if value.is_undefined() || value.is_null() {
+        return serde_json::from_str("{}").map_err(|err| JsError::new(&err.to_string()));
+    }
+    let json = js_sys::JSON::stringify(&value)
+        .map_err(|_| JsError::new("value isn't serializable to json"))?;
+    serde_json::from_str(&String::from(json)).map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn value_to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(value).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
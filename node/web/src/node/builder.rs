@@ -0,0 +1,159 @@
+use std::{sync::Arc, time::Duration};
+
+use node::{
+    account::AccountSecretKey,
+    daemon_json::Daemon,
+    p2p::{
+        channels::ChannelId, connection::outgoing::P2pConnectionOutgoingInitOpts,
+        identity::SecretKey as P2pSecretKey, P2pLimits, P2pRpcRateLimits, P2pTimeouts,
+    },
+    snark::{get_srs, get_verifier_index, VerifierKind},
+    transition_frontier::genesis::GenesisConfig,
+    BlockProducerConfig, GlobalConfig, LedgerConfig, P2pConfig, SnarkConfig, SnarkPoolConfig,
+    TransitionFrontierConfig,
+};
+use openmina_core::{consensus::ConsensusConstants, constants::constraint_constants};
+use openmina_node_common::NodeServiceCommonBuilder;
+
+use crate::{NodeService, P2pTaskSpawner};
+
+use super::Node;
+
+/// Number of web workers verifying block and snark work proofs.
+pub const DEFAULT_VERIFIER_THREADS: usize = 2;
+
+pub struct NodeBuilder {
+    rng_seed: [u8; 32],
+    genesis_config: Arc<GenesisConfig>,
+    p2p_sec_key: Option<P2pSecretKey>,
+    initial_peers: Vec<P2pConnectionOutgoingInitOpts>,
+    block_producer: Option<BlockProducerConfig>,
+    service: NodeServiceCommonBuilder,
+}
+
+impl NodeBuilder {
+    pub fn new(custom_rng_seed: Option<[u8; 32]>, genesis_config: Arc<GenesisConfig>) -> Self {
+        let rng_seed = custom_rng_seed.unwrap_or_else(|| {
+            let mut seed = [0; 32];
+            getrandom::getrandom(&mut seed).expect("failed to get random seed");
+            seed
+        });
+        let mut service = NodeServiceCommonBuilder::new(rng_seed);
+        service.verifier_threads(DEFAULT_VERIFIER_THREADS);
+        Self {
+            rng_seed,
+            genesis_config,
+            p2p_sec_key: None,
+            initial_peers: Vec::new(),
+            block_producer: None,
+            service,
+        }
+    }
+
+    /// If not called, random one will be generated and used instead.
+    pub fn p2p_sec_key(&mut self, key: P2pSecretKey) -> &mut Self {
+        self.p2p_sec_key = Some(key);
+        self
+    }
+
+    /// Extend p2p initial peers from an iterable. Only WebRTC peers can
+    /// be connected to from the browser.
+    pub fn initial_peers(
+        &mut self,
+        peers: impl IntoIterator<Item = P2pConnectionOutgoingInitOpts>,
+    ) -> &mut Self {
+        self.initial_peers.extend(peers);
+        self
+    }
+
+    /// Set up block producer.
+    pub fn block_producer(&mut self, key: AccountSecretKey) -> &mut Self {
+        let config = BlockProducerConfig {
+            pub_key: key.public_key().into(),
+            custom_coinbase_receiver: None,
+            proposed_protocol_version: None,
+            shadow: false,
+        };
+        self.block_producer = Some(config);
+        self.service.block_producer_init(key);
+        self
+    }
+
+    /// Number of web workers verifying block and snark work proofs.
+    pub fn verifier_threads(&mut self, threads: usize) -> &mut Self {
+        self.service.verifier_threads(threads);
+        self
+    }
+
+    pub fn gather_stats(&mut self) -> &mut Self {
+        self.service.gather_stats();
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<Node> {
+        if self.initial_peers.is_empty() {
+            anyhow::bail!("no initial peers, at least one WebRTC peer is required");
+        }
+        let p2p_sec_key = self.p2p_sec_key.unwrap_or_else(P2pSecretKey::rand);
+
+        let srs = get_srs();
+        let block_verifier_index = get_verifier_index(VerifierKind::Blockchain).into();
+        let work_verifier_index = get_verifier_index(VerifierKind::Transaction).into();
+
+        let initial_time = redux::Timestamp::global_now();
+        let protocol_constants = self.genesis_config.protocol_constants()?;
+        let daemon_conf = Daemon::DEFAULT;
+
+        let node_config = node::Config {
+            global: GlobalConfig {
+                build: node::BuildEnv::get().into(),
+                snarker: None,
+            },
+            p2p: P2pConfig {
+                libp2p_port: None,
+                // Browser can't accept incoming connections.
+                listen_port: 0,
+                identity_pub_key: p2p_sec_key.public_key(),
+                initial_peers: self.initial_peers,
+                ask_initial_peers_interval: Duration::from_secs(3600),
+                enabled_channels: ChannelId::iter_all().collect(),
+                // Peer discovery is done through libp2p kademlia.
+                peer_discovery: false,
+                initial_time: initial_time
+                    .checked_sub(redux::Timestamp::ZERO)
+                    .unwrap_or_default(),
+                timeouts: P2pTimeouts::default(),
+                limits: P2pLimits::default().with_max_peers(Some(100)),
+                rpc_rate_limits: P2pRpcRateLimits::default(),
+                persisted_peers: Default::default(),
+            },
+            ledger: LedgerConfig {},
+            snark: SnarkConfig {
+                block_verifier_index,
+                block_verifier_srs: srs.clone(),
+                work_verifier_index,
+                work_verifier_srs: srs,
+            },
+            transition_frontier: TransitionFrontierConfig::new(self.genesis_config),
+            block_producer: self.block_producer,
+            tx_pool: ledger::transaction_pool::Config {
+                trust_system: (),
+                pool_max_size: daemon_conf.tx_pool_max_size(),
+                slot_tx_end: daemon_conf.slot_tx_end(),
+            },
+            snark_pool: SnarkPoolConfig::default(),
+        };
+
+        // build service
+        let mut service = self.service;
+        service.ledger_init();
+        service.p2p_init(p2p_sec_key, P2pTaskSpawner {});
+        let service = NodeService::new(service.build()?);
+
+        let consensus_consts =
+            ConsensusConstants::create(constraint_constants(), &protocol_constants);
+        let state = node::State::new(node_config, &consensus_consts, initial_time);
+
+        Ok(Node::new(self.rng_seed, state, service, None))
+    }
+}
@@ -0,0 +1,4 @@
+mod builder;
+pub use builder::*;
+
+pub type Node = openmina_node_common::Node<crate::NodeService>;
@@ -0,0 +1,130 @@
+//! JavaScript api of the web node.
+//!
+//! Every method returns a promise, resolving to the node's rpc response
+//! as a plain JavaScript object, same as the native node's http api.
+
+use node::{
+    account::{AccountPublicKey, AccountSecretKey},
+    rpc::{
+        RpcInjectPayment, RpcLedgerAccountsResponse, RpcPeersGetResponse, RpcRequest,
+        RpcStatusGetResponse, RpcTransactionInjectResponse, RpcTransactionPoolResponse,
+        SyncStatsQuery,
+    },
+};
+use openmina_node_common::rpc::RpcSyncStatsGetResponse;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{js_to_value, value_to_js};
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct RpcSender(openmina_node_common::rpc::RpcSender);
+
+/// Payment sent with [`RpcSender::send_payment`], signed in the browser.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PaymentParams {
+    pub sec_key: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u32,
+    #[serde(default = "valid_until_default")]
+    pub valid_until: u32,
+    #[serde(default)]
+    pub memo: String,
+}
+
+fn valid_until_default() -> u32 {
+    u32::MAX
+}
+
+impl PaymentParams {
+    /// Signs the payment for the network the node is running on.
+    pub fn sign(&self) -> Result<RpcInjectPayment, String> {
+        let from = self
+            .sec_key
+            .parse::<AccountSecretKey>()
+            .map_err(|err| format!("invalid secret key: {err}"))?;
+        let to = self
+            .to
+            .parse::<AccountPublicKey>()
+            .map_err(|err| format!("invalid receiver: {err}"))?;
+        Ok(RpcInjectPayment::new_signed(
+            &from,
+            to,
+            self.amount,
+            self.fee,
+            self.nonce,
+            self.valid_until,
+            self.memo.clone(),
+        ))
+    }
+}
+
+impl RpcSender {
+    pub fn new(sender: openmina_node_common::rpc::RpcSender) -> Self {
+        Self(sender)
+    }
+
+    fn oneshot_request<T>(&self, req: RpcRequest) -> js_sys::Promise
+    where
+        T: 'static + Send + Serialize,
+    {
+        let sender = self.0.clone();
+        future_to_promise(async move {
+            let res = sender.oneshot_request::<T>(req).await;
+            value_to_js(&res)
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl RpcSender {
+    pub fn status(&self) -> js_sys::Promise {
+        self.oneshot_request::<RpcStatusGetResponse>(RpcRequest::StatusGet)
+    }
+
+    pub fn peers(&self) -> js_sys::Promise {
+        self.oneshot_request::<RpcPeersGetResponse>(RpcRequest::PeersGet)
+    }
+
+    pub fn sync_stats(&self, limit: Option<usize>) -> js_sys::Promise {
+        self.oneshot_request::<RpcSyncStatsGetResponse>(RpcRequest::SyncStatsGet(SyncStatsQuery {
+            limit,
+        }))
+    }
+
+    /// Accounts of the best tip's ledger, optionally only the ones of
+    /// `public_key`.
+    pub fn accounts(&self, public_key: Option<String>) -> Result<js_sys::Promise, JsError> {
+        let public_key = public_key
+            .map(|key| key.parse::<AccountPublicKey>())
+            .transpose()
+            .map_err(|err| JsError::new(&format!("invalid public key: {err}")))?;
+        Ok(
+            self.oneshot_request::<RpcLedgerAccountsResponse>(RpcRequest::LedgerAccountsGet(
+                public_key,
+            )),
+        )
+    }
+
+    pub fn transaction_pool(&self) -> js_sys::Promise {
+        self.oneshot_request::<RpcTransactionPoolResponse>(RpcRequest::TransactionPoolGet)
+    }
+
+    /// Signs the payment described by `params` and injects it into the
+    /// node's transaction pool. Resolves to `null` if the payment was
+    /// rejected or the node stopped.
+    pub fn send_payment(&self, params: JsValue) -> Result<js_sys::Promise, JsError> {
+        let payment = js_to_value::<PaymentParams>(params)?
+            .sign()
+            .map_err(|err| JsError::new(&err))?;
+        Ok(
+            self.oneshot_request::<RpcTransactionInjectResponse>(RpcRequest::TransactionInject(
+                vec![payment],
+            )),
+        )
+    }
+}
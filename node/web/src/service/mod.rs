@@ -0,0 +1,22 @@
+mod rpc;
+
+#[allow(clippy::module_inception)]
+mod service;
+pub use service::*;
+
+use openmina_node_common::p2p::TaskSpawner;
+
+/// Runs p2p tasks on the event loop of the node's web worker.
+#[derive(Clone)]
+pub struct P2pTaskSpawner {}
+
+impl TaskSpawner for P2pTaskSpawner {
+    fn spawn_main<F>(&self, _name: &str, fut: F)
+    where
+        F: 'static + Send + std::future::Future,
+    {
+        wasm_bindgen_futures::spawn_local(async move {
+            fut.await;
+        });
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
+use mina_p2p_messages::v2::{
+    CurrencyFeeStableV1, NonZeroCurvePoint, ProverExtendBlockchainInputStableV2, StateHash,
+};
+use node::{
+    block_producer::vrf_evaluator::VrfEvaluatorInput,
+    core::{
+        block::ArcBlockWithHash,
+        channels::mpsc,
+        invariants::{InvariantService, InvariantsState},
+        snark::{Snark, SnarkJobId},
+    },
+    event_source::Event,
+    external_snark_worker::{ExternalSnarkWorkerError, ExternalSnarkWorkerService, SnarkWorkSpec},
+    ledger::{LedgerManager, LedgerService},
+    p2p::{connection::outgoing::P2pConnectionOutgoingInitOpts, P2pCryptoService, PeerId},
+    service::{
+        BlockProducerService, BlockProducerVrfEvaluatorService, EventSourceService, Recorder,
+        SnarkBlockVerifyService, SnarkPoolService, SnarkWorkVerifyService,
+        TransitionFrontierArchiveService, TransitionFrontierGenesisService,
+    },
+    snark::{
+        block_verify::{SnarkBlockVerifyId, VerifiableBlockWithHash},
+        work_verify::SnarkWorkVerifyId,
+        SnarkVerifierStats, VerifierIndex, VerifierSRS,
+    },
+    stats::Stats,
+    transition_frontier::genesis::GenesisConfig,
+};
+use openmina_node_common::{
+    p2p::{
+        webrtc::{Cmd, P2pServiceWebrtc, PeerState},
+        webrtc_with_libp2p::P2pServiceWebrtcWithLibp2p,
+    },
+    rpc::{RpcReceiver, RpcSender},
+    EventReceiver, EventSender, NodeServiceCommon,
+};
+
+/// Service of the web node.
+///
+/// Only WebRTC p2p is available, ledgers are kept in memory and there is
+/// no external snark worker or archive.
+pub struct NodeService {
+    pub(super) common: NodeServiceCommon,
+    pub(super) recorder: Recorder,
+}
+
+impl NodeService {
+    pub fn new(common: NodeServiceCommon) -> Self {
+        Self {
+            common,
+            recorder: Recorder::None,
+        }
+    }
+
+    pub fn event_sender(&self) -> &EventSender {
+        self.common.event_sender()
+    }
+
+    pub fn event_receiver_with_rpc_receiver(&mut self) -> (&mut EventReceiver, &mut RpcReceiver) {
+        self.common.event_receiver_with_rpc_receiver()
+    }
+
+    pub fn rpc_sender(&self) -> RpcSender {
+        self.common.rpc_sender()
+    }
+
+    pub fn ledger_manager(&self) -> &LedgerManager {
+        &self.common.ledger_manager
+    }
+
+    pub fn stats(&mut self) -> Option<&mut Stats> {
+        self.common.stats()
+    }
+}
+
+impl AsMut<NodeServiceCommon> for NodeService {
+    fn as_mut(&mut self) -> &mut NodeServiceCommon {
+        &mut self.common
+    }
+}
+
+impl LedgerService for NodeService {
+    fn ledger_manager(&self) -> &LedgerManager {
+        LedgerService::ledger_manager(&self.common)
+    }
+
+    fn force_sync_calls(&self) -> bool {
+        LedgerService::force_sync_calls(&self.common)
+    }
+}
+
+impl redux::TimeService for NodeService {
+    fn monotonic_time(&mut self) -> redux::Instant {
+        redux::TimeService::monotonic_time(&mut self.common)
+    }
+}
+
+impl redux::Service for NodeService {}
+
+impl node::Service for NodeService {
+    fn stats(&mut self) -> Option<&mut Stats> {
+        self.common.stats()
+    }
+
+    fn verifier_stats(&mut self) -> Option<SnarkVerifierStats> {
+        Some(self.common.verifier.stats())
+    }
+
+    fn recorder(&mut self) -> &mut Recorder {
+        &mut self.recorder
+    }
+}
+
+impl P2pCryptoService for NodeService {
+    fn generate_random_nonce(&mut self) -> [u8; 24] {
+        P2pCryptoService::generate_random_nonce(&mut self.common)
+    }
+
+    fn ephemeral_sk(&mut self) -> [u8; 32] {
+        P2pCryptoService::ephemeral_sk(&mut self.common)
+    }
+
+    fn static_sk(&mut self) -> [u8; 32] {
+        P2pCryptoService::static_sk(&mut self.common)
+    }
+
+    fn sign_key(&mut self, key: &[u8; 32]) -> Vec<u8> {
+        P2pCryptoService::sign_key(&mut self.common, key)
+    }
+
+    fn sign_publication(&mut self, publication: &[u8]) -> Vec<u8> {
+        P2pCryptoService::sign_publication(&mut self.common, publication)
+    }
+}
+
+impl EventSourceService for NodeService {
+    fn next_event(&mut self) -> Option<Event> {
+        EventSourceService::next_event(&mut self.common)
+    }
+}
+
+impl P2pServiceWebrtc for NodeService {
+    type Event = <NodeServiceCommon as P2pServiceWebrtc>::Event;
+
+    fn random_pick(
+        &mut self,
+        list: &[P2pConnectionOutgoingInitOpts],
+    ) -> P2pConnectionOutgoingInitOpts {
+        P2pServiceWebrtc::random_pick(&mut self.common, list)
+    }
+
+    fn event_sender(&self) -> &mpsc::UnboundedSender<Self::Event> {
+        P2pServiceWebrtc::event_sender(&self.common)
+    }
+
+    fn cmd_sender(&self) -> &mpsc::UnboundedSender<Cmd> {
+        P2pServiceWebrtc::cmd_sender(&self.common)
+    }
+
+    fn peers(&mut self) -> &mut BTreeMap<PeerId, PeerState> {
+        P2pServiceWebrtc::peers(&mut self.common)
+    }
+}
+
+impl P2pServiceWebrtcWithLibp2p for NodeService {}
+
+impl SnarkBlockVerifyService for NodeService {
+    fn verify_init(
+        &mut self,
+        req_id: SnarkBlockVerifyId,
+        verifier_index: Arc<VerifierIndex>,
+        verifier_srs: Arc<Mutex<VerifierSRS>>,
+        block: VerifiableBlockWithHash,
+    ) {
+        SnarkBlockVerifyService::verify_init(
+            &mut self.common,
+            req_id,
+            verifier_index,
+            verifier_srs,
+            block,
+        )
+    }
+}
+
+impl SnarkWorkVerifyService for NodeService {
+    fn verify_init(
+        &mut self,
+        req_id: SnarkWorkVerifyId,
+        verifier_index: Arc<VerifierIndex>,
+        verifier_srs: Arc<Mutex<VerifierSRS>>,
+        work: Vec<Snark>,
+    ) {
+        SnarkWorkVerifyService::verify_init(
+            &mut self.common,
+            req_id,
+            verifier_index,
+            verifier_srs,
+            work,
+        )
+    }
+}
+
+use node::snark::user_command_verify_effectful::SnarkUserCommandVerifyService;
+
+impl SnarkUserCommandVerifyService for NodeService {
+    fn verify_init(
+        &mut self,
+        req_id: node::snark::user_command_verify::SnarkUserCommandVerifyId,
+        verifier_index: Arc<VerifierIndex>,
+        verifier_srs: Arc<Mutex<VerifierSRS>>,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
+    ) {
+        SnarkUserCommandVerifyService::verify_init(
+            &mut self.common,
+            req_id,
+            verifier_index,
+            verifier_srs,
+            commands,
+        )
+    }
+}
+
+impl SnarkPoolService for NodeService {
+    fn random_choose<'a>(
+        &mut self,
+        iter: impl Iterator<Item = &'a SnarkJobId>,
+        n: usize,
+    ) -> Vec<SnarkJobId> {
+        SnarkPoolService::random_choose(&mut self.common, iter, n)
+    }
+}
+
+impl BlockProducerVrfEvaluatorService for NodeService {
+    fn evaluate(&mut self, data: VrfEvaluatorInput) {
+        BlockProducerVrfEvaluatorService::evaluate(&mut self.common, data)
+    }
+}
+
+impl BlockProducerService for NodeService {
+    fn prove(&mut self, block_hash: StateHash, input: Box<ProverExtendBlockchainInputStableV2>) {
+        BlockProducerService::prove(&mut self.common, block_hash, input)
+    }
+}
+
+/// External snark worker is a separate process, which can't be started
+/// from the browser.
+impl ExternalSnarkWorkerService for NodeService {
+    fn start(
+        &mut self,
+        _public_key: NonZeroCurvePoint,
+        _fee: CurrencyFeeStableV1,
+    ) -> Result<(), ExternalSnarkWorkerError> {
+        Err(ExternalSnarkWorkerError::Error(
+            "external snark worker isn't supported in the browser".to_owned(),
+        ))
+    }
+
+    fn submit(&mut self, _spec: SnarkWorkSpec) -> Result<(), ExternalSnarkWorkerError> {
+        Err(ExternalSnarkWorkerError::NotRunning)
+    }
+
+    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError> {
+        Err(ExternalSnarkWorkerError::NotRunning)
+    }
+
+    fn kill(&mut self) -> Result<(), ExternalSnarkWorkerError> {
+        Err(ExternalSnarkWorkerError::NotRunning)
+    }
+}
+
+impl TransitionFrontierGenesisService for NodeService {
+    fn load_genesis(&mut self, config: Arc<GenesisConfig>) {
+        TransitionFrontierGenesisService::load_genesis(&mut self.common, config)
+    }
+}
+
+impl TransitionFrontierArchiveService for NodeService {
+    fn archive_block_applied(&mut self, _block: &ArcBlockWithHash) {}
+
+    fn archive_best_chain_update(&mut self, _best_chain: &[ArcBlockWithHash]) {}
+}
+
+impl InvariantService for NodeService {
+    fn invariants_state(&mut self) -> &mut InvariantsState {
+        InvariantService::invariants_state(&mut self.common)
+    }
+}
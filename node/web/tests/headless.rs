@@ -0,0 +1,63 @@
+//! Runs the node in a headless browser, as web workers aren't available
+//! in node.js.
+#![cfg(target_family = "wasm")]
+
+use openmina_node_web::run;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Peer whose signaling server doesn't exist, so the node never connects
+/// to it and stays at genesis.
+const MOCK_PEER: &str = "/2axsdDAiiZee7hUsRPMtuyHt94UMrvJmMQDhDjKhdRhgqkMdy8e/http/127.0.0.1/1";
+const SEC_KEY: &str = "EKEEpMELfQkMbJDt2fB4cFXKwSf1x4t7YD4twREy5yuJ84HBZtF9";
+const RECEIVER: &str = "B62qnLVz8wM7MfJsuYbjFf4UWbwrUBEL5ZdawExxxFhnGXB6siqokyM";
+
+fn to_js(value: serde_json::Value) -> JsValue {
+    js_sys::JSON::parse(&value.to_string()).unwrap()
+}
+
+fn from_js(value: JsValue) -> serde_json::Value {
+    let json = js_sys::JSON::stringify(&value).unwrap();
+    serde_json::from_str(&String::from(json)).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn rpc_status_and_send_payment() {
+    let config = to_js(serde_json::json!({ "peers": [MOCK_PEER] }));
+    let rpc = run(config).await.map_err(JsValue::from).unwrap();
+
+    let status = from_js(JsFuture::from(rpc.status()).await.unwrap());
+    assert!(status["transition_frontier"]["best_tip"].is_null());
+    assert_eq!(status["snark_pool"]["snarks"], 0);
+
+    // The node has no best tip to verify the payment against, so it's
+    // rejected instead of waiting for the node to sync.
+    let payment = to_js(serde_json::json!({
+        "sec_key": SEC_KEY,
+        "to": RECEIVER,
+        "amount": 1_000_000_000u64,
+        "fee": 10_000_000u64,
+        "nonce": 0,
+    }));
+    let promise = rpc.send_payment(payment).map_err(JsValue::from).unwrap();
+    assert!(JsFuture::from(promise).await.unwrap().is_null());
+
+    let invalid = to_js(serde_json::json!({ "sec_key": "invalid", "to": RECEIVER }));
+    assert!(rpc.send_payment(invalid).is_err());
+}
+
+#[wasm_bindgen_test]
+async fn run_again_on_another_network_fails() {
+    let config = to_js(serde_json::json!({ "peers": [MOCK_PEER] }));
+    run(config).await.map_err(JsValue::from).unwrap();
+
+    let config = to_js(serde_json::json!({
+        "peers": [MOCK_PEER],
+        "network": "mainnet",
+        "genesis_config": "{}",
+    }));
+    assert!(run(config).await.is_err());
+}
@@ -0,0 +1,46 @@
+#![cfg(target_family = "wasm")]
+
+use openmina_core::NetworkConfig;
+use openmina_node_web::{rpc::PaymentParams, webrtc_peers_parse};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const SEC_KEY: &str = "EKEEpMELfQkMbJDt2fB4cFXKwSf1x4t7YD4twREy5yuJ84HBZtF9";
+const RECEIVER: &str = "B62qnLVz8wM7MfJsuYbjFf4UWbwrUBEL5ZdawExxxFhnGXB6siqokyM";
+
+#[wasm_bindgen_test]
+fn webrtc_peers_parse_rejects_libp2p() {
+    let webrtc = "/2axsdDAiiZee7hUsRPMtuyHt94UMrvJmMQDhDjKhdRhgqkMdy8e/http/openmina1/3000";
+    let peers = webrtc_peers_parse(&[webrtc.to_owned()]).unwrap();
+    assert_eq!(peers.len(), 1);
+    assert!(!peers[0].is_libp2p());
+
+    let libp2p =
+        "/ip4/34.48.73.58/tcp/10003/p2p/12D3KooWAdgYL6hv18M3iDBdaK1dRygPivSfAfBNDzie6YqydVbs";
+    assert!(webrtc_peers_parse(&[libp2p.to_owned()]).is_err());
+    assert!(webrtc_peers_parse(&[]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn payment_sign() {
+    let _ = NetworkConfig::init("devnet");
+
+    let params = PaymentParams {
+        sec_key: SEC_KEY.to_owned(),
+        to: RECEIVER.to_owned(),
+        amount: 1_000_000_000,
+        fee: 10_000_000,
+        nonce: 0,
+        valid_until: u32::MAX,
+        memo: String::new(),
+    };
+    let payment = params.sign().unwrap();
+    let payment = serde_json::to_value(payment).unwrap();
+    assert_eq!(payment["to"], RECEIVER);
+    assert_eq!(payment["amount"], 1_000_000_000);
+
+    let invalid = PaymentParams {
+        sec_key: "invalid".to_owned(),
+        ..params
+    };
+    assert!(invalid.sign().is_err());
+}
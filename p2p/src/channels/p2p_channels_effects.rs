@@ -86,10 +86,15 @@ impl P2pChannelsMessageReceivedAction {
                 }
                 ChannelMsg::Rpc(msg) => match msg {
                     RpcChannelMsg::Request(id, request) => {
+                        let kind = request.kind();
                         store.dispatch(P2pChannelsRpcAction::RequestReceived {
                             peer_id,
                             id,
                             request: Box::new(request),
+                        }) || store.dispatch(P2pChannelsRpcAction::RequestRejected {
+                            peer_id,
+                            id,
+                            kind,
                         })
                     }
                     RpcChannelMsg::Response(id, response) => {
@@ -1,7 +1,14 @@
+use crate::P2pRpcRateLimits;
+
 use super::{P2pChannelsAction, P2pChannelsActionWithMetaRef, P2pChannelsState};
 
 impl P2pChannelsState {
-    pub fn reducer(&mut self, action: P2pChannelsActionWithMetaRef<'_>, is_libp2p: bool) {
+    pub fn reducer(
+        &mut self,
+        action: P2pChannelsActionWithMetaRef<'_>,
+        is_libp2p: bool,
+        rpc_rate_limits: &P2pRpcRateLimits,
+    ) {
         let (action, meta) = action.split();
         match action {
             P2pChannelsAction::MessageReceived(_) => {}
@@ -18,7 +25,7 @@ impl P2pChannelsState {
                 self.snark_job_commitment.reducer(meta.with_action(action));
             }
             P2pChannelsAction::Rpc(action) => {
-                self.rpc.reducer(meta.with_action(action));
+                self.rpc.reducer(meta.with_action(action), rpc_rate_limits);
             }
         }
     }
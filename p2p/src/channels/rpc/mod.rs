@@ -207,8 +207,8 @@ mod libp2p {
     use mina_p2p_messages::{
         rpc,
         rpc_kernel::{
-            NeedsLength, QueryHeader, QueryPayload, ResponseHeader, ResponsePayload, RpcMethod,
-            RpcResult,
+            self, NeedsLength, QueryHeader, QueryPayload, ResponseHeader, ResponsePayload,
+            RpcMethod, RpcResult,
         },
     };
 
@@ -302,6 +302,21 @@ mod libp2p {
         }
     }
 
+    /// Error response for the request that we refuse to answer.
+    pub fn internal_error_response_into_libp2p(
+        id: P2pRpcId,
+        error: &str,
+    ) -> (ResponseHeader, Data) {
+        use binprot::BinProtWrite;
+
+        let error = rpc_kernel::Error::Uncaught_exn(rpc_kernel::Sexp::atom(error));
+        let r: ResponsePayload<()> = RpcResult(Err(error));
+
+        let mut v = vec![];
+        <ResponsePayload<()> as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+        (ResponseHeader { id: id as _ }, v.into())
+    }
+
     pub fn internal_request_into_libp2p(
         request: P2pRpcRequest,
         id: P2pRpcId,
@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{P2pState, PeerId};
 
-use super::{P2pChannelsRpcState, P2pRpcId, P2pRpcLocalState, P2pRpcRequest, P2pRpcResponse};
+use super::{
+    P2pChannelsRpcState, P2pRpcId, P2pRpcKind, P2pRpcLocalState, P2pRpcRequest, P2pRpcResponse,
+};
 
 pub type P2pChannelsRpcActionWithMetaRef<'a> = redux::ActionWithMeta<&'a P2pChannelsRpcAction>;
 
@@ -41,6 +43,13 @@ pub enum P2pChannelsRpcAction {
         id: P2pRpcId,
         request: Box<P2pRpcRequest>,
     },
+    /// Peer exceeded its budget for requests of this kind, so the request
+    /// is rejected with an error response.
+    RequestRejected {
+        peer_id: PeerId,
+        id: P2pRpcId,
+        kind: P2pRpcKind,
+    },
     /// Response for the request sent by peer is pending. Dispatched when
     /// we need data from an async component, like ledger, for constructing
     /// the response.
@@ -65,6 +74,7 @@ impl P2pChannelsRpcAction {
             | Self::Timeout { peer_id, .. }
             | Self::ResponseReceived { peer_id, .. }
             | Self::RequestReceived { peer_id, .. }
+            | Self::RequestRejected { peer_id, .. }
             | Self::ResponsePending { peer_id, .. }
             | Self::ResponseSend { peer_id, .. } => peer_id,
         }
@@ -113,11 +123,20 @@ impl redux::EnablingCondition<P2pState> for P2pChannelsRpcAction {
                     _ => false,
                 })
             },
-            P2pChannelsRpcAction::RequestReceived { peer_id, id, .. } => {
+            P2pChannelsRpcAction::RequestReceived { peer_id, id, request } => {
                 state.get_ready_peer(peer_id).map_or(false, |p| match &p.channels.rpc {
                     P2pChannelsRpcState::Ready { remote, .. } => {
                         remote.pending_requests.len() < MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS &&
-                        remote.pending_requests.iter().all(|v| v.id != *id)
+                        remote.pending_requests.iter().all(|v| v.id != *id) &&
+                        remote.has_budget(request.kind(), &state.config.rpc_rate_limits, time)
+                    },
+                    _ => false,
+                })
+            },
+            P2pChannelsRpcAction::RequestRejected { peer_id, kind, .. } => {
+                state.get_ready_peer(peer_id).map_or(false, |p| match &p.channels.rpc {
+                    P2pChannelsRpcState::Ready { remote, .. } => {
+                        !remote.has_budget(kind.clone(), &state.config.rpc_rate_limits, time)
                     },
                     _ => false,
                 })
@@ -6,6 +6,7 @@ use redux::ActionMeta;
 use crate::P2pNetworkRpcAction;
 use crate::{
     channels::{ChannelId, MsgId, P2pChannelsService},
+    disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
     peer::P2pPeerAction,
 };
 
@@ -58,6 +59,35 @@ impl P2pChannelsRpcAction {
                     });
                 }
             }
+            P2pChannelsRpcAction::RequestRejected { peer_id, id, .. } => {
+                let rejected = store
+                    .state()
+                    .get_ready_peer(&peer_id)
+                    .map_or(0, |p| p.channels.rpc.remote_rejected_requests());
+                if rejected > store.state().config.rpc_rate_limits.max_rejected_requests {
+                    let reason = P2pDisconnectionReason::RpcRateLimitExceeded;
+                    store.dispatch(P2pDisconnectionAction::Init { peer_id, reason });
+                    return;
+                }
+
+                #[cfg(feature = "p2p-libp2p")]
+                if store.state().is_libp2p_peer(&peer_id) {
+                    let (response, data) = super::libp2p::internal_error_response_into_libp2p(
+                        id,
+                        "rpc rate limit exceeded",
+                    );
+                    store.dispatch(P2pNetworkRpcAction::OutgoingResponse {
+                        peer_id,
+                        response,
+                        data,
+                    });
+                    return;
+                }
+                let msg = RpcChannelMsg::Response(id, None);
+                store
+                    .service()
+                    .channel_send(peer_id, MsgId::first(), msg.into());
+            }
             P2pChannelsRpcAction::ResponseSend {
                 peer_id,
                 id,
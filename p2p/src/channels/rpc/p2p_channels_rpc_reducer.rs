@@ -1,12 +1,16 @@
-use std::collections::VecDeque;
+use crate::P2pRpcRateLimits;
 
 use super::{
     P2pChannelsRpcAction, P2pChannelsRpcActionWithMetaRef, P2pChannelsRpcState, P2pRpcLocalState,
-    P2pRpcRemotePendingRequestState, P2pRpcRemoteState, MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS,
+    P2pRpcRemotePendingRequestState, P2pRpcRemoteState,
 };
 
 impl P2pChannelsRpcState {
-    pub fn reducer(&mut self, action: P2pChannelsRpcActionWithMetaRef<'_>) {
+    pub fn reducer(
+        &mut self,
+        action: P2pChannelsRpcActionWithMetaRef<'_>,
+        rate_limits: &P2pRpcRateLimits,
+    ) {
         let (action, meta) = action.split();
         match action {
             P2pChannelsRpcAction::Init { .. } => {
@@ -19,12 +23,7 @@ impl P2pChannelsRpcState {
                 *self = Self::Ready {
                     time: meta.time(),
                     local: P2pRpcLocalState::WaitingForRequest { time: meta.time() },
-                    remote: P2pRpcRemoteState {
-                        pending_requests: VecDeque::with_capacity(
-                            MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS,
-                        ),
-                        last_responded: redux::Timestamp::ZERO,
-                    },
+                    remote: P2pRpcRemoteState::new(),
                     next_local_rpc_id: 0,
                 };
             }
@@ -62,6 +61,7 @@ impl P2pChannelsRpcState {
                 let Self::Ready { remote, .. } = self else {
                     return;
                 };
+                remote.budget_consume(request.kind(), rate_limits, meta.time());
                 remote
                     .pending_requests
                     .push_back(P2pRpcRemotePendingRequestState {
@@ -71,6 +71,12 @@ impl P2pChannelsRpcState {
                         is_pending: false,
                     });
             }
+            P2pChannelsRpcAction::RequestRejected { .. } => {
+                let Self::Ready { remote, .. } = self else {
+                    return;
+                };
+                remote.request_rejected(rate_limits.rejected_requests_window, meta.time());
+            }
             P2pChannelsRpcAction::ResponsePending { id, .. } => {
                 let Self::Ready { remote, .. } = self else {
                     return;
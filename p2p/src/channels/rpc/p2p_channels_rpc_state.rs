@@ -1,8 +1,9 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
-use crate::P2pTimeouts;
+use crate::{Limit, P2pRpcRateLimit, P2pRpcRateLimits, P2pTimeouts};
 
 use super::{P2pRpcId, P2pRpcKind, P2pRpcRequest};
 
@@ -47,6 +48,23 @@ pub enum P2pRpcLocalState {
 pub struct P2pRpcRemoteState {
     pub pending_requests: VecDeque<P2pRpcRemotePendingRequestState>,
     pub last_responded: redux::Timestamp,
+    /// Remaining request budget of the peer per rpc kind. Missing entry
+    /// means that the budget is full.
+    #[serde(default)]
+    pub budgets: BTreeMap<P2pRpcKind, P2pRpcRemoteBudget>,
+    /// Number of requests rejected because the peer exceeded its budget,
+    /// since `rejected_requests_since`.
+    #[serde(default)]
+    pub rejected_requests: usize,
+    /// Start of the window in which rejected requests are counted.
+    #[serde(default)]
+    pub rejected_requests_since: Option<redux::Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct P2pRpcRemoteBudget {
+    pub tokens: u32,
+    pub updated_at: redux::Timestamp,
 }
 
 static EMPTY_REMOTE_REQUESTS: VecDeque<P2pRpcRemotePendingRequestState> = VecDeque::new();
@@ -62,6 +80,97 @@ pub struct P2pRpcRemotePendingRequestState {
     pub is_pending: bool,
 }
 
+impl P2pRpcRemoteState {
+    pub fn new() -> Self {
+        Self {
+            pending_requests: VecDeque::with_capacity(
+                super::MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS,
+            ),
+            last_responded: redux::Timestamp::ZERO,
+            budgets: Default::default(),
+            rejected_requests: 0,
+            rejected_requests_since: None,
+        }
+    }
+
+    fn budget(
+        &self,
+        kind: P2pRpcKind,
+        limit: &P2pRpcRateLimit,
+        now: redux::Timestamp,
+    ) -> P2pRpcRemoteBudget {
+        let full = P2pRpcRemoteBudget {
+            tokens: limit.burst,
+            updated_at: now,
+        };
+        let Some(budget) = self.budgets.get(&kind) else {
+            return full;
+        };
+        let interval = (limit.refill_interval.as_nanos() as u64).max(1);
+        let elapsed = now
+            .checked_sub(budget.updated_at)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let refilled = elapsed / interval;
+        let tokens = u64::from(budget.tokens).saturating_add(refilled);
+        if tokens >= u64::from(limit.burst) {
+            full
+        } else {
+            P2pRpcRemoteBudget {
+                tokens: tokens as u32,
+                // keep the remainder, so that it counts for the next token.
+                updated_at: budget.updated_at + refilled * interval,
+            }
+        }
+    }
+
+    /// Whether the peer can make one more request of the given kind.
+    pub fn has_budget(
+        &self,
+        kind: P2pRpcKind,
+        limits: &P2pRpcRateLimits,
+        now: redux::Timestamp,
+    ) -> bool {
+        match limits.get(kind.clone()) {
+            Limit::Some(limit) => self.budget(kind, &limit, now).tokens > 0,
+            Limit::Unlimited => true,
+        }
+    }
+
+    pub fn budget_consume(
+        &mut self,
+        kind: P2pRpcKind,
+        limits: &P2pRpcRateLimits,
+        now: redux::Timestamp,
+    ) {
+        let Limit::Some(limit) = limits.get(kind.clone()) else {
+            return;
+        };
+        let mut budget = self.budget(kind.clone(), &limit, now);
+        budget.tokens = budget.tokens.saturating_sub(1);
+        self.budgets.insert(kind, budget);
+    }
+
+    /// Counts the request rejected at `now`. The count starts over every
+    /// `window`, so a peer that runs out of budget only occasionally isn't
+    /// disconnected eventually.
+    pub fn request_rejected(&mut self, window: Duration, now: redux::Timestamp) {
+        let window_passed = self.rejected_requests_since.map_or(true, |since| {
+            now.checked_sub(since).map_or(false, |d| d >= window)
+        });
+        if window_passed {
+            self.rejected_requests = 0;
+            self.rejected_requests_since = Some(now);
+        }
+        self.rejected_requests += 1;
+    }
+}
+
+impl Default for P2pRpcRemoteState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl P2pChannelsRpcState {
     pub fn is_ready(&self) -> bool {
         matches!(self, Self::Ready { .. })
@@ -170,4 +279,64 @@ impl P2pChannelsRpcState {
             _ => redux::Timestamp::ZERO,
         }
     }
+
+    pub fn remote_rejected_requests(&self) -> usize {
+        match self {
+            Self::Ready { remote, .. } => remote.rejected_requests,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_remote_budget() {
+        let limits = P2pRpcRateLimits {
+            best_tip_with_proof: Limit::Some(P2pRpcRateLimit::new(2, Duration::from_secs(1))),
+            ..P2pRpcRateLimits::unlimited()
+        };
+        let kind = P2pRpcKind::BestTipWithProof;
+        let secs = |s: u64| redux::Timestamp::ZERO + s * 1_000_000_000;
+        let mut remote = P2pRpcRemoteState::new();
+
+        for _ in 0..2 {
+            assert!(remote.has_budget(kind.clone(), &limits, secs(0)));
+            remote.budget_consume(kind.clone(), &limits, secs(0));
+        }
+        assert!(!remote.has_budget(kind.clone(), &limits, secs(0)));
+        assert!(remote.has_budget(P2pRpcKind::Block, &limits, secs(0)));
+
+        assert!(remote.has_budget(kind.clone(), &limits, secs(1)));
+        remote.budget_consume(kind.clone(), &limits, secs(1));
+        assert!(!remote.has_budget(kind.clone(), &limits, secs(1)));
+
+        // never refills above burst.
+        remote.budget_consume(kind.clone(), &limits, secs(100));
+        remote.budget_consume(kind.clone(), &limits, secs(100));
+        assert!(!remote.has_budget(kind, &limits, secs(100)));
+    }
+
+    #[test]
+    fn test_rejected_requests_window() {
+        let window = Duration::from_secs(10);
+        let secs = |s: u64| redux::Timestamp::ZERO + s * 1_000_000_000;
+        let mut remote = P2pRpcRemoteState::new();
+
+        for s in 0..5 {
+            remote.request_rejected(window, secs(s));
+        }
+        assert_eq!(remote.rejected_requests, 5);
+
+        remote.request_rejected(window, secs(9));
+        assert_eq!(remote.rejected_requests, 6);
+
+        remote.request_rejected(window, secs(10));
+        assert_eq!(remote.rejected_requests, 1);
+        assert_eq!(remote.rejected_requests_since, Some(secs(10)));
+    }
 }
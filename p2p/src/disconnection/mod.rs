@@ -32,6 +32,15 @@ pub enum P2pDisconnectionReason {
     #[error("failed to verify snark pool diff")]
     SnarkPoolVerifyError,
 
+    #[error("failed to verify block")]
+    BlockVerifyError,
+
+    #[error("ledger data doesn't match the requested ledger hash")]
+    TransitionFrontierSyncLedgerInvalid,
+
+    #[error("exceeded rpc request budget")]
+    RpcRateLimitExceeded,
+
     #[error("duplicate connection")]
     DuplicateConnection,
 
@@ -1,6 +1,9 @@
 use redux::ActionMeta;
 
-use crate::{request::P2pNetworkKadRequestAction, P2pNetworkKademliaAction, P2pStore};
+use crate::{
+    request::P2pNetworkKadRequestAction, P2pNetworkKademliaAction, P2pNetworkKademliaRpcRequest,
+    P2pStore,
+};
 
 use super::P2pNetworkKadBootstrapAction;
 
@@ -37,7 +40,12 @@ impl P2pNetworkKadBootstrapAction {
                         .collect::<Vec<_>>()
                         .into_iter()
                         .for_each(|(peer_id, addr)| {
-                            store.dispatch(P2pNetworkKadRequestAction::New { addr, peer_id, key });
+                            let request = P2pNetworkKademliaRpcRequest::find_node(key);
+                            store.dispatch(P2pNetworkKadRequestAction::New {
+                                addr,
+                                peer_id,
+                                request,
+                            });
                         });
                 }
                 Ok(())
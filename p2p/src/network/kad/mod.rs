@@ -26,3 +26,6 @@ pub use self::p2p_network_kad_protocol::*;
 
 mod p2p_network_kad_internals;
 pub use self::p2p_network_kad_internals::*;
+
+mod p2p_network_kad_records;
+pub use self::p2p_network_kad_records::*;
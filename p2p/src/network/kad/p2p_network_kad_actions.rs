@@ -1,11 +1,13 @@
 use multiaddr::Multiaddr;
-use openmina_core::ActionEvent;
+use openmina_core::{requests::RpcId, ActionEvent};
 use redux::EnablingCondition;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     kad::stream::P2pNetworkKademliaStreamAction, request::P2pNetworkKadRequestAction,
-    ConnectionAddr, P2pAction, P2pNetworkAction, P2pNetworkKadEntry, P2pState, PeerId, StreamId,
+    ConnectionAddr, P2pAction, P2pNetworkAction, P2pNetworkKadEntry, P2pNetworkKadLookupKind,
+    P2pNetworkKadRecord, P2pNetworkKadRecordKey, P2pState, PeerId, StreamId,
+    MAX_LOCAL_PROVIDED_KEYS, MAX_LOCAL_RECORDS, MAX_RECORD_VALUE_SIZE,
 };
 
 use super::bootstrap::P2pNetworkKadBootstrapAction;
@@ -65,6 +67,105 @@ pub enum P2pNetworkKademliaAction {
         stream_id: StreamId,
         closest_peers: Vec<P2pNetworkKadEntry>,
     },
+    /// Answer `PUT_VALUE` request.
+    ///
+    /// Stores the record and echoes it back to the peer.
+    AnswerPutValueRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        record: P2pNetworkKadRecord,
+    },
+    /// Answer `GET_VALUE` request.
+    ///
+    /// Replies with the stored record, if any, and nodes closest to the key.
+    AnswerGetValueRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        key: P2pNetworkKadRecordKey,
+    },
+    /// Handle `ADD_PROVIDER` request.
+    ///
+    /// Stores providers for the key. Only the peer itself can be added as a
+    /// provider. This request has no reply.
+    HandleAddProviderRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        key: P2pNetworkKadRecordKey,
+        providers: Vec<P2pNetworkKadEntry>,
+    },
+    /// Answer `GET_PROVIDERS` request.
+    ///
+    /// Replies with stored providers for the key and nodes closest to it.
+    AnswerGetProvidersRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        key: P2pNetworkKadRecordKey,
+    },
+    /// Update result of scheduled outgoing `GET_VALUE`.
+    UpdateGetValueRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        record: Option<P2pNetworkKadRecord>,
+        closest_peers: Vec<P2pNetworkKadEntry>,
+    },
+    /// Update result of scheduled outgoing `GET_PROVIDERS`.
+    UpdateGetProvidersRequest {
+        addr: ConnectionAddr,
+        peer_id: PeerId,
+        stream_id: StreamId,
+        key: P2pNetworkKadRecordKey,
+        providers: Vec<P2pNetworkKadEntry>,
+        closest_peers: Vec<P2pNetworkKadEntry>,
+    },
+
+    /// Store the record locally and publish it to the nodes closest to its key.
+    ///
+    /// The record is republished periodically.
+    #[action_event(level = info)]
+    PutRecord {
+        key: P2pNetworkKadRecordKey,
+        value: Vec<u8>,
+    },
+    /// Announce to the nodes closest to the key that this node provides it.
+    ///
+    /// The announcement is republished periodically.
+    #[action_event(level = info)]
+    StartProviding { key: P2pNetworkKadRecordKey },
+    /// Look up the record, iteratively asking nodes closer to its key.
+    /// Found record is stored locally.
+    GetRecord {
+        key: P2pNetworkKadRecordKey,
+        rpc_id: Option<RpcId>,
+    },
+    /// Look up providers for the key, iteratively asking nodes closer to it.
+    /// Found providers are stored locally.
+    GetProviders {
+        key: P2pNetworkKadRecordKey,
+        rpc_id: Option<RpcId>,
+    },
+    /// Query the next closest nodes for the lookup, or finish it if there
+    /// are none left and no requests are in progress.
+    ContinueLookup { key: P2pNetworkKadRecordKey },
+    /// Record lookup is finished.
+    #[action_event(level = info)]
+    GetRecordFinished {
+        key: P2pNetworkKadRecordKey,
+        record: Option<P2pNetworkKadRecord>,
+        rpc_id: Option<RpcId>,
+    },
+    /// Providers lookup is finished.
+    #[action_event(level = info)]
+    GetProvidersFinished {
+        key: P2pNetworkKadRecordKey,
+        providers: Vec<P2pNetworkKadEntry>,
+        rpc_id: Option<RpcId>,
+    },
+
     /// Perform local node's Kademlia bootstrap.
     #[action_event(level = info)]
     StartBootstrap { key: PeerId },
@@ -91,11 +192,31 @@ impl EnablingCondition<P2pState> for P2pNetworkKademliaAction {
             } => discovery_state
                 .find_kad_stream_state(peer_id, stream_id)
                 .is_some(),
+            P2pNetworkKademliaAction::AnswerPutValueRequest {
+                peer_id, stream_id, ..
+            }
+            | P2pNetworkKademliaAction::AnswerGetValueRequest {
+                peer_id, stream_id, ..
+            }
+            | P2pNetworkKademliaAction::HandleAddProviderRequest {
+                peer_id, stream_id, ..
+            }
+            | P2pNetworkKademliaAction::AnswerGetProvidersRequest {
+                peer_id, stream_id, ..
+            } => discovery_state
+                .find_kad_stream_state(peer_id, stream_id)
+                .is_some(),
             P2pNetworkKademliaAction::UpdateFindNodeRequest {
                 addr: _,
                 peer_id,
                 stream_id,
                 ..
+            }
+            | P2pNetworkKademliaAction::UpdateGetValueRequest {
+                peer_id, stream_id, ..
+            }
+            | P2pNetworkKademliaAction::UpdateGetProvidersRequest {
+                peer_id, stream_id, ..
             } => {
                 discovery_state
                     .find_kad_stream_state(peer_id, stream_id)
@@ -113,6 +234,33 @@ impl EnablingCondition<P2pState> for P2pNetworkKademliaAction {
                 )
             }
             P2pNetworkKademliaAction::UpdateRoutingTable { .. } => true,
+            P2pNetworkKademliaAction::PutRecord { key, value } => {
+                value.len() <= MAX_RECORD_VALUE_SIZE
+                    && (discovery_state.records.local_records.contains_key(key)
+                        || discovery_state.records.local_records.len() < MAX_LOCAL_RECORDS)
+            }
+            P2pNetworkKademliaAction::StartProviding { key } => {
+                discovery_state.this_entry().is_some()
+                    && (discovery_state.records.local_providers.contains_key(key)
+                        || discovery_state.records.local_providers.len() < MAX_LOCAL_PROVIDED_KEYS)
+            }
+            P2pNetworkKademliaAction::GetRecord { key, .. }
+            | P2pNetworkKademliaAction::GetProviders { key, .. } => {
+                discovery_state.lookup(key).is_none()
+            }
+            P2pNetworkKademliaAction::ContinueLookup { key } => {
+                discovery_state.lookup(key).is_some()
+            }
+            P2pNetworkKademliaAction::GetRecordFinished { key, .. } => {
+                discovery_state.lookup(key).map_or(false, |lookup| {
+                    lookup.kind == P2pNetworkKadLookupKind::Record
+                })
+            }
+            P2pNetworkKademliaAction::GetProvidersFinished { key, .. } => {
+                discovery_state.lookup(key).map_or(false, |lookup| {
+                    lookup.kind == P2pNetworkKadLookupKind::Providers
+                })
+            }
         }
     }
 }
@@ -8,7 +8,13 @@ use super::{
 
 use super::P2pNetworkKadAction;
 
-use crate::P2pNetworkKademliaRpcReply;
+use crate::{
+    P2pNetworkKadKey, P2pNetworkKadLookupKind, P2pNetworkKadRecord, P2pNetworkKademliaRpcReply,
+    P2pNetworkKademliaRpcRequest,
+};
+
+/// Number of closest nodes a record or a provider is published to.
+const PUBLISH_PEERS: usize = 20;
 
 impl P2pNetworkKadAction {
     pub fn effects<Store, S>(self, meta: &ActionMeta, store: &mut Store) -> Result<(), String>
@@ -79,6 +85,212 @@ impl P2pNetworkKademliaAction {
                 });
                 Ok(())
             }
+            (
+                AnswerPutValueRequest {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    record,
+                },
+                _,
+            ) => {
+                store.dispatch(P2pNetworkKademliaStreamAction::SendResponse {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    data: P2pNetworkKademliaRpcReply::PutValue { record },
+                });
+                Ok(())
+            }
+            (
+                AnswerGetValueRequest {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    key,
+                },
+                _,
+            ) => {
+                let record = state.records.get(&key, meta.time());
+                let kad_key = P2pNetworkKadKey::from(&key);
+                let closer_peers = state
+                    .routing_table
+                    .find_node(&kad_key)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let message = P2pNetworkKademliaRpcReply::GetValue {
+                    key,
+                    record,
+                    closer_peers,
+                };
+                store.dispatch(P2pNetworkKademliaStreamAction::SendResponse {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    data: message,
+                });
+                Ok(())
+            }
+            (HandleAddProviderRequest { .. }, _) => Ok(()),
+            (
+                AnswerGetProvidersRequest {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    key,
+                },
+                _,
+            ) => {
+                let provider_peers = state
+                    .records
+                    .providers(&key, meta.time())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let kad_key = P2pNetworkKadKey::from(&key);
+                let closer_peers = state
+                    .routing_table
+                    .find_node(&kad_key)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let message = P2pNetworkKademliaRpcReply::GetProviders {
+                    key,
+                    provider_peers,
+                    closer_peers,
+                };
+                store.dispatch(P2pNetworkKademliaStreamAction::SendResponse {
+                    addr,
+                    peer_id,
+                    stream_id,
+                    data: message,
+                });
+                Ok(())
+            }
+            (
+                UpdateGetValueRequest {
+                    peer_id,
+                    stream_id,
+                    closest_peers,
+                    ..
+                },
+                _,
+            )
+            | (
+                UpdateGetProvidersRequest {
+                    peer_id,
+                    stream_id,
+                    closest_peers,
+                    ..
+                },
+                _,
+            ) => {
+                store.dispatch(P2pNetworkKadRequestAction::ReplyReceived {
+                    peer_id,
+                    stream_id,
+                    data: closest_peers,
+                });
+                Ok(())
+            }
+            (PutRecord { key, value }, _) => {
+                let peers = state.closest_peers_to_request(&(&key).into(), PUBLISH_PEERS);
+                let record = P2pNetworkKadRecord { key, value };
+                for (peer_id, addr) in peers {
+                    store.dispatch(P2pNetworkKadRequestAction::New {
+                        peer_id,
+                        addr,
+                        request: P2pNetworkKademliaRpcRequest::PutValue {
+                            record: record.clone(),
+                        },
+                    });
+                }
+                Ok(())
+            }
+            (StartProviding { key }, _) => {
+                let Some(this_entry) = state.this_entry().cloned() else {
+                    return Err(String::from("no routing table entry for this node"));
+                };
+                let peers = state.closest_peers_to_request(&(&key).into(), PUBLISH_PEERS);
+                for (peer_id, addr) in peers {
+                    store.dispatch(P2pNetworkKadRequestAction::New {
+                        peer_id,
+                        addr,
+                        request: P2pNetworkKademliaRpcRequest::AddProvider {
+                            key: key.clone(),
+                            providers: vec![this_entry.clone()],
+                        },
+                    });
+                }
+                Ok(())
+            }
+            (GetRecord { key, rpc_id }, _) => {
+                if let Some(record) = state.records.get(&key, meta.time()) {
+                    store.dispatch(GetRecordFinished {
+                        key,
+                        record: Some(record),
+                        rpc_id,
+                    });
+                } else {
+                    store.dispatch(ContinueLookup { key });
+                }
+                Ok(())
+            }
+            (GetProviders { key, .. }, _) => {
+                store.dispatch(ContinueLookup { key });
+                Ok(())
+            }
+            (ContinueLookup { key }, _) => {
+                let Some(lookup) = state.lookup(&key) else {
+                    return Err(format!("no lookup for {key}"));
+                };
+                let finished = lookup.is_done() || lookup.is_timed_out(meta.time());
+                let peers = if finished {
+                    Vec::new()
+                } else {
+                    state.lookup_peers_to_request(&key)
+                };
+                if finished || (peers.is_empty() && state.lookup_requests_in_progress(&key) == 0) {
+                    let rpc_id = lookup.rpc_id;
+                    match lookup.kind {
+                        P2pNetworkKadLookupKind::Record => {
+                            let record = lookup.record.clone();
+                            store.dispatch(GetRecordFinished {
+                                key,
+                                record,
+                                rpc_id,
+                            });
+                        }
+                        P2pNetworkKadLookupKind::Providers => {
+                            let providers = lookup.providers.clone();
+                            store.dispatch(GetProvidersFinished {
+                                key,
+                                providers,
+                                rpc_id,
+                            });
+                        }
+                    }
+                    return Ok(());
+                }
+                let kind = lookup.kind;
+                for (peer_id, addr) in peers {
+                    let key = key.clone();
+                    let request = match kind {
+                        P2pNetworkKadLookupKind::Record => {
+                            P2pNetworkKademliaRpcRequest::GetValue { key }
+                        }
+                        P2pNetworkKadLookupKind::Providers => {
+                            P2pNetworkKademliaRpcRequest::GetProviders { key }
+                        }
+                    };
+                    store.dispatch(P2pNetworkKadRequestAction::New {
+                        peer_id,
+                        addr,
+                        request,
+                    });
+                }
+                Ok(())
+            }
+            (GetRecordFinished { .. } | GetProvidersFinished { .. }, _) => Ok(()),
             (StartBootstrap { .. }, _) => {
                 if store
                     .state()
@@ -11,7 +11,8 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::{
-    ConnectionType, P2pNetworkKademliaMultiaddrError, P2pNetworkKademliaPeerIdError, PeerId,
+    ConnectionType, P2pNetworkKadRecordKey, P2pNetworkKademliaMultiaddrError,
+    P2pNetworkKademliaPeerIdError, PeerId,
 };
 
 mod u256_serde {
@@ -142,6 +143,13 @@ impl From<PeerId> for P2pNetworkKadKey {
     }
 }
 
+impl From<&P2pNetworkKadRecordKey> for P2pNetworkKadKey {
+    fn from(value: &P2pNetworkKadRecordKey) -> Self {
+        let digest = Sha256::digest(value.as_bytes());
+        P2pNetworkKadKey(<U256 as ArrayEncoding>::from_be_byte_array(digest))
+    }
+}
+
 /// Kademlia distance between two nodes, calculated as `XOR` of their keys.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct P2pNetworkKadDist(#[serde(with = "u256_serde")] U256);
@@ -6,7 +6,9 @@ use std::{
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
 
-use super::{P2pNetworkKadEntry, P2pNetworkKadEntryTryFromError};
+use super::{
+    P2pNetworkKadEntry, P2pNetworkKadEntryTryFromError, P2pNetworkKadRecord, P2pNetworkKadRecordKey,
+};
 use crate::{mod_Message::MessageType, PeerId};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Serialize, Deserialize)]
@@ -41,7 +43,22 @@ impl From<ConnectionType> for super::mod_Message::ConnectionType {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum P2pNetworkKademliaRpcRequest {
-    FindNode { key: PeerId },
+    FindNode {
+        key: PeerId,
+    },
+    PutValue {
+        record: P2pNetworkKadRecord,
+    },
+    GetValue {
+        key: P2pNetworkKadRecordKey,
+    },
+    AddProvider {
+        key: P2pNetworkKadRecordKey,
+        providers: Vec<P2pNetworkKadEntry>,
+    },
+    GetProviders {
+        key: P2pNetworkKadRecordKey,
+    },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -49,12 +66,39 @@ pub enum P2pNetworkKademliaRpcReply {
     FindNode {
         closer_peers: Vec<P2pNetworkKadEntry>,
     },
+    PutValue {
+        record: P2pNetworkKadRecord,
+    },
+    GetValue {
+        key: P2pNetworkKadRecordKey,
+        record: Option<P2pNetworkKadRecord>,
+        closer_peers: Vec<P2pNetworkKadEntry>,
+    },
+    GetProviders {
+        key: P2pNetworkKadRecordKey,
+        provider_peers: Vec<P2pNetworkKadEntry>,
+        closer_peers: Vec<P2pNetworkKadEntry>,
+    },
 }
 
 impl P2pNetworkKademliaRpcRequest {
     pub fn find_node(key: PeerId) -> Self {
         P2pNetworkKademliaRpcRequest::FindNode { key }
     }
+
+    /// Whether the remote peer is expected to reply to this request.
+    pub fn expects_reply(&self) -> bool {
+        !matches!(self, P2pNetworkKademliaRpcRequest::AddProvider { .. })
+    }
+
+    /// Key of the lookup this request is a part of, if any.
+    pub fn lookup_key(&self) -> Option<&P2pNetworkKadRecordKey> {
+        match self {
+            P2pNetworkKademliaRpcRequest::GetValue { key }
+            | P2pNetworkKademliaRpcRequest::GetProviders { key } => Some(key),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, PartialEq, Deserialize, thiserror::Error)]
@@ -123,6 +167,45 @@ pub enum P2pNetworkKademliaRpcFromMessageError {
     Peer(#[from] P2pNetworkKadEntryTryFromError),
     #[error("unsupported RPC kind: {0}")]
     Unsupported(String),
+    #[error("missing record")]
+    MissingRecord,
+    #[error("record key doesn't match message key")]
+    RecordKeyMismatch,
+}
+
+fn record_try_from_message(
+    value: &super::Message<'_>,
+) -> Result<P2pNetworkKadRecord, P2pNetworkKademliaRpcFromMessageError> {
+    let record = value
+        .record
+        .as_ref()
+        .ok_or(P2pNetworkKademliaRpcFromMessageError::MissingRecord)?;
+    if !value.key.is_empty() && value.key != record.key {
+        return Err(P2pNetworkKademliaRpcFromMessageError::RecordKeyMismatch);
+    }
+    Ok(P2pNetworkKadRecord {
+        key: record.key.to_vec().into(),
+        value: record.value.to_vec(),
+    })
+}
+
+fn peers_try_from_message(
+    peers: Vec<super::mod_Message::Peer<'_>>,
+) -> Result<Vec<P2pNetworkKadEntry>, P2pNetworkKademliaRpcFromMessageError> {
+    Ok(peers
+        .into_iter()
+        .map(TryFrom::try_from)
+        .collect::<Result<_, _>>()?)
+}
+
+impl<'a> From<&'a P2pNetworkKadRecord> for super::Record<'a> {
+    fn from(value: &'a P2pNetworkKadRecord) -> Self {
+        super::Record {
+            key: value.key.as_bytes().into(),
+            value: value.value.as_slice().into(),
+            ..Default::default()
+        }
+    }
 }
 
 impl<'a> TryFrom<super::Message<'a>> for P2pNetworkKademliaRpcRequest {
@@ -134,6 +217,20 @@ impl<'a> TryFrom<super::Message<'a>> for P2pNetworkKademliaRpcRequest {
                 let key = peer_id_try_from_bytes(value.key)?;
                 Ok(P2pNetworkKademliaRpcRequest::FindNode { key })
             }
+            MessageType::PUT_VALUE => {
+                let record = record_try_from_message(&value)?;
+                Ok(P2pNetworkKademliaRpcRequest::PutValue { record })
+            }
+            MessageType::GET_VALUE => Ok(P2pNetworkKademliaRpcRequest::GetValue {
+                key: value.key.into_owned().into(),
+            }),
+            MessageType::ADD_PROVIDER => Ok(P2pNetworkKademliaRpcRequest::AddProvider {
+                key: value.key.into_owned().into(),
+                providers: peers_try_from_message(value.providerPeers)?,
+            }),
+            MessageType::GET_PROVIDERS => Ok(P2pNetworkKademliaRpcRequest::GetProviders {
+                key: value.key.into_owned().into(),
+            }),
             _ => Err(P2pNetworkKademliaRpcFromMessageError::Unsupported(format!(
                 "{:?}",
                 value.type_pb
@@ -148,13 +245,30 @@ impl<'a> TryFrom<super::Message<'a>> for P2pNetworkKademliaRpcReply {
     fn try_from(value: super::Message<'a>) -> Result<Self, Self::Error> {
         match value.type_pb {
             MessageType::FIND_NODE => {
-                let closer_peers = value
-                    .closerPeers
-                    .into_iter()
-                    .map(TryFrom::try_from)
-                    .collect::<Result<_, _>>()?;
+                let closer_peers = peers_try_from_message(value.closerPeers)?;
                 Ok(P2pNetworkKademliaRpcReply::FindNode { closer_peers })
             }
+            MessageType::PUT_VALUE => {
+                let record = record_try_from_message(&value)?;
+                Ok(P2pNetworkKademliaRpcReply::PutValue { record })
+            }
+            MessageType::GET_VALUE => {
+                let record = value
+                    .record
+                    .is_some()
+                    .then(|| record_try_from_message(&value))
+                    .transpose()?;
+                Ok(P2pNetworkKademliaRpcReply::GetValue {
+                    key: value.key.into_owned().into(),
+                    record,
+                    closer_peers: peers_try_from_message(value.closerPeers)?,
+                })
+            }
+            MessageType::GET_PROVIDERS => Ok(P2pNetworkKademliaRpcReply::GetProviders {
+                key: value.key.into_owned().into(),
+                provider_peers: peers_try_from_message(value.providerPeers)?,
+                closer_peers: peers_try_from_message(value.closerPeers)?,
+            }),
             _ => Err(P2pNetworkKademliaRpcFromMessageError::Unsupported(format!(
                 "{:?}",
                 value.type_pb
@@ -172,6 +286,32 @@ impl<'a> From<&'a P2pNetworkKademliaRpcRequest> for super::Message<'a> {
                 key: key.into(),
                 ..Default::default()
             },
+            P2pNetworkKademliaRpcRequest::PutValue { record } => super::Message {
+                type_pb: MessageType::PUT_VALUE,
+                clusterLevelRaw: 10,
+                key: record.key.as_bytes().into(),
+                record: Some(record.into()),
+                ..Default::default()
+            },
+            P2pNetworkKademliaRpcRequest::GetValue { key } => super::Message {
+                type_pb: MessageType::GET_VALUE,
+                clusterLevelRaw: 10,
+                key: key.as_bytes().into(),
+                ..Default::default()
+            },
+            P2pNetworkKademliaRpcRequest::AddProvider { key, providers } => super::Message {
+                type_pb: MessageType::ADD_PROVIDER,
+                clusterLevelRaw: 10,
+                key: key.as_bytes().into(),
+                providerPeers: providers.iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+            P2pNetworkKademliaRpcRequest::GetProviders { key } => super::Message {
+                type_pb: MessageType::GET_PROVIDERS,
+                clusterLevelRaw: 10,
+                key: key.as_bytes().into(),
+                ..Default::default()
+            },
         }
     }
 }
@@ -185,6 +325,37 @@ impl<'a> From<&'a P2pNetworkKademliaRpcReply> for super::Message<'a> {
                 closerPeers: closer_peers.iter().map(Into::into).collect(),
                 ..Default::default()
             },
+            P2pNetworkKademliaRpcReply::PutValue { record } => super::Message {
+                type_pb: MessageType::PUT_VALUE,
+                clusterLevelRaw: 10,
+                key: record.key.as_bytes().into(),
+                record: Some(record.into()),
+                ..Default::default()
+            },
+            P2pNetworkKademliaRpcReply::GetValue {
+                key,
+                record,
+                closer_peers,
+            } => super::Message {
+                type_pb: MessageType::GET_VALUE,
+                clusterLevelRaw: 10,
+                key: key.as_bytes().into(),
+                record: record.as_ref().map(Into::into),
+                closerPeers: closer_peers.iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+            P2pNetworkKademliaRpcReply::GetProviders {
+                key,
+                provider_peers,
+                closer_peers,
+            } => super::Message {
+                type_pb: MessageType::GET_PROVIDERS,
+                clusterLevelRaw: 10,
+                key: key.as_bytes().into(),
+                providerPeers: provider_peers.iter().map(Into::into).collect(),
+                closerPeers: closer_peers.iter().map(Into::into).collect(),
+                ..Default::default()
+            },
         }
     }
 }
@@ -295,7 +466,9 @@ pub mod tests {
         let message = super::P2pNetworkKademliaRpcRequest::try_from(protobuf_message)
             .expect("should be able to convert");
 
-        let P2pNetworkKademliaRpcRequest::FindNode { key } = message;
+        let P2pNetworkKademliaRpcRequest::FindNode { key } = message else {
+            panic!("expected FIND_NODE request");
+        };
         assert_eq!(
             &key.to_libp2p_string(),
             "12D3KooWNXARF5S7qTRZZuoTZwSda7XA7fBh4oz1vZadHnaFv1nL"
@@ -318,10 +491,36 @@ pub mod tests {
         let message = super::P2pNetworkKademliaRpcRequest::try_from(protobuf_message)
             .expect("should be able to convert");
 
-        let P2pNetworkKademliaRpcRequest::FindNode { key } = message;
+        let P2pNetworkKademliaRpcRequest::FindNode { key } = message else {
+            panic!("expected FIND_NODE request");
+        };
         assert_eq!(
             &key.to_libp2p_string(),
             "12D3KooWNXARF5S7qTRZZuoTZwSda7XA7fBh4oz1vZadHnaFv1nL"
         );
     }
+
+    #[test]
+    fn put_value_roundtrip() {
+        let request = P2pNetworkKademliaRpcRequest::PutValue {
+            record: crate::P2pNetworkKadRecord {
+                key: "key".into(),
+                value: b"value".to_vec(),
+            },
+        };
+        let bytes = quick_protobuf::serialize_into_vec(&super::super::Message::from(&request))
+            .expect("should be able to encode");
+        let protobuf_message = BytesReader::from_bytes(&bytes)
+            .read_message::<super::super::Message>(&bytes)
+            .expect("should be able to decode");
+
+        let P2pNetworkKademliaRpcRequest::PutValue { record } =
+            P2pNetworkKademliaRpcRequest::try_from(protobuf_message)
+                .expect("should be able to convert")
+        else {
+            panic!("expected PUT_VALUE request");
+        };
+        assert_eq!(record.key.as_bytes(), b"key");
+        assert_eq!(record.value, b"value");
+    }
 }
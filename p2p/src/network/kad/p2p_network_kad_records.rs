@@ -0,0 +1,421 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use redux::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{P2pNetworkKadEntry, PeerId};
+
+/// Maximum number of records stored for other peers.
+pub const MAX_RECORDS: usize = 1024;
+/// Maximum size of a record value.
+pub const MAX_RECORD_VALUE_SIZE: usize = 64 * 1024;
+/// Maximum number of keys we store providers for.
+pub const MAX_PROVIDED_KEYS: usize = 1024;
+/// Maximum number of providers stored for a single key.
+pub const MAX_PROVIDERS_PER_KEY: usize = 20;
+/// Maximum number of records published by this node.
+pub const MAX_LOCAL_RECORDS: usize = 64;
+/// Maximum number of keys provided by this node.
+pub const MAX_LOCAL_PROVIDED_KEYS: usize = 64;
+
+/// Time after which a stored record expires.
+pub const RECORD_TTL: Duration = Duration::from_secs(36 * 60 * 60);
+/// Time after which a stored provider record expires.
+pub const PROVIDER_TTL: Duration = Duration::from_secs(48 * 60 * 60);
+/// Interval for republishing our own records.
+pub const RECORD_REPUBLISH_INTERVAL: Duration = Duration::from_secs(22 * 60 * 60);
+/// Interval for republishing our own provider records.
+pub const PROVIDER_REPUBLISH_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Key of a Kademlia value or provider record.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, derive_more::From, derive_more::AsRef)]
+pub struct P2pNetworkKadRecordKey(Vec<u8>);
+
+impl Serialize for P2pNetworkKadRecordKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // hex in human readable formats, so the key can be used as a map key.
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(&self.0))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for P2pNetworkKadRecordKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            hex::decode(s).map(Self).map_err(|e| {
+                serde::de::Error::custom(format!("error converting from hex string: {e}"))
+            })
+        } else {
+            Vec::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+impl P2pNetworkKadRecordKey {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<&str> for P2pNetworkKadRecordKey {
+    fn from(value: &str) -> Self {
+        P2pNetworkKadRecordKey(value.as_bytes().to_vec())
+    }
+}
+
+impl std::fmt::Display for P2pNetworkKadRecordKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match std::str::from_utf8(&self.0) {
+            Ok(s) if s.chars().all(|c| !c.is_control()) => f.write_str(s),
+            _ => f.write_str(&hex::encode(&self.0)),
+        }
+    }
+}
+
+impl std::fmt::Debug for P2pNetworkKadRecordKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "P2pNetworkKadRecordKey({self})")
+    }
+}
+
+/// Kademlia value record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct P2pNetworkKadRecord {
+    pub key: P2pNetworkKadRecordKey,
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct P2pNetworkKadStoredRecord {
+    pub value: Vec<u8>,
+    pub time_received: Timestamp,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct P2pNetworkKadProviderRecord {
+    pub entry: P2pNetworkKadEntry,
+    pub time_received: Timestamp,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct P2pNetworkKadLocalRecord {
+    pub value: Vec<u8>,
+    pub published_at: Timestamp,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+pub enum P2pNetworkKadRecordStoreError {
+    #[error("record value is too large: {0} bytes")]
+    ValueTooLarge(usize),
+    #[error("record store is full")]
+    MaxRecords,
+    #[error("provider store is full")]
+    MaxProvidedKeys,
+    #[error("too many providers for the key")]
+    MaxProviders,
+    #[error("too many records published by this node")]
+    MaxLocalRecords,
+    #[error("too many keys provided by this node")]
+    MaxLocalProvidedKeys,
+}
+
+/// Local storage for Kademlia records, bounded and expiring.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct P2pNetworkKadRecordStore {
+    /// Value records, either received from peers or our own.
+    pub records: BTreeMap<P2pNetworkKadRecordKey, P2pNetworkKadStoredRecord>,
+    /// Provider records, either received from peers or our own.
+    pub providers: BTreeMap<P2pNetworkKadRecordKey, BTreeMap<PeerId, P2pNetworkKadProviderRecord>>,
+    /// Records published by this node.
+    pub local_records: BTreeMap<P2pNetworkKadRecordKey, P2pNetworkKadLocalRecord>,
+    /// Keys this node provides, with the time of the latest publication.
+    pub local_providers: BTreeMap<P2pNetworkKadRecordKey, Timestamp>,
+}
+
+fn is_expired(time_received: Timestamp, ttl: Duration, now: Timestamp) -> bool {
+    now.checked_sub(time_received)
+        .map_or(false, |elapsed| elapsed >= ttl)
+}
+
+impl P2pNetworkKadRecordStore {
+    /// Returns the record for the `key`, if it is not expired.
+    pub fn get(&self, key: &P2pNetworkKadRecordKey, now: Timestamp) -> Option<P2pNetworkKadRecord> {
+        if let Some(local) = self.local_records.get(key) {
+            return Some(P2pNetworkKadRecord {
+                key: key.clone(),
+                value: local.value.clone(),
+            });
+        }
+        self.records
+            .get(key)
+            .filter(|record| !is_expired(record.time_received, RECORD_TTL, now))
+            .map(|record| P2pNetworkKadRecord {
+                key: key.clone(),
+                value: record.value.clone(),
+            })
+    }
+
+    /// Returns non-expired providers for the `key`.
+    pub fn providers<'a>(
+        &'a self,
+        key: &P2pNetworkKadRecordKey,
+        now: Timestamp,
+    ) -> impl Iterator<Item = &'a P2pNetworkKadEntry> {
+        self.providers
+            .get(key)
+            .into_iter()
+            .flat_map(|providers| providers.values())
+            .filter(move |provider| !is_expired(provider.time_received, PROVIDER_TTL, now))
+            .map(|provider| &provider.entry)
+    }
+
+    /// Stores the record received from a peer.
+    pub fn put(
+        &mut self,
+        record: P2pNetworkKadRecord,
+        now: Timestamp,
+    ) -> Result<(), P2pNetworkKadRecordStoreError> {
+        if record.value.len() > MAX_RECORD_VALUE_SIZE {
+            return Err(P2pNetworkKadRecordStoreError::ValueTooLarge(
+                record.value.len(),
+            ));
+        }
+        if !self.records.contains_key(&record.key) && self.records.len() >= MAX_RECORDS {
+            self.prune(now);
+            if self.records.len() >= MAX_RECORDS {
+                return Err(P2pNetworkKadRecordStoreError::MaxRecords);
+            }
+        }
+        self.records.insert(
+            record.key,
+            P2pNetworkKadStoredRecord {
+                value: record.value,
+                time_received: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stores the provider for the `key`.
+    pub fn add_provider(
+        &mut self,
+        key: P2pNetworkKadRecordKey,
+        entry: P2pNetworkKadEntry,
+        now: Timestamp,
+    ) -> Result<(), P2pNetworkKadRecordStoreError> {
+        if !self.providers.contains_key(&key) && self.providers.len() >= MAX_PROVIDED_KEYS {
+            self.prune(now);
+            if self.providers.len() >= MAX_PROVIDED_KEYS {
+                return Err(P2pNetworkKadRecordStoreError::MaxProvidedKeys);
+            }
+        }
+        let providers = self.providers.entry(key).or_default();
+        if !providers.contains_key(&entry.peer_id) && providers.len() >= MAX_PROVIDERS_PER_KEY {
+            providers.retain(|_, p| !is_expired(p.time_received, PROVIDER_TTL, now));
+            if providers.len() >= MAX_PROVIDERS_PER_KEY {
+                return Err(P2pNetworkKadRecordStoreError::MaxProviders);
+            }
+        }
+        providers.insert(
+            entry.peer_id,
+            P2pNetworkKadProviderRecord {
+                entry,
+                time_received: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stores our own record. It is republished periodically.
+    pub fn put_local(
+        &mut self,
+        key: P2pNetworkKadRecordKey,
+        value: Vec<u8>,
+        now: Timestamp,
+    ) -> Result<(), P2pNetworkKadRecordStoreError> {
+        if value.len() > MAX_RECORD_VALUE_SIZE {
+            return Err(P2pNetworkKadRecordStoreError::ValueTooLarge(value.len()));
+        }
+        if !self.local_records.contains_key(&key) && self.local_records.len() >= MAX_LOCAL_RECORDS {
+            return Err(P2pNetworkKadRecordStoreError::MaxLocalRecords);
+        }
+        self.local_records.insert(
+            key,
+            P2pNetworkKadLocalRecord {
+                value,
+                published_at: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Marks the `key` as provided by this node. It is republished
+    /// periodically.
+    pub fn add_local_provider(
+        &mut self,
+        key: P2pNetworkKadRecordKey,
+        now: Timestamp,
+    ) -> Result<(), P2pNetworkKadRecordStoreError> {
+        if !self.local_providers.contains_key(&key)
+            && self.local_providers.len() >= MAX_LOCAL_PROVIDED_KEYS
+        {
+            return Err(P2pNetworkKadRecordStoreError::MaxLocalProvidedKeys);
+        }
+        self.local_providers.insert(key, now);
+        Ok(())
+    }
+
+    /// Our own records that should be republished.
+    pub fn local_records_to_republish(
+        &self,
+        now: Timestamp,
+    ) -> impl Iterator<Item = (&P2pNetworkKadRecordKey, &P2pNetworkKadLocalRecord)> {
+        self.local_records
+            .iter()
+            .filter(move |(_, r)| is_expired(r.published_at, RECORD_REPUBLISH_INTERVAL, now))
+    }
+
+    /// Keys provided by this node that should be republished.
+    pub fn local_providers_to_republish(
+        &self,
+        now: Timestamp,
+    ) -> impl Iterator<Item = &P2pNetworkKadRecordKey> {
+        self.local_providers
+            .iter()
+            .filter(move |(_, t)| is_expired(**t, PROVIDER_REPUBLISH_INTERVAL, now))
+            .map(|(key, _)| key)
+    }
+
+    /// Removes expired records and providers.
+    pub fn prune(&mut self, now: Timestamp) {
+        self.records
+            .retain(|_, r| !is_expired(r.time_received, RECORD_TTL, now));
+        self.providers.retain(|_, providers| {
+            providers.retain(|_, p| !is_expired(p.time_received, PROVIDER_TTL, now));
+            !providers.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> P2pNetworkKadEntry {
+        let peer_id = "2bEgBrPTzL8wov2D4Kz34WVLCxR4uCarsBmHYXWKQA5wvBQzd9H"
+            .parse::<PeerId>()
+            .unwrap();
+        P2pNetworkKadEntry::new(peer_id, vec![])
+    }
+
+    #[test]
+    fn records_expire() {
+        let mut store = P2pNetworkKadRecordStore::default();
+        let key = P2pNetworkKadRecordKey::from("key");
+        let record = P2pNetworkKadRecord {
+            key: key.clone(),
+            value: b"value".to_vec(),
+        };
+        let t0 = Timestamp::ZERO;
+        let expired = t0 + RECORD_TTL.as_nanos() as u64;
+
+        store.put(record.clone(), t0).unwrap();
+        assert_eq!(store.get(&key, t0), Some(record));
+        assert_eq!(store.get(&key, expired), None);
+
+        store.prune(expired);
+        assert!(store.records.is_empty());
+    }
+
+    #[test]
+    fn records_bounded() {
+        let mut store = P2pNetworkKadRecordStore::default();
+        let t0 = Timestamp::ZERO;
+        for i in 0..MAX_RECORDS {
+            let key = P2pNetworkKadRecordKey::from(i.to_string().as_str());
+            store
+                .put(P2pNetworkKadRecord { key, value: vec![] }, t0)
+                .unwrap();
+        }
+        let record = P2pNetworkKadRecord {
+            key: "extra".into(),
+            value: vec![],
+        };
+        assert_eq!(
+            store.put(record.clone(), t0),
+            Err(P2pNetworkKadRecordStoreError::MaxRecords)
+        );
+        // expired records are evicted to make room for the new one.
+        assert_eq!(store.put(record, t0 + RECORD_TTL.as_nanos() as u64), Ok(()));
+        assert_eq!(store.records.len(), 1);
+
+        let value = vec![0; MAX_RECORD_VALUE_SIZE + 1];
+        assert!(store
+            .put(
+                P2pNetworkKadRecord {
+                    key: "big".into(),
+                    value
+                },
+                t0
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn local_records_bounded() {
+        let mut store = P2pNetworkKadRecordStore::default();
+        let t0 = Timestamp::ZERO;
+        for i in 0..MAX_LOCAL_RECORDS {
+            let key = P2pNetworkKadRecordKey::from(i.to_string().as_str());
+            store.put_local(key.clone(), vec![], t0).unwrap();
+            store.add_local_provider(key, t0).unwrap();
+        }
+        assert_eq!(
+            store.put_local("extra".into(), vec![], t0),
+            Err(P2pNetworkKadRecordStoreError::MaxLocalRecords)
+        );
+        assert_eq!(
+            store.add_local_provider("extra".into(), t0),
+            Err(P2pNetworkKadRecordStoreError::MaxLocalProvidedKeys)
+        );
+        // updating an existing record is still possible.
+        assert_eq!(store.put_local("0".into(), b"value".to_vec(), t0), Ok(()));
+        assert_eq!(store.local_records.len(), MAX_LOCAL_RECORDS);
+    }
+
+    #[test]
+    fn record_key_json_map_key() {
+        let mut store = P2pNetworkKadRecordStore::default();
+        store
+            .put_local("key".into(), b"value".to_vec(), Timestamp::ZERO)
+            .unwrap();
+        let json = serde_json::to_string(&store).unwrap();
+        let store: P2pNetworkKadRecordStore = serde_json::from_str(&json).unwrap();
+        assert!(store
+            .local_records
+            .contains_key(&P2pNetworkKadRecordKey::from("key")));
+    }
+
+    #[test]
+    fn providers_expire() {
+        let mut store = P2pNetworkKadRecordStore::default();
+        let key = P2pNetworkKadRecordKey::from("snarker");
+        let t0 = Timestamp::ZERO;
+        store.add_provider(key.clone(), entry(), t0).unwrap();
+        assert_eq!(store.providers(&key, t0).count(), 1);
+        let expired = t0 + PROVIDER_TTL.as_nanos() as u64;
+        assert_eq!(store.providers(&key, expired).count(), 0);
+        store.prune(expired);
+        assert!(store.providers.is_empty());
+    }
+}
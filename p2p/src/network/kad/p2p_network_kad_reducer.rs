@@ -2,7 +2,10 @@ use redux::ActionWithMeta;
 
 use crate::{P2pLimits, P2pNetworkKadEntry};
 
-use super::{P2pNetworkKadAction, P2pNetworkKadLatestRequestPeerKind, P2pNetworkKadStatus};
+use super::{
+    P2pNetworkKadAction, P2pNetworkKadLatestRequestPeerKind, P2pNetworkKadLookupKind,
+    P2pNetworkKadStatus,
+};
 
 use super::stream::P2pNetworkKademliaStreamAction;
 
@@ -27,11 +30,24 @@ impl super::P2pNetworkKadState {
                 }
             }
             P2pNetworkKadAction::Request(
-                action @ super::request::P2pNetworkKadRequestAction::New { addr, peer_id, key },
-            ) => self
-                .create_request(*addr, *peer_id, *key)
-                .map_err(|_request| format!("kademlia request to {addr} is already in progress"))
-                .and_then(|request| request.reducer(meta.with_action(action))),
+                action @ super::request::P2pNetworkKadRequestAction::New {
+                    addr,
+                    peer_id,
+                    request,
+                },
+            ) => {
+                if let Some(lookup) = request
+                    .lookup_key()
+                    .and_then(|key| self.lookups.get_mut(key))
+                {
+                    lookup.queried.insert(*peer_id);
+                }
+                self.create_request(*addr, *peer_id, request.clone())
+                    .map_err(|_request| {
+                        format!("kademlia request to {addr} is already in progress")
+                    })
+                    .and_then(|request| request.reducer(meta.with_action(action)))
+            }
             P2pNetworkKadAction::Request(super::request::P2pNetworkKadRequestAction::Prune {
                 peer_id,
             }) => self
@@ -74,6 +90,116 @@ impl super::P2pNetworkKadState {
         let (action, meta) = action.split();
         match (&mut self.status, action) {
             (_, AnswerFindNodeRequest { .. }) => Ok(()),
+            (_, AnswerPutValueRequest { record, .. }) => {
+                // a peer that failed to store the record will not receive its echo.
+                let _ = self.records.put(record.clone(), meta.time());
+                Ok(())
+            }
+            (_, AnswerGetValueRequest { .. }) => Ok(()),
+            (
+                _,
+                HandleAddProviderRequest {
+                    peer_id,
+                    key,
+                    providers,
+                    ..
+                },
+            ) => {
+                for provider in providers.iter().filter(|p| &p.peer_id == peer_id) {
+                    let _ = self
+                        .records
+                        .add_provider(key.clone(), provider.clone(), meta.time());
+                }
+                Ok(())
+            }
+            (_, AnswerGetProvidersRequest { .. }) => Ok(()),
+            (
+                _,
+                UpdateGetValueRequest {
+                    peer_id,
+                    record,
+                    closest_peers,
+                    ..
+                },
+            ) => {
+                if let Some(record) = record {
+                    let _ = self.records.put(record.clone(), meta.time());
+                }
+                self.routing_table.extend(closest_peers.iter().cloned());
+                let lookup_key = self
+                    .requests
+                    .get(peer_id)
+                    .and_then(|request| request.request.lookup_key())
+                    .cloned();
+                if let Some(key) = lookup_key {
+                    if let Some(lookup) = self.lookups.get_mut(&key) {
+                        lookup.add_closest_peers(&key, closest_peers);
+                        if lookup.record.is_none() {
+                            lookup.record = record.clone().filter(|record| record.key == key);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            (
+                _,
+                UpdateGetProvidersRequest {
+                    key,
+                    providers,
+                    closest_peers,
+                    ..
+                },
+            ) => {
+                for provider in providers {
+                    let _ = self
+                        .records
+                        .add_provider(key.clone(), provider.clone(), meta.time());
+                }
+                self.routing_table.extend(closest_peers.iter().cloned());
+                if let Some(lookup) = self.lookups.get_mut(key) {
+                    lookup.add_closest_peers(key, closest_peers);
+                    lookup.add_providers(providers);
+                }
+                Ok(())
+            }
+            (_, PutRecord { key, value }) => self
+                .records
+                .put_local(key.clone(), value.clone(), meta.time())
+                .map_err(|e| e.to_string()),
+            (_, StartProviding { key }) => {
+                self.records
+                    .add_local_provider(key.clone(), meta.time())
+                    .map_err(|e| e.to_string())?;
+                if let Some(this_entry) = self.this_entry().cloned() {
+                    let _ = self
+                        .records
+                        .add_provider(key.clone(), this_entry, meta.time());
+                }
+                Ok(())
+            }
+            (_, GetRecord { key, rpc_id }) => {
+                self.create_lookup(
+                    key.clone(),
+                    P2pNetworkKadLookupKind::Record,
+                    *rpc_id,
+                    meta.time(),
+                );
+                Ok(())
+            }
+            (_, GetProviders { key, rpc_id }) => {
+                self.create_lookup(
+                    key.clone(),
+                    P2pNetworkKadLookupKind::Providers,
+                    *rpc_id,
+                    meta.time(),
+                );
+                Ok(())
+            }
+            (_, ContinueLookup { .. }) => Ok(()),
+            (_, GetRecordFinished { key, .. } | GetProvidersFinished { key, .. }) => {
+                self.lookups.remove(key);
+                Ok(())
+            }
             (_, UpdateFindNodeRequest { closest_peers, .. }) => {
                 let mut latest_request_peers = Vec::new();
                 for entry in closest_peers {
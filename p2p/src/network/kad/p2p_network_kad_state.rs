@@ -1,17 +1,34 @@
-use std::{collections::BTreeMap, net::SocketAddr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::SocketAddr,
+    time::Duration,
+};
 
+use openmina_core::requests::RpcId;
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
 use super::{
     bootstrap::P2pNetworkKadBootstrapState, request::P2pNetworkKadRequestState,
-    stream::P2pNetworkKadStreamState, P2pNetworkKadRoutingTable,
+    stream::P2pNetworkKadStreamState, P2pNetworkKadEntry, P2pNetworkKadRecord,
+    P2pNetworkKadRecordKey, P2pNetworkKadRecordStore, P2pNetworkKadRoutingTable,
+    MAX_PROVIDERS_PER_KEY,
 };
 use crate::{
     bootstrap::{P2pNetworkKadBootstrapRequestStat, P2pNetworkKadBootstrapStats},
-    is_time_passed, P2pTimeouts, PeerId, StreamId,
+    is_time_passed, socket_addr_try_from_multiaddr, P2pNetworkKadKey, P2pNetworkKademliaRpcRequest,
+    P2pTimeouts, PeerId, StreamId,
 };
 
+/// Number of nodes queried concurrently during a lookup.
+pub const LOOKUP_PEERS: usize = 3;
+/// Number of nodes closest to the key a lookup tracks.
+pub const LOOKUP_CLOSEST_PEERS: usize = 20;
+/// Maximum number of nodes queried during a single lookup.
+pub const MAX_LOOKUP_QUERIES: usize = 60;
+/// Time after which a lookup is finished with whatever was found.
+pub const LOOKUP_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Kademlia status.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -57,6 +74,12 @@ pub struct P2pNetworkKadState {
     pub streams: crate::network::scheduler::StreamState<P2pNetworkKadStreamState>,
     pub status: P2pNetworkKadStatus,
     pub filter_addrs: bool,
+    /// Value and provider records.
+    #[serde(default)]
+    pub records: P2pNetworkKadRecordStore,
+    /// Record and provider lookups in progress.
+    #[serde(default)]
+    pub lookups: BTreeMap<P2pNetworkKadRecordKey, P2pNetworkKadLookupState>,
 }
 
 impl Default for P2pNetworkKadState {
@@ -71,6 +94,8 @@ impl Default for P2pNetworkKadState {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(true),
+            records: Default::default(),
+            lookups: Default::default(),
         }
     }
 }
@@ -80,6 +105,11 @@ impl P2pNetworkKadState {
         matches!(&self.status, P2pNetworkKadStatus::Bootstrapped { .. })
     }
 
+    /// Routing table entry of this node.
+    pub fn this_entry(&self) -> Option<&P2pNetworkKadEntry> {
+        self.routing_table.look_up(&self.routing_table.this_key)
+    }
+
     pub fn bootstrap_state(&self) -> Option<&super::bootstrap::P2pNetworkKadBootstrapState> {
         if let P2pNetworkKadStatus::Bootstrapping(state) = &self.status {
             Some(state)
@@ -112,13 +142,13 @@ impl P2pNetworkKadState {
         &mut self,
         addr: SocketAddr,
         peer_id: PeerId,
-        key: PeerId,
+        request: P2pNetworkKademliaRpcRequest,
     ) -> Result<&mut P2pNetworkKadRequestState, &P2pNetworkKadRequestState> {
         match self.requests.entry(peer_id) {
             std::collections::btree_map::Entry::Vacant(v) => {
                 Ok(v.insert(P2pNetworkKadRequestState {
                     peer_id,
-                    key,
+                    request,
                     addr,
                     status: crate::request::P2pNetworkKadRequestStatus::Default,
                 }))
@@ -127,6 +157,110 @@ impl P2pNetworkKadState {
         }
     }
 
+    /// Nodes closest to the `key` that have a usable address and no request
+    /// in progress, at most `count` of them.
+    pub fn closest_peers_to_request(
+        &self,
+        key: &P2pNetworkKadKey,
+        count: usize,
+    ) -> Vec<(PeerId, SocketAddr)> {
+        self.routing_table
+            .closest_peers(key)
+            .filter_map(|entry| self.entry_to_request(entry))
+            .take(count)
+            .collect()
+    }
+
+    /// Address to send a request to the `entry`, unless it is this node or a
+    /// request to it is already in progress.
+    fn entry_to_request(&self, entry: &P2pNetworkKadEntry) -> Option<(PeerId, SocketAddr)> {
+        if entry.key == self.routing_table.this_key
+            || self.requests.contains_key(&entry.peer_id)
+            || self
+                .bootstrap_state()
+                .map_or(false, |state| state.request(&entry.peer_id).is_some())
+        {
+            return None;
+        }
+        let filter_addrs = self.filter_addrs;
+        let addr = entry
+            .addrs
+            .iter()
+            .filter_map(|addr| socket_addr_try_from_multiaddr(addr).ok())
+            .find(|addr| {
+                !filter_addrs
+                    || match addr.ip() {
+                        std::net::IpAddr::V4(v) => !(v.is_loopback() || v.is_private()),
+                        std::net::IpAddr::V6(v) => !v.is_loopback(),
+                    }
+            })?;
+        Some((entry.peer_id, addr))
+    }
+
+    pub fn lookup(&self, key: &P2pNetworkKadRecordKey) -> Option<&P2pNetworkKadLookupState> {
+        self.lookups.get(key)
+    }
+
+    /// Creates a lookup for the `key`, starting from the closest nodes in
+    /// the routing table.
+    pub fn create_lookup(
+        &mut self,
+        key: P2pNetworkKadRecordKey,
+        kind: P2pNetworkKadLookupKind,
+        rpc_id: Option<RpcId>,
+        time: Timestamp,
+    ) {
+        let kad_key = P2pNetworkKadKey::from(&key);
+        let closest_peers = self
+            .routing_table
+            .closest_peers(&kad_key)
+            .filter(|entry| entry.key != self.routing_table.this_key)
+            .take(LOOKUP_CLOSEST_PEERS)
+            .cloned()
+            .collect();
+        self.lookups.insert(
+            key,
+            P2pNetworkKadLookupState {
+                kind,
+                rpc_id,
+                time,
+                closest_peers,
+                queried: Default::default(),
+                record: None,
+                providers: Vec::new(),
+            },
+        );
+    }
+
+    /// Number of requests of the lookup for the `key` that are in progress.
+    pub fn lookup_requests_in_progress(&self, key: &P2pNetworkKadRecordKey) -> usize {
+        self.requests
+            .values()
+            .filter(|request| request.request.lookup_key() == Some(key))
+            .count()
+    }
+
+    /// Closest nodes not yet queried by the lookup for the `key`, as many as
+    /// it can query now.
+    pub fn lookup_peers_to_request(
+        &self,
+        key: &P2pNetworkKadRecordKey,
+    ) -> Vec<(PeerId, SocketAddr)> {
+        let Some(lookup) = self.lookups.get(key) else {
+            return Vec::new();
+        };
+        let count = LOOKUP_PEERS
+            .saturating_sub(self.lookup_requests_in_progress(key))
+            .min(MAX_LOOKUP_QUERIES.saturating_sub(lookup.queried.len()));
+        lookup
+            .closest_peers
+            .iter()
+            .filter(|entry| !lookup.queried.contains(&entry.peer_id))
+            .filter_map(|entry| self.entry_to_request(entry))
+            .take(count)
+            .collect()
+    }
+
     pub fn find_kad_stream_state(
         &self,
         peer_id: &PeerId,
@@ -164,6 +298,76 @@ impl P2pNetworkKadState {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum P2pNetworkKadLookupKind {
+    Record,
+    Providers,
+}
+
+/// Iterative lookup of a record or providers for a key.
+///
+/// Nodes closest to the key are queried, and closer nodes from their replies
+/// are queried next, until the record is found or there are no closer nodes
+/// left to query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct P2pNetworkKadLookupState {
+    pub kind: P2pNetworkKadLookupKind,
+    /// RPC request waiting for the result of the lookup.
+    pub rpc_id: Option<RpcId>,
+    /// Time the lookup started.
+    pub time: Timestamp,
+    /// Nodes closest to the key known so far, closest first.
+    pub closest_peers: Vec<P2pNetworkKadEntry>,
+    /// Nodes the lookup already sent its request to.
+    pub queried: BTreeSet<PeerId>,
+    /// Found record.
+    pub record: Option<P2pNetworkKadRecord>,
+    /// Found providers.
+    pub providers: Vec<P2pNetworkKadEntry>,
+}
+
+impl P2pNetworkKadLookupState {
+    /// Merges nodes from a reply into the closest nodes to the `key`.
+    pub fn add_closest_peers<'a, I>(&mut self, key: &P2pNetworkKadRecordKey, peers: I)
+    where
+        I: IntoIterator<Item = &'a P2pNetworkKadEntry>,
+    {
+        for peer in peers {
+            if !self.closest_peers.iter().any(|p| p.peer_id == peer.peer_id) {
+                self.closest_peers.push(peer.clone());
+            }
+        }
+        let kad_key = P2pNetworkKadKey::from(key);
+        self.closest_peers
+            .sort_by_cached_key(|entry| &entry.key - &kad_key);
+        self.closest_peers.truncate(LOOKUP_CLOSEST_PEERS);
+    }
+
+    pub fn add_providers<'a, I>(&mut self, providers: I)
+    where
+        I: IntoIterator<Item = &'a P2pNetworkKadEntry>,
+    {
+        for provider in providers {
+            if self.providers.len() >= MAX_PROVIDERS_PER_KEY {
+                break;
+            }
+            if !self.providers.iter().any(|p| p.peer_id == provider.peer_id) {
+                self.providers.push(provider.clone());
+            }
+        }
+    }
+
+    /// Whether the lookup has nothing more to wait for.
+    pub fn is_done(&self) -> bool {
+        self.kind == P2pNetworkKadLookupKind::Record && self.record.is_some()
+    }
+
+    pub fn is_timed_out(&self, now: Timestamp) -> bool {
+        now.checked_sub(self.time)
+            .map_or(false, |elapsed| elapsed >= LOOKUP_TIMEOUT)
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, derive_more::Deref, derive_more::From)]
 pub struct P2pNetworkKadLatestRequestPeers(Vec<(PeerId, P2pNetworkKadLatestRequestPeerKind)>);
 
@@ -195,3 +399,70 @@ pub enum P2pNetworkKadLatestRequestPeerKind {
     Existing,
     Discarded,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> P2pNetworkKadEntry {
+        let peer_id = crate::identity::SecretKey::rand().public_key().peer_id();
+        P2pNetworkKadEntry::new(peer_id, vec!["/ip4/1.2.3.4/tcp/8302".parse().unwrap()])
+    }
+
+    #[test]
+    fn lookup_requests_closest_peers_not_queried() {
+        let mut state = P2pNetworkKadState {
+            routing_table: P2pNetworkKadRoutingTable::new(entry()),
+            filter_addrs: true,
+            ..Default::default()
+        };
+        let key = P2pNetworkKadRecordKey::from("key");
+        state.create_lookup(
+            key.clone(),
+            P2pNetworkKadLookupKind::Record,
+            None,
+            Timestamp::ZERO,
+        );
+        let peers = (0..LOOKUP_CLOSEST_PEERS + 5)
+            .map(|_| entry())
+            .collect::<Vec<_>>();
+        state
+            .lookups
+            .get_mut(&key)
+            .unwrap()
+            .add_closest_peers(&key, &peers);
+
+        let lookup = state.lookup(&key).unwrap();
+        let kad_key = P2pNetworkKadKey::from(&key);
+        assert_eq!(lookup.closest_peers.len(), LOOKUP_CLOSEST_PEERS);
+        assert!(lookup
+            .closest_peers
+            .windows(2)
+            .all(|w| &w[0].key - &kad_key <= &w[1].key - &kad_key));
+
+        let closest = lookup
+            .closest_peers
+            .iter()
+            .map(|entry| entry.peer_id)
+            .collect::<Vec<_>>();
+        let to_request = state
+            .lookup_peers_to_request(&key)
+            .into_iter()
+            .map(|(peer_id, _)| peer_id)
+            .collect::<Vec<_>>();
+        assert_eq!(to_request, closest[..LOOKUP_PEERS]);
+
+        state
+            .lookups
+            .get_mut(&key)
+            .unwrap()
+            .queried
+            .insert(closest[0]);
+        let to_request = state
+            .lookup_peers_to_request(&key)
+            .into_iter()
+            .map(|(peer_id, _)| peer_id)
+            .collect::<Vec<_>>();
+        assert_eq!(to_request, closest[1..LOOKUP_PEERS + 1]);
+    }
+}
@@ -4,15 +4,18 @@ use openmina_core::ActionEvent;
 use redux::EnablingCondition;
 use serde::{Deserialize, Serialize};
 
-use crate::{ConnectionAddr, P2pAction, P2pNetworkKadEntry, P2pState, PeerId, StreamId};
+use crate::{
+    ConnectionAddr, P2pAction, P2pNetworkKadEntry, P2pNetworkKademliaRpcRequest, P2pState, PeerId,
+    StreamId,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize, ActionEvent)]
-#[action_event(fields(display(peer_id), display(addr), display(key), stream_id, error))]
+#[action_event(fields(display(peer_id), display(addr), debug(request), stream_id, error))]
 pub enum P2pNetworkKadRequestAction {
     New {
         peer_id: PeerId,
         addr: SocketAddr,
-        key: PeerId,
+        request: P2pNetworkKademliaRpcRequest,
     },
     PeerIsConnecting {
         peer_id: PeerId,
@@ -6,7 +6,7 @@ use crate::{
     connection::outgoing::{P2pConnectionOutgoingAction, P2pConnectionOutgoingInitOpts},
     peer::P2pPeerAction,
     socket_addr_try_from_multiaddr, ConnectionAddr, P2pNetworkConnectionMuxState,
-    P2pNetworkKadBootstrapAction, P2pNetworkYamuxAction, P2pPeerState,
+    P2pNetworkKadBootstrapAction, P2pNetworkKademliaAction, P2pNetworkYamuxAction, P2pPeerState,
 };
 
 use super::{super::stream::P2pNetworkKademliaStreamAction, P2pNetworkKadRequestAction};
@@ -28,6 +28,7 @@ impl P2pNetworkKadRequestAction {
         let Some(request_state) = discovery_state.request(self_id) else {
             return Err(format!("no request for {self_id}"));
         };
+        let lookup_key = request_state.request.lookup_key().cloned();
 
         match self {
             P2pNetworkKadRequestAction::New { peer_id, addr, .. } => {
@@ -137,7 +138,8 @@ impl P2pNetworkKadRequestAction {
                 stream_id,
                 addr,
             } => {
-                let data = crate::P2pNetworkKademliaRpcRequest::find_node(request_state.key);
+                let data = request_state.request.clone();
+                let expects_reply = data.expects_reply();
                 store.dispatch(P2pNetworkKademliaStreamAction::SendRequest {
                     addr,
                     peer_id,
@@ -145,6 +147,14 @@ impl P2pNetworkKadRequestAction {
                     data,
                 });
                 store.dispatch(P2pNetworkKadRequestAction::RequestSent { peer_id });
+                if !expects_reply {
+                    // e.g. `ADD_PROVIDER`, finish the request right away.
+                    store.dispatch(P2pNetworkKadRequestAction::ReplyReceived {
+                        peer_id,
+                        stream_id,
+                        data: Vec::new(),
+                    });
+                }
             }
             P2pNetworkKadRequestAction::RequestSent { .. } => {}
             P2pNetworkKadRequestAction::ReplyReceived {
@@ -200,6 +210,9 @@ impl P2pNetworkKadRequestAction {
                     stream_id,
                 });
                 store.dispatch(P2pNetworkKadRequestAction::Prune { peer_id });
+                if let Some(key) = lookup_key {
+                    store.dispatch(P2pNetworkKademliaAction::ContinueLookup { key });
+                }
             }
             P2pNetworkKadRequestAction::Error { peer_id, error } => {
                 let bootstrap_request = discovery_state
@@ -210,6 +223,9 @@ impl P2pNetworkKadRequestAction {
                     store.dispatch(P2pNetworkKadBootstrapAction::RequestError { peer_id, error });
                 }
                 store.dispatch(P2pNetworkKadRequestAction::Prune { peer_id });
+                if let Some(key) = lookup_key {
+                    store.dispatch(P2pNetworkKademliaAction::ContinueLookup { key });
+                }
             }
             P2pNetworkKadRequestAction::Prune { .. } => {}
         }
@@ -1,7 +1,5 @@
 use redux::ActionWithMeta;
 
-use crate::P2pNetworkKademliaRpcRequest;
-
 use super::{P2pNetworkKadRequestAction, P2pNetworkKadRequestState};
 
 impl P2pNetworkKadRequestState {
@@ -21,8 +19,7 @@ impl P2pNetworkKadRequestState {
                 self.status = super::P2pNetworkKadRequestStatus::WaitingForKadStream(*stream_id)
             }
             P2pNetworkKadRequestAction::StreamReady { .. } => {
-                let find_node = P2pNetworkKademliaRpcRequest::FindNode { key: self.key };
-                let message = super::super::Message::from(&find_node);
+                let message = super::super::Message::from(&self.request);
                 self.status = quick_protobuf::serialize_into_vec(&message).map_or_else(
                     |e| {
                         super::P2pNetworkKadRequestStatus::Error(format!(
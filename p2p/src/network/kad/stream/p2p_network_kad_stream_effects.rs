@@ -2,6 +2,7 @@ use openmina_core::{fuzzed_maybe, warn};
 use redux::ActionMeta;
 
 use crate::{
+    request::P2pNetworkKadRequestAction,
     stream::{
         P2pNetworkKadIncomingStreamError, P2pNetworkKadOutgoingStreamError,
         P2pNetworkKadStreamState,
@@ -52,23 +53,66 @@ impl P2pNetworkKademliaStreamAction {
                     ..
                 },
                 P2pNetworkKadStreamState::Incoming(
-                    P2pNetworkKadIncomingStreamState::RequestIsReady {
-                        data: P2pNetworkKademliaRpcRequest::FindNode { key },
-                    },
+                    P2pNetworkKadIncomingStreamState::RequestIsReady { data },
                 ),
             ) => {
-                let key = *key;
-                store.dispatch(P2pNetworkKademliaStreamAction::WaitOutgoing {
-                    addr,
-                    peer_id,
-                    stream_id,
-                });
-                store.dispatch(P2pNetworkKademliaAction::AnswerFindNodeRequest {
-                    addr,
-                    peer_id,
-                    stream_id,
-                    key,
-                });
+                let data = data.clone();
+                if data.expects_reply() {
+                    store.dispatch(P2pNetworkKademliaStreamAction::WaitOutgoing {
+                        addr,
+                        peer_id,
+                        stream_id,
+                    });
+                } else {
+                    store.dispatch(P2pNetworkKademliaStreamAction::WaitIncoming {
+                        addr,
+                        peer_id,
+                        stream_id,
+                    });
+                }
+                match data {
+                    P2pNetworkKademliaRpcRequest::FindNode { key } => {
+                        store.dispatch(P2pNetworkKademliaAction::AnswerFindNodeRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            key,
+                        });
+                    }
+                    P2pNetworkKademliaRpcRequest::PutValue { record } => {
+                        store.dispatch(P2pNetworkKademliaAction::AnswerPutValueRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            record,
+                        });
+                    }
+                    P2pNetworkKademliaRpcRequest::GetValue { key } => {
+                        store.dispatch(P2pNetworkKademliaAction::AnswerGetValueRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            key,
+                        });
+                    }
+                    P2pNetworkKademliaRpcRequest::AddProvider { key, providers } => {
+                        store.dispatch(P2pNetworkKademliaAction::HandleAddProviderRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            key,
+                            providers,
+                        });
+                    }
+                    P2pNetworkKademliaRpcRequest::GetProviders { key } => {
+                        store.dispatch(P2pNetworkKademliaAction::AnswerGetProvidersRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            key,
+                        });
+                    }
+                }
                 Ok(())
             }
             (
@@ -79,23 +123,59 @@ impl P2pNetworkKademliaStreamAction {
                     ..
                 },
                 P2pNetworkKadStreamState::Outgoing(
-                    P2pNetworkKadOutgoingStreamState::ResponseIsReady {
-                        data: P2pNetworkKademliaRpcReply::FindNode { closer_peers },
-                    },
+                    P2pNetworkKadOutgoingStreamState::ResponseIsReady { data },
                 ),
             ) => {
-                let closest_peers = closer_peers.clone();
+                let data = data.clone();
                 store.dispatch(P2pNetworkKademliaStreamAction::WaitOutgoing {
                     addr,
                     peer_id,
                     stream_id,
                 });
-                store.dispatch(P2pNetworkKademliaAction::UpdateFindNodeRequest {
-                    addr,
-                    peer_id,
-                    stream_id,
-                    closest_peers,
-                });
+                match data {
+                    P2pNetworkKademliaRpcReply::FindNode { closer_peers } => {
+                        store.dispatch(P2pNetworkKademliaAction::UpdateFindNodeRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            closest_peers: closer_peers,
+                        });
+                    }
+                    P2pNetworkKademliaRpcReply::PutValue { .. } => {
+                        store.dispatch(P2pNetworkKadRequestAction::ReplyReceived {
+                            peer_id,
+                            stream_id,
+                            data: Vec::new(),
+                        });
+                    }
+                    P2pNetworkKademliaRpcReply::GetValue {
+                        record,
+                        closer_peers,
+                        ..
+                    } => {
+                        store.dispatch(P2pNetworkKademliaAction::UpdateGetValueRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            record,
+                            closest_peers: closer_peers,
+                        });
+                    }
+                    P2pNetworkKademliaRpcReply::GetProviders {
+                        key,
+                        provider_peers,
+                        closer_peers,
+                    } => {
+                        store.dispatch(P2pNetworkKademliaAction::UpdateGetProvidersRequest {
+                            addr,
+                            peer_id,
+                            stream_id,
+                            key,
+                            providers: provider_peers,
+                            closest_peers: closer_peers,
+                        });
+                    }
+                }
                 Ok(())
             }
             (
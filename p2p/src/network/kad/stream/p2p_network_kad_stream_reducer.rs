@@ -80,6 +80,11 @@ impl P2pNetworkKadIncomingStreamState {
                 *self = S::WaitingForReply;
                 Ok(())
             }
+            (S::RequestIsReady { .. }, A::WaitIncoming { .. }) => {
+                // request without reply, e.g. `ADD_PROVIDER`
+                *self = S::WaitingForRequest { expect_close: true };
+                Ok(())
+            }
             (S::WaitingForReply, A::SendResponse { data, .. }) => {
                 let message = Message::from(data);
                 let bytes = serialize_into_vec(&message).map_err(|e| format!("{e}"))?;
@@ -209,6 +214,11 @@ impl P2pNetworkKadOutgoingStreamState {
                 *self = S::RequestBytesAreReady { bytes: Vec::new() };
                 Ok(())
             }
+            (S::WaitingForReply, A::Close { .. }) => {
+                // request without reply, e.g. `ADD_PROVIDER`
+                *self = S::RequestBytesAreReady { bytes: Vec::new() };
+                Ok(())
+            }
             (S::Closing, A::RemoteClose { .. }) => {
                 *self = S::Closed;
                 Ok(())
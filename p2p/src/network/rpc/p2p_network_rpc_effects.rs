@@ -12,9 +12,12 @@ use mina_p2p_messages::{
 use openmina_core::{error, fuzz_maybe, fuzzed_maybe};
 
 use crate::{
-    channels::rpc::{
-        BestTipWithProof, P2pChannelsRpcAction, P2pRpcRequest, P2pRpcResponse,
-        StagedLedgerAuxAndPendingCoinbases,
+    channels::{
+        rpc::{
+            BestTipWithProof, P2pChannelsRpcAction, P2pRpcId, P2pRpcRequest, P2pRpcResponse,
+            StagedLedgerAuxAndPendingCoinbases,
+        },
+        ChannelId,
     },
     connection::outgoing::P2pConnectionOutgoingInitOpts,
     disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
@@ -39,6 +42,37 @@ enum RpcResponseError {
     Other { rpc_id: String, error: String },
 }
 
+/// Dispatches [`P2pChannelsRpcAction::RequestReceived`], or rejects the
+/// request if the peer exceeded its budget. Returns whether the request
+/// was accepted.
+///
+/// Request that is neither accepted nor rejected, e.g. one with duplicate
+/// id, is unexpected and the peer gets disconnected, like on the WebRTC
+/// rpc channel.
+fn request_received<Store, S>(
+    store: &mut Store,
+    peer_id: PeerId,
+    id: P2pRpcId,
+    request: P2pRpcRequest,
+) -> bool
+where
+    Store: crate::P2pStore<S>,
+{
+    let kind = request.kind();
+    if store.dispatch(P2pChannelsRpcAction::RequestReceived {
+        peer_id,
+        id,
+        request: Box::new(request),
+    }) {
+        return true;
+    }
+    if !store.dispatch(P2pChannelsRpcAction::RequestRejected { peer_id, id, kind }) {
+        let reason = P2pDisconnectionReason::P2pChannelMsgUnexpected(ChannelId::Rpc);
+        store.dispatch(P2pDisconnectionAction::Init { peer_id, reason });
+    }
+    false
+}
+
 fn rpc_query_effects<'a, Store, S>(
     peer_id: PeerId,
     QueryHeader { tag, version, id }: &'a QueryHeader,
@@ -49,24 +83,16 @@ where
     Store: crate::P2pStore<S>,
 {
     let id = *id;
-    match (tag.as_ref(), *version) {
+    let requests = match (tag.as_ref(), *version) {
         (rpc::GetBestTipV2::NAME, rpc::GetBestTipV2::VERSION) => {
             rpc::GetBestTipV2::query_payload(&mut bytes)?;
-            store.dispatch(P2pChannelsRpcAction::RequestReceived {
-                peer_id,
-                id,
-                request: Box::new(P2pRpcRequest::BestTipWithProof),
-            });
+            vec![P2pRpcRequest::BestTipWithProof]
         }
         (rpc::AnswerSyncLedgerQueryV2::NAME, rpc::AnswerSyncLedgerQueryV2::VERSION) => {
             let (hash, query) = rpc::AnswerSyncLedgerQueryV2::query_payload(&mut bytes)?;
             let hash = v2::LedgerHash::from(v2::MinaBaseLedgerHash0StableV1(hash));
 
-            store.dispatch(P2pChannelsRpcAction::RequestReceived {
-                peer_id,
-                id,
-                request: Box::new(P2pRpcRequest::LedgerQuery(hash, query)),
-            });
+            vec![P2pRpcRequest::LedgerQuery(hash, query)]
         }
         (
             rpc::GetStagedLedgerAuxAndPendingCoinbasesAtHashV2::NAME,
@@ -75,37 +101,33 @@ where
             let hash =
                 rpc::GetStagedLedgerAuxAndPendingCoinbasesAtHashV2::query_payload(&mut bytes)?;
             let hash = v2::StateHash::from(v2::DataHashLibStateHashStableV1(hash));
-            let request = Box::new(P2pRpcRequest::StagedLedgerAuxAndPendingCoinbasesAtBlock(
-                hash,
-            ));
 
-            store.dispatch(P2pChannelsRpcAction::RequestReceived {
-                peer_id,
-                id,
-                request,
-            });
+            vec![P2pRpcRequest::StagedLedgerAuxAndPendingCoinbasesAtBlock(
+                hash,
+            )]
         }
         (rpc::GetTransitionChainV2::NAME, rpc::GetTransitionChainV2::VERSION) => {
             let hashes = rpc::GetTransitionChainV2::query_payload(&mut bytes)?;
-            for hash in hashes {
-                let hash = v2::StateHash::from(v2::DataHashLibStateHashStableV1(hash));
-
-                store.dispatch(P2pChannelsRpcAction::RequestReceived {
-                    peer_id,
-                    id,
-                    request: Box::new(P2pRpcRequest::Block(hash)),
-                });
-            }
+            hashes
+                .into_iter()
+                .map(|hash| {
+                    let hash = v2::StateHash::from(v2::DataHashLibStateHashStableV1(hash));
+                    P2pRpcRequest::Block(hash)
+                })
+                .collect()
         }
         (rpc::GetSomeInitialPeersV1ForV2::NAME, rpc::GetSomeInitialPeersV1ForV2::VERSION) => {
             let () = rpc::GetSomeInitialPeersV1ForV2::query_payload(&mut bytes)?;
-            store.dispatch(P2pChannelsRpcAction::RequestReceived {
-                peer_id,
-                id,
-                request: Box::new(P2pRpcRequest::InitialPeers),
-            });
+            vec![P2pRpcRequest::InitialPeers]
         }
         (name, version) => return Err(RpcQueryError::Unimplemented(name, version)),
+    };
+    // Rest of the requests for the same query are dropped once one isn't
+    // accepted, it was either answered with an error or the peer is gone.
+    for request in requests {
+        if !request_received(store, peer_id, id, request) {
+            break;
+        }
     }
     Ok(())
 }
@@ -3,7 +3,10 @@ use std::{collections::BTreeSet, time::Duration};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    channels::ChannelId, connection::outgoing::P2pConnectionOutgoingInitOpts, identity::PublicKey,
+    channels::{rpc::P2pRpcKind, ChannelId},
+    connection::outgoing::P2pConnectionOutgoingInitOpts,
+    identity::PublicKey,
+    peer::P2pPersistedPeers,
 };
 
 pub const DEVNET_SEEDS: &[&str] = &[
@@ -33,6 +36,15 @@ pub struct P2pConfig {
 
     pub limits: P2pLimits,
 
+    /// Budgets for RPC requests that we serve to peers.
+    #[serde(default)]
+    pub rpc_rate_limits: P2pRpcRateLimits,
+
+    /// Peers persisted by the previous run of the node, used to seed
+    /// discovery in addition to `initial_peers`.
+    #[serde(default)]
+    pub persisted_peers: P2pPersistedPeers,
+
     /// Use peers discovery.
     pub peer_discovery: bool,
 
@@ -349,8 +361,10 @@ impl Default for P2pLimits {
         let yamux_message_size = Limit::Some(0x10000000);
 
         let identify_message = Limit::Some(0x1000);
-        let kademlia_request = Limit::Some(50);
-        let kademlia_response = identify_message.map(|v| v * 20); // should be enough to fit 20 addresses supplied by identify
+        // should be enough to fit PUT_VALUE with the largest record we accept
+        let kademlia_request = Limit::Some(crate::MAX_RECORD_VALUE_SIZE + 0x1000);
+        // should be enough to fit 20 addresses supplied by identify, and a record for GET_VALUE
+        let kademlia_response = identify_message.map(|v| v * 20 + crate::MAX_RECORD_VALUE_SIZE);
 
         let rpc_service_message = Limit::Some(7); // 7 for handshake, 1 for heartbeat
         let rpc_query = Limit::Some(256); // max is 96
@@ -379,6 +393,87 @@ impl Default for P2pLimits {
     }
 }
 
+/// Token bucket budget for incoming RPC requests of a single kind from a
+/// single peer.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct P2pRpcRateLimit {
+    /// Maximum number of requests the peer can send at once.
+    pub burst: u32,
+    /// Time needed for the peer to get budget for one more request.
+    pub refill_interval: Duration,
+}
+
+impl P2pRpcRateLimit {
+    pub const fn new(burst: u32, refill_interval: Duration) -> Self {
+        Self {
+            burst,
+            refill_interval,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct P2pRpcRateLimits {
+    pub best_tip_with_proof: Limit<P2pRpcRateLimit>,
+    pub ledger_query: Limit<P2pRpcRateLimit>,
+    pub staged_ledger_aux_and_pending_coinbases_at_block: Limit<P2pRpcRateLimit>,
+    pub block: Limit<P2pRpcRateLimit>,
+    pub snark: Limit<P2pRpcRateLimit>,
+    pub initial_peers: Limit<P2pRpcRateLimit>,
+    /// Number of requests rejected because of exhausted budget within
+    /// `rejected_requests_window`, after which the peer is disconnected.
+    pub max_rejected_requests: Limit<usize>,
+    pub rejected_requests_window: Duration,
+}
+
+impl P2pRpcRateLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            best_tip_with_proof: Limit::Unlimited,
+            ledger_query: Limit::Unlimited,
+            staged_ledger_aux_and_pending_coinbases_at_block: Limit::Unlimited,
+            block: Limit::Unlimited,
+            snark: Limit::Unlimited,
+            initial_peers: Limit::Unlimited,
+            max_rejected_requests: Limit::Unlimited,
+            rejected_requests_window: Duration::from_secs(60),
+        }
+    }
+
+    pub fn get(&self, kind: P2pRpcKind) -> Limit<P2pRpcRateLimit> {
+        match kind {
+            P2pRpcKind::BestTipWithProof => self.best_tip_with_proof,
+            P2pRpcKind::LedgerQuery => self.ledger_query,
+            P2pRpcKind::StagedLedgerAuxAndPendingCoinbasesAtBlock => {
+                self.staged_ledger_aux_and_pending_coinbases_at_block
+            }
+            P2pRpcKind::Block => self.block,
+            P2pRpcKind::Snark => self.snark,
+            P2pRpcKind::InitialPeers => self.initial_peers,
+        }
+    }
+}
+
+impl Default for P2pRpcRateLimits {
+    fn default() -> Self {
+        Self {
+            best_tip_with_proof: Limit::Some(P2pRpcRateLimit::new(5, Duration::from_secs(2))),
+            // ledger sync issues a lot of small queries.
+            ledger_query: Limit::Some(P2pRpcRateLimit::new(1000, Duration::from_millis(5))),
+            // these are big and expensive to construct.
+            staged_ledger_aux_and_pending_coinbases_at_block: Limit::Some(P2pRpcRateLimit::new(
+                3,
+                Duration::from_secs(10),
+            )),
+            block: Limit::Some(P2pRpcRateLimit::new(100, Duration::from_millis(50))),
+            snark: Limit::Some(P2pRpcRateLimit::new(50, Duration::from_millis(100))),
+            initial_peers: Limit::Some(P2pRpcRateLimit::new(3, Duration::from_secs(10))),
+            max_rejected_requests: Limit::Some(50),
+            rejected_requests_window: Duration::from_secs(60),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -244,14 +244,48 @@ where
     #[cfg(feature = "p2p-libp2p")]
     if let Some(discovery_state) = state.network.scheduler.discovery_state() {
         let key = state.my_id();
-        if discovery_state
+        let start_bootstrap = discovery_state
             .routing_table
             .closest_peers(&P2pNetworkKadKey::from(&key))
             .any(|_| true)
-            && discovery_state.status.can_bootstrap(now, &config.timeouts)
-        {
+            && discovery_state.status.can_bootstrap(now, &config.timeouts);
+
+        // republish our own records and provider announcements
+        let mut republish = Vec::new();
+        if discovery_state.is_bootstrapped() {
+            let records = &discovery_state.records;
+            republish.extend(
+                records
+                    .local_records_to_republish(now)
+                    .map(|(key, record)| P2pNetworkKademliaAction::PutRecord {
+                        key: key.clone(),
+                        value: record.value.clone(),
+                    }),
+            );
+            republish.extend(
+                records
+                    .local_providers_to_republish(now)
+                    .map(|key| P2pNetworkKademliaAction::StartProviding { key: key.clone() }),
+            );
+        }
+
+        // finish lookups that take too long
+        let timed_out_lookups = discovery_state
+            .lookups
+            .iter()
+            .filter(|(_, lookup)| lookup.is_timed_out(now))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        if start_bootstrap {
             store.dispatch(P2pNetworkKademliaAction::StartBootstrap { key });
         }
+        for key in timed_out_lookups {
+            store.dispatch(P2pNetworkKademliaAction::ContinueLookup { key });
+        }
+        for action in republish {
+            store.dispatch(action);
+        }
     }
 }
 
@@ -81,6 +81,14 @@ impl P2pState {
                     },
                 };
                 p2p_connection_reducer(peer, my_id, meta.with_action(action));
+
+                if let P2pConnectionAction::Outgoing(P2pConnectionOutgoingAction::Error {
+                    peer_id,
+                    ..
+                }) = action
+                {
+                    state.address_book.connection_failed(peer_id);
+                }
             }
             P2pAction::Disconnection(action) => match action {
                 P2pDisconnectionAction::Init { .. } => {}
@@ -100,6 +108,7 @@ impl P2pState {
                         return;
                     };
                     peer.status = P2pPeerStatus::Disconnected { time: meta.time() };
+                    state.address_book.seen(peer_id, meta.time());
                 }
             },
             P2pAction::Peer(action) => {
@@ -110,10 +119,12 @@ impl P2pState {
                     return;
                 };
                 let is_libp2p = state.is_libp2p_peer(peer_id);
+                let rpc_rate_limits = state.config.rpc_rate_limits;
                 let Some(peer) = state.get_ready_peer_mut(peer_id) else {
                     return;
                 };
-                peer.channels.reducer(meta.with_action(action), is_libp2p);
+                peer.channels
+                    .reducer(meta.with_action(action), is_libp2p, &rpc_rate_limits);
             }
             P2pAction::Discovery(action) => {
                 p2p_discovery_reducer(state, meta.with_action(action));
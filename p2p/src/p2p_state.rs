@@ -10,7 +10,8 @@ use crate::channels::{ChannelId, P2pChannelsState};
 use crate::connection::incoming::P2pConnectionIncomingState;
 use crate::connection::outgoing::{P2pConnectionOutgoingInitOpts, P2pConnectionOutgoingState};
 use crate::network::identify::P2pNetworkIdentify;
-use crate::network::P2pNetworkState;
+use crate::network::{P2pNetworkKadEntry, P2pNetworkState};
+use crate::peer::P2pAddressBook;
 use crate::{is_time_passed, Limit, P2pTimeouts, PeerId};
 
 use super::connection::P2pConnectionState;
@@ -22,10 +23,15 @@ pub struct P2pState {
     pub config: P2pConfig,
     pub network: P2pNetworkState,
     pub peers: BTreeMap<PeerId, P2pPeerState>,
+    /// Peers we have connected to, persisted across restarts.
+    #[serde(default)]
+    pub address_book: P2pAddressBook,
 }
 
 impl P2pState {
-    pub fn new(config: P2pConfig, chain_id: &ChainId) -> Self {
+    pub fn new(mut config: P2pConfig, chain_id: &ChainId) -> Self {
+        let persisted_peers = std::mem::take(&mut config.persisted_peers);
+
         let addrs = if cfg!(feature = "p2p-libp2p") {
             config
                 .libp2p_port
@@ -70,7 +76,7 @@ impl P2pState {
             Vec::new()
         };
 
-        let peers = initial_peers
+        let mut peers: BTreeMap<_, _> = initial_peers
             .map(|peer| {
                 (
                     *peer.peer_id(),
@@ -86,18 +92,45 @@ impl P2pState {
             })
             .collect();
 
-        let network = P2pNetworkState::new(
+        let address_book = persisted_peers.address_book;
+        for entry in address_book.best_peers() {
+            let peer_id = *entry.dial_opts.peer_id();
+            if peer_id == my_id {
+                continue;
+            }
+            peers.entry(peer_id).or_insert_with(|| P2pPeerState {
+                dial_opts: Some(entry.dial_opts.clone()),
+                is_libp2p: entry.is_libp2p(),
+                status: P2pPeerStatus::Disconnected {
+                    time: Timestamp::ZERO,
+                },
+                identify: None,
+            });
+        }
+
+        let mut network = P2pNetworkState::new(
             config.identity_pub_key.clone(),
             addrs,
             known_peers,
             chain_id,
             config.peer_discovery,
         );
+        if let Some(discovery_state) = network.scheduler.discovery_state.as_mut() {
+            discovery_state.routing_table.extend(
+                persisted_peers
+                    .kad_entries
+                    .into_iter()
+                    .filter(|entry| entry.peer_id != my_id)
+                    .map(|entry| P2pNetworkKadEntry::new(entry.peer_id, entry.addrs)),
+            );
+        }
+
         Self {
             chain_id: chain_id.clone(),
             config,
             network,
             peers,
+            address_book,
         }
     }
 
@@ -1,6 +1,9 @@
 mod p2p_peer_actions;
 pub use p2p_peer_actions::*;
 
+mod p2p_peer_address_book;
+pub use p2p_peer_address_book::*;
+
 mod p2p_peer_reducer;
 pub use p2p_peer_reducer::*;
 
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use redux::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{connection::outgoing::P2pConnectionOutgoingInitOpts, P2pNetworkKadEntry, PeerId};
+
+/// Maximum number of peers kept in the address book.
+pub const MAX_ADDRESS_BOOK_SIZE: usize = 1000;
+
+/// Peers we managed to connect to in the past, with connection statistics.
+///
+/// It is persisted across restarts and used to seed discovery, so that the
+/// node can find the network even if configured seeds are unavailable.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct P2pAddressBook {
+    entries: BTreeMap<PeerId, P2pAddressBookEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct P2pAddressBookEntry {
+    pub dial_opts: P2pConnectionOutgoingInitOpts,
+    /// Last time the peer was connected.
+    pub last_seen: Timestamp,
+    /// Number of successful connections.
+    pub successes: u32,
+    /// Number of failed outgoing connection attempts.
+    pub failures: u32,
+}
+
+impl P2pAddressBookEntry {
+    pub fn is_libp2p(&self) -> bool {
+        self.dial_opts.is_libp2p()
+    }
+
+    /// Estimated probability of a successful connection, between 0 and 1.
+    pub fn success_rate(&self) -> f64 {
+        // add-one smoothing, so that a single failure doesn't discard the peer.
+        (self.successes as f64 + 1.0) / ((self.successes + self.failures) as f64 + 2.0)
+    }
+}
+
+impl P2pAddressBook {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, peer_id: &PeerId) -> Option<&P2pAddressBookEntry> {
+        self.entries.get(peer_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PeerId, &P2pAddressBookEntry)> {
+        self.entries.iter()
+    }
+
+    /// Peers sorted from the most to the least promising one.
+    pub fn best_peers(&self) -> impl Iterator<Item = &P2pAddressBookEntry> {
+        let mut entries = self.entries.values().collect::<Vec<_>>();
+        entries.sort_by(|a, b| {
+            b.success_rate()
+                .total_cmp(&a.success_rate())
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+        entries.into_iter()
+    }
+
+    /// Records successful connection to the peer.
+    pub fn connected(&mut self, dial_opts: &P2pConnectionOutgoingInitOpts, now: Timestamp) {
+        let peer_id = *dial_opts.peer_id();
+        match self.entries.get_mut(&peer_id) {
+            Some(entry) => {
+                entry.dial_opts = dial_opts.clone();
+                entry.last_seen = now;
+                entry.successes = entry.successes.saturating_add(1);
+            }
+            None => {
+                if self.entries.len() >= MAX_ADDRESS_BOOK_SIZE {
+                    self.remove_worst();
+                }
+                self.entries.insert(
+                    peer_id,
+                    P2pAddressBookEntry {
+                        dial_opts: dial_opts.clone(),
+                        last_seen: now,
+                        successes: 1,
+                        failures: 0,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Records failed connection attempt to the known peer.
+    pub fn connection_failed(&mut self, peer_id: &PeerId) {
+        if let Some(entry) = self.entries.get_mut(peer_id) {
+            entry.failures = entry.failures.saturating_add(1);
+        }
+    }
+
+    /// Updates last seen time of the known peer.
+    pub fn seen(&mut self, peer_id: &PeerId, now: Timestamp) {
+        if let Some(entry) = self.entries.get_mut(peer_id) {
+            entry.last_seen = now;
+        }
+    }
+
+    fn remove_worst(&mut self) {
+        let worst = self
+            .entries
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                a.success_rate()
+                    .total_cmp(&b.success_rate())
+                    .then(a.last_seen.cmp(&b.last_seen))
+            })
+            .map(|(peer_id, _)| *peer_id);
+        if let Some(peer_id) = worst {
+            self.entries.remove(&peer_id);
+        }
+    }
+}
+
+/// Peers persisted across restarts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct P2pPersistedPeers {
+    pub address_book: P2pAddressBook,
+    /// Kademlia routing table entries, except for the local node.
+    pub kad_entries: Vec<P2pNetworkKadEntry>,
+}
+
+impl P2pPersistedPeers {
+    pub fn is_empty(&self) -> bool {
+        self.address_book.is_empty() && self.kad_entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{identity::SecretKey, webrtc};
+
+    use super::*;
+
+    fn dial_opts(i: u8) -> P2pConnectionOutgoingInitOpts {
+        P2pConnectionOutgoingInitOpts::WebRTC {
+            peer_id: SecretKey::from_bytes([i; 32]).public_key().peer_id(),
+            signaling: webrtc::SignalingMethod::Http(webrtc::HttpSignalingInfo {
+                host: [127, 0, 0, 1].into(),
+                port: 3000 + i as u16,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_best_peers() {
+        let mut book = P2pAddressBook::default();
+        let (a, b, c) = (dial_opts(1), dial_opts(2), dial_opts(3));
+        book.connected(&a, Timestamp::ZERO);
+        book.connected(&b, Timestamp::ZERO);
+        book.connected(&c, Timestamp::ZERO + 1);
+        book.connection_failed(a.peer_id());
+        book.connection_failed(a.peer_id());
+        book.connected(&b, Timestamp::ZERO);
+
+        let best = book
+            .best_peers()
+            .map(|entry| *entry.dial_opts.peer_id())
+            .collect::<Vec<_>>();
+        assert_eq!(best, vec![*b.peer_id(), *c.peer_id(), *a.peer_id()]);
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let mut book = P2pAddressBook::default();
+        let worst = dial_opts(0);
+        book.connected(&worst, Timestamp::ZERO);
+        book.connection_failed(worst.peer_id());
+        for i in 1..=MAX_ADDRESS_BOOK_SIZE {
+            let mut bytes = [1; 32];
+            bytes[..8].copy_from_slice(&(i as u64).to_le_bytes());
+            let opts = P2pConnectionOutgoingInitOpts::WebRTC {
+                peer_id: SecretKey::from_bytes(bytes).public_key().peer_id(),
+                signaling: webrtc::SignalingMethod::Http(webrtc::HttpSignalingInfo {
+                    host: [127, 0, 0, 1].into(),
+                    port: 3000,
+                }),
+            };
+            book.connected(&opts, Timestamp::ZERO);
+        }
+        assert_eq!(book.len(), MAX_ADDRESS_BOOK_SIZE);
+        assert!(book.get(worst.peer_id()).is_none());
+    }
+}
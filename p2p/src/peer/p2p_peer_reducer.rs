@@ -30,6 +30,9 @@ pub fn p2p_peer_reducer(state: &mut P2pState, action: P2pPeerActionWithMetaRef<'
                 meta.time(),
                 &state.config.enabled_channels,
             ));
+            if let Some(dial_opts) = &peer.dial_opts {
+                state.address_book.connected(dial_opts, meta.time());
+            }
         }
         P2pPeerAction::BestTipUpdate { peer_id, best_tip } => {
             let Some(peer) = state.get_ready_peer_mut(peer_id) else {
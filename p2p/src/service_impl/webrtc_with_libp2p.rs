@@ -168,6 +168,31 @@ where
     }
 }
 
+/// Without libp2p there are no mio connections, commands are ignored.
+#[cfg(not(feature = "p2p-libp2p"))]
+impl<T> crate::P2pMioService for T
+where
+    T: P2pServiceWebrtcWithLibp2p,
+{
+    fn start_mio(&mut self) {}
+
+    fn send_mio_cmd(&mut self, _cmd: crate::MioCmd) {}
+}
+
+#[cfg(not(feature = "p2p-libp2p"))]
+impl<T: P2pServiceWebrtcWithLibp2p> crate::P2pNetworkService for T {
+    fn resolve_name(
+        &mut self,
+        _host: &str,
+    ) -> Result<Vec<std::net::IpAddr>, crate::P2pNetworkServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn detect_local_ip(&mut self) -> Result<Vec<std::net::IpAddr>, crate::P2pNetworkServiceError> {
+        Ok(Vec::new())
+    }
+}
+
 impl P2pServiceCtx {
     pub fn mocked(sec_key: SecretKey) -> Self {
         use openmina_core::channels::mpsc;
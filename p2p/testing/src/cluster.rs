@@ -359,6 +359,8 @@ impl Cluster {
             peer_discovery: config.discovery,
             timeouts: config.timeouts,
             limits: config.limits,
+            rpc_rate_limits: config.rpc_rate_limits,
+            persisted_peers: Default::default(),
             initial_time: Duration::ZERO,
         };
 
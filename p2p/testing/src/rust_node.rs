@@ -5,7 +5,7 @@ use std::{
 };
 
 use futures::Stream;
-use p2p::{P2pAction, P2pEvent, P2pLimits, P2pState, P2pTimeouts, PeerId};
+use p2p::{P2pAction, P2pEvent, P2pLimits, P2pRpcRateLimits, P2pState, P2pTimeouts, PeerId};
 use redux::{EnablingCondition, SubStore};
 use tokio::sync::mpsc;
 
@@ -26,6 +26,7 @@ pub struct RustNodeConfig {
     pub initial_peers: Vec<Listener>,
     pub timeouts: P2pTimeouts,
     pub limits: P2pLimits,
+    pub rpc_rate_limits: P2pRpcRateLimits,
     pub discovery: bool,
 }
 
@@ -53,6 +54,11 @@ impl RustNodeConfig {
         self
     }
 
+    pub fn with_rpc_rate_limits(mut self, rpc_rate_limits: P2pRpcRateLimits) -> Self {
+        self.rpc_rate_limits = rpc_rate_limits;
+        self
+    }
+
     pub fn with_discovery(mut self, discovery: bool) -> Self {
         self.discovery = discovery;
         self
@@ -25,6 +25,9 @@ pub use snark_actions::*;
 mod snark_config;
 pub use snark_config::*;
 
+mod snark_verifier_stats;
+pub use snark_verifier_stats::*;
+
 mod snark_state;
 pub use snark_state::*;
 
@@ -0,0 +1,27 @@
+use ledger::proofs::verifier_index::ZkappVerifierIndexCacheStats;
+use serde::{Deserialize, Serialize};
+
+/// Metrics of the service's proof verifier pool.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct SnarkVerifierStats {
+    pub threads: usize,
+    /// Requests waiting for a free verifier, by priority.
+    pub queued_blocks: usize,
+    pub queued_works: usize,
+    pub queued_user_commands: usize,
+    /// Verifications currently running.
+    pub in_progress: usize,
+    pub verified: u64,
+    pub failed: u64,
+    /// Requests dropped unverified because the queue was full.
+    pub shed: u64,
+    /// Requests answered from the cache of verified proofs.
+    pub cache_hits: u64,
+    /// Requests for proofs already queued or being verified, answered
+    /// together with the original request.
+    pub deduplicated: u64,
+    /// Number of digests in the cache of verified proofs.
+    pub cached_proofs: usize,
+    /// Cache of zkApp verifier indexes, by verification key hash.
+    pub zkapp_verifier_indexes: ZkappVerifierIndexCacheStats,
+}
@@ -21,13 +21,20 @@ pub fn reducer<State, Action>(
     let (action, meta) = action.split();
     match action {
         SnarkUserCommandVerifyAction::Init {
-            commands, req_id, ..
+            commands,
+            req_id,
+            from_rpc,
+            on_success,
+            on_error,
         } => {
             let substate = state.get_substate_mut().unwrap();
 
             substate.jobs.add(SnarkUserCommandVerifyStatus::Init {
                 time: meta.time(),
                 commands: commands.clone(),
+                from_rpc: *from_rpc,
+                on_success: on_success.clone(),
+                on_error: on_error.clone(),
             });
 
             // Dispatch
@@ -42,20 +49,24 @@ pub fn reducer<State, Action>(
             });
             dispatcher.push(SnarkUserCommandVerifyAction::Pending { req_id: *req_id });
         }
-        // TODO
-        #[allow(unreachable_code)]
         SnarkUserCommandVerifyAction::Pending { req_id } => {
             let substate = state.get_substate_mut().unwrap();
 
             if let Some(req) = substate.jobs.get_mut(*req_id) {
                 *req = match req {
-                    SnarkUserCommandVerifyStatus::Init { .. } => {
-                        SnarkUserCommandVerifyStatus::Pending {
-                            time: meta.time(),
-                            commands: todo!(),
-                            // commands: std::mem::take(commands),
-                        }
-                    }
+                    SnarkUserCommandVerifyStatus::Init {
+                        commands,
+                        from_rpc,
+                        on_success,
+                        on_error,
+                        ..
+                    } => SnarkUserCommandVerifyStatus::Pending {
+                        time: meta.time(),
+                        commands: std::mem::take(commands),
+                        from_rpc: *from_rpc,
+                        on_success: on_success.clone(),
+                        on_error: on_error.clone(),
+                    },
                     _ => return,
                 };
             }
@@ -63,34 +74,66 @@ pub fn reducer<State, Action>(
         SnarkUserCommandVerifyAction::Error { req_id, error } => {
             let substate = state.get_substate_mut().unwrap();
 
-            if let Some(req) = substate.jobs.get_mut(*req_id) {
-                if let SnarkUserCommandVerifyStatus::Pending { commands, .. } = req {
+            let callback_and_arg = substate.jobs.get_mut(*req_id).and_then(|req| {
+                if let SnarkUserCommandVerifyStatus::Pending {
+                    commands,
+                    from_rpc,
+                    on_error,
+                    ..
+                } = req
+                {
+                    let callback = on_error.clone();
+                    let from_rpc = *from_rpc;
                     *req = SnarkUserCommandVerifyStatus::Error {
                         time: meta.time(),
                         commands: std::mem::take(commands),
                         error: error.clone(),
-                    }
+                    };
+                    Some((callback, (*req_id, error.clone(), from_rpc)))
+                } else {
+                    None
                 }
-            }
+            });
 
             // Dispatch
             let dispatcher = state.into_dispatcher();
+
+            if let Some((callback, args)) = callback_and_arg {
+                dispatcher.push_callback(callback, args);
+            }
+
             dispatcher.push(SnarkUserCommandVerifyAction::Finish { req_id: *req_id });
         }
-        SnarkUserCommandVerifyAction::Success { req_id } => {
+        SnarkUserCommandVerifyAction::Success { req_id, commands } => {
             let substate = state.get_substate_mut().unwrap();
 
-            if let Some(req) = substate.jobs.get_mut(*req_id) {
-                if let SnarkUserCommandVerifyStatus::Pending { commands, .. } = req {
+            let callback_and_arg = substate.jobs.get_mut(*req_id).and_then(|req| {
+                if let SnarkUserCommandVerifyStatus::Pending {
+                    commands: verifiable,
+                    from_rpc,
+                    on_success,
+                    ..
+                } = req
+                {
+                    let callback = on_success.clone();
+                    let from_rpc = *from_rpc;
                     *req = SnarkUserCommandVerifyStatus::Success {
                         time: meta.time(),
-                        commands: std::mem::take(commands),
+                        commands: std::mem::take(verifiable),
                     };
+                    Some((callback, (*req_id, commands.clone(), from_rpc)))
+                } else {
+                    None
                 }
-            }
+            });
 
             // Dispatch
             let dispatcher = state.into_dispatcher();
+
+            if let Some((callback, args)) = callback_and_arg {
+                dispatcher.push_callback(callback, args);
+            }
+
             dispatcher.push(SnarkUserCommandVerifyAction::Finish { req_id: *req_id });
         }
         SnarkUserCommandVerifyAction::Finish { req_id } => {
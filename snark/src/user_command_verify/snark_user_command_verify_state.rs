@@ -1,13 +1,13 @@
 use std::sync::{Arc, Mutex};
 
 use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
-use mina_p2p_messages::{list::List, v2};
 use serde::{Deserialize, Serialize};
 
-use openmina_core::requests::PendingRequests;
+use openmina_core::requests::{PendingRequests, RpcId};
 
 use crate::{VerifierIndex, VerifierSRS};
 
+use super::snark_user_command_verify_actions::{OnError, OnSuccess};
 use super::{SnarkUserCommandVerifyError, SnarkUserCommandVerifyId, SnarkUserCommandVerifyIdType};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -46,11 +46,17 @@ impl std::fmt::Debug for SnarkUserCommandVerifyState {
 pub enum SnarkUserCommandVerifyStatus {
     Init {
         time: redux::Timestamp,
-        commands: List<v2::MinaBaseUserCommandStableV2>,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
+        from_rpc: Option<RpcId>,
+        on_success: OnSuccess,
+        on_error: OnError,
     },
     Pending {
         time: redux::Timestamp,
         commands: Vec<WithStatus<verifiable::UserCommand>>,
+        from_rpc: Option<RpcId>,
+        on_success: OnSuccess,
+        on_error: OnError,
     },
     Error {
         time: redux::Timestamp,
@@ -24,4 +24,6 @@ pub enum SnarkUserCommandVerifyError {
     VerificationFailed,
     #[error("validator thread crashed")]
     ValidatorThreadCrashed,
+    #[error("verifier overloaded")]
+    Overloaded,
 }
@@ -1,6 +1,6 @@
 use std::sync::{Arc, Mutex};
 
-use mina_p2p_messages::{list::List, v2};
+use ledger::scan_state::transaction_logic::{verifiable, WithStatus};
 use serde::{Deserialize, Serialize};
 
 use crate::{VerifierIndex, VerifierSRS};
@@ -11,9 +11,7 @@ use super::SnarkUserCommandVerifyId;
 pub enum SnarkUserCommandVerifyEffectfulAction {
     Init {
         req_id: SnarkUserCommandVerifyId,
-        commands: List<v2::MinaBaseUserCommandStableV2>,
-        // commands: Vec<WithStatus<verifiable::UserCommand>>,
-        // sender: String,
+        commands: Vec<WithStatus<verifiable::UserCommand>>,
         verifier_index: Arc<VerifierIndex>,
         verifier_srs: Arc<Mutex<VerifierSRS>>,
     },
@@ -10,10 +10,11 @@ impl SnarkUserCommandVerifyEffectfulAction {
     {
         match self {
             Self::Init {
-                req_id, commands, ..
+                req_id,
+                commands,
+                verifier_index,
+                verifier_srs,
             } => {
-                let verifier_index = store.state().work_verify.verifier_index.clone();
-                let verifier_srs = store.state().work_verify.verifier_srs.clone();
                 store
                     .service()
                     .verify_init(req_id, verifier_index, verifier_srs, commands);
@@ -23,4 +23,6 @@ pub enum SnarkWorkVerifyError {
     VerificationFailed,
     #[error("validator thread crashed")]
     ValidatorThreadCrashed,
+    #[error("verifier overloaded")]
+    Overloaded,
 }
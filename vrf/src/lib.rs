@@ -59,6 +59,15 @@ pub struct VrfWonSlot {
     pub account_index: AccountIndex,
     pub vrf_output: Box<VrfOutput>,
     pub value_with_threshold: Option<(f64, f64)>,
+    /// Whether the vrf output met the threshold, i.e. the slot was
+    /// actually won. Only `false` for slots evaluated with
+    /// [`evaluate_vrf_ignoring_threshold`], which can't be proven.
+    #[serde(default = "threshold_met_default")]
+    pub threshold_met: bool,
+}
+
+fn threshold_met_default() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -136,6 +145,25 @@ fn calculate_vrf(
 
 /// Evaluate vrf with a specific input. Used by the block producer
 pub fn evaluate_vrf(vrf_input: VrfEvaluationInput) -> VrfResult<VrfEvaluationOutput> {
+    let global_slot = vrf_input.global_slot;
+    let (won_slot, threshold_met) = evaluate_vrf_with_threshold(vrf_input)?;
+
+    if threshold_met {
+        Ok(VrfEvaluationOutput::SlotWon(won_slot))
+    } else {
+        Ok(VrfEvaluationOutput::SlotLost(global_slot))
+    }
+}
+
+/// Evaluate vrf with a specific input, treating the slot as won even if
+/// the threshold isn't met. Used by the block producer in shadow mode.
+///
+/// [`VrfWonSlot::threshold_met`] tells whether the slot was actually won.
+pub fn evaluate_vrf_ignoring_threshold(vrf_input: VrfEvaluationInput) -> VrfResult<VrfWonSlot> {
+    evaluate_vrf_with_threshold(vrf_input).map(|(won_slot, _)| won_slot)
+}
+
+fn evaluate_vrf_with_threshold(vrf_input: VrfEvaluationInput) -> VrfResult<(VrfWonSlot, bool)> {
     let VrfEvaluationInput {
         producer_key,
         global_slot,
@@ -150,24 +178,24 @@ pub fn evaluate_vrf(vrf_input: VrfEvaluationInput) -> VrfResult<VrfEvaluationOut
 
     let value = vrf_output.truncated().into_repr();
     let threshold = Threshold::new(delegated_stake, total_currency);
+    let threshold_met = threshold.threshold_met(value);
 
-    if threshold.threshold_met(value) {
-        Ok(VrfEvaluationOutput::SlotWon(VrfWonSlot {
-            producer: producer_key.get_address(),
-            vrf_output: Box::new(vrf_output),
-            winner_account: account_pub_key,
-            global_slot,
-            account_index: delegator_index,
-            value_with_threshold: None.or_else(|| {
-                Some((
-                    self::threshold::get_fractional(value).to_f64()?,
-                    threshold.threshold_rational.to_f64()?,
-                ))
-            }),
-        }))
-    } else {
-        Ok(VrfEvaluationOutput::SlotLost(global_slot))
-    }
+    let won_slot = VrfWonSlot {
+        producer: producer_key.get_address(),
+        vrf_output: Box::new(vrf_output),
+        winner_account: account_pub_key,
+        global_slot,
+        account_index: delegator_index,
+        value_with_threshold: None.or_else(|| {
+            Some((
+                self::threshold::get_fractional(value).to_f64()?,
+                threshold.threshold_rational.to_f64()?,
+            ))
+        }),
+        threshold_met,
+    };
+
+    Ok((won_slot, threshold_met))
 }
 
 fn keypair_from_bs58_string(str: &str) -> Keypair {
@@ -192,7 +220,7 @@ mod test {
 
     use crate::{genesis_vrf, keypair_from_bs58_string, VrfEvaluationInput, VrfEvaluationOutput};
 
-    use super::evaluate_vrf;
+    use super::{evaluate_vrf, evaluate_vrf_ignoring_threshold};
 
     #[test]
     fn test_genesis_vrf() {
@@ -261,6 +289,31 @@ mod test {
         // assert_eq!(expected, evaluation_result)
     }
 
+    #[test]
+    fn test_evaluate_vrf_ignoring_threshold() {
+        let vrf_input = VrfEvaluationInput {
+            producer_key: keypair_from_bs58_string(
+                "EKEEpMELfQkMbJDt2fB4cFXKwSf1x4t7YD4twREy5yuJ84HBZtF9",
+            ),
+            epoch_seed: EpochSeed::from_str("2va9BGv9JrLTtrzZttiEMDYw1Zj6a6EHzXjmP9evHDTG3oEquURA")
+                .unwrap(),
+            global_slot: 518,
+            delegator_index: AccountIndex(2),
+            delegated_stake: BigInt::from_str("1000000000000000")
+                .expect("Cannot convert to BigInt"),
+            total_currency: BigInt::from_str("6000000000001000").expect("Cannot convert to BigInt"),
+            account_pub_key: "Placeholder".to_string(),
+        };
+
+        // slot 518 is lost (see `test_evaluate_vrf_lost_slot`), but is reported as won.
+        let won_slot = evaluate_vrf_ignoring_threshold(vrf_input).expect("Failed to evaluate vrf");
+        assert_eq!(won_slot.global_slot, 518);
+        assert_eq!(won_slot.account_index, AccountIndex(2));
+        assert!(!won_slot.threshold_met);
+        let (value, threshold) = won_slot.value_with_threshold.unwrap();
+        assert!(value > threshold);
+    }
+
     #[test]
     #[ignore]
     fn test_slot_calculation_time_big_producer() {